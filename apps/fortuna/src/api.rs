@@ -4,51 +4,93 @@ use {
             BlockNumber,
             BlockStatus,
             EntropyReader,
+            Request,
         },
+        reorg::RevealedSequences,
         state::HashChainState,
     },
     anyhow::Result,
     axum::{
         body::Body,
-        http::StatusCode,
+        http::{
+            header,
+            StatusCode,
+        },
+        middleware,
         response::{
             IntoResponse,
             Response,
         },
         routing::get,
+        Json,
         Router,
     },
     ethers::core::types::Address,
+    futures::future::{
+        BoxFuture,
+        Shared,
+    },
     prometheus_client::{
         encoding::EncodeLabelSet,
         metrics::{
             counter::Counter,
             family::Family,
+            gauge::Gauge,
+            histogram::Histogram,
         },
         registry::Registry,
     },
     std::{
-        collections::HashMap,
-        sync::Arc,
+        collections::{
+            BTreeMap,
+            HashMap,
+        },
+        net::IpAddr,
+        sync::{
+            Arc,
+            Mutex,
+        },
+        time::{
+            Duration,
+            Instant,
+        },
+    },
+    tokio::sync::{
+        watch,
+        RwLock,
     },
-    tokio::sync::RwLock,
     url::Url,
+    utoipa::ToSchema,
 };
 pub use {
     chain_ids::*,
+    hash_chain_params::*,
     index::*,
     live::*,
     metrics::*,
+    query::*,
+    rate_limit::*,
     ready::*,
+    revealed::*,
     revelation::*,
+    revelation_batch::*,
+    revelation_context::*,
+    verify_reveal::*,
 };
 
 mod chain_ids;
+mod hash_chain_params;
 mod index;
 mod live;
 mod metrics;
+mod query;
+mod rate_limit;
 mod ready;
+mod revealed;
 mod revelation;
+mod revelation_batch;
+mod revelation_context;
+mod verify_reveal;
 
 pub type ChainId = String;
 
@@ -57,8 +99,218 @@ pub struct RequestLabel {
     pub value: String,
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct ChainLabel {
+    pub chain_id: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct RevealOutcomeLabel {
+    pub chain_id: String,
+    /// `ok` for successful reveals, otherwise the code of the returned error.
+    pub outcome:  String,
+}
+
 pub struct ApiMetrics {
-    pub http_requests: Family<RequestLabel, Counter>,
+    pub http_requests:             Family<RequestLabel, Counter>,
+    /// Reveal requests for hot sequence numbers, i.e., sequence numbers that have already been
+    /// revealed more than the hot sequence threshold.
+    pub hot_sequence_reveals:      Family<ChainLabel, Counter>,
+    /// Reveal requests for supported chains, by outcome.
+    pub reveals:                   Family<RevealOutcomeLabel, Counter>,
+    /// The time taken to answer reveal requests for supported chains, in seconds.
+    pub reveal_latency:            Family<ChainLabel, Histogram>,
+    /// The highest sequence number revealed since the server started. Dashboards can alert if it
+    /// stops increasing.
+    pub highest_revealed_sequence: Family<ChainLabel, Gauge>,
+    /// Random values served from the reveal cache. The hit ratio is
+    /// `reveal_cache_hits / (reveal_cache_hits + reveal_cache_misses)`.
+    pub reveal_cache_hits:         Family<ChainLabel, Counter>,
+    /// Random values computed from the hash chain because they weren't in the reveal cache.
+    pub reveal_cache_misses:       Family<ChainLabel, Counter>,
+}
+
+impl ApiMetrics {
+    /// Record the outcome and latency of a reveal request for `sequence` on `chain_id`.
+    pub fn record_reveal<T>(
+        &self,
+        chain_id: &ChainId,
+        sequence: u64,
+        result: &Result<T, RestError>,
+        latency: Duration,
+    ) {
+        let outcome = match result {
+            Ok(_) => "ok",
+            Err(e) => e.code(),
+        };
+        self.reveals
+            .get_or_create(&RevealOutcomeLabel {
+                chain_id: chain_id.clone(),
+                outcome:  outcome.to_string(),
+            })
+            .inc();
+
+        let label = ChainLabel {
+            chain_id: chain_id.clone(),
+        };
+        self.reveal_latency
+            .get_or_create(&label)
+            .observe(latency.as_secs_f64());
+        if result.is_ok() {
+            // prometheus only supports i64 for Gauge types
+            let sequence = i64::try_from(sequence).unwrap_or(i64::MAX);
+            let highest_revealed_sequence = self.highest_revealed_sequence.get_or_create(&label);
+            if highest_revealed_sequence.get() < sequence {
+                highest_revealed_sequence.set(sequence);
+            }
+        }
+    }
+}
+
+/// A revelation that is currently being computed, along with the on-chain request it answers. The
+/// future is shared so that every caller waiting on the same (chain id, sequence number, block
+/// number) receives the same result.
+pub type InFlightReveal = Shared<BoxFuture<'static, Result<([u8; 32], Request), RestError>>>;
+
+/// The chain id, sequence number and block number (`None` for the latest block) of a revelation.
+pub type InFlightRevealKey = (ChainId, u64, Option<BlockNumber>);
+
+/// The client IP (`None` if it is unknown), chain id and sequence number of a reveal, as counted by
+/// `HotSequences`.
+pub type HotSequenceKey = (Option<IpAddr>, ChainId, u64);
+
+/// Counts the recent successful reveals of each sequence number by each client. A revealed value
+/// never changes, so a sequence that a client reveals abnormally often (e.g., because it is stuck
+/// in a reveal loop) is pure load, and that client's reveals of the sequence are rate limited more
+/// tightly once its count exceeds the hot sequence threshold. Other clients aren't affected.
+///
+/// Counts decay: they cover the last `WINDOW`, estimated from the counts of the current and the
+/// previous window, so a sequence stops being hot once the client stops hammering it.
+///
+/// Only successful reveals are counted, so every tracked sequence number has been requested
+/// on-chain and callers can't grow the counts with arbitrary sequence numbers.
+pub struct HotSequences {
+    window_start: Instant,
+    current:      HashMap<HotSequenceKey, u64>,
+    previous:     HashMap<HotSequenceKey, u64>,
+}
+
+impl Default for HotSequences {
+    fn default() -> Self {
+        HotSequences::new(Instant::now())
+    }
+}
+
+impl HotSequences {
+    /// The period over which reveals are counted.
+    pub const WINDOW: Duration = Duration::from_secs(60);
+
+    /// The maximum number of keys to track per window. Once reached, the counts of the current
+    /// window are reset so that memory usage stays bounded.
+    const MAX_TRACKED_SEQUENCES: usize = 100_000;
+
+    fn new(now: Instant) -> Self {
+        HotSequences {
+            window_start: now,
+            current:      HashMap::new(),
+            previous:     HashMap::new(),
+        }
+    }
+
+    /// Record a successful reveal of `key` at time `now`.
+    pub fn record(&mut self, key: &HotSequenceKey, now: Instant) {
+        self.advance(now);
+        if self.current.len() >= Self::MAX_TRACKED_SEQUENCES && !self.current.contains_key(key) {
+            self.current.clear();
+        }
+        *self.current.entry(key.clone()).or_insert(0) += 1;
+    }
+
+    /// The number of successful reveals of `key` in the `WINDOW` before `now`. The reveals of the
+    /// previous window are weighted by how much of it overlaps with that period.
+    pub fn count(&mut self, key: &HotSequenceKey, now: Instant) -> u64 {
+        self.advance(now);
+        let elapsed = now.saturating_duration_since(self.window_start);
+        let previous_weight = 1.0 - elapsed.as_secs_f64() / Self::WINDOW.as_secs_f64();
+        let previous = self.previous.get(key).copied().unwrap_or(0) as f64 * previous_weight;
+        self.current.get(key).copied().unwrap_or(0) + previous as u64
+    }
+
+    /// Start a new window if the current one ended before `now`.
+    fn advance(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.window_start);
+        if elapsed >= 2 * Self::WINDOW {
+            self.previous.clear();
+            self.current.clear();
+            self.window_start = now;
+        } else if elapsed >= Self::WINDOW {
+            self.previous = std::mem::take(&mut self.current);
+            self.window_start += Self::WINDOW;
+        }
+    }
+}
+
+/// The provider address, chain id and sequence number of a cached random value.
+pub type RevealCacheKey = (Address, ChainId, u64);
+
+/// The most recently revealed random values, so that repeated reveals of the same sequence number
+/// don't recompute them from the hash chain. Once `capacity` values are cached, the least recently
+/// used one is evicted. Values are only inserted after the on-chain request check, and are only
+/// returned after it too, so caching them doesn't change which values can be revealed.
+pub struct RevealCache {
+    capacity: usize,
+    /// Incremented on every access, so that lower ticks are less recently used.
+    tick:     u64,
+    values:   HashMap<RevealCacheKey, ([u8; 32], u64)>,
+    /// The key of each cached value by the tick of its last access.
+    recency:  BTreeMap<u64, RevealCacheKey>,
+}
+
+impl RevealCache {
+    /// Create a cache of at most `capacity` values. A capacity of 0 disables the cache.
+    pub fn new(capacity: usize) -> Self {
+        RevealCache {
+            capacity,
+            tick: 0,
+            values: HashMap::new(),
+            recency: BTreeMap::new(),
+        }
+    }
+
+    /// The cached value of `key`, if any. This marks it as the most recently used.
+    pub fn get(&mut self, key: &RevealCacheKey) -> Option<[u8; 32]> {
+        self.tick += 1;
+        let (value, last_used) = self.values.get_mut(key)?;
+        if let Some(key) = self.recency.remove(last_used) {
+            self.recency.insert(self.tick, key);
+        }
+        *last_used = self.tick;
+        Some(*value)
+    }
+
+    /// Cache `value` for `key`, evicting the least recently used value if the cache is full.
+    pub fn insert(&mut self, key: RevealCacheKey, value: [u8; 32]) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        if let Some((_, last_used)) = self.values.insert(key.clone(), (value, self.tick)) {
+            self.recency.remove(&last_used);
+        } else if self.values.len() > self.capacity {
+            if let Some((_, evicted)) = self.recency.pop_first() {
+                self.values.remove(&evicted);
+            }
+        }
+        self.recency.insert(self.tick, key);
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
 }
 
 #[derive(Clone)]
@@ -69,15 +321,62 @@ pub struct ApiState {
 
     /// Prometheus metrics
     pub metrics: Arc<ApiMetrics>,
+
+    /// Revelations that are currently in flight, keyed by chain id, sequence number and the block
+    /// the request is read at (`None` for the latest block). Concurrent requests for the same
+    /// revelation share a single computation (and set of RPC calls) instead of each hitting the
+    /// blockchain.
+    pub in_flight_reveals: Arc<Mutex<HashMap<InFlightRevealKey, InFlightReveal>>>,
+
+    /// Sequence numbers that a client revealed more than `hot_sequence_threshold` times in the last
+    /// `HotSequences::WINDOW` are limited to `hot_sequence_limiter`'s rate for that client.
+    pub hot_sequences:          Arc<Mutex<HotSequences>>,
+    pub hot_sequence_threshold: u64,
+    pub hot_sequence_limiter:   Arc<RateLimiter<HotSequenceKey>>,
+
+    /// The most recently revealed random values, shared by all the chains and providers.
+    pub reveal_cache: Arc<Mutex<RevealCache>>,
+
+    /// Reject requests with unrecognized query parameters instead of ignoring them.
+    pub strict_query_params: bool,
+
+    /// Limits the number of requests per client IP to the /v1 endpoints, or None if requests
+    /// aren't limited.
+    pub rate_limiter: Option<Arc<RateLimiter>>,
+
+    /// Set once the hash chains are set up. /ready answers HTTP 503 until then.
+    pub ready:           watch::Receiver<bool>,
+    /// Whether /ready also checks that the RPC endpoint of every chain is reachable.
+    pub ready_rpc_check: bool,
 }
 
 impl ApiState {
     pub async fn new(
         chains: HashMap<ChainId, BlockchainState>,
         metrics_registry: Arc<RwLock<Registry>>,
+        hot_sequence_threshold: u64,
+        hot_sequence_reveals_per_second: u32,
+        reveal_cache_size: usize,
+        strict_query_params: bool,
+        rate_limit_per_second: Option<u32>,
+        ready: watch::Receiver<bool>,
+        ready_rpc_check: bool,
     ) -> ApiState {
         let metrics = ApiMetrics {
-            http_requests: Family::default(),
+            http_requests:             Family::default(),
+            hot_sequence_reveals:      Family::default(),
+            reveals:                   Family::default(),
+            reveal_latency:            Family::new_with_constructor(|| {
+                Histogram::new(
+                    [
+                        0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 2.0, 5.0, 10.0, 20.0,
+                    ]
+                    .into_iter(),
+                )
+            }),
+            highest_revealed_sequence: Family::default(),
+            reveal_cache_hits:         Family::default(),
+            reveal_cache_misses:       Family::default(),
         };
 
         let http_requests = metrics.http_requests.clone();
@@ -87,10 +386,62 @@ impl ApiState {
             http_requests,
         );
 
+        let hot_sequence_reveals = metrics.hot_sequence_reveals.clone();
+        metrics_registry.write().await.register(
+            "hot_sequence_reveals",
+            "Number of reveal requests for sequence numbers that the client revealed more than the hot sequence threshold",
+            hot_sequence_reveals,
+        );
+
+        let reveals = metrics.reveals.clone();
+        metrics_registry.write().await.register(
+            "reveals",
+            "Number of reveal requests for supported chains, by outcome",
+            reveals,
+        );
+
+        let reveal_latency = metrics.reveal_latency.clone();
+        metrics_registry.write().await.register(
+            "reveal_latency",
+            "The time taken to answer reveal requests, in seconds",
+            reveal_latency,
+        );
+
+        let highest_revealed_sequence = metrics.highest_revealed_sequence.clone();
+        metrics_registry.write().await.register(
+            "highest_revealed_sequence",
+            "The highest sequence number revealed since the server started",
+            highest_revealed_sequence,
+        );
+
+        let reveal_cache_hits = metrics.reveal_cache_hits.clone();
+        metrics_registry.write().await.register(
+            "reveal_cache_hits",
+            "Number of random values served from the reveal cache",
+            reveal_cache_hits,
+        );
+
+        let reveal_cache_misses = metrics.reveal_cache_misses.clone();
+        metrics_registry.write().await.register(
+            "reveal_cache_misses",
+            "Number of random values computed because they weren't in the reveal cache",
+            reveal_cache_misses,
+        );
+
         ApiState {
             chains: Arc::new(chains),
             metrics: Arc::new(metrics),
             metrics_registry,
+            in_flight_reveals: Arc::new(Mutex::new(HashMap::new())),
+            hot_sequences: Arc::new(Mutex::new(HotSequences::default())),
+            hot_sequence_threshold,
+            hot_sequence_limiter: Arc::new(RateLimiter::new(hot_sequence_reveals_per_second)),
+            reveal_cache: Arc::new(Mutex::new(RevealCache::new(reveal_cache_size))),
+            strict_query_params,
+            rate_limiter: rate_limit_per_second
+                .map(|requests_per_second| Arc::new(RateLimiter::new(requests_per_second))),
+            ready,
+            ready_rpc_check,
         }
     }
 }
@@ -112,13 +463,36 @@ pub struct BlockchainState {
     /// The BlockStatus of the block that is considered to be confirmed on the blockchain.
     /// For eg., Finalized, Safe
     pub confirmed_block_status: BlockStatus,
+    /// The revealed sequence numbers whose requests are re-checked for reorgs, or None if reorg
+    /// detection is disabled for this blockchain.
+    pub revealed_sequences:     Option<Arc<Mutex<RevealedSequences>>>,
 }
 
+impl BlockchainState {
+    /// Record that the random value for `sequence` was revealed because of a request made at
+    /// `block_number`, so that the request can be re-checked for reorgs.
+    pub fn record_reveal(&self, sequence: u64, block_number: BlockNumber) {
+        if let Some(revealed_sequences) = &self.revealed_sequences {
+            revealed_sequences
+                .lock()
+                .unwrap()
+                .record(sequence, block_number);
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
 pub enum RestError {
     /// The caller passed a sequence number that isn't within the supported range
     InvalidSequenceNumber,
     /// The caller passed an unsupported chain id
     InvalidChainId,
+    /// The caller asked for a number of revelations that isn't between 1 and
+    /// `MAX_BATCH_REVELATIONS`
+    InvalidRevelationCount,
+    /// The caller passed query parameters that the endpoint doesn't recognize (only returned if
+    /// strict query parameter parsing is enabled)
+    UnknownQueryParameters(Vec<String>),
     /// The caller requested a random value that can't currently be revealed (because it
     /// hasn't been committed to on-chain)
     NoPendingRequest,
@@ -128,58 +502,148 @@ pub enum RestError {
     /// The server cannot currently communicate with the blockchain, so is not able to verify
     /// which random values have been requested.
     TemporarilyUnavailable,
+    /// The caller made more requests than the rate limit allows and should retry after the
+    /// given number of seconds.
+    RateLimited { retry_after_secs: u64 },
     /// A catch-all error for all other types of errors that could occur during processing.
     Unknown,
 }
 
+impl RestError {
+    /// The error for a request that was rejected by a rate limit and can be retried after
+    /// `retry_after`.
+    pub fn rate_limited(retry_after: Duration) -> RestError {
+        RestError::RateLimited {
+            // Retry-After is in whole seconds, so round up to avoid retrying too early.
+            retry_after_secs: retry_after.as_secs_f64().ceil().max(1.0) as u64,
+        }
+    }
+
+    /// A stable, machine-readable identifier for this error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            RestError::InvalidSequenceNumber => "invalid_sequence_number",
+            RestError::InvalidChainId => "invalid_chain_id",
+            RestError::InvalidRevelationCount => "invalid_revelation_count",
+            RestError::UnknownQueryParameters(_) => "unknown_query_parameters",
+            RestError::NoPendingRequest => "no_pending_request",
+            RestError::PendingConfirmation => "pending_confirmation",
+            RestError::TemporarilyUnavailable => "temporarily_unavailable",
+            RestError::RateLimited { .. } => "rate_limited",
+            RestError::Unknown => "unknown",
+        }
+    }
+}
+
+/// The body of every error returned by the API.
+#[derive(Debug, serde::Serialize, serde::Deserialize, ToSchema, PartialEq)]
+pub struct ErrorResponse {
+    /// A stable, machine-readable identifier for the error, e.g. `no_pending_request`.
+    pub code:    String,
+    /// A human-readable description of the error.
+    pub message: String,
+}
+
 impl IntoResponse for RestError {
     fn into_response(self) -> Response {
-        match self {
+        let (status, message) = match &self {
             RestError::InvalidSequenceNumber => (
                 StatusCode::BAD_REQUEST,
-                "The sequence number is out of the permitted range",
-            )
-                .into_response(),
-            RestError::InvalidChainId => {
-                (StatusCode::BAD_REQUEST, "The chain id is not supported").into_response()
-            }
+                "The sequence number is out of the permitted range".to_string(),
+            ),
+            RestError::InvalidChainId => (
+                StatusCode::BAD_REQUEST,
+                "The chain id is not supported".to_string(),
+            ),
+            RestError::InvalidRevelationCount => (
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "The number of revelations must be between 1 and {}",
+                    MAX_BATCH_REVELATIONS
+                ),
+            ),
+            RestError::UnknownQueryParameters(names) => (
+                StatusCode::BAD_REQUEST,
+                format!("Unrecognized query parameters: {}", names.join(", ")),
+            ),
             RestError::NoPendingRequest => (
                 StatusCode::FORBIDDEN,
-                "The random value cannot currently be retrieved",
-            ).into_response(),
+                "The random value cannot currently be retrieved".to_string(),
+            ),
             RestError::PendingConfirmation => (
                 StatusCode::FORBIDDEN,
-                "The request needs additional confirmations before the random value can be retrieved. Try your request again later.",
-            )
-                .into_response(),
+                "The request needs additional confirmations before the random value can be retrieved. Try your request again later.".to_string(),
+            ),
             RestError::TemporarilyUnavailable => (
                 StatusCode::SERVICE_UNAVAILABLE,
-                "This service is temporarily unavailable",
-            )
-                .into_response(),
+                "This service is temporarily unavailable".to_string(),
+            ),
+            RestError::RateLimited { retry_after_secs } => (
+                StatusCode::TOO_MANY_REQUESTS,
+                format!("Too many requests. Try again in {} seconds.", retry_after_secs),
+            ),
             RestError::Unknown => (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                "An unknown error occurred processing the request",
+                "An unknown error occurred processing the request".to_string(),
+            ),
+        };
+
+        let body = Json(ErrorResponse {
+            code: self.code().to_string(),
+            message,
+        });
+        match self {
+            RestError::RateLimited { retry_after_secs } => (
+                status,
+                [(header::RETRY_AFTER, retry_after_secs.to_string())],
+                body,
             )
                 .into_response(),
+            _ => (status, body).into_response(),
         }
     }
 }
 
 pub fn routes(state: ApiState) -> Router<(), Body> {
-    Router::new()
-        .route("/", get(index))
-        .route("/live", get(live))
-        .route("/metrics", get(metrics))
-        .route("/ready", get(ready))
+    // Only the /v1 endpoints are rate limited so that health checks and metrics scrapes always
+    // get through.
+    let v1_routes = Router::new()
         .route("/v1/chains", get(chain_ids))
+        .route("/v1/chains/:chain_id/revelations", get(revelation_batch))
         .route(
             "/v1/chains/:chain_id/revelations/:sequence",
             get(revelation),
         )
+        .route(
+            "/v1/chains/:chain_id/revelations/:sequence/context",
+            get(revelation_context),
+        )
+        .route("/v1/chains/:chain_id/revealed", get(revealed))
+        .route(
+            "/v1/chains/:chain_id/hash_chain_params",
+            get(hash_chain_params),
+        )
+        .route("/v1/verify_reveal", get(verify_reveal))
+        .route_layer(middleware::from_fn_with_state(state.clone(), rate_limit));
+
+    Router::new()
+        .route("/", get(index))
+        .route("/live", get(live))
+        .route("/metrics", get(metrics))
+        .route("/ready", get(ready))
+        .merge(v1_routes)
         .with_state(state)
 }
 
+/// The routes served while the hash chains are computed at startup, which can take minutes. The
+/// server is live, but every other endpoint (including /ready) answers HTTP 503 until the chains
+/// are set up and `routes` are served instead.
+pub fn startup_routes() -> Router<(), Body> {
+    Router::new()
+        .route("/live", get(live))
+        .fallback(|| async { RestError::TemporarilyUnavailable })
+}
+
 /// We are registering the provider on chain with the following url:
 /// `{base_uri}/v1/chains/{chain_id}`
 /// The path and API are highly coupled. Please be sure to keep them consistent.
@@ -196,11 +660,29 @@ mod test {
         crate::{
             api::{
                 self,
+                reveal,
                 ApiState,
                 BinaryEncoding,
                 Blob,
                 BlockchainState,
+                ChainId,
+                ErrorResponse,
                 GetRandomValueResponse,
+                GetRandomValuesResponse,
+                GetRevelationContextResponse,
+                HashChainInfo,
+                HashChainParamsResponse,
+                HotSequences,
+                RequestContext,
+                RestError,
+                RevealCache,
+                RevealedValuesResponse,
+                Revelation,
+                VerifyRevealResponse,
+                CBOR_CONTENT_TYPE,
+                HASH_CHAIN_CONSTRUCTION,
+                MAX_BATCH_REVELATIONS,
+                MAX_VERIFY_REVEAL_HASHES,
             },
             chain::reader::{
                 mock::MockEntropyReader,
@@ -211,22 +693,44 @@ mod test {
                 PebbleHashChain,
             },
         },
-        axum::http::StatusCode,
+        axum::{
+            extract::connect_info::MockConnectInfo,
+            http::{
+                header,
+                HeaderValue,
+                StatusCode,
+            },
+        },
         axum_test::{
             TestResponse,
             TestServer,
         },
         ethers::prelude::Address,
+        futures::future::join_all,
         lazy_static::lazy_static,
         prometheus_client::registry::Registry,
         std::{
             collections::HashMap,
+            net::{
+                IpAddr,
+                Ipv4Addr,
+                SocketAddr,
+            },
             sync::Arc,
+            time::{
+                Duration,
+                Instant,
+            },
+        },
+        tokio::sync::{
+            watch,
+            RwLock,
         },
-        tokio::sync::RwLock,
     };
 
     const PROVIDER: Address = Address::zero();
+    const HOT_SEQUENCE_THRESHOLD: u64 = 5;
+    const REVEAL_CACHE_SIZE: usize = 2;
     lazy_static! {
         static ref OTHER_PROVIDER: Address = Address::from_low_u64_be(1);
         // Note: these chains are immutable. They are wrapped in Arc because we need Arcs to
@@ -242,6 +746,32 @@ mod test {
     }
 
     async fn test_server() -> (TestServer, Arc<MockEntropyReader>, Arc<MockEntropyReader>) {
+        test_server_with_strict_query_params(false).await
+    }
+
+    async fn test_server_with_strict_query_params(
+        strict_query_params: bool,
+    ) -> (TestServer, Arc<MockEntropyReader>, Arc<MockEntropyReader>) {
+        test_server_with_options(strict_query_params, None).await
+    }
+
+    async fn test_server_with_options(
+        strict_query_params: bool,
+        rate_limit_per_second: Option<u32>,
+    ) -> (TestServer, Arc<MockEntropyReader>, Arc<MockEntropyReader>) {
+        let (api_state, eth_read, avax_read) =
+            test_state(strict_query_params, rate_limit_per_second).await;
+
+        // The test server doesn't provide the address of the client, which the rate limiter needs.
+        let app =
+            api::routes(api_state).layer(MockConnectInfo(SocketAddr::from(([127, 0, 0, 1], 8080))));
+        (TestServer::new(app).unwrap(), eth_read, avax_read)
+    }
+
+    async fn test_state(
+        strict_query_params: bool,
+        rate_limit_per_second: Option<u32>,
+    ) -> (ApiState, Arc<MockEntropyReader>, Arc<MockEntropyReader>) {
         let eth_read = Arc::new(MockEntropyReader::with_requests(10, &[]));
 
         let eth_state = BlockchainState {
@@ -251,6 +781,7 @@ mod test {
             provider_address:       PROVIDER,
             reveal_delay_blocks:    1,
             confirmed_block_status: BlockStatus::Latest,
+            revealed_sequences:     None,
         };
 
         let metrics_registry = Arc::new(RwLock::new(Registry::default()));
@@ -264,16 +795,27 @@ mod test {
             provider_address:       PROVIDER,
             reveal_delay_blocks:    2,
             confirmed_block_status: BlockStatus::Latest,
+            revealed_sequences:     None,
         };
 
         let mut chains = HashMap::new();
         chains.insert("ethereum".into(), eth_state);
         chains.insert("avalanche".into(), avax_state);
 
-        let api_state = ApiState::new(chains, metrics_registry).await;
+        let api_state = ApiState::new(
+            chains,
+            metrics_registry,
+            HOT_SEQUENCE_THRESHOLD,
+            1,
+            REVEAL_CACHE_SIZE,
+            strict_query_params,
+            rate_limit_per_second,
+            watch::channel(true).1,
+            false,
+        )
+        .await;
 
-        let app = api::routes(api_state);
-        (TestServer::new(app).unwrap(), eth_read, avax_read)
+        (api_state, eth_read, avax_read)
     }
 
     async fn get_and_assert_status(
@@ -391,30 +933,653 @@ mod test {
         .await;
 
         // Bad chain ids fail
-        get_and_assert_status(
+        let response = get_and_assert_status(
             &server,
             "/v1/chains/not_a_chain/revelations/0",
             StatusCode::BAD_REQUEST,
         )
         .await;
+        response.assert_json(&ErrorResponse {
+            code:    "invalid_chain_id".to_string(),
+            message: "The chain id is not supported".to_string(),
+        });
 
-        // Requesting a number that has a request, but isn't in the HashChainState also fails.
-        // (Note that this shouldn't happen in normal operation)
+        // Requesting a number that isn't in the HashChainState fails as invalid, even if it has a
+        // request. (Note that this shouldn't happen in normal operation)
         get_and_assert_status(
             &server,
             "/v1/chains/avalanche/revelations/99",
-            StatusCode::FORBIDDEN,
+            StatusCode::BAD_REQUEST,
         )
         .await;
         avax_contract.insert(PROVIDER, 99, 1, false);
         get_and_assert_status(
             &server,
             "/v1/chains/avalanche/revelations/99",
-            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::BAD_REQUEST,
         )
         .await;
     }
 
+    #[tokio::test]
+    async fn test_revelation_cbor() {
+        let (server, eth_contract, _) = test_server().await;
+        eth_contract.insert(PROVIDER, 0, 1, false);
+
+        let response = server
+            .get("/v1/chains/ethereum/revelations/0")
+            .add_header(header::ACCEPT, HeaderValue::from_static(CBOR_CONTENT_TYPE))
+            .await;
+        response.assert_status(StatusCode::OK);
+        assert_eq!(response.header(header::CONTENT_TYPE), CBOR_CONTENT_TYPE);
+        assert_eq!(
+            serde_cbor::from_slice::<GetRandomValueResponse>(response.as_bytes()).unwrap(),
+            GetRandomValueResponse {
+                value: Blob::new(BinaryEncoding::Hex, ETH_CHAIN.reveal(0).unwrap()),
+            }
+        );
+
+        // JSON remains the default
+        let response = server
+            .get("/v1/chains/ethereum/revelations/0")
+            .add_query_param("encoding", "array")
+            .add_header(header::ACCEPT, HeaderValue::from_static("*/*"))
+            .await;
+        response.assert_status(StatusCode::OK);
+        response.assert_json(&GetRandomValueResponse {
+            value: Blob::new(BinaryEncoding::Array, ETH_CHAIN.reveal(0).unwrap()),
+        });
+    }
+
+    #[tokio::test]
+    async fn test_revelation_context() {
+        let (server, eth_contract, _) = test_server().await;
+
+        get_and_assert_status(
+            &server,
+            "/v1/chains/ethereum/revelations/0/context",
+            StatusCode::FORBIDDEN,
+        )
+        .await;
+
+        eth_contract.insert(PROVIDER, 0, 1, false);
+
+        for block_number in [None, Some(1)] {
+            let mut request = server.get("/v1/chains/ethereum/revelations/0/context");
+            if let Some(block_number) = block_number {
+                request = request.add_query_param("block_number", block_number);
+            }
+            let response = request.await;
+            response.assert_status(StatusCode::OK);
+            response.assert_json(&GetRevelationContextResponse {
+                value:   Some(Blob::new(BinaryEncoding::Hex, ETH_CHAIN.reveal(0).unwrap())),
+                request: Some(RequestContext {
+                    provider:        PROVIDER,
+                    sequence_number: 0,
+                    requester:       Address::zero(),
+                    block_number:    1,
+                    commitment:      [0; 32],
+                    num_hashes:      0,
+                    use_blockhash:   false,
+                }),
+            });
+        }
+
+        // Only the selected fields are included
+        let response = server
+            .get("/v1/chains/ethereum/revelations/0/context")
+            .add_query_param("fields", "value")
+            .await;
+        response.assert_status(StatusCode::OK);
+        response.assert_json(&serde_json::json!({
+            "value": Blob::new(BinaryEncoding::Hex, ETH_CHAIN.reveal(0).unwrap()),
+        }));
+
+        let response = server
+            .get("/v1/chains/ethereum/revelations/0/context")
+            .add_query_param("fields", "request,value")
+            .await;
+        response.assert_status(StatusCode::OK);
+        let response: GetRevelationContextResponse = response.json();
+        assert!(response.value.is_some() && response.request.is_some());
+
+        server
+            .get("/v1/chains/ethereum/revelations/0/context")
+            .add_query_param("fields", "proof")
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+
+        get_and_assert_status(
+            &server,
+            "/v1/chains/not_a_chain/revelations/0/context",
+            StatusCode::BAD_REQUEST,
+        )
+        .await;
+
+        // The context reveals are recorded like the other reveals
+        let response = get_and_assert_status(&server, "/metrics", StatusCode::OK).await;
+        let text = response.text();
+        assert!(text.contains(r#"reveals_total{chain_id="ethereum",outcome="ok"} 4"#));
+        assert!(
+            text.contains(r#"reveals_total{chain_id="ethereum",outcome="no_pending_request"} 1"#)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_revelations() {
+        // The requests are made directly rather than through the test server, which handles a
+        // single request at a time.
+        let (state, eth_contract, _) = test_state(false, None).await;
+        let chain_id: ChainId = "ethereum".into();
+
+        eth_contract.insert(PROVIDER, 0, 1, false);
+
+        // Identical requests that arrive at the same time share a single reveal, and every caller
+        // receives the same result. Hold the chain lookup until the first request makes it so that
+        // all the requests are guaranteed to overlap.
+        let gate = eth_contract.hold_requests().await;
+        let (results, _) = tokio::join!(
+            join_all((0..5).map(|_| reveal(&state, None, &chain_id, 0))),
+            async {
+                while eth_contract.request_calls() == 0 {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+                drop(gate);
+            }
+        );
+        assert_eq!(eth_contract.request_calls(), 1);
+        for result in results {
+            assert_eq!(result.unwrap(), ETH_CHAIN.reveal(0).unwrap());
+        }
+
+        // Nothing is left behind once the requests complete, so later requests check the chain
+        // again.
+        assert!(state.in_flight_reveals.lock().unwrap().is_empty());
+        reveal(&state, None, &chain_id, 0).await.unwrap();
+        assert_eq!(eth_contract.request_calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_revelation() {
+        let (state, eth_contract, _) = test_state(false, None).await;
+        let chain_id: ChainId = "ethereum".into();
+
+        eth_contract.insert(PROVIDER, 0, 1, false);
+
+        // A request that is dropped while its reveal is in flight (e.g., because the client
+        // disconnected) doesn't leave the reveal behind.
+        let gate = eth_contract.hold_requests().await;
+        tokio::select! {
+            _ = reveal(&state, None, &chain_id, 0) => panic!("The reveal should be held"),
+            _ = async {
+                while eth_contract.request_calls() == 0 {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            } => {}
+        }
+        assert!(state.in_flight_reveals.lock().unwrap().is_empty());
+        drop(gate);
+    }
+
+    #[tokio::test]
+    async fn test_hot_sequence_rate_limit() {
+        let (server, eth_contract, _) = test_server().await;
+
+        eth_contract.insert(PROVIDER, 0, 9, false);
+
+        // Failed reveals don't count towards the hot sequence threshold.
+        for _ in 0..=HOT_SEQUENCE_THRESHOLD {
+            get_and_assert_status(
+                &server,
+                "/v1/chains/ethereum/revelations/1",
+                StatusCode::FORBIDDEN,
+            )
+            .await;
+        }
+
+        for _ in 0..=HOT_SEQUENCE_THRESHOLD {
+            get_and_assert_status(&server, "/v1/chains/ethereum/revelations/0", StatusCode::OK)
+                .await;
+        }
+
+        // Hot sequences are still checked against the blockchain ...
+        eth_contract.set_block_number(5);
+        get_and_assert_status(
+            &server,
+            "/v1/chains/ethereum/revelations/0",
+            StatusCode::FORBIDDEN,
+        )
+        .await;
+        // ... but only once per second.
+        let response = get_and_assert_status(
+            &server,
+            "/v1/chains/ethereum/revelations/0",
+            StatusCode::TOO_MANY_REQUESTS,
+        )
+        .await;
+        assert_eq!(response.header(header::RETRY_AFTER), "1");
+        // The context endpoint shares the limit, including when it reads an older block.
+        server
+            .get("/v1/chains/ethereum/revelations/0/context")
+            .add_query_param("block_number", 9)
+            .await
+            .assert_status(StatusCode::TOO_MANY_REQUESTS);
+
+        // Other sequences aren't affected.
+        eth_contract.set_block_number(10);
+        eth_contract.insert(PROVIDER, 1, 9, false);
+        get_and_assert_status(&server, "/v1/chains/ethereum/revelations/1", StatusCode::OK).await;
+
+        let response = get_and_assert_status(&server, "/metrics", StatusCode::OK).await;
+        let metrics = response.text();
+        assert!(metrics.contains(r#"hot_sequence_reveals_total{chain_id="ethereum"} 3"#));
+    }
+
+    #[tokio::test]
+    async fn test_hot_sequence_rate_limit_per_client() {
+        let (state, eth_contract, _) = test_state(false, None).await;
+        let chain_id: ChainId = "ethereum".into();
+        let client = Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)));
+        let other_client = Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)));
+
+        eth_contract.insert(PROVIDER, 0, 1, false);
+
+        for _ in 0..=HOT_SEQUENCE_THRESHOLD {
+            reveal(&state, client, &chain_id, 0).await.unwrap();
+        }
+        reveal(&state, client, &chain_id, 0).await.unwrap();
+        assert!(matches!(
+            reveal(&state, client, &chain_id, 0).await,
+            Err(RestError::RateLimited { .. })
+        ));
+
+        // A client stuck in a reveal loop doesn't slow down the other clients.
+        for _ in 0..=HOT_SEQUENCE_THRESHOLD {
+            reveal(&state, other_client, &chain_id, 0).await.unwrap();
+        }
+    }
+
+    #[test]
+    fn test_hot_sequences_decay() {
+        let key = (None, "ethereum".to_string(), 0);
+        let start = Instant::now();
+        let mut hot_sequences = HotSequences::new(start);
+        for _ in 0..10 {
+            hot_sequences.record(&key, start);
+        }
+        assert_eq!(hot_sequences.count(&key, start), 10);
+
+        // The reveals of the previous window fade out as the current window goes on.
+        let next_window = start + HotSequences::WINDOW;
+        assert_eq!(hot_sequences.count(&key, next_window), 10);
+        hot_sequences.record(&key, next_window);
+        assert_eq!(
+            hot_sequences.count(&key, next_window + HotSequences::WINDOW / 2),
+            6
+        );
+
+        // Sequences that aren't revealed anymore are forgotten.
+        assert_eq!(
+            hot_sequences.count(&key, start + 3 * HotSequences::WINDOW),
+            0
+        );
+        assert!(hot_sequences.previous.is_empty());
+        assert!(hot_sequences.current.is_empty());
+    }
+
+    #[test]
+    fn test_reveal_cache_evicts_least_recently_used() {
+        let key = |sequence| (PROVIDER, "ethereum".to_string(), sequence);
+        let mut cache = RevealCache::new(2);
+        cache.insert(key(0), [0; 32]);
+        cache.insert(key(1), [1; 32]);
+        // Reading 0 makes 1 the least recently used value.
+        assert_eq!(cache.get(&key(0)), Some([0; 32]));
+        cache.insert(key(2), [2; 32]);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&key(1)), None);
+        assert_eq!(cache.get(&key(0)), Some([0; 32]));
+        assert_eq!(cache.get(&key(2)), Some([2; 32]));
+
+        // Values of other providers are cached separately.
+        assert_eq!(
+            cache.get(&(*OTHER_PROVIDER, "ethereum".to_string(), 0)),
+            None
+        );
+
+        let mut cache = RevealCache::new(0);
+        cache.insert(key(0), [0; 32]);
+        assert!(cache.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reveal_cache() {
+        let (server, eth_contract, _) = test_server().await;
+
+        eth_contract.insert(PROVIDER, 0, 1, false);
+        for _ in 0..3 {
+            let response =
+                get_and_assert_status(&server, "/v1/chains/ethereum/revelations/0", StatusCode::OK)
+                    .await;
+            response.assert_json(&GetRandomValueResponse {
+                value: Blob::new(BinaryEncoding::Hex, ETH_CHAIN.reveal(0).unwrap()),
+            });
+        }
+
+        // Cached values are still only served for requested sequence numbers.
+        eth_contract.set_block_number(0);
+        get_and_assert_status(
+            &server,
+            "/v1/chains/ethereum/revelations/0",
+            StatusCode::FORBIDDEN,
+        )
+        .await;
+
+        let response = get_and_assert_status(&server, "/metrics", StatusCode::OK).await;
+        let metrics = response.text();
+        assert!(metrics.contains(r#"reveal_cache_misses_total{chain_id="ethereum"} 1"#));
+        assert!(metrics.contains(r#"reveal_cache_hits_total{chain_id="ethereum"} 2"#));
+    }
+
+    #[tokio::test]
+    async fn test_reveal_metrics() {
+        let (server, eth_contract, _) = test_server().await;
+
+        eth_contract.insert(PROVIDER, 3, 1, false);
+        eth_contract.insert(PROVIDER, 7, 1, false);
+        get_and_assert_status(&server, "/v1/chains/ethereum/revelations/7", StatusCode::OK).await;
+        get_and_assert_status(&server, "/v1/chains/ethereum/revelations/3", StatusCode::OK).await;
+        get_and_assert_status(
+            &server,
+            "/v1/chains/ethereum/revelations/4",
+            StatusCode::FORBIDDEN,
+        )
+        .await;
+        get_and_assert_status(
+            &server,
+            "/v1/chains/not-a-chain/revelations/0",
+            StatusCode::BAD_REQUEST,
+        )
+        .await;
+
+        let response = get_and_assert_status(&server, "/metrics", StatusCode::OK).await;
+        let text = response.text();
+        assert!(text.contains(r#"reveals_total{chain_id="ethereum",outcome="ok"} 2"#));
+        assert!(
+            text.contains(r#"reveals_total{chain_id="ethereum",outcome="no_pending_request"} 1"#)
+        );
+        assert!(text.contains(r#"reveal_latency_count{chain_id="ethereum"} 3"#));
+        // The gauge keeps the highest sequence even if a lower one is revealed later.
+        assert!(text.contains(r#"highest_revealed_sequence{chain_id="ethereum"} 7"#));
+        assert!(!text.contains("not-a-chain"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit() {
+        let (server, eth_contract, _) = test_server_with_options(false, Some(2)).await;
+        eth_contract.insert(PROVIDER, 0, 1, false);
+
+        get_and_assert_status(&server, "/v1/chains/ethereum/revelations/0", StatusCode::OK).await;
+        get_and_assert_status(&server, "/v1/chains", StatusCode::OK).await;
+
+        // The limit is shared by all the /v1 endpoints.
+        let response = get_and_assert_status(
+            &server,
+            "/v1/chains/ethereum/revelations/0",
+            StatusCode::TOO_MANY_REQUESTS,
+        )
+        .await;
+        assert_eq!(response.header(header::RETRY_AFTER), "1");
+        assert_eq!(response.json::<ErrorResponse>().code, "rate_limited");
+
+        // Health checks and metrics are not limited.
+        get_and_assert_status(&server, "/live", StatusCode::OK).await;
+        get_and_assert_status(&server, "/metrics", StatusCode::OK).await;
+    }
+
+    #[tokio::test]
+    async fn test_startup_routes() {
+        let server = TestServer::new(api::startup_routes()).unwrap();
+
+        get_and_assert_status(&server, "/live", StatusCode::OK).await;
+        get_and_assert_status(&server, "/ready", StatusCode::SERVICE_UNAVAILABLE).await;
+        let response = get_and_assert_status(
+            &server,
+            "/v1/chains/ethereum/revelations/0",
+            StatusCode::SERVICE_UNAVAILABLE,
+        )
+        .await;
+        assert_eq!(
+            response.json::<ErrorResponse>().code,
+            "temporarily_unavailable"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ready() {
+        let (mut state, eth_contract, _) = test_state(false, None).await;
+        let (tx_ready, rx_ready) = watch::channel(false);
+        state.ready = rx_ready;
+        state.ready_rpc_check = true;
+        let server = TestServer::new(api::routes(state)).unwrap();
+
+        get_and_assert_status(&server, "/ready", StatusCode::SERVICE_UNAVAILABLE).await;
+        tx_ready.send(true).unwrap();
+        get_and_assert_status(&server, "/ready", StatusCode::OK).await;
+
+        // The instance isn't ready while the RPC endpoint of any chain is unreachable.
+        eth_contract.set_unreachable(true);
+        get_and_assert_status(&server, "/ready", StatusCode::SERVICE_UNAVAILABLE).await;
+        get_and_assert_status(&server, "/live", StatusCode::OK).await;
+        eth_contract.set_unreachable(false);
+        get_and_assert_status(&server, "/ready", StatusCode::OK).await;
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_batch() {
+        let (server, eth_contract, _) = test_server_with_options(false, Some(2)).await;
+        for sequence in 0..5 {
+            eth_contract.insert(PROVIDER, sequence, 1, false);
+        }
+
+        // A batch counts as one request per revealed value, so the client has to wait for all of
+        // them to be refilled.
+        server
+            .get("/v1/chains/ethereum/revelations")
+            .add_query_param("start", 0)
+            .add_query_param("count", 5)
+            .await
+            .assert_status(StatusCode::OK);
+        let response = get_and_assert_status(
+            &server,
+            "/v1/chains/ethereum/revelations/0",
+            StatusCode::TOO_MANY_REQUESTS,
+        )
+        .await;
+        assert_eq!(response.header(header::RETRY_AFTER), "2");
+    }
+
+    #[tokio::test]
+    async fn test_revelation_batch() {
+        let (server, eth_contract, avax_contract) = test_server().await;
+        let revelation_batch = |chain_id: &str, start: u64, count: u64| {
+            server
+                .get(&format!("/v1/chains/{}/revelations", chain_id))
+                .add_query_param("start", start)
+                .add_query_param("count", count)
+        };
+
+        eth_contract.insert(PROVIDER, 5, 1, false);
+        eth_contract.insert(PROVIDER, 6, 1, false);
+        eth_contract.insert(PROVIDER, 7, 1, false);
+        let response = revelation_batch("ethereum", 5, 3).await;
+        response.assert_status(StatusCode::OK);
+        response.assert_json(&GetRandomValuesResponse {
+            values: (5..8)
+                .map(|sequence| Blob::new(BinaryEncoding::Hex, ETH_CHAIN.reveal(sequence).unwrap()))
+                .collect(),
+        });
+
+        let response = revelation_batch("ethereum", 5, 1)
+            .add_query_param("encoding", "base64")
+            .await;
+        response.assert_status(StatusCode::OK);
+        response.assert_json(&GetRandomValuesResponse {
+            values: vec![Blob::new(
+                BinaryEncoding::Base64,
+                ETH_CHAIN.reveal(5).unwrap(),
+            )],
+        });
+
+        // Every sequence number in the range must have been requested...
+        revelation_batch("ethereum", 5, 4)
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+
+        // ...and confirmed.
+        avax_contract.insert(PROVIDER, 100, 1, false);
+        avax_contract.insert(PROVIDER, 101, 9, false);
+        revelation_batch("avalanche", 100, 2)
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+
+        revelation_batch("ethereum", 5, 0)
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+        let response = revelation_batch("ethereum", 5, MAX_BATCH_REVELATIONS + 1).await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.json::<ErrorResponse>().code,
+            "invalid_revelation_count"
+        );
+        revelation_batch("ethereum", u64::MAX, 2)
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+        revelation_batch("not-a-chain", 5, 1)
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_revelation_out_of_range() {
+        let (server, eth_contract, _) = test_server().await;
+
+        // Even if a request exists on-chain, sequence numbers outside of the hash chains are
+        // rejected as invalid rather than failing with an internal error.
+        eth_contract.insert(PROVIDER, 999, 1, false);
+        eth_contract.insert(PROVIDER, 1000, 1, false);
+        get_and_assert_status(
+            &server,
+            "/v1/chains/ethereum/revelations/999",
+            StatusCode::OK,
+        )
+        .await;
+        let response = get_and_assert_status(
+            &server,
+            "/v1/chains/ethereum/revelations/1000",
+            StatusCode::BAD_REQUEST,
+        )
+        .await;
+        assert_eq!(
+            response.json::<ErrorResponse>().code,
+            "invalid_sequence_number"
+        );
+        get_and_assert_status(
+            &server,
+            "/v1/chains/ethereum/revelations/1000/context",
+            StatusCode::BAD_REQUEST,
+        )
+        .await;
+
+        get_and_assert_status(
+            &server,
+            &format!("/v1/chains/avalanche/revelations/{}", u64::MAX),
+            StatusCode::BAD_REQUEST,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_revealed() {
+        let (server, eth_contract, avax_contract) = test_server().await;
+        let revealed = |chain_id: &str, from: u64, limit: u64| {
+            server
+                .get(&format!("/v1/chains/{}/revealed", chain_id))
+                .add_query_param("from", from)
+                .add_query_param("limit", limit)
+        };
+        let revelations = |sequences: std::ops::Range<u64>| {
+            sequences
+                .map(|sequence| Revelation {
+                    sequence,
+                    value: Blob::new(BinaryEncoding::Hex, ETH_CHAIN.reveal(sequence).unwrap()),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Nothing has been requested yet.
+        let response = revealed("ethereum", 0, 10).await;
+        response.assert_status(StatusCode::OK);
+        response.assert_json(&RevealedValuesResponse {
+            revelations: vec![],
+            next:        None,
+        });
+
+        // Sequence numbers 0 to 4 have been requested, but only the requests up to block 9 are
+        // confirmed at block 10.
+        eth_contract.insert(PROVIDER, 2, 9, false);
+        eth_contract.insert(PROVIDER, 4, 10, false);
+        eth_contract.insert(*OTHER_PROVIDER, 7, 1, false);
+        let response = revealed("ethereum", 0, 2).await;
+        response.assert_status(StatusCode::OK);
+        response.assert_json(&RevealedValuesResponse {
+            revelations: revelations(0..2),
+            next:        Some(2),
+        });
+        let response = revealed("ethereum", 2, 2).await;
+        response.assert_status(StatusCode::OK);
+        response.assert_json(&RevealedValuesResponse {
+            revelations: revelations(2..3),
+            next:        None,
+        });
+
+        eth_contract.set_block_number(11);
+        let response = revealed("ethereum", 2, 10).await;
+        response.assert_status(StatusCode::OK);
+        response.assert_json(&RevealedValuesResponse {
+            revelations: revelations(2..5),
+            next:        None,
+        });
+
+        // Sequence numbers before the first configured commitment are skipped.
+        avax_contract.insert(PROVIDER, 100, 1, false);
+        let response = revealed("avalanche", 98, 10).await;
+        response.assert_status(StatusCode::OK);
+        response.assert_json(&RevealedValuesResponse {
+            revelations: vec![Revelation {
+                sequence: 100,
+                value:    Blob::new(BinaryEncoding::Hex, AVAX_CHAIN.reveal(100).unwrap()),
+            }],
+            next:        None,
+        });
+
+        revealed("ethereum", 0, 0)
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+        revealed("ethereum", 0, MAX_BATCH_REVELATIONS + 1)
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+        revealed("not-a-chain", 0, 1)
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+
+        // Pending requests are revealed like by the revelation endpoint.
+        let response = get_and_assert_status(&server, "/metrics", StatusCode::OK).await;
+        assert!(response
+            .text()
+            .contains(r#"reveals_total{chain_id="ethereum",outcome="ok"} 3"#));
+    }
+
     #[tokio::test]
     async fn test_revelation_confirmation_delay() {
         let (server, eth_contract, avax_contract) = test_server().await;
@@ -488,4 +1653,105 @@ mod test {
         )
         .await;
     }
+
+    #[tokio::test]
+    async fn test_verify_reveal() {
+        let (server, _, _) = test_server().await;
+        let root = hex::encode(ETH_CHAIN.reveal(0).unwrap());
+        let value = hex::encode(ETH_CHAIN.reveal(5).unwrap());
+
+        let verify_reveal = |sequence: u64, root_sequence: Option<u64>| {
+            let mut request = server
+                .get("/v1/verify_reveal")
+                .add_query_param("sequence", sequence)
+                .add_query_param("value", &value)
+                .add_query_param("root", &root);
+            if let Some(root_sequence) = root_sequence {
+                request = request.add_query_param("root_sequence", root_sequence);
+            }
+            request
+        };
+
+        let response = verify_reveal(5, None).await;
+        response.assert_status(StatusCode::OK);
+        response.assert_json(&VerifyRevealResponse { valid: true });
+
+        // The value doesn't hash up to the root from another sequence number
+        let response = verify_reveal(4, None).await;
+        response.assert_status(StatusCode::OK);
+        response.assert_json(&VerifyRevealResponse { valid: false });
+
+        // The root can be a commitment at any sequence number
+        let response = verify_reveal(105, Some(100)).await;
+        response.assert_status(StatusCode::OK);
+        response.assert_json(&VerifyRevealResponse { valid: true });
+
+        verify_reveal(5, Some(6))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+        verify_reveal(MAX_VERIFY_REVEAL_HASHES + 1, None)
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_strict_query_params() {
+        let (server, eth_contract, _) = test_server().await;
+        eth_contract.insert(PROVIDER, 0, 1, false);
+
+        // Unknown query parameters are ignored by default
+        server
+            .get("/v1/chains/ethereum/revelations/0")
+            .add_query_param("encodign", "array")
+            .await
+            .assert_status(StatusCode::OK);
+
+        let (server, eth_contract, _) = test_server_with_strict_query_params(true).await;
+        eth_contract.insert(PROVIDER, 0, 1, false);
+
+        let response = server
+            .get("/v1/chains/ethereum/revelations/0")
+            .add_query_param("encodign", "array")
+            .add_query_param("sequnce", 0)
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+        response.assert_json(&ErrorResponse {
+            code:    "unknown_query_parameters".to_string(),
+            message: "Unrecognized query parameters: encodign, sequnce".to_string(),
+        });
+
+        server
+            .get("/v1/chains/ethereum/revelations/0/context")
+            .add_query_param("encoding", "array")
+            .add_query_param("block_number", 1)
+            .await
+            .assert_status(StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_hash_chain_params() {
+        let (server, _, _) = test_server().await;
+
+        let response = get_and_assert_status(
+            &server,
+            "/v1/chains/avalanche/hash_chain_params",
+            StatusCode::OK,
+        )
+        .await;
+        response.assert_json(&HashChainParamsResponse {
+            hash:         "keccak256".to_string(),
+            construction: HASH_CHAIN_CONSTRUCTION.to_string(),
+            hash_chains:  vec![HashChainInfo {
+                start_sequence: 100,
+                chain_length:   1000,
+            }],
+        });
+
+        get_and_assert_status(
+            &server,
+            "/v1/chains/not_a_chain/hash_chain_params",
+            StatusCode::BAD_REQUEST,
+        )
+        .await;
+    }
 }
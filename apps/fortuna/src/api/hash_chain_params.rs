@@ -0,0 +1,97 @@
+use {
+    crate::api::{
+        ChainId,
+        RequestLabel,
+        RestError,
+    },
+    anyhow::Result,
+    axum::{
+        extract::{
+            Path,
+            State,
+        },
+        Json,
+    },
+    utoipa::{
+        IntoParams,
+        ToSchema,
+    },
+};
+
+/// The hash function used to build the hash chains.
+pub const HASH_CHAIN_HASH_FUNCTION: &str = "keccak256";
+
+/// How the random values of a hash chain relate to each other and to the provider commitment.
+pub const HASH_CHAIN_CONSTRUCTION: &str = "The random value of sequence number n is keccak256(value of n + 1), using the full 32 byte digest of the 32 byte value without any truncation or domain separation. The value at the start sequence number of a hash chain is the provider commitment, so the value of sequence n hashes to the commitment after n - start_sequence hashes.";
+
+/// Get the parameters of the hash chains used to serve random values on a blockchain.
+///
+/// Independent verifiers can use these parameters to check the revealed values against the
+/// provider commitment (see also `/v1/verify_reveal`).
+#[utoipa::path(
+get,
+path = "/v1/chains/{chain_id}/hash_chain_params",
+responses(
+(status = 200, description = "Hash chain parameters successfully retrieved", body = HashChainParamsResponse),
+(status = 400, description = "The chain id is invalid", body = ErrorResponse),
+),
+params(HashChainParamsPathParams)
+)]
+pub async fn hash_chain_params(
+    State(state): State<crate::api::ApiState>,
+    Path(HashChainParamsPathParams { chain_id }): Path<HashChainParamsPathParams>,
+) -> Result<Json<HashChainParamsResponse>, RestError> {
+    state
+        .metrics
+        .http_requests
+        .get_or_create(&RequestLabel {
+            value: "/v1/chains/{chain_id}/hash_chain_params".to_string(),
+        })
+        .inc();
+
+    let chain_state = state
+        .chains
+        .get(&chain_id)
+        .ok_or(RestError::InvalidChainId)?;
+
+    Ok(Json(HashChainParamsResponse {
+        hash:         HASH_CHAIN_HASH_FUNCTION.to_string(),
+        construction: HASH_CHAIN_CONSTRUCTION.to_string(),
+        hash_chains:  chain_state
+            .state
+            .offsets
+            .iter()
+            .zip(chain_state.state.hash_chains.iter())
+            .map(|(offset, hash_chain)| HashChainInfo {
+                start_sequence: *offset as u64,
+                chain_length:   hash_chain.len() as u64,
+            })
+            .collect(),
+    }))
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, IntoParams)]
+#[into_params(parameter_in=Path)]
+pub struct HashChainParamsPathParams {
+    #[param(value_type = String)]
+    pub chain_id: ChainId,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, ToSchema, PartialEq)]
+pub struct HashChainParamsResponse {
+    /// The hash function used to build the hash chains, e.g. `keccak256`.
+    pub hash:         String,
+    /// A description of how the hash chains are built.
+    pub construction: String,
+    /// The hash chains used on this blockchain, sorted by start sequence number. There is more
+    /// than one if the provider rotated its commitment.
+    pub hash_chains:  Vec<HashChainInfo>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, ToSchema, PartialEq)]
+pub struct HashChainInfo {
+    /// The sequence number of the first value of the hash chain, whose value is the commitment.
+    pub start_sequence: u64,
+    /// The number of values in the hash chain.
+    pub chain_length:   u64,
+}
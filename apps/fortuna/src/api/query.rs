@@ -0,0 +1,60 @@
+use {
+    crate::api::{
+        ApiState,
+        RestError,
+    },
+    axum::{
+        async_trait,
+        extract::{
+            FromRequestParts,
+            Query,
+        },
+        http::request::Parts,
+        response::{
+            IntoResponse,
+            Response,
+        },
+    },
+    serde::de::DeserializeOwned,
+};
+
+/// The query parameters accepted by an endpoint.
+pub trait QueryParams: DeserializeOwned {
+    /// The names of the parameters, which must match the serialized names of the fields.
+    const NAMES: &'static [&'static str];
+}
+
+/// Like `Query`, but if `ApiState::strict_query_params` is set, requests with query parameters
+/// that the endpoint doesn't recognize are rejected instead of silently ignoring them. This helps
+/// clients catch typos (e.g., `sequnce` instead of `sequence`).
+pub struct StrictQuery<T>(pub T);
+
+#[async_trait]
+impl<T: QueryParams> FromRequestParts<ApiState> for StrictQuery<T> {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &ApiState) -> Result<Self, Response> {
+        if state.strict_query_params {
+            let unknown_params = unknown_query_params(parts.uri.query().unwrap_or(""), T::NAMES);
+            if !unknown_params.is_empty() {
+                return Err(RestError::UnknownQueryParameters(unknown_params).into_response());
+            }
+        }
+
+        let Query(params) = Query::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        Ok(StrictQuery(params))
+    }
+}
+
+/// The names of the parameters of `query` that aren't in `known_names`, without duplicates.
+fn unknown_query_params(query: &str, known_names: &[&str]) -> Vec<String> {
+    let mut unknown_params: Vec<String> = vec![];
+    for (name, _) in url::form_urlencoded::parse(query.as_bytes()) {
+        if !known_names.contains(&name.as_ref()) && !unknown_params.iter().any(|n| *n == name) {
+            unknown_params.push(name.into_owned());
+        }
+    }
+    unknown_params
+}
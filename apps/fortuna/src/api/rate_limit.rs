@@ -0,0 +1,296 @@
+use {
+    crate::api::{
+        ApiState,
+        RestError,
+    },
+    axum::{
+        extract::{
+            ConnectInfo,
+            State,
+        },
+        http::Request,
+        middleware::Next,
+        response::{
+            IntoResponse,
+            Response,
+        },
+    },
+    std::{
+        collections::{
+            BTreeMap,
+            HashMap,
+        },
+        hash::Hash,
+        net::{
+            IpAddr,
+            Ipv6Addr,
+            SocketAddr,
+        },
+        sync::Mutex,
+        time::{
+            Duration,
+            Instant,
+        },
+    },
+};
+
+/// Limits the number of requests for each key (e.g., each client IP) using a token bucket per
+/// key. Every bucket holds up to one second worth of requests and is refilled continuously, so
+/// clients can burst up to the limit and then make requests at the limit's rate.
+///
+/// Note that clients are identified by the address of the connection, so all the clients behind
+/// the same proxy share a bucket.
+pub struct RateLimiter<K = IpAddr> {
+    requests_per_second: f64,
+    max_tracked_keys:    usize,
+    buckets:             Mutex<Buckets<K>>,
+}
+
+/// The token buckets of the tracked keys, and the keys ordered by when they were last used.
+struct Buckets<K> {
+    buckets:   HashMap<K, TokenBucket>,
+    last_used: BTreeMap<u64, K>,
+    clock:     u64,
+}
+
+struct TokenBucket {
+    tokens:      f64,
+    last_refill: Instant,
+    /// The entry of the key in `Buckets::last_used`.
+    last_used:   u64,
+}
+
+impl<K: Clone + Eq + Hash> RateLimiter<K> {
+    /// The maximum number of keys to track. Once reached, the least recently used key is evicted
+    /// so that memory usage stays bounded. Clients that are actively making requests are never
+    /// evicted by clients that make a single request each.
+    const MAX_TRACKED_KEYS: usize = 100_000;
+
+    pub fn new(requests_per_second: u32) -> RateLimiter<K> {
+        Self::with_max_tracked_keys(requests_per_second, Self::MAX_TRACKED_KEYS)
+    }
+
+    fn with_max_tracked_keys(requests_per_second: u32, max_tracked_keys: usize) -> RateLimiter<K> {
+        RateLimiter {
+            requests_per_second: f64::from(requests_per_second),
+            max_tracked_keys,
+            buckets: Mutex::new(Buckets {
+                buckets:   HashMap::new(),
+                last_used: BTreeMap::new(),
+                clock:     0,
+            }),
+        }
+    }
+
+    /// Take a token from the bucket of `key` at time `now`. If the bucket is empty, return how long
+    /// the client has to wait before its next request is accepted.
+    pub fn check(&self, key: K, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = self.refilled_bucket(&mut buckets, key, now);
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / self.requests_per_second,
+            ))
+        }
+    }
+
+    /// Take `tokens` more tokens from the bucket of `key` at time `now`, for a request that was
+    /// accepted by `check` but does the work of several requests (e.g., a batch). The bucket can
+    /// go below zero, in which case the client has to wait until it is refilled before its next
+    /// request is accepted.
+    pub fn charge(&self, key: K, tokens: u64, now: Instant) {
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = self.refilled_bucket(&mut buckets, key, now);
+        bucket.tokens -= tokens as f64;
+    }
+
+    /// The bucket of `key`, refilled up to `now` and marked as the most recently used one.
+    fn refilled_bucket<'a>(
+        &self,
+        buckets: &'a mut Buckets<K>,
+        key: K,
+        now: Instant,
+    ) -> &'a mut TokenBucket {
+        let Buckets {
+            buckets,
+            last_used,
+            clock,
+        } = buckets;
+
+        if buckets.len() >= self.max_tracked_keys && !buckets.contains_key(&key) {
+            if let Some((_, least_recently_used)) = last_used.pop_first() {
+                buckets.remove(&least_recently_used);
+            }
+        }
+        let bucket = buckets.entry(key.clone()).or_insert(TokenBucket {
+            tokens:      self.requests_per_second,
+            last_refill: now,
+            // Ticks start at 1, so new buckets have no entry in last_used to remove.
+            last_used:   0,
+        });
+        last_used.remove(&bucket.last_used);
+        *clock += 1;
+        bucket.last_used = *clock;
+        last_used.insert(*clock, key);
+
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * self.requests_per_second)
+            .min(self.requests_per_second);
+        bucket.last_refill = now;
+        bucket
+    }
+}
+
+/// The rate limit key of a client. IPv6 clients are limited per /64 prefix, since a single host
+/// is usually assigned a whole /64 and could otherwise get a new bucket for every address.
+fn client_key(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V4(_) => ip,
+        IpAddr::V6(ipv6) => match ipv6.to_ipv4_mapped() {
+            Some(ipv4) => IpAddr::V4(ipv4),
+            None => IpAddr::V6(Ipv6Addr::from(u128::from(ipv6) & !u128::from(u64::MAX))),
+        },
+    }
+}
+
+/// The rate limit key of the client of a request, or None if its address is unknown.
+pub fn client_ip(connect_info: Option<ConnectInfo<SocketAddr>>) -> Option<IpAddr> {
+    connect_info.map(|ConnectInfo(addr)| client_key(addr.ip()))
+}
+
+/// Reject requests from clients that exceed `ApiState::rate_limiter` with HTTP 429. Requests are
+/// not limited if rate limiting is disabled or the address of the client is unknown.
+pub async fn rate_limit<B>(
+    State(state): State<ApiState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    if let (Some(rate_limiter), Some(client)) = (&state.rate_limiter, client_ip(connect_info)) {
+        if let Err(retry_after) = rate_limiter.check(client, Instant::now()) {
+            return RestError::rate_limited(retry_after).into_response();
+        }
+    }
+    next.run(request).await
+}
+
+/// Charge a request that reveals `count` random values as `count` requests, so that batches don't
+/// bypass the rate limit. The middleware has already charged the request itself.
+pub fn charge_revelations(state: &ApiState, client: Option<IpAddr>, count: u64) {
+    if let (Some(rate_limiter), Some(client)) = (&state.rate_limiter, client) {
+        rate_limiter.charge(client, count.saturating_sub(1), Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::{
+            client_key,
+            RateLimiter,
+        },
+        std::{
+            net::{
+                IpAddr,
+                Ipv4Addr,
+                Ipv6Addr,
+            },
+            time::{
+                Duration,
+                Instant,
+            },
+        },
+    };
+
+    #[test]
+    fn test_rate_limiter() {
+        let rate_limiter = RateLimiter::new(2);
+        let client = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let other_client = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+        let start = Instant::now();
+
+        // Clients can burst up to the limit.
+        assert_eq!(rate_limiter.check(client, start), Ok(()));
+        assert_eq!(rate_limiter.check(client, start), Ok(()));
+        assert_eq!(
+            rate_limiter.check(client, start),
+            Err(Duration::from_millis(500))
+        );
+
+        // Each client has its own bucket.
+        assert_eq!(rate_limiter.check(other_client, start), Ok(()));
+
+        // Tokens are refilled at the limit's rate.
+        let later = start + Duration::from_millis(500);
+        assert_eq!(rate_limiter.check(client, later), Ok(()));
+        assert!(rate_limiter.check(client, later).is_err());
+
+        // Buckets never hold more than one second worth of requests.
+        let much_later = start + Duration::from_secs(60);
+        assert_eq!(rate_limiter.check(client, much_later), Ok(()));
+        assert_eq!(rate_limiter.check(client, much_later), Ok(()));
+        assert!(rate_limiter.check(client, much_later).is_err());
+    }
+
+    #[test]
+    fn test_rate_limiter_charge() {
+        let rate_limiter = RateLimiter::new(2);
+        let client = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let start = Instant::now();
+
+        // A request that does the work of several requests is accepted, but the client then has to
+        // wait for all of them to be refilled.
+        assert_eq!(rate_limiter.check(client, start), Ok(()));
+        rate_limiter.charge(client, 4, start);
+        assert_eq!(
+            rate_limiter.check(client, start),
+            Err(Duration::from_secs(2))
+        );
+        let later = start + Duration::from_secs(2);
+        assert_eq!(rate_limiter.check(client, later), Ok(()));
+        assert!(rate_limiter.check(client, later).is_err());
+    }
+
+    #[test]
+    fn test_rate_limiter_eviction() {
+        let rate_limiter = RateLimiter::with_max_tracked_keys(1, 2);
+        let client = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let start = Instant::now();
+
+        assert_eq!(rate_limiter.check(client, start), Ok(()));
+        assert!(rate_limiter.check(client, start).is_err());
+
+        // New clients evict the least recently used client first, so the limit of a client that
+        // keeps making requests isn't reset.
+        for i in 2..10 {
+            let other_client = IpAddr::V4(Ipv4Addr::new(10, 0, 0, i));
+            assert_eq!(rate_limiter.check(other_client, start), Ok(()));
+            assert!(rate_limiter.check(client, start).is_err());
+        }
+        let buckets = rate_limiter.buckets.lock().unwrap();
+        assert_eq!(buckets.buckets.len(), 2);
+        assert_eq!(buckets.last_used.len(), 2);
+    }
+
+    #[test]
+    fn test_client_key() {
+        let ipv4 = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        assert_eq!(client_key(ipv4), ipv4);
+
+        // IPv6 clients share a bucket with their /64.
+        let ipv6 = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 1, 2, 3, 4, 5));
+        assert_eq!(
+            client_key(ipv6),
+            IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 1, 0, 0, 0, 0))
+        );
+
+        // IPv4 clients connecting to an IPv6 socket are identified by their IPv4 address.
+        assert_eq!(
+            client_key(IpAddr::V6(Ipv4Addr::new(10, 0, 0, 1).to_ipv6_mapped())),
+            ipv4
+        );
+    }
+}
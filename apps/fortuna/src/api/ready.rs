@@ -1,13 +1,65 @@
-use axum::{
-    http::StatusCode,
-    response::{
-        IntoResponse,
-        Response,
+use {
+    crate::api::{
+        ApiState,
+        RestError,
     },
+    axum::{
+        extract::State,
+        http::StatusCode,
+        response::{
+            IntoResponse,
+            Response,
+        },
+    },
+    futures::future::join_all,
+    std::time::Duration,
+    tokio::time,
 };
 
-pub async fn ready() -> Response {
-    // TODO: are there useful checks here? At the moment, everything important (specifically hash
-    // chain computation) occurs synchronously on startup.
+/// How long /ready waits for the RPC endpoint of each chain with --ready-rpc-check.
+const READY_RPC_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Answer HTTP 200 once the service can reveal random values, i.e., once the hash chains are set up
+/// and, with --ready-rpc-check, the RPC endpoint of every chain is reachable. Otherwise, answer
+/// HTTP 503 so that the instance doesn't receive traffic yet.
+pub async fn ready(State(state): State<ApiState>) -> Response {
+    if !*state.ready.borrow() {
+        return RestError::TemporarilyUnavailable.into_response();
+    }
+
+    if state.ready_rpc_check {
+        let checks = state.chains.values().map(|chain_state| async move {
+            let block_number = time::timeout(
+                READY_RPC_TIMEOUT,
+                chain_state
+                    .contract
+                    .get_block_number(chain_state.confirmed_block_status),
+            )
+            .await;
+            match block_number {
+                Ok(Ok(_)) => true,
+                Ok(Err(e)) => {
+                    tracing::warn!(
+                        chain_id = chain_state.id,
+                        "RPC endpoint is unreachable {}",
+                        e
+                    );
+                    false
+                }
+                Err(_) => {
+                    tracing::warn!(chain_id = chain_state.id, "RPC endpoint timed out");
+                    false
+                }
+            }
+        });
+        if !join_all(checks)
+            .await
+            .into_iter()
+            .all(|reachable| reachable)
+        {
+            return RestError::TemporarilyUnavailable.into_response();
+        }
+    }
+
     (StatusCode::OK, "OK").into_response()
 }
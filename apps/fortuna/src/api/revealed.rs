@@ -0,0 +1,164 @@
+use {
+    crate::api::{
+        cached_reveal,
+        charge_revelations,
+        client_ip,
+        reveal,
+        BinaryEncoding,
+        Blob,
+        ChainId,
+        QueryParams,
+        RequestLabel,
+        RestError,
+        StrictQuery,
+        MAX_BATCH_REVELATIONS,
+    },
+    anyhow::Result,
+    axum::{
+        extract::{
+            ConnectInfo,
+            Path,
+            State,
+        },
+        Json,
+    },
+    futures::future::try_join_all,
+    std::net::SocketAddr,
+    utoipa::{
+        IntoParams,
+        ToSchema,
+    },
+};
+
+/// Page through the random values of the sequence numbers that have been requested on a blockchain.
+///
+/// Returns the random values of up to `limit` sequence numbers starting at `from`, in order. Only
+/// sequence numbers whose request is confirmed are returned, so this endpoint never reveals a value
+/// before it is requested. Pending requests are revealed exactly like by the revelation endpoint,
+/// and fulfilled requests (whose values are already public on-chain) are returned too. Pass `next` as
+/// `from` to get the following page; it is omitted once the response reaches the last requested
+/// sequence number. Every sequence number in the page counts as one request towards the rate limit.
+#[utoipa::path(
+get,
+path = "/v1/chains/{chain_id}/revealed",
+responses(
+(status = 200, description = "Random values successfully retrieved", body = RevealedValuesResponse),
+(status = 400, description = "The chain id or limit is invalid", body = ErrorResponse),
+(status = 429, description = "The client made too many requests", body = ErrorResponse),
+(status = 500, description = "An unknown error occurred", body = ErrorResponse),
+(status = 503, description = "The service cannot currently communicate with the blockchain", body = ErrorResponse)
+),
+params(RevealedPathParams, RevealedQueryParams)
+)]
+pub async fn revealed(
+    State(state): State<crate::api::ApiState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    Path(RevealedPathParams { chain_id }): Path<RevealedPathParams>,
+    StrictQuery(RevealedQueryParams {
+        from,
+        limit,
+        encoding,
+    }): StrictQuery<RevealedQueryParams>,
+) -> Result<Json<RevealedValuesResponse>, RestError> {
+    state
+        .metrics
+        .http_requests
+        .get_or_create(&RequestLabel {
+            value: "/v1/chains/{chain_id}/revealed".to_string(),
+        })
+        .inc();
+
+    let chain_state = state
+        .chains
+        .get(&chain_id)
+        .ok_or(RestError::InvalidChainId)?;
+    if limit == 0 || limit > MAX_BATCH_REVELATIONS {
+        return Err(RestError::InvalidRevelationCount);
+    }
+
+    let rpc_error = |e: anyhow::Error| {
+        tracing::error!(chain_id = chain_id, "RPC request failed {}", e);
+        RestError::TemporarilyUnavailable
+    };
+    let current_block_number = chain_state
+        .contract
+        .get_block_number(chain_state.confirmed_block_status)
+        .await
+        .map_err(rpc_error)?;
+    // Every sequence number below this one was requested at least reveal_delay_blocks ago.
+    let end_of_requested = chain_state
+        .contract
+        .get_provider_sequence_number_at_block(
+            chain_state.provider_address,
+            current_block_number.saturating_sub(chain_state.reveal_delay_blocks),
+        )
+        .await
+        .map_err(rpc_error)?;
+
+    let end = from.saturating_add(limit).min(end_of_requested);
+    let client = client_ip(connect_info);
+    charge_revelations(&state, client, end.saturating_sub(from));
+    let encoding = encoding.unwrap_or(BinaryEncoding::Hex);
+    let revelations = try_join_all(
+        (from..end)
+            // Skip the sequence numbers of commitments that aren't configured.
+            .filter(|sequence| chain_state.state.contains(*sequence))
+            .map(|sequence| {
+                let (state, chain_id) = (&state, &chain_id);
+                async move {
+                    // Reveal through the same checks as the revelation endpoint. A request that is
+                    // no longer pending was fulfilled, so its value is already public on-chain.
+                    let value = match reveal(state, client, chain_id, sequence).await {
+                        Err(RestError::NoPendingRequest) => {
+                            cached_reveal(state, chain_state, sequence)?
+                        }
+                        result => result?,
+                    };
+                    Ok::<_, RestError>(Revelation {
+                        sequence,
+                        value: Blob::new(encoding, value),
+                    })
+                }
+            }),
+    )
+    .await?;
+
+    Ok(Json(RevealedValuesResponse {
+        revelations,
+        next: (end < end_of_requested).then_some(end),
+    }))
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, IntoParams)]
+#[into_params(parameter_in=Path)]
+pub struct RevealedPathParams {
+    #[param(value_type = String)]
+    pub chain_id: ChainId,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, IntoParams)]
+#[into_params(parameter_in=Query)]
+pub struct RevealedQueryParams {
+    /// The first sequence number of the page.
+    pub from:     u64,
+    /// The maximum number of sequence numbers in the page, at most `MAX_BATCH_REVELATIONS` (100).
+    pub limit:    u64,
+    pub encoding: Option<BinaryEncoding>,
+}
+
+impl QueryParams for RevealedQueryParams {
+    const NAMES: &'static [&'static str] = &["from", "limit", "encoding"];
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, ToSchema, PartialEq)]
+pub struct Revelation {
+    pub sequence: u64,
+    pub value:    Blob,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, ToSchema, PartialEq)]
+pub struct RevealedValuesResponse {
+    pub revelations: Vec<Revelation>,
+    /// The `from` of the next page, or None if there are no more requested sequence numbers.
+    pub next:        Option<u64>,
+}
@@ -1,20 +1,55 @@
 use {
-    crate::api::{
-        ChainId,
-        RequestLabel,
-        RestError,
+    crate::{
+        api::{
+            client_ip,
+            ApiState,
+            BlockchainState,
+            ChainId,
+            ChainLabel,
+            InFlightReveal,
+            InFlightRevealKey,
+            QueryParams,
+            RequestLabel,
+            RestError,
+            StrictQuery,
+        },
+        chain::reader::{
+            BlockNumber,
+            Request,
+        },
     },
     anyhow::Result,
     axum::{
         extract::{
+            ConnectInfo,
             Path,
-            Query,
             State,
         },
+        http::{
+            header,
+            HeaderMap,
+        },
+        response::{
+            IntoResponse,
+            Response,
+        },
         Json,
     },
+    futures::FutureExt,
     pythnet_sdk::wire::array,
     serde_with::serde_as,
+    std::{
+        collections::HashMap,
+        net::{
+            IpAddr,
+            SocketAddr,
+        },
+        sync::{
+            Mutex,
+            PoisonError,
+        },
+        time::Instant,
+    },
     tokio::try_join,
     utoipa::{
         IntoParams,
@@ -29,20 +64,29 @@ use {
 ///
 /// Every blockchain supported by this service has a distinct sequence of random numbers and chain_id.
 /// Callers must pass the appropriate chain_id to ensure they fetch the correct random number.
+///
+/// The response is JSON by default. Clients that send `Accept: application/cbor` get the same
+/// response encoded as CBOR instead.
 #[utoipa::path(
 get,
 path = "/v1/chains/{chain_id}/revelations/{sequence}",
 responses(
 (status = 200, description = "Random value successfully retrieved", body = GetRandomValueResponse),
-(status = 403, description = "Random value cannot currently be retrieved", body = String)
+(status = 400, description = "The chain id or sequence number is invalid", body = ErrorResponse),
+(status = 403, description = "Random value cannot currently be retrieved", body = ErrorResponse),
+(status = 429, description = "The client made too many requests", body = ErrorResponse),
+(status = 500, description = "An unknown error occurred", body = ErrorResponse),
+(status = 503, description = "The service cannot currently communicate with the blockchain", body = ErrorResponse)
 ),
 params(RevelationPathParams, RevelationQueryParams)
 )]
 pub async fn revelation(
     State(state): State<crate::api::ApiState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
     Path(RevelationPathParams { chain_id, sequence }): Path<RevelationPathParams>,
-    Query(RevelationQueryParams { encoding }): Query<RevelationQueryParams>,
-) -> Result<Json<GetRandomValueResponse>, RestError> {
+    StrictQuery(RevelationQueryParams { encoding }): StrictQuery<RevelationQueryParams>,
+    headers: HeaderMap,
+) -> Result<Response, RestError> {
     state
         .metrics
         .http_requests
@@ -55,12 +99,192 @@ pub async fn revelation(
         .try_into()
         .map_err(|_| RestError::InvalidSequenceNumber)?;
 
-    let state = state
+    let value = reveal(&state, client_ip(connect_info), &chain_id, sequence).await?;
+    let encoded_value = Blob::new(encoding.unwrap_or(BinaryEncoding::Hex), value);
+    let response = GetRandomValueResponse {
+        value: encoded_value,
+    };
+
+    if accepts_cbor(&headers) {
+        let body = serde_cbor::to_vec(&response).map_err(|e| {
+            tracing::error!("Failed to serialize the response to CBOR {}", e);
+            RestError::Unknown
+        })?;
+        Ok(([(header::CONTENT_TYPE, CBOR_CONTENT_TYPE)], body).into_response())
+    } else {
+        Ok(Json(response).into_response())
+    }
+}
+
+pub const CBOR_CONTENT_TYPE: &str = "application/cbor";
+
+/// Whether the `Accept` header lists CBOR. Quality values are ignored, and anything else
+/// (including a missing header or `*/*`) gets the default JSON response.
+fn accepts_cbor(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|media_range| media_range.split(';').next())
+        .any(|media_type| media_type.trim().eq_ignore_ascii_case(CBOR_CONTENT_TYPE))
+}
+
+/// Reveal the random value for `sequence` on `chain_id` if it has been requested on-chain, and
+/// record the outcome in the reveal metrics. `client` is the IP of the client that asked for the
+/// value (see `client_ip`), since hot sequences are limited per client.
+pub(crate) async fn reveal(
+    state: &ApiState,
+    client: Option<IpAddr>,
+    chain_id: &ChainId,
+    sequence: u64,
+) -> Result<[u8; 32], RestError> {
+    reveal_with_request(state, client, chain_id, sequence, None)
+        .await
+        .map(|(value, _)| value)
+}
+
+/// Like `reveal`, but also return the on-chain request that the value answers. If `block_number`
+/// is set, the request is read as it was stored at that block, so that fulfilled requests can be
+/// revealed too (this requires an archive node).
+pub(crate) async fn reveal_with_request(
+    state: &ApiState,
+    client: Option<IpAddr>,
+    chain_id: &ChainId,
+    sequence: u64,
+    block_number: Option<BlockNumber>,
+) -> Result<([u8; 32], Request), RestError> {
+    let start = Instant::now();
+    let result = reveal_deduplicated(state, client, chain_id, sequence, block_number).await;
+    // Unsupported chain ids are not recorded so that callers can't create arbitrary time series.
+    if state.chains.contains_key(chain_id) {
+        state
+            .metrics
+            .record_reveal(chain_id, sequence, &result, start.elapsed());
+    }
+    result
+}
+
+/// Reveal the random value for `sequence` on `chain_id`, sharing the work with any other request
+/// for the same revelation that is currently in flight.
+async fn reveal_deduplicated(
+    state: &ApiState,
+    client: Option<IpAddr>,
+    chain_id: &ChainId,
+    sequence: u64,
+    block_number: Option<BlockNumber>,
+) -> Result<([u8; 32], Request), RestError> {
+    let chain_state = state
         .chains
-        .get(&chain_id)
-        .ok_or_else(|| RestError::InvalidChainId)?;
+        .get(chain_id)
+        .ok_or(RestError::InvalidChainId)?
+        .clone();
+    // Reject sequence numbers outside the hash chains before making any RPC calls.
+    if !chain_state.state.contains(sequence) {
+        return Err(RestError::InvalidSequenceNumber);
+    }
 
-    let maybe_request_fut = state.contract.get_request(state.provider_address, sequence);
+    let key = (client, chain_id.clone(), sequence);
+    let reveal_count = state
+        .hot_sequences
+        .lock()
+        .unwrap()
+        .count(&key, Instant::now());
+    if reveal_count > state.hot_sequence_threshold {
+        state
+            .metrics
+            .hot_sequence_reveals
+            .get_or_create(&ChainLabel {
+                chain_id: chain_id.clone(),
+            })
+            .inc();
+        state
+            .hot_sequence_limiter
+            .check(key.clone(), Instant::now())
+            .map_err(RestError::rate_limited)?;
+    }
+
+    let in_flight_key = (chain_id.clone(), sequence, block_number);
+    let reveal = state
+        .in_flight_reveals
+        .lock()
+        .unwrap()
+        .entry(in_flight_key.clone())
+        .or_insert_with(|| {
+            reveal_if_requested(state.clone(), chain_state, sequence, block_number)
+                .boxed()
+                .shared()
+        })
+        .clone();
+
+    // The guard removes the entry even if this request is dropped before the reveal completes (e.g.,
+    // because the client disconnected), so abandoned reveals don't stay in the map.
+    let _in_flight_guard = InFlightRevealGuard {
+        in_flight_reveals: &state.in_flight_reveals,
+        key:               in_flight_key,
+        reveal:            reveal.clone(),
+    };
+    let result = reveal.await;
+
+    if result.is_ok() {
+        state
+            .hot_sequences
+            .lock()
+            .unwrap()
+            .record(&key, Instant::now());
+    }
+
+    result
+}
+
+/// Removes an in-flight reveal from `ApiState::in_flight_reveals` when the request that awaits it
+/// completes or is dropped. Other requests that share the reveal keep their own handle on it.
+struct InFlightRevealGuard<'a> {
+    in_flight_reveals: &'a Mutex<HashMap<InFlightRevealKey, InFlightReveal>>,
+    key:               InFlightRevealKey,
+    reveal:            InFlightReveal,
+}
+
+impl Drop for InFlightRevealGuard<'_> {
+    fn drop(&mut self) {
+        // Only remove the entry if it still refers to our future; a newer request may have already
+        // replaced it. Completed reveals are not kept around so that later requests re-check the chain.
+        let mut in_flight_reveals = self
+            .in_flight_reveals
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if in_flight_reveals
+            .get(&self.key)
+            .map_or(false, |in_flight| in_flight.ptr_eq(&self.reveal))
+        {
+            in_flight_reveals.remove(&self.key);
+        }
+    }
+}
+
+async fn reveal_if_requested(
+    api_state: ApiState,
+    state: BlockchainState,
+    sequence: u64,
+    block_number: Option<BlockNumber>,
+) -> Result<([u8; 32], Request), RestError> {
+    let chain_id = &state.id;
+    let maybe_request_fut = async {
+        match block_number {
+            Some(block_number) => {
+                state
+                    .contract
+                    .get_request_at_block(state.provider_address, sequence, block_number)
+                    .await
+            }
+            None => {
+                state
+                    .contract
+                    .get_request(state.provider_address, sequence)
+                    .await
+            }
+        }
+    };
 
     let current_block_number_fut = state
         .contract
@@ -76,26 +300,53 @@ pub async fn revelation(
         Some(r)
             if current_block_number.saturating_sub(state.reveal_delay_blocks) >= r.block_number =>
         {
-            let value = &state.state.reveal(sequence).map_err(|e| {
-                tracing::error!(
-                    chain_id = chain_id,
-                    sequence = sequence,
-                    "Reveal failed {}",
-                    e
-                );
-                RestError::Unknown
-            })?;
-            let encoded_value = Blob::new(encoding.unwrap_or(BinaryEncoding::Hex), value.clone());
-
-            Ok(Json(GetRandomValueResponse {
-                value: encoded_value,
-            }))
+            let value = cached_reveal(&api_state, &state, sequence)?;
+            state.record_reveal(sequence, r.block_number);
+            Ok((value, r))
         }
         Some(_) => Err(RestError::PendingConfirmation),
         None => Err(RestError::NoPendingRequest),
     }
 }
 
+/// The random value for `sequence`, from the reveal cache if it's there, otherwise computed from the
+/// hash chain and added to the cache. Callers must check the on-chain request first.
+pub(crate) fn cached_reveal(
+    api_state: &ApiState,
+    state: &BlockchainState,
+    sequence: u64,
+) -> Result<[u8; 32], RestError> {
+    let label = ChainLabel {
+        chain_id: state.id.clone(),
+    };
+    let key = (state.provider_address, state.id.clone(), sequence);
+    if let Some(value) = api_state.reveal_cache.lock().unwrap().get(&key) {
+        api_state
+            .metrics
+            .reveal_cache_hits
+            .get_or_create(&label)
+            .inc();
+        return Ok(value);
+    }
+
+    api_state
+        .metrics
+        .reveal_cache_misses
+        .get_or_create(&label)
+        .inc();
+    let value = state.state.reveal(sequence).map_err(|e| {
+        tracing::error!(
+            chain_id = state.id,
+            sequence = sequence,
+            "Reveal failed {}",
+            e
+        );
+        RestError::Unknown
+    })?;
+    api_state.reveal_cache.lock().unwrap().insert(key, value);
+    Ok(value)
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, IntoParams)]
 #[into_params(parameter_in=Path)]
 pub struct RevelationPathParams {
@@ -110,7 +361,11 @@ pub struct RevelationQueryParams {
     pub encoding: Option<BinaryEncoding>,
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize, ToSchema)]
+impl QueryParams for RevelationQueryParams {
+    const NAMES: &'static [&'static str] = &["encoding"];
+}
+
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize, ToSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum BinaryEncoding {
     #[serde(rename = "hex")]
@@ -0,0 +1,122 @@
+use {
+    crate::api::{
+        charge_revelations,
+        client_ip,
+        reveal,
+        BinaryEncoding,
+        Blob,
+        ChainId,
+        QueryParams,
+        RequestLabel,
+        RestError,
+        StrictQuery,
+    },
+    anyhow::Result,
+    axum::{
+        extract::{
+            ConnectInfo,
+            Path,
+            State,
+        },
+        Json,
+    },
+    futures::future::try_join_all,
+    std::net::SocketAddr,
+    utoipa::{
+        IntoParams,
+        ToSchema,
+    },
+};
+
+/// The maximum number of random values that can be revealed by a single batch request, to bound
+/// the work (and RPC calls) done for a single request.
+pub const MAX_BATCH_REVELATIONS: u64 = 100;
+
+/// Reveal the random values for a contiguous range of sequence numbers on a given blockchain.
+///
+/// Returns the random values of the sequence numbers `start` to `start + count - 1`, in order.
+/// Every sequence number is checked the same way as by the single revelation endpoint, and the
+/// whole request fails if any of the random values cannot be revealed. The request counts as
+/// `count` requests towards the rate limit.
+#[utoipa::path(
+get,
+path = "/v1/chains/{chain_id}/revelations",
+responses(
+(status = 200, description = "Random values successfully retrieved", body = GetRandomValuesResponse),
+(status = 400, description = "The chain id, start or count is invalid", body = ErrorResponse),
+(status = 403, description = "A random value cannot currently be retrieved", body = ErrorResponse),
+(status = 429, description = "The client made too many requests", body = ErrorResponse),
+(status = 500, description = "An unknown error occurred", body = ErrorResponse),
+(status = 503, description = "The service cannot currently communicate with the blockchain", body = ErrorResponse)
+),
+params(RevelationBatchPathParams, RevelationBatchQueryParams)
+)]
+pub async fn revelation_batch(
+    State(state): State<crate::api::ApiState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    Path(RevelationBatchPathParams { chain_id }): Path<RevelationBatchPathParams>,
+    StrictQuery(RevelationBatchQueryParams {
+        start,
+        count,
+        encoding,
+    }): StrictQuery<RevelationBatchQueryParams>,
+) -> Result<Json<GetRandomValuesResponse>, RestError> {
+    state
+        .metrics
+        .http_requests
+        .get_or_create(&RequestLabel {
+            value: "/v1/chains/{chain_id}/revelations".to_string(),
+        })
+        .inc();
+
+    if !state.chains.contains_key(&chain_id) {
+        return Err(RestError::InvalidChainId);
+    }
+    if count == 0 || count > MAX_BATCH_REVELATIONS {
+        return Err(RestError::InvalidRevelationCount);
+    }
+    let client = client_ip(connect_info);
+    charge_revelations(&state, client, count);
+    let end = start
+        .checked_add(count)
+        .ok_or(RestError::InvalidSequenceNumber)?;
+
+    let values =
+        try_join_all((start..end).map(|sequence| reveal(&state, client, &chain_id, sequence)))
+            .await?;
+
+    let encoding = encoding.unwrap_or(BinaryEncoding::Hex);
+    Ok(Json(GetRandomValuesResponse {
+        values: values
+            .into_iter()
+            .map(|value| Blob::new(encoding, value))
+            .collect(),
+    }))
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, IntoParams)]
+#[into_params(parameter_in=Path)]
+pub struct RevelationBatchPathParams {
+    #[param(value_type = String)]
+    pub chain_id: ChainId,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, IntoParams)]
+#[into_params(parameter_in=Query)]
+pub struct RevelationBatchQueryParams {
+    /// The first sequence number to reveal.
+    pub start:    u64,
+    /// The number of sequence numbers to reveal, at most `MAX_BATCH_REVELATIONS` (100).
+    pub count:    u64,
+    pub encoding: Option<BinaryEncoding>,
+}
+
+impl QueryParams for RevelationBatchQueryParams {
+    const NAMES: &'static [&'static str] = &["start", "count", "encoding"];
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, ToSchema, PartialEq)]
+pub struct GetRandomValuesResponse {
+    /// The random values of the sequence numbers `start` to `start + count - 1`, in order.
+    pub values: Vec<Blob>,
+}
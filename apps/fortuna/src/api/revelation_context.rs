@@ -0,0 +1,210 @@
+use {
+    crate::{
+        api::{
+            client_ip,
+            reveal_with_request,
+            BinaryEncoding,
+            Blob,
+            ChainId,
+            QueryParams,
+            RequestLabel,
+            RestError,
+            StrictQuery,
+        },
+        chain::reader::BlockNumber,
+    },
+    anyhow::anyhow,
+    axum::{
+        extract::{
+            ConnectInfo,
+            Path,
+            State,
+        },
+        Json,
+    },
+    ethers::types::Address,
+    serde_with::{
+        formats::CommaSeparator,
+        serde_as,
+        StringWithSeparator,
+    },
+    std::{
+        fmt::{
+            self,
+            Display,
+            Formatter,
+        },
+        net::SocketAddr,
+        str::FromStr,
+    },
+    utoipa::{
+        IntoParams,
+        ToSchema,
+    },
+};
+
+/// Reveal the random value for a given sequence number along with the on-chain request it answers.
+///
+/// This endpoint is intended for auditing and dispute resolution: it returns the random value together
+/// with the request details (requester, block number, commitment) that were stored in the contract.
+///
+/// Requests are deleted from the contract once they are fulfilled. Pass `block_number` to read the
+/// request as it was stored at that block (e.g., the block in which the request was made), which
+/// lets callers inspect requests that have already been fulfilled. Reading historical blocks requires
+/// the chain's RPC endpoint to be an archive node.
+///
+/// Pass `fields` (e.g., `fields=value`) to only include some fields in the response. By default,
+/// all the fields are included.
+#[utoipa::path(
+get,
+path = "/v1/chains/{chain_id}/revelations/{sequence}/context",
+responses(
+(status = 200, description = "Random value and request context successfully retrieved", body = GetRevelationContextResponse),
+(status = 400, description = "The chain id or sequence number is invalid", body = ErrorResponse),
+(status = 403, description = "Random value cannot currently be retrieved", body = ErrorResponse),
+(status = 429, description = "The client made too many requests", body = ErrorResponse),
+(status = 500, description = "An unknown error occurred", body = ErrorResponse),
+(status = 503, description = "The service cannot currently communicate with the blockchain", body = ErrorResponse)
+),
+params(RevelationContextPathParams, RevelationContextQueryParams)
+)]
+pub async fn revelation_context(
+    State(state): State<crate::api::ApiState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    Path(RevelationContextPathParams { chain_id, sequence }): Path<RevelationContextPathParams>,
+    StrictQuery(RevelationContextQueryParams {
+        encoding,
+        block_number,
+        fields,
+    }): StrictQuery<RevelationContextQueryParams>,
+) -> Result<Json<GetRevelationContextResponse>, RestError> {
+    state
+        .metrics
+        .http_requests
+        .get_or_create(&RequestLabel {
+            value: "/v1/chains/{chain_id}/revelations/{sequence}/context".to_string(),
+        })
+        .inc();
+
+    let (value, r) = reveal_with_request(
+        &state,
+        client_ip(connect_info),
+        &chain_id,
+        sequence,
+        block_number,
+    )
+    .await?;
+
+    let fields = fields.unwrap_or_else(|| RevelationContextField::ALL.to_vec());
+    Ok(Json(GetRevelationContextResponse {
+        value:   fields
+            .contains(&RevelationContextField::Value)
+            .then(|| Blob::new(encoding.unwrap_or(BinaryEncoding::Hex), value)),
+        request: fields
+            .contains(&RevelationContextField::Request)
+            .then_some({
+                RequestContext {
+                    provider:        r.provider,
+                    sequence_number: r.sequence_number,
+                    requester:       r.requester,
+                    block_number:    r.block_number,
+                    commitment:      r.commitment,
+                    num_hashes:      r.num_hashes,
+                    use_blockhash:   r.use_blockhash,
+                }
+            }),
+    }))
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, IntoParams)]
+#[into_params(parameter_in=Path)]
+pub struct RevelationContextPathParams {
+    #[param(value_type = String)]
+    pub chain_id: ChainId,
+    pub sequence: u64,
+}
+
+#[serde_as]
+#[derive(Debug, serde::Serialize, serde::Deserialize, IntoParams)]
+#[into_params(parameter_in=Query)]
+pub struct RevelationContextQueryParams {
+    pub encoding:     Option<BinaryEncoding>,
+    /// Read the request as it was stored in the contract at this block. Defaults to the latest block.
+    pub block_number: Option<BlockNumber>,
+    /// A comma-separated list of the fields to include in the response (`value`, `request`).
+    /// Defaults to all the fields.
+    #[serde_as(as = "Option<StringWithSeparator::<CommaSeparator, RevelationContextField>>")]
+    #[serde(default)]
+    #[param(value_type = Option<String>)]
+    pub fields:       Option<Vec<RevelationContextField>>,
+}
+
+impl QueryParams for RevelationContextQueryParams {
+    const NAMES: &'static [&'static str] = &["encoding", "block_number", "fields"];
+}
+
+/// A field of `GetRevelationContextResponse` that can be selected with the `fields` parameter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RevelationContextField {
+    Value,
+    Request,
+}
+
+impl RevelationContextField {
+    const ALL: [RevelationContextField; 2] = [
+        RevelationContextField::Value,
+        RevelationContextField::Request,
+    ];
+}
+
+impl FromStr for RevelationContextField {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "value" => Ok(RevelationContextField::Value),
+            "request" => Ok(RevelationContextField::Request),
+            _ => Err(anyhow!("Unknown field {}", s)),
+        }
+    }
+}
+
+impl Display for RevelationContextField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RevelationContextField::Value => write!(f, "value"),
+            RevelationContextField::Request => write!(f, "request"),
+        }
+    }
+}
+
+/// The fields that weren't selected with the `fields` parameter are omitted.
+#[derive(Debug, serde::Serialize, serde::Deserialize, ToSchema, PartialEq)]
+pub struct GetRevelationContextResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value:   Option<Blob>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request: Option<RequestContext>,
+}
+
+/// The on-chain request that a random value was revealed for.
+#[serde_as]
+#[derive(Debug, serde::Serialize, serde::Deserialize, ToSchema, PartialEq)]
+pub struct RequestContext {
+    #[schema(value_type = String)]
+    pub provider:        Address,
+    pub sequence_number: u64,
+    /// The address that requested the random number.
+    #[schema(value_type = String)]
+    pub requester:       Address,
+    /// The block in which the request was made.
+    pub block_number:    BlockNumber,
+    /// keccak256(user commitment, provider commitment)
+    #[serde_as(as = "serde_with::hex::Hex")]
+    #[schema(value_type = String)]
+    pub commitment:      [u8; 32],
+    /// The number of hashes required to verify the provider revelation.
+    pub num_hashes:      u32,
+    /// Whether the blockhash of `block_number` is incorporated into the random value.
+    pub use_blockhash:   bool,
+}
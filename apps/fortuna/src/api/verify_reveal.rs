@@ -0,0 +1,99 @@
+use {
+    crate::{
+        api::{
+            QueryParams,
+            RequestLabel,
+            RestError,
+            StrictQuery,
+        },
+        state::PebbleHashChain,
+    },
+    anyhow::Result,
+    axum::{
+        extract::State,
+        Json,
+    },
+    serde_with::serde_as,
+    utoipa::{
+        IntoParams,
+        ToSchema,
+    },
+};
+
+/// The maximum distance between `root_sequence` and `sequence` that the verification accepts, to
+/// bound the work done for a single request. This is the length of a typical hash chain, so a value
+/// can always be verified against the commitment of its chain or any later revealed value.
+pub const MAX_VERIFY_REVEAL_HASHES: u64 = 100_000;
+
+/// Verify that a random value was revealed from a given hash chain commitment.
+///
+/// The value is valid if hashing it `sequence - root_sequence` times with keccak256 gives `root`,
+/// where `root` is a commitment of the provider (e.g., the commitment registered on-chain) and
+/// `root_sequence` is the sequence number of that commitment. This check is stateless: it doesn't
+/// use the secret of this service, so it can verify the reveals of any provider.
+#[utoipa::path(
+get,
+path = "/v1/verify_reveal",
+responses(
+(status = 200, description = "The verification result", body = VerifyRevealResponse),
+(status = 400, description = "The sequence numbers are invalid", body = ErrorResponse),
+),
+params(VerifyRevealQueryParams)
+)]
+pub async fn verify_reveal(
+    State(state): State<crate::api::ApiState>,
+    StrictQuery(VerifyRevealQueryParams {
+        sequence,
+        value,
+        root,
+        root_sequence,
+    }): StrictQuery<VerifyRevealQueryParams>,
+) -> Result<Json<VerifyRevealResponse>, RestError> {
+    state
+        .metrics
+        .http_requests
+        .get_or_create(&RequestLabel {
+            value: "/v1/verify_reveal".to_string(),
+        })
+        .inc();
+
+    let num_hashes = sequence
+        .checked_sub(root_sequence.unwrap_or(0))
+        .filter(|num_hashes| *num_hashes <= MAX_VERIFY_REVEAL_HASHES)
+        .ok_or(RestError::InvalidSequenceNumber)?;
+
+    // Hashing can take tens of milliseconds, so keep it off the async runtime.
+    let valid =
+        tokio::task::spawn_blocking(move || PebbleHashChain::verify(value, num_hashes, root))
+            .await
+            .map_err(|_| RestError::Unknown)?;
+
+    Ok(Json(VerifyRevealResponse { valid }))
+}
+
+#[serde_as]
+#[derive(Debug, serde::Serialize, serde::Deserialize, IntoParams)]
+#[into_params(parameter_in=Query)]
+pub struct VerifyRevealQueryParams {
+    /// The sequence number of the revealed value.
+    pub sequence:      u64,
+    /// The revealed value, hex encoded.
+    #[serde_as(as = "serde_with::hex::Hex")]
+    #[param(value_type = String)]
+    pub value:         [u8; 32],
+    /// The commitment to verify the value against, hex encoded.
+    #[serde_as(as = "serde_with::hex::Hex")]
+    #[param(value_type = String)]
+    pub root:          [u8; 32],
+    /// The sequence number of the commitment. Defaults to 0.
+    pub root_sequence: Option<u64>,
+}
+
+impl QueryParams for VerifyRevealQueryParams {
+    const NAMES: &'static [&'static str] = &["sequence", "value", "root", "root_sequence"];
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, ToSchema, PartialEq)]
+pub struct VerifyRevealResponse {
+    pub valid: bool,
+}
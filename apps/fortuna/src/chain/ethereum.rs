@@ -314,6 +314,23 @@ impl InstrumentedPythContract {
     }
 }
 
+fn to_reader_request(r: Request) -> Result<Option<reader::Request>> {
+    // sequence_number == 0 means the request does not exist.
+    if r.sequence_number != 0 {
+        Ok(Some(reader::Request {
+            provider:        r.provider,
+            sequence_number: r.sequence_number,
+            block_number:    r.block_number,
+            use_blockhash:   r.use_blockhash,
+            requester:       r.requester,
+            commitment:      r.commitment,
+            num_hashes:      r.num_hashes,
+        }))
+    } else {
+        Ok(None)
+    }
+}
+
 #[async_trait]
 impl<T: JsonRpcClient + 'static> EntropyReader for PythRandom<Provider<T>> {
     async fn get_request(
@@ -328,17 +345,36 @@ impl<T: JsonRpcClient + 'static> EntropyReader for PythRandom<Provider<T>> {
             .call()
             .await?;
 
-        // sequence_number == 0 means the request does not exist.
-        if r.sequence_number != 0 {
-            Ok(Some(reader::Request {
-                provider:        r.provider,
-                sequence_number: r.sequence_number,
-                block_number:    r.block_number.try_into()?,
-                use_blockhash:   r.use_blockhash,
-            }))
-        } else {
-            Ok(None)
-        }
+        to_reader_request(r)
+    }
+
+    async fn get_request_at_block(
+        &self,
+        provider_address: Address,
+        sequence_number: u64,
+        block_number: BlockNumber,
+    ) -> Result<Option<reader::Request>> {
+        let r = self
+            .get_request(provider_address, sequence_number)
+            .block(EthersBlockNumber::Number(block_number.into()))
+            .call()
+            .await?;
+
+        to_reader_request(r)
+    }
+
+    async fn get_provider_sequence_number_at_block(
+        &self,
+        provider_address: Address,
+        block_number: BlockNumber,
+    ) -> Result<u64> {
+        let provider_info = self
+            .get_provider_info(provider_address)
+            .block(EthersBlockNumber::Number(block_number.into()))
+            .call()
+            .await?;
+
+        Ok(provider_info.sequence_number)
     }
 
     async fn get_block_number(&self, confirmed_block_status: BlockStatus) -> Result<BlockNumber> {
@@ -355,6 +391,15 @@ impl<T: JsonRpcClient + 'static> EntropyReader for PythRandom<Provider<T>> {
             .as_u64())
     }
 
+    async fn get_block_hash(&self, block_number: BlockNumber) -> Result<Option<[u8; 32]>> {
+        let block = self
+            .client()
+            .get_block(EthersBlockNumber::Number(block_number.into()))
+            .await?;
+
+        Ok(block.and_then(|block| block.hash).map(|hash| hash.0))
+    }
+
     async fn get_request_with_callback_events(
         &self,
         from_block: BlockNumber,
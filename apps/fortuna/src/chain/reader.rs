@@ -49,8 +49,29 @@ pub trait EntropyReader: Send + Sync {
     async fn get_request(&self, provider: Address, sequence_number: u64)
         -> Result<Option<Request>>;
 
+    /// Get a request as it was stored in the contract at `block_number`. Unlike `get_request`,
+    /// this also finds requests that have since been fulfilled (and therefore deleted from the
+    /// contract). Note that querying old blocks requires the RPC endpoint to be an archive node.
+    async fn get_request_at_block(
+        &self,
+        provider: Address,
+        sequence_number: u64,
+        block_number: BlockNumber,
+    ) -> Result<Option<Request>>;
+
+    /// Get the sequence number that the provider will assign to its next request, as of
+    /// `block_number`. Every lower sequence number has been requested by then.
+    async fn get_provider_sequence_number_at_block(
+        &self,
+        provider: Address,
+        block_number: BlockNumber,
+    ) -> Result<u64>;
+
     async fn get_block_number(&self, confirmed_block_status: BlockStatus) -> Result<BlockNumber>;
 
+    /// Get the hash of the block at `block_number` on the current canonical chain (if it exists).
+    async fn get_block_hash(&self, block_number: BlockNumber) -> Result<Option<[u8; 32]>>;
+
     async fn get_request_with_callback_events(
         &self,
         from_block: BlockNumber,
@@ -77,6 +98,12 @@ pub struct Request {
     // The block number where this request was created
     pub block_number:    BlockNumber,
     pub use_blockhash:   bool,
+    // The address that requested this random number
+    pub requester:       Address,
+    // keccak256(user commitment, provider commitment)
+    pub commitment:      [u8; 32],
+    // The number of hashes required to verify the provider revelation
+    pub num_hashes:      u32,
 }
 
 
@@ -89,22 +116,41 @@ pub mod mock {
             EntropyReader,
             Request,
         },
-        anyhow::Result,
+        anyhow::{
+            anyhow,
+            Result,
+        },
         axum::async_trait,
         ethers::types::{
             Address,
             U256,
         },
-        std::sync::RwLock,
+        std::sync::{
+            atomic::{
+                AtomicBool,
+                AtomicUsize,
+                Ordering,
+            },
+            RwLock,
+        },
     };
 
     /// Mock version of the entropy contract intended for testing.
     /// This class is internally locked to allow tests to modify the in-flight requests while
     /// the API is also holding a pointer to the same data structure.
     pub struct MockEntropyReader {
-        block_number: RwLock<BlockNumber>,
+        block_number:  RwLock<BlockNumber>,
+        /// The hash returned for every block. Change it to simulate a reorg.
+        block_hash:    RwLock<[u8; 32]>,
         /// The set of requests that are currently in-flight.
-        requests:     RwLock<Vec<Request>>,
+        requests:      RwLock<Vec<Request>>,
+        /// The number of times get_request has been called.
+        request_calls: AtomicUsize,
+        /// get_request waits for a read lock on this, so tests can hold the write lock to keep
+        /// requests in flight.
+        request_gate:  tokio::sync::RwLock<()>,
+        /// Whether get_block_number fails, to simulate an unreachable RPC endpoint.
+        unreachable:   AtomicBool,
     }
 
     impl MockEntropyReader {
@@ -113,8 +159,9 @@ pub mod mock {
             requests: &[(Address, u64, BlockNumber, bool)],
         ) -> MockEntropyReader {
             MockEntropyReader {
-                block_number: RwLock::new(block_number),
-                requests:     RwLock::new(
+                block_number:  RwLock::new(block_number),
+                block_hash:    RwLock::new([0; 32]),
+                requests:      RwLock::new(
                     requests
                         .iter()
                         .map(|&(a, s, b, u)| Request {
@@ -122,9 +169,15 @@ pub mod mock {
                             sequence_number: s,
                             block_number:    b,
                             use_blockhash:   u,
+                            requester:       Address::zero(),
+                            commitment:      [0; 32],
+                            num_hashes:      0,
                         })
                         .collect(),
                 ),
+                request_calls: AtomicUsize::new(0),
+                request_gate:  tokio::sync::RwLock::new(()),
+                unreachable:   AtomicBool::new(false),
             }
         }
 
@@ -141,14 +194,46 @@ pub mod mock {
                 sequence_number: sequence,
                 block_number,
                 use_blockhash,
+                requester: Address::zero(),
+                commitment: [0; 32],
+                num_hashes: 0,
             });
             self
         }
 
+        /// Remove a request from the set of in-flight requests.
+        pub fn remove(&self, provider: Address, sequence: u64) -> &Self {
+            self.requests
+                .write()
+                .unwrap()
+                .retain(|r| r.provider != provider || r.sequence_number != sequence);
+            self
+        }
+
         pub fn set_block_number(&self, block_number: BlockNumber) -> &Self {
             *(self.block_number.write().unwrap()) = block_number;
             self
         }
+
+        pub fn set_block_hash(&self, block_hash: [u8; 32]) -> &Self {
+            *(self.block_hash.write().unwrap()) = block_hash;
+            self
+        }
+
+        pub fn set_unreachable(&self, unreachable: bool) -> &Self {
+            self.unreachable.store(unreachable, Ordering::SeqCst);
+            self
+        }
+
+        /// The number of times get_request has been called.
+        pub fn request_calls(&self) -> usize {
+            self.request_calls.load(Ordering::SeqCst)
+        }
+
+        /// Block every get_request call until the returned guard is dropped.
+        pub async fn hold_requests(&self) -> tokio::sync::RwLockWriteGuard<'_, ()> {
+            self.request_gate.write().await
+        }
     }
 
     #[async_trait]
@@ -158,6 +243,8 @@ pub mod mock {
             provider: Address,
             sequence_number: u64,
         ) -> Result<Option<Request>> {
+            self.request_calls.fetch_add(1, Ordering::SeqCst);
+            let _gate = self.request_gate.read().await;
             Ok(self
                 .requests
                 .read()
@@ -167,13 +254,50 @@ pub mod mock {
                 .map(|r| (*r).clone()))
         }
 
+        async fn get_request_at_block(
+            &self,
+            provider: Address,
+            sequence_number: u64,
+            block_number: BlockNumber,
+        ) -> Result<Option<Request>> {
+            // The mock doesn't keep any history, so requests are never removed. They only exist
+            // from the block they were made in.
+            Ok(self
+                .get_request(provider, sequence_number)
+                .await?
+                .filter(|r| r.block_number <= block_number))
+        }
+
+        async fn get_provider_sequence_number_at_block(
+            &self,
+            provider: Address,
+            block_number: BlockNumber,
+        ) -> Result<u64> {
+            Ok(self
+                .requests
+                .read()
+                .unwrap()
+                .iter()
+                .filter(|r| r.provider == provider && r.block_number <= block_number)
+                .map(|r| r.sequence_number + 1)
+                .max()
+                .unwrap_or(0))
+        }
+
         async fn get_block_number(
             &self,
             confirmed_block_status: BlockStatus,
         ) -> Result<BlockNumber> {
+            if self.unreachable.load(Ordering::SeqCst) {
+                return Err(anyhow!("RPC endpoint is unreachable"));
+            }
             Ok(*self.block_number.read().unwrap())
         }
 
+        async fn get_block_hash(&self, _block_number: BlockNumber) -> Result<Option<[u8; 32]>> {
+            Ok(Some(*self.block_hash.read().unwrap()))
+        }
+
         async fn get_request_with_callback_events(
             &self,
             _from_block: BlockNumber,
@@ -1,18 +1,22 @@
+mod bench_reveal;
 mod generate;
 mod get_request;
 mod inspect;
 mod register_provider;
 mod request_randomness;
+mod rotate_secret;
 mod run;
 mod setup_provider;
 mod withdraw_fees;
 
 pub use {
+    bench_reveal::bench_reveal,
     generate::generate,
     get_request::get_request,
     inspect::inspect,
     register_provider::register_provider,
     request_randomness::request_randomness,
+    rotate_secret::rotate_secret,
     run::run,
     setup_provider::setup_provider,
     withdraw_fees::withdraw_fees,
@@ -0,0 +1,87 @@
+use {
+    crate::{
+        api::GetRandomValueResponse,
+        config::BenchRevealOptions,
+    },
+    anyhow::{
+        anyhow,
+        Result,
+    },
+    futures::{
+        stream,
+        StreamExt,
+    },
+    std::time::{
+        Duration,
+        Instant,
+    },
+};
+
+/// Request revelations from a running service and report the latency distribution and the error rate.
+/// The latency includes the RPC read the service performs to check that the request is confirmed.
+pub async fn bench_reveal(opts: &BenchRevealOptions) -> Result<()> {
+    if opts.concurrency == 0 {
+        return Err(anyhow!("concurrency must be at least 1"));
+    }
+
+    let client = reqwest::Client::new();
+    let start = Instant::now();
+
+    let results: Vec<(Duration, Result<()>)> =
+        stream::iter(opts.start_sequence..opts.start_sequence.saturating_add(opts.sequences))
+            .map(|sequence| {
+                let client = client.clone();
+                async move {
+                    let url = opts.url.join(&format!(
+                        "/v1/chains/{}/revelations/{}",
+                        opts.chain_id, sequence
+                    ))?;
+                    let request_start = Instant::now();
+                    let result = reveal(&client, url).await;
+                    if let Err(e) = &result {
+                        tracing::debug!(sequence = sequence, "Revelation failed: {:?}", e);
+                    }
+                    Ok::<_, anyhow::Error>((request_start.elapsed(), result))
+                }
+            })
+            .buffer_unordered(opts.concurrency)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<_>>()?;
+
+    let elapsed = start.elapsed();
+    let num_errors = results.iter().filter(|(_, result)| result.is_err()).count();
+    let mut latencies: Vec<Duration> = results.into_iter().map(|(latency, _)| latency).collect();
+    latencies.sort();
+
+    tracing::info!(
+        requests = latencies.len(),
+        errors = num_errors,
+        error_rate = num_errors as f64 / latencies.len().max(1) as f64,
+        throughput = latencies.len() as f64 / elapsed.as_secs_f64(),
+        p50 = ?percentile(&latencies, 50),
+        p95 = ?percentile(&latencies, 95),
+        p99 = ?percentile(&latencies, 99),
+        "Benchmark complete."
+    );
+
+    Ok(())
+}
+
+async fn reveal(client: &reqwest::Client, url: reqwest::Url) -> Result<()> {
+    client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<GetRandomValueResponse>()
+        .await?;
+    Ok(())
+}
+
+/// The nearest-rank percentile of a sorted list of latencies.
+fn percentile(sorted_latencies: &[Duration], pct: usize) -> Option<Duration> {
+    let rank = (sorted_latencies.len() * pct + 99) / 100;
+    sorted_latencies.get(rank.saturating_sub(1)).copied()
+}
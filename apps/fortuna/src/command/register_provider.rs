@@ -34,15 +34,48 @@ pub struct CommitmentMetadata {
     pub chain_length: u64,
 }
 
-/// Register as a randomness provider. This method will generate and commit to a new random
-/// hash chain from the configured secret & a newly generated random value.
+/// Register as a randomness provider on each of the given chains. This method will generate and
+/// commit to a new random hash chain from the configured secret & a newly generated random value.
 pub async fn register_provider(opts: &RegisterProviderOptions) -> Result<()> {
     let config = Config::load(&opts.config.config)?;
-    let chain_config = config.get_chain_config(&opts.chain_id)?;
+    let mut provider_config = config.provider.clone();
+    if let Some(chain_length) = opts.chain_length {
+        provider_config.chain_length = chain_length;
+    }
 
-    register_provider_from_config(&config.provider, &opts.chain_id, &chain_config).await?;
+    let mut results = vec![];
+    for chain_id in &opts.chain_ids {
+        let result = match config.get_chain_config(chain_id) {
+            Ok(chain_config) => {
+                register_provider_from_config(&provider_config, chain_id, &chain_config).await
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = &result {
+            tracing::error!("Failed to register provider on {} {}", chain_id, e);
+        }
+        results.push((chain_id, result));
+    }
 
-    Ok(())
+    println!("Registration summary:");
+    let mut failed_chain_ids = vec![];
+    for (chain_id, result) in results {
+        match result {
+            Ok(()) => println!("  {}: registered", chain_id),
+            Err(e) => {
+                println!("  {}: failed ({})", chain_id, e);
+                failed_chain_ids.push(chain_id.as_str());
+            }
+        }
+    }
+
+    match failed_chain_ids.is_empty() {
+        true => Ok(()),
+        false => Err(anyhow!(
+            "Failed to register provider on {}",
+            failed_chain_ids.join(", ")
+        )),
+    }
 }
 
 pub async fn register_provider_from_config(
@@ -50,9 +83,12 @@ pub async fn register_provider_from_config(
     chain_id: &ChainId,
     chain_config: &EthereumConfig,
 ) -> Result<()> {
-    let private_key_string = provider_config.private_key.load()?.ok_or(anyhow!(
-        "Please specify a provider private key in the config"
-    ))?;
+    let private_key_string = provider_config
+        .private_key
+        .load_private_key()?
+        .ok_or(anyhow!(
+            "Please specify a provider private key in the config"
+        ))?;
 
     // Initialize a Provider to interface with the EVM contract.
     let contract =
@@ -61,7 +97,7 @@ pub async fn register_provider_from_config(
     let random = rand::random::<[u8; 32]>();
     let secret = provider_config
         .secret
-        .load()?
+        .load_hash_chain_secret()?
         .ok_or(anyhow!("Please specify a provider secret in the config"))?;
 
     let commitment_length = provider_config.chain_length;
@@ -0,0 +1,356 @@
+use {
+    crate::{
+        api::ChainId,
+        chain::ethereum::SignablePythContract,
+        command::register_provider::{
+            register_provider_from_config,
+            CommitmentMetadata,
+        },
+        config::{
+            Commitment,
+            Config,
+            EthereumConfig,
+            ProviderConfig,
+            RotateSecretOptions,
+            SecretString,
+        },
+        state::PebbleHashChain,
+    },
+    anyhow::{
+        anyhow,
+        Result,
+    },
+    ethers::{
+        signers::Signer,
+        types::Address,
+    },
+    std::{
+        collections::BTreeMap,
+        fs,
+        io::Write,
+        path::Path,
+    },
+};
+
+/// Rotate the provider secret. This registers a new commitment generated from the new secret on
+/// each chain, and prints the historical commitments to put in the config so that the sequence
+/// numbers requested before the rotation can still be revealed with the previous secret.
+///
+/// The printed commitments reference the secrets by file, so that no secret ends up in the output.
+pub async fn rotate_secret(opts: &RotateSecretOptions) -> Result<()> {
+    let config = Config::load(&opts.config.config)?;
+
+    let chain_ids = match opts.chain_ids.is_empty() {
+        true => config.chains.keys().cloned().collect(),
+        false => opts.chain_ids.clone(),
+    };
+    check_secrets_in_files(&config, &chain_ids)?;
+
+    // The new secret must be safely stored before it is committed to on any chain.
+    let new_provider_config = ProviderConfig {
+        secret: load_or_create_secret_file(&opts.new_secret_file)?,
+        ..config.provider.clone()
+    };
+
+    let mut all_ok = true;
+    let mut chain_commitments = BTreeMap::new();
+    for chain_id in chain_ids {
+        match rotate_chain_secret(&config, &new_provider_config, &chain_id).await {
+            Ok(Some(commitments)) => {
+                tracing::info!("Rotated the secret on {}", chain_id);
+                chain_commitments.insert(chain_id, commitments);
+            }
+            Ok(None) => {
+                tracing::warn!(
+                    "The commitment of {} is already generated from the new secret, skipping it. Its commitments were printed when it was rotated",
+                    chain_id
+                );
+            }
+            Err(e) => {
+                tracing::error!("Failed to rotate the secret on {} {}", chain_id, e);
+                all_ok = false;
+            }
+        }
+    }
+
+    if !chain_commitments.is_empty() {
+        println!(
+            "Set provider.secret.file to {}, and the commitments of each chain to:",
+            opts.new_secret_file
+        );
+        println!("{}", serde_yaml::to_string(&chain_commitments)?);
+    }
+
+    match all_ok {
+        true => Ok(()),
+        false => Err(anyhow!("Failed to rotate the secret on all chains")),
+    }
+}
+
+/// Check that the current provider secret and the secrets of the existing commitments of
+/// `chain_ids` are stored in files. The commitments printed after the rotation reference these
+/// secrets, and they would otherwise be printed in clear.
+fn check_secrets_in_files(config: &Config, chain_ids: &[ChainId]) -> Result<()> {
+    if !is_file_secret(&config.provider.secret) {
+        return Err(anyhow!(
+            "Move provider.secret to a file (provider.secret.file) before rotating it"
+        ));
+    }
+    for chain_id in chain_ids {
+        let chain_config = config.get_chain_config(chain_id)?;
+        let commitments = chain_config.commitments.unwrap_or_default();
+        if commitments
+            .iter()
+            .filter_map(|commitment| commitment.secret.as_ref())
+            .any(|secret| !is_file_secret(secret))
+        {
+            return Err(anyhow!(
+                "Move the commitment secrets of {} to files before rotating the secret",
+                chain_id
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn is_file_secret(secret: &SecretString) -> bool {
+    secret.value.is_none() && secret.file.is_some()
+}
+
+/// Load the hash chain secret stored in `path`, or generate a random one and write it to `path`
+/// (readable by the owner only) if the file doesn't exist.
+fn load_or_create_secret_file(path: &str) -> Result<SecretString> {
+    let secret = SecretString {
+        value: None,
+        file:  Some(path.to_string()),
+    };
+    if Path::new(path).exists() {
+        secret.load_hash_chain_secret()?;
+        tracing::info!("Using the new secret in {}", path);
+        return Ok(secret);
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(hex::encode(rand::random::<[u8; 32]>()).as_bytes())?;
+    file.sync_all()?;
+    tracing::info!("Wrote a new random secret to {}", path);
+    Ok(secret)
+}
+
+/// Register a new commitment generated from the secret of `new_provider_config` on `chain_id`, and
+/// return the historical commitments of the chain, including the commitment that was replaced.
+///
+/// The replaced commitment is only recorded if it was generated from the current provider secret,
+/// since its sequence numbers couldn't be revealed otherwise. Returns `None` without registering
+/// anything if the chain already uses a commitment generated from the new secret.
+async fn rotate_chain_secret(
+    config: &Config,
+    new_provider_config: &ProviderConfig,
+    chain_id: &ChainId,
+) -> Result<Option<Vec<Commitment>>> {
+    let chain_config = config.get_chain_config(chain_id)?;
+    let private_key = config
+        .provider
+        .private_key
+        .load_private_key()?
+        .ok_or(anyhow!(
+            "Please specify a provider private key in the config file."
+        ))?;
+    let contract = SignablePythContract::from_config(&chain_config, &private_key).await?;
+
+    let provider_info = contract
+        .get_provider_info(contract.wallet().address())
+        .call()
+        .await?;
+    let metadata = bincode::deserialize::<CommitmentMetadata>(&provider_info.commitment_metadata)
+        .map_err(|e| {
+        anyhow!(
+            "Failed to deserialize commitment metadata. Is the provider registered? {}",
+            e
+        )
+    })?;
+
+    let provider_address = contract.wallet().address();
+    let commitment_from = |secret: &SecretString| {
+        generate_commitment(
+            secret,
+            chain_id,
+            &provider_address,
+            &chain_config,
+            &metadata,
+            config.provider.chain_sample_interval,
+        )
+    };
+    if commitment_from(&new_provider_config.secret)? == provider_info.original_commitment {
+        return Ok(None);
+    }
+    if commitment_from(&config.provider.secret)? != provider_info.original_commitment {
+        return Err(anyhow!(
+            "The on-chain commitment wasn't generated from provider.secret, so it can't be recorded as a historical commitment"
+        ));
+    }
+
+    register_provider_from_config(new_provider_config, chain_id, &chain_config).await?;
+    tracing::info!(
+        "The commitment generated from the previous secret covers sequence numbers {} to {}",
+        provider_info.original_commitment_sequence_number,
+        provider_info.end_sequence_number
+    );
+
+    let mut commitments = chain_config.commitments.clone().unwrap_or_default();
+    commitments.push(Commitment {
+        seed:                                metadata.seed,
+        chain_length:                        metadata.chain_length,
+        original_commitment_sequence_number: provider_info.original_commitment_sequence_number,
+        secret:                              None,
+    });
+    // All the commitments made so far were generated from the previous secret.
+    for commitment in commitments.iter_mut() {
+        if commitment.secret.is_none() {
+            commitment.secret = Some(config.provider.secret.clone());
+        }
+    }
+    Ok(Some(commitments))
+}
+
+/// Recompute the commitment that was registered with `metadata` if the hash chain was generated
+/// from `secret`.
+fn generate_commitment(
+    secret: &SecretString,
+    chain_id: &ChainId,
+    provider_address: &Address,
+    chain_config: &EthereumConfig,
+    metadata: &CommitmentMetadata,
+    sample_interval: u64,
+) -> Result<[u8; 32]> {
+    let secret = secret
+        .load_hash_chain_secret()?
+        .ok_or(anyhow!("Please specify a provider secret in the config"))?;
+    PebbleHashChain::from_config(
+        &secret,
+        chain_id,
+        provider_address,
+        &chain_config.contract_addr,
+        &metadata.seed,
+        metadata.chain_length,
+        sample_interval,
+    )?
+    .reveal_ith(0)
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        crate::{
+            command::{
+                register_provider::CommitmentMetadata,
+                rotate_secret::{
+                    check_secrets_in_files,
+                    generate_commitment,
+                    load_or_create_secret_file,
+                },
+            },
+            config::{
+                test::CONFIG,
+                Commitment,
+                Config,
+                SecretString,
+            },
+        },
+        ethers::types::Address,
+        tempfile::TempDir,
+    };
+
+    #[test]
+    fn test_load_or_create_secret_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("new_secret.txt");
+        let path = path.to_str().unwrap();
+
+        let created = load_or_create_secret_file(path).unwrap();
+        assert_eq!(created.value, None);
+        let secret = created.load_hash_chain_secret().unwrap().unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // Retrying with the same file reuses the secret.
+        let loaded = load_or_create_secret_file(path).unwrap();
+        assert_eq!(loaded.load_hash_chain_secret().unwrap().unwrap(), secret);
+
+        std::fs::write(path, "not a secret").unwrap();
+        assert!(load_or_create_secret_file(path).is_err());
+    }
+
+    #[test]
+    fn test_check_secrets_in_files() {
+        let mut config: Config = serde_yaml::from_str(CONFIG).unwrap();
+        let chain_ids = vec!["ethereum".to_string()];
+        assert!(check_secrets_in_files(&config, &chain_ids).is_err());
+
+        let file_secret = SecretString {
+            value: None,
+            file:  Some("secret.txt".to_string()),
+        };
+        config.provider.secret = file_secret.clone();
+        assert!(check_secrets_in_files(&config, &chain_ids).is_ok());
+
+        let mut commitment = Commitment {
+            seed:                                [0; 32],
+            chain_length:                        100,
+            original_commitment_sequence_number: 0,
+            secret:                              Some(file_secret),
+        };
+        let chain_config = config.chains.get_mut("ethereum").unwrap();
+        chain_config.commitments = Some(vec![commitment.clone()]);
+        assert!(check_secrets_in_files(&config, &chain_ids).is_ok());
+
+        commitment.secret = Some(SecretString {
+            value: Some("00".repeat(32)),
+            file:  None,
+        });
+        let chain_config = config.chains.get_mut("ethereum").unwrap();
+        chain_config.commitments = Some(vec![commitment]);
+        assert!(check_secrets_in_files(&config, &chain_ids).is_err());
+    }
+
+    #[test]
+    fn test_generate_commitment() {
+        let config: Config = serde_yaml::from_str(CONFIG).unwrap();
+        let chain_id = "ethereum".to_string();
+        let chain_config = config.get_chain_config(&chain_id).unwrap();
+        let metadata = CommitmentMetadata {
+            seed:         [1; 32],
+            chain_length: 100,
+        };
+        let secret = |value: &str| SecretString {
+            value: Some(value.repeat(32)),
+            file:  None,
+        };
+        let commitment = |secret: &SecretString, sample_interval: u64| {
+            generate_commitment(
+                secret,
+                &chain_id,
+                &Address::zero(),
+                &chain_config,
+                &metadata,
+                sample_interval,
+            )
+            .unwrap()
+        };
+
+        // The commitment only depends on the secret and the metadata, not on how the chain is sampled.
+        assert_eq!(commitment(&secret("00"), 1), commitment(&secret("00"), 7));
+        assert_ne!(commitment(&secret("00"), 1), commitment(&secret("01"), 1));
+    }
+}
@@ -23,6 +23,10 @@ use {
             self,
             KeeperMetrics,
         },
+        reorg::{
+            self,
+            RevealedSequences,
+        },
         state::{
             HashChainState,
             PebbleHashChain,
@@ -52,8 +56,14 @@ use {
     },
     std::{
         collections::HashMap,
-        net::SocketAddr,
-        sync::Arc,
+        net::{
+            SocketAddr,
+            TcpListener,
+        },
+        sync::{
+            Arc,
+            Mutex,
+        },
         time::{
             Duration,
             SystemTime,
@@ -77,22 +87,38 @@ use {
 const TRACK_INTERVAL: Duration = Duration::from_secs(10);
 
 pub async fn run_api(
-    socket_addr: SocketAddr,
+    listener: TcpListener,
     chains: HashMap<String, api::BlockchainState>,
     metrics_registry: Arc<RwLock<Registry>>,
+    opts: &RunOptions,
+    rx_ready: watch::Receiver<bool>,
     mut rx_exit: watch::Receiver<bool>,
 ) -> Result<()> {
     #[derive(OpenApi)]
     #[openapi(
     paths(
     crate::api::revelation,
+    crate::api::revelation_batch,
+    crate::api::revelation_context,
+    crate::api::revealed,
+    crate::api::verify_reveal,
+    crate::api::hash_chain_params,
     crate::api::chain_ids,
     ),
     components(
     schemas(
     crate::api::GetRandomValueResponse,
+    crate::api::GetRandomValuesResponse,
     crate::api::Blob,
     crate::api::BinaryEncoding,
+    crate::api::ErrorResponse,
+    crate::api::GetRevelationContextResponse,
+    crate::api::RequestContext,
+    crate::api::Revelation,
+    crate::api::RevealedValuesResponse,
+    crate::api::VerifyRevealResponse,
+    crate::api::HashChainParamsResponse,
+    crate::api::HashChainInfo,
     )
     ),
     tags(
@@ -101,7 +127,18 @@ pub async fn run_api(
     )]
     struct ApiDoc;
 
-    let api_state = api::ApiState::new(chains, metrics_registry).await;
+    let api_state = api::ApiState::new(
+        chains,
+        metrics_registry,
+        opts.hot_sequence_threshold,
+        opts.hot_sequence_reveals_per_second,
+        opts.reveal_cache_size,
+        opts.strict_query_params,
+        opts.rate_limit_per_second,
+        rx_ready,
+        opts.ready_rpc_check,
+    )
+    .await;
 
     // Initialize Axum Router. Note the type here is a `Router<State>` due to the use of the
     // `with_state` method which replaces `Body` with `State` in the type signature.
@@ -112,11 +149,12 @@ pub async fn run_api(
         // Permissive CORS layer to allow all origins
         .layer(CorsLayer::permissive());
 
-    tracing::info!("Starting server on: {:?}", &socket_addr);
-    // Binds the axum's server to the configured address and port. This is a blocking call and will
-    // not return until the server is shutdown.
-    axum::Server::try_bind(&socket_addr)?
-        .serve(app.into_make_service())
+    tracing::info!("Starting server on: {:?}", listener.local_addr()?);
+    // Serves the API on the listener bound at startup. This is a blocking call and will not return
+    // until the server is shutdown.
+    axum::Server::from_tcp(listener)?
+        // The rate limiter identifies clients by the address of their connection.
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
         .with_graceful_shutdown(async {
             // It can return an error or an Ok(()). In both cases, we would shut down.
             // As Ok(()) means, exit signal (ctrl + c) was received.
@@ -130,6 +168,22 @@ pub async fn run_api(
     Ok(())
 }
 
+/// Serve `api::startup_routes` on `listener` until `rx_ready` is set, i.e., until the hash chains
+/// are computed and `run_api` can serve the API on the same listener.
+async fn run_startup_api(listener: TcpListener, mut rx_ready: watch::Receiver<bool>) -> Result<()> {
+    tracing::info!(
+        "Serving the startup routes on {:?} while the hash chains are computed",
+        listener.local_addr()?
+    );
+    axum::Server::from_tcp(listener)?
+        .serve(api::startup_routes().into_make_service())
+        .with_graceful_shutdown(async {
+            let _ = rx_ready.changed().await;
+        })
+        .await?;
+
+    Ok(())
+}
 
 pub async fn run_keeper(
     chains: HashMap<String, api::BlockchainState>,
@@ -161,9 +215,26 @@ pub async fn run_keeper(
 
 pub async fn run(opts: &RunOptions) -> Result<()> {
     let config = Config::load(&opts.config.config)?;
-    let secret = config.provider.secret.load()?.ok_or(anyhow!(
-        "Please specify a provider secret in the config file."
-    ))?;
+    let secret = config
+        .provider
+        .secret
+        .load_hash_chain_secret()?
+        .ok_or(anyhow!(
+            "Please specify a provider secret in the config file."
+        ))?;
+    // Bind the HTTP server before computing the hash chains, which can take minutes, so that an
+    // unavailable address fails immediately. Until the chains are ready, the server answers /live
+    // and HTTP 503 for every other endpoint, including /ready, so that clients and health checks
+    // get an answer instead of waiting for the API.
+    let listener = TcpListener::bind(opts.addr).map_err(|e| {
+        anyhow!(
+            "Failed to bind the HTTP server to {}: {}. Is the port already in use?",
+            opts.addr,
+            e
+        )
+    })?;
+    let (tx_ready, rx_ready) = watch::channel(false);
+    let startup_api = spawn(run_startup_api(listener.try_clone()?, rx_ready.clone()));
     let (tx_exit, rx_exit) = watch::channel(false);
     let metrics_registry = Arc::new(RwLock::new(Registry::default()));
     let rpc_metrics = Arc::new(RpcMetrics::new(metrics_registry.clone()).await);
@@ -205,6 +276,9 @@ pub async fn run(opts: &RunOptions) -> Result<()> {
         return Err(anyhow!("No chains were successfully setup"));
     }
 
+    // Stop serving the startup routes, so that the API can take over the listener.
+    let _ = tx_ready.send(true);
+    startup_api.await??;
 
     // Listen for Ctrl+C so we can set the exit flag and wait for a graceful shutdown.
     spawn(async move {
@@ -218,7 +292,7 @@ pub async fn run(opts: &RunOptions) -> Result<()> {
         Ok::<(), Error>(())
     });
 
-    if let Some(keeper_private_key) = config.keeper.private_key.load()? {
+    if let Some(keeper_private_key) = config.keeper.private_key.load_private_key()? {
         spawn(run_keeper(
             chains.clone(),
             config.clone(),
@@ -237,7 +311,7 @@ pub async fn run(opts: &RunOptions) -> Result<()> {
         rpc_metrics.clone(),
     ));
 
-    run_api(opts.addr.clone(), chains, metrics_registry, rx_exit).await?;
+    run_api(listener, chains, metrics_registry, opts, rx_ready, rx_exit).await?;
 
     Ok(())
 }
@@ -287,6 +361,7 @@ async fn setup_chain_state(
         seed:                                latest_metadata.seed,
         chain_length:                        latest_metadata.chain_length,
         original_commitment_sequence_number: provider_info.original_commitment_sequence_number,
+        secret:                              None,
     });
 
     // TODO: we may want to load the hash chain in a lazy/fault-tolerant way. If there are many blockchains,
@@ -300,8 +375,17 @@ async fn setup_chain_state(
         let offset = commitment.original_commitment_sequence_number.try_into()?;
         offsets.push(offset);
 
+        // Commitments made before the secret was rotated are generated from the previous secret.
+        let commitment_secret = match &commitment.secret {
+            Some(secret) => secret.load_hash_chain_secret()?.ok_or(anyhow!(
+                "Chain: {} - Please specify the secret of the commitment at sequence number {}",
+                &chain_id,
+                commitment.original_commitment_sequence_number
+            ))?,
+            None => secret.clone(),
+        };
         let pebble_hash_chain = PebbleHashChain::from_config(
-            &secret,
+            &commitment_secret,
             &chain_id,
             &provider,
             &chain_config.contract_addr,
@@ -326,6 +410,10 @@ async fn setup_chain_state(
         tracing::info!("Root of chain id {} matches commitment", &chain_id);
     }
 
+    let revealed_sequences = chain_config
+        .reorg_check_interval_seconds
+        .map(|_| Arc::new(Mutex::new(RevealedSequences::default())));
+
     let state = BlockchainState {
         id: chain_id.clone(),
         state: Arc::new(chain_state),
@@ -333,7 +421,21 @@ async fn setup_chain_state(
         provider_address: provider.clone(),
         reveal_delay_blocks: chain_config.reveal_delay_blocks,
         confirmed_block_status: chain_config.confirmed_block_status,
+        revealed_sequences,
     };
+
+    if let (Some(revealed_sequences), Some(reorg_check_interval_seconds)) = (
+        state.revealed_sequences.clone(),
+        chain_config.reorg_check_interval_seconds,
+    ) {
+        spawn(reorg::track_reorgs(
+            state.clone(),
+            revealed_sequences,
+            Duration::from_secs(reorg_check_interval_seconds),
+            chain_config.reorg_tracking_blocks,
+        ));
+    }
+
     Ok(state)
 }
 
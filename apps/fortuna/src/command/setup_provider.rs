@@ -94,9 +94,12 @@ async fn setup_chain_provider(
 ) -> Result<()> {
     tracing::info!("Setting up provider for chain: {0}", chain_id);
     let provider_config = &config.provider;
-    let private_key = provider_config.private_key.load()?.ok_or(anyhow!(
-        "Please specify a provider private key in the config file."
-    ))?;
+    let private_key = provider_config
+        .private_key
+        .load_private_key()?
+        .ok_or(anyhow!(
+            "Please specify a provider private key in the config file."
+        ))?;
     let provider_address = private_key.clone().parse::<LocalWallet>()?.address();
     // Initialize a Provider to interface with the EVM contract.
     let contract = Arc::new(SignablePythContract::from_config(&chain_config, &private_key).await?);
@@ -127,9 +130,12 @@ async fn setup_chain_provider(
                     )
                 })?;
 
-        let secret = provider_config.secret.load()?.ok_or(anyhow!(
-            "Please specify a provider secret in the config file."
-        ))?;
+        let secret = provider_config
+            .secret
+            .load_hash_chain_secret()?
+            .ok_or(anyhow!(
+                "Please specify a provider secret in the config file."
+            ))?;
         if metadata.chain_length != provider_config.chain_length {
             tracing::info!(
                 "Chain length mismatch. metadata.chain_length={}, provider_config.chain_length={}",
@@ -3,6 +3,7 @@ use {
         chain::ethereum::SignablePythContract,
         config::{
             Config,
+            EthereumConfig,
             WithdrawFeesOptions,
         },
     },
@@ -11,8 +12,13 @@ use {
         Result,
     },
     ethers::{
+        middleware::Middleware,
         signers::Signer,
-        types::Address,
+        types::{
+            Address,
+            TransactionRequest,
+            TxHash,
+        },
     },
 };
 
@@ -21,80 +27,167 @@ pub async fn withdraw_fees(opts: &WithdrawFeesOptions) -> Result<()> {
     let config = Config::load(&opts.config.config)?;
 
     let private_key_string = if opts.keeper {
-        config.keeper.private_key.load()?.ok_or(anyhow!("Please specify a keeper private key in the config or omit the --keeper option to use the provider private key"))?
+        config.keeper.private_key.load_private_key()?.ok_or(anyhow!("Please specify a keeper private key in the config or omit the --keeper option to use the provider private key"))?
     } else {
-        config.provider.private_key.load()?.ok_or(anyhow!(
+        config.provider.private_key.load_private_key()?.ok_or(anyhow!(
             "Please specify a provider private key in the config or provide the --keeper option to use the keeper private key instead."
         ))?
     };
 
-    match opts.chain_id.clone() {
-        Some(chain_id) => {
-            let chain_config = &config.get_chain_config(&chain_id)?;
-            let contract =
-                SignablePythContract::from_config(&chain_config, &private_key_string).await?;
+    let chain_ids = match opts.chain_id.clone() {
+        Some(chain_id) => vec![chain_id],
+        None => config.chains.keys().cloned().collect(),
+    };
 
-            withdraw_fees_for_chain(
-                contract,
-                config.provider.address.clone(),
-                opts.keeper,
-                opts.retain_balance_wei,
-            )
-            .await?;
+    let mut results = vec![];
+    for chain_id in chain_ids {
+        tracing::info!("Withdrawing fees for chain: {}", chain_id);
+        let result = match config.get_chain_config(&chain_id) {
+            Ok(chain_config) => {
+                withdraw_fees_from_config(&chain_config, &private_key_string, &config, opts).await
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = &result {
+            tracing::error!("Failed to withdraw fees on {} {}", chain_id, e);
         }
-        None => {
-            for (chain_id, chain_config) in config.chains.iter() {
-                tracing::info!("Withdrawing fees for chain: {}", chain_id);
-                let contract =
-                    SignablePythContract::from_config(&chain_config, &private_key_string).await?;
+        results.push((chain_id, result));
+    }
 
-                withdraw_fees_for_chain(
-                    contract,
-                    config.provider.address.clone(),
-                    opts.keeper,
-                    opts.retain_balance_wei,
-                )
-                .await?;
+    println!("Withdrawal summary:");
+    let mut failed_chain_ids = vec![];
+    for (chain_id, result) in &results {
+        match result {
+            Ok(Some(withdrawal)) => {
+                println!(
+                    "  {}: withdrew {} wei (tx {:?})",
+                    chain_id, withdrawal.amount_wei, withdrawal.transaction_hash
+                );
+                if let Some((to, transfer_transaction_hash)) = withdrawal.transfer {
+                    println!(
+                        "  {}: transferred {} wei to {:?} (tx {:?})",
+                        chain_id, withdrawal.amount_wei, to, transfer_transaction_hash
+                    );
+                }
+            }
+            Ok(None) => println!("  {}: nothing to withdraw", chain_id),
+            Err(e) => {
+                println!("  {}: failed ({})", chain_id, e);
+                failed_chain_ids.push(chain_id.as_str());
             }
         }
     }
-    Ok(())
+
+    match failed_chain_ids.is_empty() {
+        true => Ok(()),
+        false => Err(anyhow!(
+            "Failed to withdraw fees on {}",
+            failed_chain_ids.join(", ")
+        )),
+    }
+}
+
+async fn withdraw_fees_from_config(
+    chain_config: &EthereumConfig,
+    private_key_string: &str,
+    config: &Config,
+    opts: &WithdrawFeesOptions,
+) -> Result<Option<Withdrawal>> {
+    let contract = SignablePythContract::from_config(chain_config, private_key_string).await?;
+    withdraw_fees_for_chain(
+        contract,
+        config.provider.address,
+        opts.keeper,
+        opts.retain_balance_wei,
+        opts.to,
+    )
+    .await
 }
 
+/// A successful fee withdrawal on a chain.
+pub struct Withdrawal {
+    pub amount_wei:       u128,
+    pub transaction_hash: TxHash,
+    /// The recipient and hash of the transaction that transferred the fees to `--to`, if any.
+    pub transfer:         Option<(Address, TxHash)>,
+}
+
+/// Withdraw the fees of the provider above `retained_balance`, and transfer them to `to` if set.
+/// Returns `None` if there is nothing to withdraw. A transaction without a receipt is an error,
+/// since whether it was mined is unknown.
 pub async fn withdraw_fees_for_chain(
     contract: SignablePythContract,
     provider_address: Address,
     is_fee_manager: bool,
     retained_balance: u128,
-) -> Result<()> {
+    to: Option<Address>,
+) -> Result<Option<Withdrawal>> {
     tracing::info!("Fetching fees for provider: {:?}", provider_address);
     let provider_info = contract.get_provider_info(provider_address).call().await?;
     let fees = provider_info.accrued_fees_in_wei;
     tracing::info!("Accrued fees: {} wei", fees);
 
     let withdrawal_amount_wei = fees.saturating_sub(retained_balance);
-    if withdrawal_amount_wei > 0 {
-        tracing::info!(
-            "Withdrawing {} wei to {}...",
-            withdrawal_amount_wei,
-            contract.wallet().address()
-        );
+    if withdrawal_amount_wei == 0 {
+        return Ok(None);
+    }
 
-        let call = match is_fee_manager {
-            true => contract.withdraw_as_fee_manager(provider_address, withdrawal_amount_wei),
-            false => contract.withdraw(withdrawal_amount_wei),
-        };
-        let tx_result = call.send().await?.await?;
+    tracing::info!(
+        "Withdrawing {} wei to {}...",
+        withdrawal_amount_wei,
+        contract.wallet().address()
+    );
+    let call = match is_fee_manager {
+        true => contract.withdraw_as_fee_manager(provider_address, withdrawal_amount_wei),
+        false => contract.withdraw(withdrawal_amount_wei),
+    };
+    let pending_tx = call.send().await?;
+    let transaction_hash = pending_tx.tx_hash();
+    tracing::info!("Withdrawal transaction hash {:?}", transaction_hash);
+    // Don't transfer funds that may not have been withdrawn.
+    let receipt = pending_tx.await?.ok_or(anyhow!(
+        "No receipt for withdrawal transaction {:?}. Check whether it was mined before retrying",
+        transaction_hash
+    ))?;
+    if receipt.status != Some(1.into()) {
+        return Err(anyhow!(
+            "Withdrawal transaction {:?} failed",
+            transaction_hash
+        ));
+    }
 
-        match &tx_result {
-            Some(receipt) => {
-                tracing::info!("Withdrawal transaction hash {:?}", receipt.transaction_hash);
-            }
-            None => {
-                tracing::warn!("No transaction receipt. Unclear what happened to the transaction");
-            }
+    let mut transfer = None;
+    if let Some(to) = to.filter(|to| *to != contract.wallet().address()) {
+        tracing::info!("Transferring {} wei to {:?}...", withdrawal_amount_wei, to);
+        let transfer_request = TransactionRequest::new()
+            .to(to)
+            .value(withdrawal_amount_wei);
+        let pending_tx = contract
+            .client()
+            .send_transaction(transfer_request, None)
+            .await?;
+        let transfer_transaction_hash = pending_tx.tx_hash();
+        tracing::info!("Transfer transaction hash {:?}", transfer_transaction_hash);
+        let receipt = pending_tx.await?.ok_or(anyhow!(
+            "Withdrew {} wei in transaction {:?}, but got no receipt for transfer transaction {:?}. Check whether it was mined before retrying",
+            withdrawal_amount_wei,
+            transaction_hash,
+            transfer_transaction_hash
+        ))?;
+        if receipt.status != Some(1.into()) {
+            return Err(anyhow!(
+                "Withdrew {} wei in transaction {:?}, but transfer transaction {:?} failed",
+                withdrawal_amount_wei,
+                transaction_hash,
+                transfer_transaction_hash
+            ));
         }
+        transfer = Some((to, transfer_transaction_hash));
     }
 
-    Ok(())
+    Ok(Some(Withdrawal {
+        amount_wei: withdrawal_amount_wei,
+        transaction_hash,
+        transfer,
+    }))
 }
@@ -18,28 +18,35 @@ use {
         Args,
         Parser,
     },
-    ethers::types::Address,
+    ethers::{
+        signers::LocalWallet,
+        types::Address,
+    },
     std::{
         collections::HashMap,
         fs,
     },
 };
 pub use {
+    bench_reveal::BenchRevealOptions,
     generate::GenerateOptions,
     get_request::GetRequestOptions,
     inspect::InspectOptions,
     register_provider::RegisterProviderOptions,
     request_randomness::RequestRandomnessOptions,
+    rotate_secret::RotateSecretOptions,
     run::RunOptions,
     setup_provider::SetupProviderOptions,
     withdraw_fees::WithdrawFeesOptions,
 };
 
+mod bench_reveal;
 mod generate;
 mod get_request;
 mod inspect;
 mod register_provider;
 mod request_randomness;
+mod rotate_secret;
 mod run;
 mod setup_provider;
 mod withdraw_fees;
@@ -78,6 +85,12 @@ pub enum Options {
 
     /// Withdraw any of the provider's accumulated fees from the contract.
     WithdrawFees(WithdrawFeesOptions),
+
+    /// Register new commitments generated from a new provider secret on all the configured chains.
+    RotateSecret(RotateSecretOptions),
+
+    /// Measure the reveal latency of a running service by requesting revelations concurrently.
+    BenchReveal(BenchRevealOptions),
 }
 
 #[derive(Args, Clone, Debug)]
@@ -112,11 +125,36 @@ impl Config {
             {
                 return Err(anyhow!("chain id {:?} configuration is invalid. Config must satisfy min_profit_pct <= target_profit_pct <= max_profit_pct.", chain_id));
             }
+            if config.reorg_check_interval_seconds == Some(0) {
+                return Err(anyhow!("chain id {:?} configuration is invalid. reorg_check_interval_seconds must be greater than 0.", chain_id));
+            }
+        }
+
+        // Secrets set inline are as exposed as the config file itself.
+        if config.has_inline_secrets() {
+            warn_if_readable_by_others(path);
         }
 
         Ok(config)
     }
 
+    /// Whether any of the secrets are set inline with `value` rather than in a file.
+    fn has_inline_secrets(&self) -> bool {
+        let commitment_secrets = self
+            .chains
+            .values()
+            .flat_map(|chain_config| chain_config.commitments.iter().flatten())
+            .filter_map(|commitment| commitment.secret.as_ref());
+        [
+            &self.provider.private_key,
+            &self.provider.secret,
+            &self.keeper.private_key,
+        ]
+        .into_iter()
+        .chain(commitment_secrets)
+        .any(|secret| secret.value.is_some())
+    }
+
     pub fn get_chain_config(&self, chain_id: &ChainId) -> Result<EthereumConfig> {
         self.chains
             .get(chain_id)
@@ -183,6 +221,21 @@ pub struct EthereumConfig {
 
     /// Historical commitments made by the provider.
     pub commitments: Option<Vec<Commitment>>,
+
+    /// If provided, the requests of revealed sequence numbers are re-checked at this interval, and
+    /// a warning is emitted if a reorg removed a request after its random value was revealed.
+    /// Must be greater than 0.
+    #[serde(default)]
+    pub reorg_check_interval_seconds: Option<u64>,
+
+    /// The number of blocks after which the request of a revealed sequence number is no longer
+    /// re-checked for reorgs.
+    #[serde(default = "default_reorg_tracking_blocks")]
+    pub reorg_tracking_blocks: BlockNumber,
+}
+
+fn default_reorg_tracking_blocks() -> BlockNumber {
+    128
 }
 
 
@@ -195,6 +248,10 @@ pub struct Commitment {
     pub seed:                                [u8; 32],
     pub chain_length:                        u64,
     pub original_commitment_sequence_number: u64,
+    /// The secret used to generate this commitment. Only needed if it differs from the current
+    /// provider secret, i.e., if the secret was rotated after this commitment was made.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret:                              Option<SecretString>,
 }
 
 /// Configuration values that are common to a single provider (and shared across chains).
@@ -237,7 +294,7 @@ fn default_chain_sample_interval() -> u64 {
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct KeeperConfig {
     /// If provided, the keeper will run alongside the Fortuna API service.
-    /// The private key is a 20-byte (40 char) hex encoded Ethereum private key.
+    /// The private key is a 32-byte (64 char) hex encoded Ethereum private key.
     /// This key is required to submit transactions for entropy callback requests.
     /// This key *does not need to be a registered provider*. In particular, production deployments
     /// should ensure this is a different key in order to reduce the severity of security breaches.
@@ -264,6 +321,7 @@ impl SecretString {
 
         match &self.file {
             Some(v) => {
+                warn_if_readable_by_others(v);
                 return Ok(Some(fs::read_to_string(v)?.trim().to_string()));
             }
             _ => {}
@@ -271,4 +329,216 @@ impl SecretString {
 
         Ok(None)
     }
+
+    /// Load a hex encoded secp256k1 private key (32 bytes, optionally 0x prefixed) and check its
+    /// format, so that a malformed key fails with a clear error instead of deep inside an RPC call.
+    pub fn load_private_key(&self) -> Result<Option<String>> {
+        let private_key = self.load()?;
+        if let Some(private_key) = &private_key {
+            private_key.parse::<LocalWallet>().map_err(|_| {
+                anyhow!("The private key must be a 64-char hex string, optionally 0x prefixed")
+            })?;
+        }
+        Ok(private_key)
+    }
+
+    /// Load a hash chain secret and check that it is a 64-char hex string.
+    pub fn load_hash_chain_secret(&self) -> Result<Option<String>> {
+        let secret = self.load()?;
+        if let Some(secret) = &secret {
+            validate_hash_chain_secret(secret)?;
+        }
+        Ok(secret)
+    }
+}
+
+/// Check that `secret` is a 64-char hex string (32 bytes, without 0x prefix).
+fn validate_hash_chain_secret(secret: &str) -> Result<()> {
+    match hex::decode(secret.trim()) {
+        Ok(decoded) if decoded.len() == 32 => Ok(()),
+        _ => Err(anyhow!(
+            "The secret must be a 64-char hex string, without 0x prefix"
+        )),
+    }
+}
+
+/// Files holding secrets should only be readable by their owner, e.g., mode 600.
+fn warn_if_readable_by_others(path: &str) {
+    if is_readable_by_others(path) {
+        tracing::warn!(
+            "The file {} holds secrets and is accessible by other users. Consider running `chmod 600 {}`",
+            path,
+            path
+        );
+    }
+}
+
+#[cfg(unix)]
+fn is_readable_by_others(path: &str) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path)
+        .map(|metadata| metadata.permissions().mode() & 0o077 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_readable_by_others(_path: &str) -> bool {
+    false
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use {
+        crate::config::{
+            is_readable_by_others,
+            validate_hash_chain_secret,
+            Config,
+            SecretString,
+        },
+        std::io::Write,
+        tempfile::NamedTempFile,
+    };
+
+    pub(crate) const CONFIG: &str = r#"
+chains:
+  ethereum:
+    geth_rpc_addr: http://localhost:8545
+    contract_addr: 0x0000000000000000000000000000000000000001
+    reveal_delay_blocks: 1
+    gas_limit: 500000
+    min_profit_pct: 0
+    target_profit_pct: 20
+    max_profit_pct: 100
+provider:
+  uri: http://localhost:8080/
+  chain_length: 100
+  address: 0x0000000000000000000000000000000000000002
+  private_key:
+    value: 0x0000000000000000000000000000000000000000000000000000000000000001
+  secret:
+    value: 0000000000000000000000000000000000000000000000000000000000000003
+keeper:
+  private_key:
+    value: 0x0000000000000000000000000000000000000000000000000000000000000004
+"#;
+
+    fn write_config(config: &Config) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(serde_yaml::to_string(config).unwrap().as_bytes())
+            .unwrap();
+        file
+    }
+
+    #[test]
+    fn test_load_config() {
+        let config: Config = serde_yaml::from_str(CONFIG).unwrap();
+        let file = write_config(&config);
+        assert!(Config::load(file.path().to_str().unwrap()).is_ok());
+
+        // A reorg check interval of 0 would make the server hammer the RPC endpoint.
+        let mut invalid_config = config.clone();
+        for chain_config in invalid_config.chains.values_mut() {
+            chain_config.reorg_check_interval_seconds = Some(0);
+        }
+        let file = write_config(&invalid_config);
+        assert!(Config::load(file.path().to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_load_secret() {
+        let inline = SecretString {
+            value: Some("inline".to_string()),
+            file:  None,
+        };
+        assert_eq!(inline.load().unwrap(), Some("inline".to_string()));
+
+        // File contents are trimmed.
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b" from file\n").unwrap();
+        let from_file = SecretString {
+            value: None,
+            file:  Some(file.path().to_str().unwrap().to_string()),
+        };
+        assert_eq!(from_file.load().unwrap(), Some("from file".to_string()));
+
+        let missing_file = SecretString {
+            value: None,
+            file:  Some("/nonexistent/secret.txt".to_string()),
+        };
+        assert!(missing_file.load().is_err());
+
+        let unset = SecretString {
+            value: None,
+            file:  None,
+        };
+        assert_eq!(unset.load().unwrap(), None);
+        assert_eq!(unset.load_private_key().unwrap(), None);
+        assert_eq!(unset.load_hash_chain_secret().unwrap(), None);
+    }
+
+    #[test]
+    fn test_load_private_key() {
+        let private_key = |value: &str| SecretString {
+            value: Some(value.to_string()),
+            file:  None,
+        };
+        let key = "0000000000000000000000000000000000000000000000000000000000000001";
+        assert!(private_key(key).load_private_key().is_ok());
+        assert!(private_key(&format!("0x{}", key))
+            .load_private_key()
+            .is_ok());
+        assert!(private_key("0xabcd").load_private_key().is_err());
+        assert!(private_key(&format!("0x{}zz", &key[..62]))
+            .load_private_key()
+            .is_err());
+    }
+
+    #[test]
+    fn test_validate_hash_chain_secret() {
+        let secret = "0000000000000000000000000000000000000000000000000000000000000003";
+        assert!(validate_hash_chain_secret(secret).is_ok());
+        assert!(validate_hash_chain_secret(&format!("{}\n", secret)).is_ok());
+        // Wrong length
+        assert!(validate_hash_chain_secret("abcd").is_err());
+        assert!(validate_hash_chain_secret(&format!("{}00", secret)).is_err());
+        // Not hex
+        assert!(validate_hash_chain_secret(&secret.replace('0', "z")).is_err());
+        assert!(validate_hash_chain_secret(&format!("0x{}", &secret[2..])).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_is_readable_by_others() {
+        use std::{
+            fs,
+            os::unix::fs::PermissionsExt,
+        };
+
+        let file = NamedTempFile::new().unwrap();
+        let path = file.path().to_str().unwrap();
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600)).unwrap();
+        assert!(!is_readable_by_others(path));
+        fs::set_permissions(path, fs::Permissions::from_mode(0o640)).unwrap();
+        assert!(is_readable_by_others(path));
+        fs::set_permissions(path, fs::Permissions::from_mode(0o604)).unwrap();
+        assert!(is_readable_by_others(path));
+    }
+
+    #[test]
+    fn test_has_inline_secrets() {
+        let mut config: Config = serde_yaml::from_str(CONFIG).unwrap();
+        assert!(config.has_inline_secrets());
+
+        for secret in [
+            &mut config.provider.private_key,
+            &mut config.provider.secret,
+            &mut config.keeper.private_key,
+        ] {
+            *secret = SecretString {
+                value: None,
+                file:  Some("secret.txt".to_string()),
+            };
+        }
+        assert!(!config.has_inline_secrets());
+    }
 }
@@ -0,0 +1,36 @@
+use {
+    crate::api::ChainId,
+    clap::Args,
+    reqwest::Url,
+};
+
+#[derive(Args, Clone, Debug)]
+#[command(next_help_heading = "Bench Reveal Options")]
+#[group(id = "BenchReveal")]
+pub struct BenchRevealOptions {
+    /// The URL of the running service to benchmark.
+    #[arg(long = "url")]
+    #[arg(default_value = super::DEFAULT_HTTP_ADDR)]
+    pub url: Url,
+
+    /// The chain to request revelations for.
+    #[arg(long = "chain-id")]
+    #[arg(env = "FORTUNA_CHAIN_ID")]
+    pub chain_id: ChainId,
+
+    /// The number of revelations to request. Sequence numbers are requested in order,
+    /// starting from `start-sequence`.
+    #[arg(long = "sequences")]
+    #[arg(default_value = "100")]
+    pub sequences: u64,
+
+    /// The first sequence number to request.
+    #[arg(long = "start-sequence")]
+    #[arg(default_value = "0")]
+    pub start_sequence: u64,
+
+    /// The maximum number of requests in flight at the same time.
+    #[arg(long = "concurrency")]
+    #[arg(default_value = "10")]
+    pub concurrency: usize,
+}
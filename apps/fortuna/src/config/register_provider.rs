@@ -13,8 +13,18 @@ pub struct RegisterProviderOptions {
     #[command(flatten)]
     pub config: ConfigOptions,
 
-    /// Register the provider on this chain
-    #[arg(long = "chain-id")]
+    /// Register the provider on these chains. Pass the option several times (or a comma separated
+    /// list) to register on several chains. A failure on one chain doesn't stop the registration on
+    /// the other chains.
+    #[arg(long = "chain-id", required = true)]
     #[arg(env = "FORTUNA_CHAIN_ID")]
-    pub chain_id: ChainId,
+    #[arg(value_delimiter = ',')]
+    pub chain_ids: Vec<ChainId>,
+
+    /// The length of the hash chain to generate, i.e., the number of requests the provider can
+    /// serve before registering again. Overrides `provider.chain_length` of the config file.
+    #[arg(long = "chain-length")]
+    #[arg(env = "FORTUNA_CHAIN_LENGTH")]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    pub chain_length: Option<u64>,
 }
@@ -0,0 +1,29 @@
+use {
+    crate::{
+        api::ChainId,
+        config::ConfigOptions,
+    },
+    clap::Args,
+};
+
+#[derive(Args, Clone, Debug)]
+#[command(next_help_heading = "Rotate Secret Options")]
+#[group(id = "RotateSecret")]
+pub struct RotateSecretOptions {
+    #[command(flatten)]
+    pub config: ConfigOptions,
+
+    /// The file holding the new provider secret, a 64-char hex string. If the file doesn't exist,
+    /// a random secret is generated and written to it (readable by the owner only) before any
+    /// transaction is sent.
+    #[arg(long = "new-secret-file")]
+    #[arg(env = "FORTUNA_NEW_SECRET_FILE")]
+    pub new_secret_file: String,
+
+    /// Rotate the secret on these chains only. By default, the secret is rotated on all the
+    /// configured chains, since they all share the provider secret. Use this option to retry the
+    /// chains that failed with the same `--new-secret-file`.
+    #[arg(long = "chain-id")]
+    #[arg(value_delimiter = ',')]
+    pub chain_ids: Vec<ChainId>,
+}
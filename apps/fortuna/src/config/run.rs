@@ -10,9 +10,49 @@ pub struct RunOptions {
     #[command(flatten)]
     pub config: ConfigOptions,
 
-    /// Address and port the HTTP server will bind to.
+    /// Address and port the HTTP server will bind to, e.g., `0.0.0.0:34000` or `[::1]:34000`.
     #[arg(long = "rpc-listen-addr")]
     #[arg(default_value = super::DEFAULT_RPC_ADDR)]
     #[arg(env = "RPC_ADDR")]
     pub addr: SocketAddr,
+
+    /// Sequence numbers that a client revealed more than this many times in the last minute are
+    /// limited to --hot-sequence-reveals-per-second reveals for that client.
+    #[arg(long = "hot-sequence-threshold")]
+    #[arg(default_value = "100")]
+    pub hot_sequence_threshold: u64,
+
+    /// The maximum number of reveals per second of each sequence number by a client that revealed
+    /// it more than --hot-sequence-threshold times in the last minute.
+    #[arg(long = "hot-sequence-reveals-per-second")]
+    #[arg(default_value = "1")]
+    #[arg(value_parser = clap::value_parser!(u32).range(1..))]
+    pub hot_sequence_reveals_per_second: u32,
+
+    /// The maximum number of revealed random values to keep in memory, so that repeated reveals of
+    /// the same sequence number don't recompute them from the hash chain. The least recently used
+    /// values are evicted first. Set to 0 to disable the cache.
+    #[arg(long = "reveal-cache-size")]
+    #[arg(default_value = "10000")]
+    pub reveal_cache_size: usize,
+
+    /// Reject requests with query parameters that the endpoint doesn't recognize (HTTP 400)
+    /// instead of ignoring them.
+    #[arg(long = "strict-query-params")]
+    #[arg(env = "STRICT_QUERY_PARAMS")]
+    pub strict_query_params: bool,
+
+    /// The maximum number of requests per second that each client IP (or IPv6 /64 prefix) can
+    /// make to the /v1 endpoints. Requests over the limit get HTTP 429 with a Retry-After header. Requests are
+    /// not limited if unset.
+    #[arg(long = "rate-limit-per-second")]
+    #[arg(env = "RATE_LIMIT_PER_SECOND")]
+    #[arg(value_parser = clap::value_parser!(u32).range(1..))]
+    pub rate_limit_per_second: Option<u32>,
+
+    /// Only report the service as ready on /ready if the RPC endpoint of every chain answers, in
+    /// addition to the hash chains being set up.
+    #[arg(long = "ready-rpc-check")]
+    #[arg(env = "READY_RPC_CHECK")]
+    pub ready_rpc_check: bool,
 }
@@ -4,6 +4,7 @@ use {
         config::ConfigOptions,
     },
     clap::Args,
+    ethers::types::Address,
 };
 
 #[derive(Args, Clone, Debug)]
@@ -28,4 +29,10 @@ pub struct WithdrawFeesOptions {
     #[arg(long = "retain-balance")]
     #[arg(default_value = "0")]
     pub retain_balance_wei: u128,
+
+    /// If specified, transfer the withdrawn fees from the wallet running the command to this address
+    /// (e.g., a cold wallet). The contract always pays out to the wallet that withdraws, so this sends
+    /// a second transaction from that wallet.
+    #[arg(long = "to")]
+    pub to: Option<Address>,
 }
@@ -12,6 +12,7 @@ pub mod chain;
 pub mod command;
 pub mod config;
 pub mod keeper;
+pub mod reorg;
 pub mod state;
 
 // Server TODO list:
@@ -44,5 +45,7 @@ async fn main() -> Result<()> {
         config::Options::RequestRandomness(opts) => command::request_randomness(&opts).await,
         config::Options::Inspect(opts) => command::inspect(&opts).await,
         config::Options::WithdrawFees(opts) => command::withdraw_fees(&opts).await,
+        config::Options::RotateSecret(opts) => command::rotate_secret(&opts).await,
+        config::Options::BenchReveal(opts) => command::bench_reveal(&opts).await,
     }
 }
@@ -0,0 +1,305 @@
+use {
+    crate::{
+        api::BlockchainState,
+        chain::reader::{
+            BlockNumber,
+            BlockStatus,
+        },
+    },
+    anyhow::Result,
+    std::{
+        collections::BTreeMap,
+        sync::{
+            Arc,
+            Mutex,
+        },
+        time::Duration,
+    },
+    tokio::time,
+};
+
+/// A revealed sequence number and the block of the request that justified the reveal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RevealedSequence {
+    pub block_number: BlockNumber,
+    /// The hash of `block_number` the last time the request was seen in it. None until the first check.
+    pub block_hash:   Option<[u8; 32]>,
+}
+
+/// The sequence numbers revealed by the API whose requests haven't been checked deeply enough yet.
+/// If a reorg rolls back the request after its random value was revealed, the value is public even
+/// though nobody can use it on-chain anymore, so these requests are re-checked periodically.
+#[derive(Default)]
+pub struct RevealedSequences {
+    sequences: BTreeMap<u64, RevealedSequence>,
+}
+
+impl RevealedSequences {
+    /// The maximum number of sequence numbers to track. Once reached, the lowest sequence numbers
+    /// are dropped first so that memory usage stays bounded.
+    const MAX_TRACKED_SEQUENCES: usize = 100_000;
+
+    pub fn record(&mut self, sequence: u64, block_number: BlockNumber) {
+        if self.sequences.len() >= Self::MAX_TRACKED_SEQUENCES
+            && !self.sequences.contains_key(&sequence)
+        {
+            self.sequences.pop_first();
+        }
+        self.sequences.entry(sequence).or_insert(RevealedSequence {
+            block_number,
+            block_hash: None,
+        });
+    }
+
+    pub fn get(&self, sequence: u64) -> Option<RevealedSequence> {
+        self.sequences.get(&sequence).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.sequences.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sequences.is_empty()
+    }
+}
+
+/// Re-check the requests of the revealed sequence numbers of a chain every `interval`, until their
+/// block is `tracking_blocks` deep.
+pub async fn track_reorgs(
+    chain_state: BlockchainState,
+    revealed_sequences: Arc<Mutex<RevealedSequences>>,
+    interval: Duration,
+    tracking_blocks: BlockNumber,
+) {
+    loop {
+        time::sleep(interval).await;
+        if let Err(e) = check_reorgs(&chain_state, &revealed_sequences, tracking_blocks).await {
+            tracing::error!(
+                chain_id = chain_state.id,
+                "Failed to check revealed sequences for reorgs {:?}",
+                e
+            );
+        }
+    }
+}
+
+/// Check that the request of every revealed sequence number is still in the canonical chain. The
+/// request is only read again if the hash of its block changed since the last check. If it is no
+/// longer in its block, it is looked up in the latest block in case the reorg included it in a
+/// later block, and a warning is emitted if it vanished. Sequence numbers are no longer tracked
+/// once their block is `tracking_blocks` deep or their request vanished. Sequence numbers that
+/// can't be checked because of an RPC error are retried on the next check.
+pub async fn check_reorgs(
+    chain_state: &BlockchainState,
+    revealed_sequences: &Mutex<RevealedSequences>,
+    tracking_blocks: BlockNumber,
+) -> Result<()> {
+    let latest_block_number = chain_state
+        .contract
+        .get_block_number(BlockStatus::Latest)
+        .await?;
+    let sequences: Vec<(u64, RevealedSequence)> = revealed_sequences
+        .lock()
+        .unwrap()
+        .sequences
+        .iter()
+        .map(|(sequence, revealed)| (*sequence, *revealed))
+        .collect();
+
+    for (sequence, revealed) in sequences {
+        match check_revealed_sequence(chain_state, sequence, revealed).await {
+            Ok(checked) => {
+                let mut revealed_sequences = revealed_sequences.lock().unwrap();
+                match checked {
+                    Some(checked)
+                        if latest_block_number.saturating_sub(checked.block_number)
+                            < tracking_blocks =>
+                    {
+                        if let Some(revealed) = revealed_sequences.sequences.get_mut(&sequence) {
+                            *revealed = checked;
+                        }
+                    }
+                    _ => {
+                        revealed_sequences.sequences.remove(&sequence);
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::error!(
+                    chain_id = chain_state.id,
+                    sequence = sequence,
+                    "Failed to check the request of a revealed sequence number for reorgs {:?}",
+                    e
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that the request of a revealed sequence number is still in the canonical chain, and
+/// return where it is now, or None if it vanished.
+async fn check_revealed_sequence(
+    chain_state: &BlockchainState,
+    sequence: u64,
+    revealed: RevealedSequence,
+) -> Result<Option<RevealedSequence>> {
+    let block_hash = chain_state
+        .contract
+        .get_block_hash(revealed.block_number)
+        .await?;
+    if block_hash.is_some() && block_hash == revealed.block_hash {
+        return Ok(Some(revealed));
+    }
+
+    let request = chain_state
+        .contract
+        .get_request_at_block(
+            chain_state.provider_address,
+            sequence,
+            revealed.block_number,
+        )
+        .await?;
+    if request.is_some() {
+        return Ok(Some(RevealedSequence {
+            block_number: revealed.block_number,
+            block_hash,
+        }));
+    }
+
+    // The reorg may have included the request in a later block.
+    if let Some(request) = chain_state
+        .contract
+        .get_request(chain_state.provider_address, sequence)
+        .await?
+    {
+        tracing::info!(
+            chain_id = chain_state.id,
+            sequence = sequence,
+            block_number = request.block_number,
+            "The request for a revealed sequence number moved to a later block because of a reorg"
+        );
+        return Ok(Some(RevealedSequence {
+            block_number: request.block_number,
+            block_hash:   None,
+        }));
+    }
+
+    tracing::warn!(
+        chain_id = chain_state.id,
+        sequence = sequence,
+        block_number = revealed.block_number,
+        "The request for a revealed sequence number is no longer in the chain, likely because of a reorg"
+    );
+    Ok(None)
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        crate::{
+            api::BlockchainState,
+            chain::reader::{
+                mock::MockEntropyReader,
+                BlockStatus,
+            },
+            reorg::{
+                check_reorgs,
+                RevealedSequence,
+                RevealedSequences,
+            },
+            state::{
+                HashChainState,
+                PebbleHashChain,
+            },
+        },
+        ethers::types::Address,
+        std::sync::{
+            Arc,
+            Mutex,
+        },
+    };
+
+    const PROVIDER: Address = Address::zero();
+    const TRACKING_BLOCKS: u64 = 10;
+
+    fn chain_state(contract: Arc<MockEntropyReader>) -> BlockchainState {
+        BlockchainState {
+            id: "ethereum".into(),
+            state: Arc::new(HashChainState::from_chain_at_offset(
+                0,
+                PebbleHashChain::new([0u8; 32], 1000, 1),
+            )),
+            contract,
+            provider_address: PROVIDER,
+            reveal_delay_blocks: 1,
+            confirmed_block_status: BlockStatus::Latest,
+            revealed_sequences: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_reorgs() {
+        let contract = Arc::new(MockEntropyReader::with_requests(
+            10,
+            &[(PROVIDER, 1, 9, false), (PROVIDER, 2, 9, false)],
+        ));
+        let chain_state = chain_state(contract.clone());
+        let revealed_sequences = Mutex::new(RevealedSequences::default());
+        revealed_sequences.lock().unwrap().record(1, 9);
+        revealed_sequences.lock().unwrap().record(2, 9);
+
+        // The first check records the block hash
+        check_reorgs(&chain_state, &revealed_sequences, TRACKING_BLOCKS)
+            .await
+            .unwrap();
+        assert_eq!(
+            revealed_sequences.lock().unwrap().get(1),
+            Some(RevealedSequence {
+                block_number: 9,
+                block_hash:   Some([0; 32]),
+            })
+        );
+
+        // A reorg removes the request for sequence 1 but keeps the one for sequence 2
+        contract.set_block_hash([1; 32]).remove(PROVIDER, 1);
+        check_reorgs(&chain_state, &revealed_sequences, TRACKING_BLOCKS)
+            .await
+            .unwrap();
+        assert_eq!(revealed_sequences.lock().unwrap().get(1), None);
+        assert_eq!(
+            revealed_sequences.lock().unwrap().get(2),
+            Some(RevealedSequence {
+                block_number: 9,
+                block_hash:   Some([1; 32]),
+            })
+        );
+
+        // A reorg that moves the request for sequence 2 to a later block isn't reported as a
+        // vanished request, and the request is tracked in its new block.
+        contract
+            .set_block_hash([2; 32])
+            .remove(PROVIDER, 2)
+            .insert(PROVIDER, 2, 11, false)
+            .set_block_number(12);
+        check_reorgs(&chain_state, &revealed_sequences, TRACKING_BLOCKS)
+            .await
+            .unwrap();
+        assert_eq!(
+            revealed_sequences.lock().unwrap().get(2),
+            Some(RevealedSequence {
+                block_number: 11,
+                block_hash:   None,
+            })
+        );
+
+        // Sequences are no longer tracked once their block is deep enough
+        contract.set_block_number(11 + TRACKING_BLOCKS);
+        check_reorgs(&chain_state, &revealed_sequences, TRACKING_BLOCKS)
+            .await
+            .unwrap();
+        assert!(revealed_sequences.lock().unwrap().is_empty());
+    }
+}
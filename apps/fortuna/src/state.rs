@@ -93,6 +93,16 @@ impl PebbleHashChain {
     pub fn len(&self) -> usize {
         self.length
     }
+
+    /// Whether hashing `value` `num_hashes` times gives `root`, i.e. whether `value` is the element
+    /// `num_hashes` positions after `root` in a hash chain. This doesn't require knowing the secret.
+    pub fn verify(value: [u8; 32], num_hashes: u64, root: [u8; 32]) -> bool {
+        let mut current = value;
+        for _ in 0..num_hashes {
+            current = Keccak256::digest(current).into();
+        }
+        current == root
+    }
 }
 
 /// `HashChainState` tracks the mapping between on-chain sequence numbers to hash chains.
@@ -123,6 +133,25 @@ impl HashChainState {
             ))?;
         self.hash_chains[chain_index].reveal_ith(sequence_number - self.offsets[chain_index])
     }
+
+    /// Whether `sequence_number` is covered by one of the hash chains, i.e., whether `reveal` can
+    /// return its random value.
+    pub fn contains(&self, sequence_number: u64) -> bool {
+        let sequence_number: usize = match sequence_number.try_into() {
+            Ok(sequence_number) => sequence_number,
+            Err(_) => return false,
+        };
+        match self
+            .offsets
+            .partition_point(|x| x <= &sequence_number)
+            .checked_sub(1)
+        {
+            Some(chain_index) => {
+                sequence_number - self.offsets[chain_index] < self.hash_chains[chain_index].len()
+            }
+            None => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -158,6 +187,17 @@ mod test {
             let expected_last_val: [u8; 32] = Keccak256::digest(cur_val).into();
             assert_eq!(expected_last_val, last_val);
             last_val = cur_val;
+
+            assert!(PebbleHashChain::verify(
+                cur_val,
+                i as u64,
+                chain.reveal_ith(0).unwrap()
+            ));
+            assert!(!PebbleHashChain::verify(
+                cur_val,
+                i as u64 - 1,
+                chain.reveal_ith(0).unwrap()
+            ));
         }
     }
 
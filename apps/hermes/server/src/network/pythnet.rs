@@ -98,7 +98,8 @@ async fn fetch_guardian_set(
     // extract the new Signer set.
     match GuardianSetData::deserialize(&mut guardian_set.data.as_ref()) {
         Ok(guardian_set) => Ok(GuardianSet {
-            keys: guardian_set.keys,
+            keys:            guardian_set.keys,
+            expiration_time: guardian_set.expiration_time,
         }),
 
         Err(err) => Err(anyhow!(
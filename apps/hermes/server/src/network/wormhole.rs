@@ -35,7 +35,30 @@ pub type VaaBytes = Vec<u8>;
 
 #[derive(Eq, PartialEq, Clone, Hash, Debug)]
 pub struct GuardianSet {
-    pub keys: Vec<[u8; 20]>,
+    pub keys:            Vec<[u8; 20]>,
+    /// The unix timestamp after which the guardian set can't sign VAAs anymore, or 0 if it
+    /// doesn't expire (which is the case of the current guardian set).
+    pub expiration_time: u32,
+}
+
+impl GuardianSet {
+    pub fn is_expired(&self, now: u32) -> bool {
+        self.expiration_time != 0 && self.expiration_time <= now
+    }
+
+    pub fn num_guardians(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// The number of signatures required for a VAA to be valid, more than two thirds of the
+    /// guardians.
+    pub fn quorum(&self) -> usize {
+        (self.num_guardians() * 2) / 3 + 1
+    }
+
+    pub fn key_at(&self, index: usize) -> Option<[u8; 20]> {
+        self.keys.get(index).copied()
+    }
 }
 
 impl std::fmt::Display for GuardianSet {
@@ -73,10 +96,10 @@ pub struct BridgeConfig {
 /// GuardianSetData extracted from wormhole bridge account, due to no API.
 #[derive(borsh::BorshDeserialize)]
 pub struct GuardianSetData {
-    pub _index:           u32,
-    pub keys:             Vec<[u8; 20]>,
-    pub _creation_time:   u32,
-    pub _expiration_time: u32,
+    pub _index:          u32,
+    pub keys:            Vec<[u8; 20]>,
+    pub _creation_time:  u32,
+    pub expiration_time: u32,
 }
 
 /// Wormhole `prost` compiled definitions.
@@ -169,3 +192,29 @@ where
 
     Err(anyhow!("Wormhole gRPC stream terminated."))
 }
+
+#[cfg(test)]
+mod test {
+    use super::GuardianSet;
+
+    #[test]
+    fn test_guardian_set() {
+        let guardian_set = GuardianSet {
+            keys:            (0..19).map(|i| [i; 20]).collect(),
+            expiration_time: 0,
+        };
+        assert_eq!(guardian_set.num_guardians(), 19);
+        assert_eq!(guardian_set.quorum(), 13);
+        assert_eq!(guardian_set.key_at(18), Some([18; 20]));
+        assert_eq!(guardian_set.key_at(19), None);
+        assert!(!guardian_set.is_expired(u32::MAX));
+
+        let guardian_set = GuardianSet {
+            keys:            vec![[0; 20]],
+            expiration_time: 100,
+        };
+        assert_eq!(guardian_set.quorum(), 1);
+        assert!(!guardian_set.is_expired(99));
+        assert!(guardian_set.is_expired(100));
+    }
+}
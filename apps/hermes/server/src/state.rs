@@ -114,7 +114,8 @@ pub mod test {
             &*state,
             0,
             GuardianSet {
-                keys: vec![[0; 20]],
+                keys:            vec![[0; 20]],
+                expiration_time: 0,
             },
         )
         .await;
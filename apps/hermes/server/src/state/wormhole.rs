@@ -196,11 +196,7 @@ fn verify_vaa<'a>(
 
     // Ideally we need to test the signatures but currently Wormhole doesn't give us any easy way
     // to do it, so we just bypass the check in tests.
-    let quorum = if cfg!(test) {
-        0
-    } else {
-        (guardian_set.keys.len() * 2) / 3 + 1
-    };
+    let quorum = if cfg!(test) { 0 } else { guardian_set.quorum() };
 
     let secp = Secp256k1::new();
     let mut last_signer_id: Option<usize> = None;
@@ -213,11 +209,11 @@ fn verify_vaa<'a>(
         }
 
         let signer_id: usize = signature.index.into();
-        if signer_id >= guardian_set.keys.len() {
+        if signer_id >= guardian_set.num_guardians() {
             return Err(anyhow!(
                 "Signer ID is out of range. Signer ID: {}, guardian set size: {}",
                 signer_id,
-                guardian_set.keys.len()
+                guardian_set.num_guardians()
             ));
         }
 
@@ -247,7 +243,7 @@ fn verify_vaa<'a>(
         let address: [u8; 20] = address[address.len() - 20..].try_into()?;
 
         // Confirm the recovered address matches an address in the guardian set.
-        if guardian_set.keys.get(signer_id) == Some(&address) {
+        if guardian_set.key_at(signer_id) == Some(address) {
             signatures.push(signature);
         }
 
@@ -86,9 +86,14 @@ impl<H: Hasher> MerkleRoot<H> {
 
     /// Given a item and corresponding MerklePath, check that it is a valid membership proof.
     pub fn check(&self, proof: MerklePath<H>, item: &[u8]) -> bool {
+        self.check_ref(&proof, item)
+    }
+
+    /// Same as `check`, but borrows the MerklePath so that callers that only have a reference to it don't need to clone it.
+    pub fn check_ref(&self, proof: &MerklePath<H>, item: &[u8]) -> bool {
         let mut current: <H as Hasher>::Hash = MerkleTree::<H>::hash_leaf(item);
-        for hash in proof.0 {
-            current = MerkleTree::<H>::hash_node(&current, &hash);
+        for hash in proof.0.iter() {
+            current = MerkleTree::<H>::hash_node(&current, hash);
         }
         current == self.0
     }
@@ -405,6 +410,48 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_reordered_proofs() {
+        let items: Vec<[u8; 8]> = (0..8usize).map(|i| i.to_be_bytes()).collect();
+        let accumulator =
+            MerkleTree::<Keccak256>::from_set(items.iter().map(|item| item.as_ref())).unwrap();
+
+        // The nodes of a proof must be hashed in order from the leaf to the root, so swapping any
+        // two nodes of a valid proof must make it fail.
+        for item in items.iter() {
+            let proof = accumulator.prove(item).unwrap();
+            assert_eq!(proof.0.len(), 3);
+            for i in 0..proof.0.len() {
+                for j in (i + 1)..proof.0.len() {
+                    let mut reordered_proof = proof.clone();
+                    reordered_proof.0.swap(i, j);
+                    assert!(!accumulator.verify_path(reordered_proof, item));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_swapped_sibling_proofs() {
+        let items: Vec<[u8; 8]> = (0..8usize).map(|i| i.to_be_bytes()).collect();
+        let accumulator =
+            MerkleTree::<Keccak256>::from_set(items.iter().map(|item| item.as_ref())).unwrap();
+
+        // Replacing the sibling of the leaf with any other leaf of the tree must make the proof fail.
+        for item in items.iter() {
+            let proof = accumulator.prove(item).unwrap();
+            for other_item in items.iter() {
+                let other_leaf = MerkleTree::<Keccak256>::hash_leaf(other_item);
+                if other_leaf == proof.0[0] {
+                    continue;
+                }
+                let mut swapped_proof = proof.clone();
+                swapped_proof.0[0] = other_leaf;
+                assert!(!accumulator.verify_path(swapped_proof, item));
+            }
+        }
+    }
+
     #[test]
     #[should_panic]
     // Generates a tree with four leaves, then uses the first leaf of the right subtree as the
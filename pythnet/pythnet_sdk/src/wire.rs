@@ -14,6 +14,7 @@ mod ser;
 pub use {
     de::{
         from_slice,
+        from_slice_with_offset,
         Deserializer,
         DeserializerError,
     },
@@ -155,6 +156,8 @@ pub mod v1 {
 mod tests {
     use crate::wire::{
         array,
+        from_slice_with_offset,
+        to_vec,
         v1::{
             AccumulatorUpdateData,
             Proof,
@@ -539,4 +542,21 @@ mod tests {
         buffer[4] = 0x03;
         AccumulatorUpdateData::try_from_slice(&buffer).unwrap_err();
     }
+
+    // Test that a truncated AccumulatorUpdateData reports where deserialization stopped.
+    #[test]
+    fn test_deserialization_error_offset() {
+        let empty_update = AccumulatorUpdateData::new(Proof::WormholeMerkle {
+            vaa:     PrefixedVec::from(vec![]),
+            updates: vec![],
+        });
+        let buffer = to_vec::<_, byteorder::BE>(&empty_update).unwrap();
+
+        // Magic (4) + versions (2) + trailing length (1) + proof variant (1) precede the length
+        // of the VAA, which is a u16 of which only one byte is left.
+        let (_, offset) =
+            from_slice_with_offset::<byteorder::BE, AccumulatorUpdateData>(&buffer[..9])
+                .unwrap_err();
+        assert_eq!(offset, 8);
+    }
 }
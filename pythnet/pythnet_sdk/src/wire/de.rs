@@ -64,6 +64,18 @@ where
     T::deserialize(&mut deserializer)
 }
 
+/// Like `from_slice`, but on failure also returns the offset in `bytes` at which deserialization
+/// stopped. This is best-effort: the offset points at the start of the value that couldn't be
+/// read, which is usually enough to locate a truncated or misaligned region of the buffer.
+pub fn from_slice_with_offset<'de, B, T>(bytes: &'de [u8]) -> Result<T, (DeserializerError, u64)>
+where
+    T: Deserialize<'de>,
+    B: ByteOrder,
+{
+    let mut deserializer = Deserializer::<B>::new(bytes);
+    T::deserialize(&mut deserializer).map_err(|err| (err, deserializer.position()))
+}
+
 #[derive(Debug, Error)]
 pub enum DeserializerError {
     #[error("io error: {0}")]
@@ -112,6 +124,11 @@ where
             endian: std::marker::PhantomData,
         }
     }
+
+    /// The number of bytes of the buffer consumed so far.
+    pub fn position(&self) -> u64 {
+        self.cursor.position()
+    }
 }
 
 impl<'de, B> serde::de::Deserializer<'de> for &'_ mut Deserializer<'de, B>
@@ -29,6 +29,12 @@ pub struct Cli {
     pub url:      String,
     #[clap(short = 'w', long, parse(try_from_str = Pubkey::from_str), help = "Address of the wormhole contract")]
     pub wormhole: Pubkey,
+    #[clap(
+        short = 's',
+        long,
+        help = "JSON file mapping symbols (e.g. \"BTC/USD\") to feed ids. A small embedded mapping is used by default"
+    )]
+    pub symbols:  Option<String>,
     #[clap(subcommand)]
     pub action:   Action,
 }
@@ -39,11 +45,21 @@ pub enum Action {
     PostPriceUpdate {
         #[clap(short = 'p', long, help = "Payload from Hermes")]
         payload: String,
+        #[clap(
+            long,
+            help = "Symbol of the feed to post (e.g. \"BTC/USD\"). Defaults to the first update of the payload"
+        )]
+        feed:    Option<String>,
     },
     #[clap(about = "Post a price update from Hermes to Solana in one transaction")]
     PostPriceUpdateAtomic {
         #[clap(short = 'p', long, help = "Payload from Hermes")]
         payload:      String,
+        #[clap(
+            long,
+            help = "Symbol of the feed to post (e.g. \"BTC/USD\"). Defaults to the first update of the payload"
+        )]
+        feed:         Option<String>,
         #[clap(
             short = 'n',
             default_value = "5",
@@ -51,6 +67,40 @@ pub enum Action {
         )]
         n_signatures: usize,
     },
+    #[clap(
+        about = "Post all the price updates of a Hermes response saved to a file, to replay a captured update"
+    )]
+    PostFromFile {
+        #[clap(
+            short = 'f',
+            long,
+            help = "JSON response of the Hermes /v2/updates/price endpoints, with the binary data encoded in hex or base64"
+        )]
+        file: String,
+    },
+    #[clap(about = "Print the content of the VAA of a price update from Hermes")]
+    InspectVaa {
+        #[clap(short = 'p', long, help = "Payload from Hermes")]
+        payload: String,
+    },
+    #[clap(about = "Print the latest price of a feed stored by the push oracle on several chains")]
+    GetPrices {
+        #[clap(
+            short = 'c',
+            long,
+            help = "JSON file with a list of {\"chain\", \"rpc_url\", \"program_id\"} objects, one per push oracle deployment"
+        )]
+        chains:   String,
+        #[clap(short = 'f', long, help = "Symbol (e.g. \"BTC/USD\") or hex feed id")]
+        feed:     String,
+        #[clap(
+            short = 's',
+            long,
+            default_value = "0",
+            help = "Shard id of the price feed"
+        )]
+        shard_id: u16,
+    },
     #[clap(
         about = "Initialize a wormhole receiver contract by sequentially replaying the guardian set updates"
     )]
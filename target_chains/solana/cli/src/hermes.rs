@@ -0,0 +1,36 @@
+use {
+    anyhow::{
+        anyhow,
+        Result,
+    },
+    serde::Deserialize,
+};
+
+/// The part of a Hermes `/v2/updates/price` response that contains the accumulator update data.
+#[derive(Debug, Deserialize)]
+struct HermesResponse {
+    binary: BinaryUpdate,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinaryUpdate {
+    encoding: String,
+    data:     Vec<String>,
+}
+
+/// Read the accumulator update data of a Hermes `/v2/updates/price` response saved as JSON at `path`.
+/// Both the `hex` and `base64` encodings are supported, the `parsed` field of the response is ignored.
+pub fn read_accumulator_updates_file(path: &str) -> Result<Vec<Vec<u8>>> {
+    let response: HermesResponse =
+        serde_json::from_str(&std::fs::read_to_string(&*shellexpand::tilde(path))?)?;
+    response
+        .binary
+        .data
+        .iter()
+        .map(|data| match response.binary.encoding.as_str() {
+            "hex" => Ok(hex::decode(data.trim_start_matches("0x"))?),
+            "base64" => Ok(base64::decode(data)?),
+            encoding => Err(anyhow!("Unsupported encoding {encoding}")),
+        })
+        .collect()
+}
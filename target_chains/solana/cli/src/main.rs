@@ -1,27 +1,52 @@
 #![deny(warnings)]
 
 pub mod cli;
+pub mod hermes;
+pub mod multi_chain;
+pub mod symbols;
 
 
 use {
     anchor_client::anchor_lang::{
+        AccountDeserialize,
         InstructionData,
         ToAccountMetas,
     },
-    anyhow::Result,
+    anyhow::{
+        anyhow,
+        Result,
+    },
     borsh::BorshDeserialize,
     clap::Parser,
     cli::{
         Action,
         Cli,
     },
+    multi_chain::MultiChainPriceReader,
     pyth_solana_receiver::sdk::{
         deserialize_accumulator_update_data,
+        fits_in_transaction,
+        get_fee_recipient_account_metas,
+        get_price_change_baseline_account_meta,
         get_random_treasury_id,
+        get_transaction_size,
+        MAX_TRANSACTION_SIZE,
         VAA_SPLIT_INDEX,
     },
-    pyth_solana_receiver_sdk::config::DataSource,
-    pythnet_sdk::wire::v1::MerklePriceUpdate,
+    pyth_solana_receiver_sdk::{
+        config::{
+            Config,
+            DataSource,
+        },
+        pda::get_config_address,
+    },
+    pythnet_sdk::{
+        messages::Message,
+        wire::{
+            from_slice,
+            v1::MerklePriceUpdate,
+        },
+    },
     serde_wormhole::RawMessage,
     solana_client::{
         rpc_client::RpcClient,
@@ -30,7 +55,11 @@ use {
     solana_sdk::{
         commitment_config::CommitmentConfig,
         compute_budget::ComputeBudgetInstruction,
-        instruction::Instruction,
+        instruction::{
+            AccountMeta,
+            Instruction,
+        },
+        keccak,
         pubkey::Pubkey,
         rent::Rent,
         signature::{
@@ -41,6 +70,11 @@ use {
         system_instruction,
         transaction::Transaction,
     },
+    std::time::{
+        Duration,
+        Instant,
+    },
+    symbols::SymbolMap,
     wormhole_core_bridge_solana::sdk::{
         WriteEncodedVaaArgs,
         VAA_START,
@@ -81,10 +115,12 @@ fn main() -> Result<()> {
         keypair,
         url,
         wormhole,
+        symbols,
     } = cli;
+    let symbol_map = SymbolMap::load(symbols.as_deref())?;
 
     match action {
-        Action::PostPriceUpdate { payload } => {
+        Action::PostPriceUpdate { payload, feed } => {
             let rpc_client = RpcClient::new(url);
             let payer =
                 read_keypair_file(&*shellexpand::tilde(&keypair)).expect("Keypair not found");
@@ -92,16 +128,19 @@ fn main() -> Result<()> {
             let payload_bytes: Vec<u8> = base64::decode(payload)?;
             let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(payload_bytes)?;
 
+            let start = Instant::now();
             process_write_encoded_vaa_and_post_price_update(
                 &rpc_client,
                 &vaa,
                 wormhole,
                 &payer,
-                &merkle_price_updates[0],
+                select_price_update(&merkle_price_updates, feed.as_deref(), &symbol_map)?,
             )?;
+            print_throughput(1, 2, start.elapsed());
         }
         Action::PostPriceUpdateAtomic {
             payload,
+            feed,
             n_signatures,
         } => {
             let rpc_client = RpcClient::new(url);
@@ -111,14 +150,96 @@ fn main() -> Result<()> {
             let payload_bytes: Vec<u8> = base64::decode(payload)?;
             let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(payload_bytes)?;
 
+            let start = Instant::now();
             process_post_price_update_atomic(
                 &rpc_client,
                 &vaa,
                 n_signatures,
                 &wormhole,
                 &payer,
-                &merkle_price_updates[0],
+                select_price_update(&merkle_price_updates, feed.as_deref(), &symbol_map)?,
             )?;
+            print_throughput(1, 1, start.elapsed());
+        }
+
+        Action::PostFromFile { file } => {
+            let rpc_client = RpcClient::new(url);
+            let payer =
+                read_keypair_file(&*shellexpand::tilde(&keypair)).expect("Keypair not found");
+
+            for accumulator_update_data in hermes::read_accumulator_updates_file(&file)? {
+                let (vaa, merkle_price_updates) =
+                    deserialize_accumulator_update_data(accumulator_update_data)?;
+
+                let start = Instant::now();
+                let (price_update_accounts, n_transactions) =
+                    process_write_encoded_vaa_and_post_price_updates(
+                        &rpc_client,
+                        &vaa,
+                        wormhole,
+                        &payer,
+                        &merkle_price_updates,
+                    )?;
+                print_throughput(merkle_price_updates.len(), n_transactions, start.elapsed());
+                for (merkle_price_update, price_update_account) in
+                    merkle_price_updates.iter().zip(price_update_accounts)
+                {
+                    println!(
+                        "Posted {} to {price_update_account}",
+                        symbol_map.format_feed_id(&get_feed_id(merkle_price_update)?)
+                    );
+                }
+            }
+        }
+
+        Action::InspectVaa { payload } => {
+            let payload_bytes: Vec<u8> = base64::decode(payload)?;
+            let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(payload_bytes)?;
+            let (header, body): (Header, Body<&RawMessage>) = serde_wormhole::from_slice(&vaa)?;
+
+            println!("Version : {}", header.version);
+            println!("Guardian set index : {}", header.guardian_set_index);
+            println!("Number of signatures : {}", header.signatures.len());
+            println!("Timestamp : {}", body.timestamp);
+            println!("Emitter chain : {}", u16::from(body.emitter_chain));
+            println!(
+                "Emitter address : {}",
+                format_emitter_address(&body.emitter_address.0)
+            );
+            println!("Sequence : {}", body.sequence);
+            println!("Number of price updates : {}", merkle_price_updates.len());
+            for merkle_price_update in merkle_price_updates.iter() {
+                println!(
+                    "Feed id : {}",
+                    symbol_map.format_feed_id(&get_feed_id(merkle_price_update)?)
+                );
+            }
+        }
+
+        Action::GetPrices {
+            chains,
+            feed,
+            shard_id,
+        } => {
+            let feed_id = symbol_map.parse_feed_id(&feed)?;
+            let multi_chain_price_reader = MultiChainPriceReader::from_file(&chains)?;
+
+            println!("Feed id : {}", symbol_map.format_feed_id(&feed_id));
+            for (chain, price_update) in
+                multi_chain_price_reader.get_latest_prices(shard_id, feed_id)
+            {
+                match price_update {
+                    Ok(price_update) => println!(
+                        "{chain} : price {} conf {} exponent {} publish time {} posted slot {}",
+                        price_update.price_message.price,
+                        price_update.price_message.conf,
+                        price_update.price_message.exponent,
+                        price_update.price_message.publish_time,
+                        price_update.posted_slot
+                    ),
+                    Err(e) => println!("{chain} : failed to read the price ({e})"),
+                }
+            }
         }
 
         Action::InitializeWormholeReceiver {} => {
@@ -185,6 +306,18 @@ fn main() -> Result<()> {
                         valid_data_sources: vec![DataSource { chain, emitter }],
                         single_update_fee_in_lamports: fee,
                         minimum_signatures: 3,
+                        pinned_guardian_set_hash: None,
+                        fee_recipients: vec![],
+                        post_vaa_programs: vec![],
+                        guardian_set_expiration_skew_seconds: 0,
+                        price_change_limits: vec![],
+                        min_price_update_lifetime_slots: 0,
+                        max_staleness_seconds: 0,
+                        max_future_publish_time_seconds: 0,
+                        exponent_range: None,
+                        max_conf_bps: 0,
+                        allowed_feed_ids: vec![],
+                        min_consistency_level: 0,
                     },
                 );
 
@@ -247,7 +380,7 @@ pub fn process_post_price_update_atomic(
     let request_compute_units_instruction: Instruction =
         ComputeBudgetInstruction::set_compute_unit_limit(400_000);
 
-    let post_update_instruction = pyth_solana_receiver::instruction::PostUpdateAtomic::populate(
+    let mut post_update_instruction = pyth_solana_receiver::instruction::PostUpdateAtomic::populate(
         payer.pubkey(),
         payer.pubkey(),
         price_update_keypair.pubkey(),
@@ -257,15 +390,118 @@ pub fn process_post_price_update_atomic(
         merkle_price_update.clone(),
         get_random_treasury_id(),
     );
+    post_update_instruction
+        .accounts
+        .extend(get_post_update_remaining_account_metas(
+            &get_receiver_config(rpc_client)?,
+            merkle_price_update,
+        )?);
+
+    let instructions = vec![request_compute_units_instruction, post_update_instruction];
+    let transaction_size = get_transaction_size(&instructions, &payer.pubkey());
+    if transaction_size > MAX_TRANSACTION_SIZE {
+        return Err(anyhow!(
+            "The transaction is {transaction_size} bytes but the limit is {MAX_TRANSACTION_SIZE} bytes, try again with fewer signatures"
+        ));
+    }
 
     process_transaction(
         rpc_client,
-        vec![request_compute_units_instruction, post_update_instruction],
+        instructions,
         &vec![payer, &price_update_keypair],
     )?;
     Ok(price_update_keypair.pubkey())
 }
 
+/**
+ * Read the receiver config account, the post instructions need the accounts of its fee recipients and of the price change baselines of its limited feeds.
+ */
+fn get_receiver_config(rpc_client: &RpcClient) -> Result<Config> {
+    let data = rpc_client.get_account_data(&get_config_address())?;
+    Ok(Config::try_deserialize(&mut data.as_slice())?)
+}
+
+/**
+ * The remaining accounts of an instruction that posts `merkle_price_update`: the fee recipient accounts, followed by the price change baseline account of the feed if it has a price change limit.
+ */
+fn get_post_update_remaining_account_metas(
+    config: &Config,
+    merkle_price_update: &MerklePriceUpdate,
+) -> Result<Vec<AccountMeta>> {
+    let mut account_metas = get_fee_recipient_account_metas(&config.fee_recipients);
+    account_metas.extend(get_price_change_baseline_account_meta(
+        config,
+        &get_feed_id(merkle_price_update)?,
+    ));
+    Ok(account_metas)
+}
+
+/**
+ * Format a Wormhole emitter address the way explorers display it.
+ * Emitters from EVM chains are 20 byte addresses left-padded with zeros, so they are shown as EIP-55 checksummed addresses.
+ * Other emitters (e.g. Pythnet or Solana programs) are shown as base58 public keys.
+ */
+fn format_emitter_address(emitter_address: &[u8; 32]) -> String {
+    let hex_address = format!("0x{}", hex::encode(emitter_address));
+    if emitter_address[..12].iter().all(|&byte| byte == 0) {
+        format!(
+            "{hex_address} (EVM address {})",
+            to_checksummed_evm_address(&emitter_address[12..])
+        )
+    } else {
+        format!(
+            "{hex_address} (Solana address {})",
+            Pubkey::from(*emitter_address)
+        )
+    }
+}
+
+fn to_checksummed_evm_address(address: &[u8]) -> String {
+    let lowercase_address = hex::encode(address);
+    let hash = keccak::hash(lowercase_address.as_bytes()).to_bytes();
+    let checksummed_address: String = lowercase_address
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("0x{checksummed_address}")
+}
+
+fn get_feed_id(merkle_price_update: &MerklePriceUpdate) -> Result<[u8; 32]> {
+    Ok(from_slice::<byteorder::BE, Message>(merkle_price_update.message.as_ref())?.feed_id())
+}
+
+/// Select the update of the feed with the given symbol, or the first update if no symbol is given.
+fn select_price_update<'a>(
+    merkle_price_updates: &'a [MerklePriceUpdate],
+    symbol: Option<&str>,
+    symbol_map: &SymbolMap,
+) -> Result<&'a MerklePriceUpdate> {
+    let Some(symbol) = symbol else {
+        return merkle_price_updates
+            .first()
+            .ok_or_else(|| anyhow!("The payload doesn't contain any price update"));
+    };
+    let feed_id = symbol_map
+        .symbol_to_feed_id(symbol)
+        .ok_or_else(|| anyhow!("Unknown symbol {symbol}"))?;
+    for merkle_price_update in merkle_price_updates {
+        if get_feed_id(merkle_price_update)? == feed_id {
+            return Ok(merkle_price_update);
+        }
+    }
+    Err(anyhow!(
+        "The payload doesn't contain an update for {symbol}"
+    ))
+}
+
 fn trim_signatures(header: &mut Header, n_signatures: usize) {
     header.signatures = header.signatures[..(n_signatures)].to_vec();
 }
@@ -359,6 +595,28 @@ pub fn process_write_encoded_vaa_and_post_price_update(
     payer: &Keypair,
     merkle_price_update: &MerklePriceUpdate,
 ) -> Result<Pubkey> {
+    let (price_update_accounts, _) = process_write_encoded_vaa_and_post_price_updates(
+        rpc_client,
+        vaa,
+        wormhole,
+        payer,
+        std::slice::from_ref(merkle_price_update),
+    )?;
+    Ok(price_update_accounts[0])
+}
+
+/**
+ * This function posts a VAA using the new way of interacting with wormhole and then posts all the price updates of `merkle_price_updates` using the VAA.
+ * The updates are packed in as few transactions as the transaction size limit allows, the first ones share a transaction with the end of the VAA.
+ * It returns the price update accounts, in the same order as the updates, and the number of transactions that were sent.
+ */
+pub fn process_write_encoded_vaa_and_post_price_updates(
+    rpc_client: &RpcClient,
+    vaa: &[u8],
+    wormhole: Pubkey,
+    payer: &Keypair,
+    merkle_price_updates: &[MerklePriceUpdate],
+) -> Result<(Vec<Pubkey>, usize)> {
     let encoded_vaa_keypair = Keypair::new();
     let encoded_vaa_size: usize = vaa.len() + VAA_START;
 
@@ -442,31 +700,76 @@ pub fn process_write_encoded_vaa_and_post_price_update(
         data:       wormhole_core_bridge_solana::instruction::VerifyEncodedVaaV1 {}.data(),
     };
 
-    let price_update_keypair = Keypair::new();
+    let mut n_transactions = 1;
+    let mut instructions = vec![
+        request_compute_units_instruction.clone(),
+        write_encoded_vaa_accounts_instruction_2,
+        verify_encoded_vaa_instruction,
+    ];
+    let mut price_update_keypairs: Vec<Keypair> = vec![];
+    let mut price_update_accounts: Vec<Pubkey> = vec![];
+    let config = get_receiver_config(rpc_client)?;
+    for merkle_price_update in merkle_price_updates {
+        let price_update_keypair = Keypair::new();
+        let mut post_update_instruction = pyth_solana_receiver::instruction::PostUpdate::populate(
+            payer.pubkey(),
+            payer.pubkey(),
+            encoded_vaa_keypair.pubkey(),
+            price_update_keypair.pubkey(),
+            merkle_price_update.clone(),
+            get_random_treasury_id(),
+        );
+        post_update_instruction
+            .accounts
+            .extend(get_post_update_remaining_account_metas(
+                &config,
+                merkle_price_update,
+            )?);
+
+        let mut candidate_instructions = instructions.clone();
+        candidate_instructions.push(post_update_instruction.clone());
+        if !price_update_keypairs.is_empty()
+            && !fits_in_transaction(&candidate_instructions, &payer.pubkey())
+        {
+            process_transaction(
+                rpc_client,
+                instructions,
+                &std::iter::once(payer)
+                    .chain(price_update_keypairs.iter())
+                    .collect(),
+            )?;
+            n_transactions += 1;
+            instructions = vec![request_compute_units_instruction.clone()];
+            price_update_keypairs.clear();
+        }
 
-    let post_update_instructions = pyth_solana_receiver::instruction::PostUpdate::populate(
-        payer.pubkey(),
-        payer.pubkey(),
-        encoded_vaa_keypair.pubkey(),
-        price_update_keypair.pubkey(),
-        merkle_price_update.clone(),
-        get_random_treasury_id(),
-    );
+        instructions.push(post_update_instruction);
+        price_update_accounts.push(price_update_keypair.pubkey());
+        price_update_keypairs.push(price_update_keypair);
+    }
 
-    // 2nd transaction
     process_transaction(
         rpc_client,
-        vec![
-            request_compute_units_instruction,
-            write_encoded_vaa_accounts_instruction_2,
-            verify_encoded_vaa_instruction,
-            post_update_instructions,
-        ],
-        &vec![payer, &price_update_keypair],
+        instructions,
+        &std::iter::once(payer)
+            .chain(price_update_keypairs.iter())
+            .collect(),
     )?;
+    n_transactions += 1;
 
+    Ok((price_update_accounts, n_transactions))
+}
 
-    Ok(price_update_keypair.pubkey())
+/**
+ * Report how many price updates were verified per transaction and per second, to help operators size their relayers
+ */
+fn print_throughput(n_updates: usize, n_transactions: usize, elapsed: Duration) {
+    println!(
+        "Verified {n_updates} update(s) in {n_transactions} transaction(s) and {:.2}s : {:.2} updates per transaction, {:.2} updates per second",
+        elapsed.as_secs_f64(),
+        n_updates as f64 / n_transactions as f64,
+        n_updates as f64 / elapsed.as_secs_f64(),
+    );
 }
 
 pub fn process_transaction(
@@ -0,0 +1,91 @@
+use {
+    anchor_client::anchor_lang::AccountDeserialize,
+    anyhow::{
+        anyhow,
+        Result,
+    },
+    pyth_push_oracle::sdk::get_price_feed_address_for_program,
+    pyth_solana_receiver_sdk::price_update::PriceUpdateV2,
+    pythnet_sdk::messages::FeedId,
+    serde::Deserialize,
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::pubkey::Pubkey,
+    std::{
+        collections::BTreeMap,
+        str::FromStr,
+    },
+};
+
+/// A deployment of the push oracle on an SVM chain.
+#[derive(Debug, Deserialize)]
+pub struct ChainConfig {
+    /// A name for the chain, used as the key of the results (e.g. "solana" or "eclipse").
+    pub chain:      String,
+    pub rpc_url:    String,
+    /// The address of the push oracle program on this chain.
+    pub program_id: String,
+}
+
+/// Reads the latest price of a feed stored by the push oracle on several chains.
+pub struct MultiChainPriceReader {
+    chains: Vec<(String, RpcClient, Pubkey)>,
+}
+
+impl MultiChainPriceReader {
+    pub fn new(chain_configs: Vec<ChainConfig>) -> Result<Self> {
+        let chains = chain_configs
+            .into_iter()
+            .map(|chain_config| {
+                let program_id = Pubkey::from_str(&chain_config.program_id).map_err(|_| {
+                    anyhow!(
+                        "Invalid program id {} for {}",
+                        chain_config.program_id,
+                        chain_config.chain
+                    )
+                })?;
+                Ok((
+                    chain_config.chain,
+                    RpcClient::new(chain_config.rpc_url),
+                    program_id,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(MultiChainPriceReader { chains })
+    }
+
+    /// Load the chain configs from a JSON file containing a list of `ChainConfig`.
+    pub fn from_file(path: &str) -> Result<Self> {
+        let chain_configs: Vec<ChainConfig> =
+            serde_json::from_str(&std::fs::read_to_string(&*shellexpand::tilde(path))?)?;
+        Self::new(chain_configs)
+    }
+
+    /// Fetch the price update account of `feed_id` in `shard_id` on every chain.
+    /// A chain that can't be read (e.g. the RPC is down or the feed isn't pushed there) gets an error instead of failing the other chains.
+    pub fn get_latest_prices(
+        &self,
+        shard_id: u16,
+        feed_id: FeedId,
+    ) -> BTreeMap<String, Result<PriceUpdateV2>> {
+        self.chains
+            .iter()
+            .map(|(chain, rpc_client, program_id)| {
+                (
+                    chain.clone(),
+                    get_latest_price(rpc_client, program_id, shard_id, feed_id),
+                )
+            })
+            .collect()
+    }
+}
+
+fn get_latest_price(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    shard_id: u16,
+    feed_id: FeedId,
+) -> Result<PriceUpdateV2> {
+    let price_feed_address = get_price_feed_address_for_program(program_id, shard_id, feed_id);
+    let data = rpc_client.get_account_data(&price_feed_address)?;
+    Ok(PriceUpdateV2::try_deserialize(&mut data.as_slice())?)
+}
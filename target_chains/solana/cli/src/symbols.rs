@@ -0,0 +1,68 @@
+use {
+    anyhow::{
+        anyhow,
+        Result,
+    },
+    std::collections::HashMap,
+};
+
+/// The mapping used when no `--symbols` file is given. It only contains a few feeds, operators can pass a file with the feeds they use.
+const DEFAULT_SYMBOLS: &str = include_str!("../symbols.json");
+
+/// A mapping between human-readable symbols (e.g. "BTC/USD") and feed ids.
+/// It is loaded from a JSON object whose keys are the symbols and whose values are the hex encoded feed ids (with or without a 0x prefix).
+#[derive(Debug, Default)]
+pub struct SymbolMap {
+    feed_ids: HashMap<String, [u8; 32]>,
+    symbols:  HashMap<[u8; 32], String>,
+}
+
+impl SymbolMap {
+    pub fn from_json(json: &str) -> Result<Self> {
+        let entries: HashMap<String, String> = serde_json::from_str(json)?;
+        let mut symbol_map = SymbolMap::default();
+        for (symbol, feed_id_hex) in entries {
+            let feed_id: [u8; 32] = hex::decode(feed_id_hex.trim_start_matches("0x"))?
+                .try_into()
+                .map_err(|_| anyhow!("The feed id of {symbol} is not 32 bytes long"))?;
+            symbol_map.feed_ids.insert(symbol.clone(), feed_id);
+            symbol_map.symbols.insert(feed_id, symbol);
+        }
+        Ok(symbol_map)
+    }
+
+    /// Load the mapping from the JSON file at `path`, or the embedded default mapping if `path` is None.
+    pub fn load(path: Option<&str>) -> Result<Self> {
+        match path {
+            Some(path) => Self::from_json(&std::fs::read_to_string(&*shellexpand::tilde(path))?),
+            None => Self::from_json(DEFAULT_SYMBOLS),
+        }
+    }
+
+    pub fn symbol_to_feed_id(&self, symbol: &str) -> Option<[u8; 32]> {
+        self.feed_ids.get(symbol).copied()
+    }
+
+    /// Parse a feed given either as a known symbol or as a hex encoded feed id.
+    pub fn parse_feed_id(&self, feed: &str) -> Result<[u8; 32]> {
+        if let Some(feed_id) = self.symbol_to_feed_id(feed) {
+            return Ok(feed_id);
+        }
+        hex::decode(feed.trim_start_matches("0x"))
+            .ok()
+            .and_then(|feed_id| feed_id.try_into().ok())
+            .ok_or_else(|| anyhow!("{feed} is neither a known symbol nor a feed id"))
+    }
+
+    pub fn feed_id_to_symbol(&self, feed_id: &[u8; 32]) -> Option<&str> {
+        self.symbols.get(feed_id).map(String::as_str)
+    }
+
+    /// Format a feed id as hex, followed by its symbol if it is known.
+    pub fn format_feed_id(&self, feed_id: &[u8; 32]) -> String {
+        match self.feed_id_to_symbol(feed_id) {
+            Some(symbol) => format!("0x{} ({symbol})", hex::encode(feed_id)),
+            None => format!("0x{}", hex::encode(feed_id)),
+        }
+    }
+}
@@ -6,6 +6,7 @@ use {
         instruction::Initialize,
         sdk::get_guardian_set_address,
         ID,
+        MAX_GUARDIANS,
     },
     pyth_solana_receiver_sdk::{
         config::{
@@ -34,6 +35,11 @@ use {
         signature::Keypair,
         signer::Signer,
     },
+    std::time::{
+        Duration,
+        SystemTime,
+        UNIX_EPOCH,
+    },
     wormhole_core_bridge_solana::{
         state::{
             EncodedVaa,
@@ -48,6 +54,9 @@ use {
 
 pub const DEFAULT_GUARDIAN_SET_INDEX: u32 = 0;
 pub const WRONG_GUARDIAN_SET_INDEX: u32 = 1;
+pub const ROTATED_GUARDIAN_SET_INDEX: u32 = 1;
+/// How long after the start of the test the guardian set of `WrongSetupOption::GuardianSetExpiringSoon` expires.
+pub const GUARDIAN_SET_EXPIRING_SOON_DELAY: Duration = Duration::from_secs(24 * 60 * 60);
 
 pub fn default_receiver_config(governance_authority: Pubkey) -> Config {
     Config {
@@ -60,6 +69,18 @@ pub fn default_receiver_config(governance_authority: Pubkey) -> Config {
         }],
         single_update_fee_in_lamports: 1,
         minimum_signatures: 5,
+        pinned_guardian_set_hash: None,
+        fee_recipients: vec![],
+        post_vaa_programs: vec![],
+        guardian_set_expiration_skew_seconds: 0,
+        price_change_limits: vec![],
+        min_price_update_lifetime_slots: 0,
+        max_staleness_seconds: 0,
+        max_future_publish_time_seconds: 0,
+        exponent_range: None,
+        max_conf_bps: 0,
+        allowed_feed_ids: vec![],
+        min_consistency_level: 0,
     }
 }
 
@@ -101,34 +122,69 @@ pub fn build_encoded_vaa_account_from_vaa(
     }
 }
 
+/**
+ * The Ethereum addresses of the guardians that sign the VAAs created by pythnet_sdk::test_utils
+ */
+pub fn dummy_guardian_keys() -> Vec<[u8; 20]> {
+    dummy_guardians()
+        .iter()
+        .map(|x| {
+            let mut result: [u8; 20] = [0u8; 20];
+            result.copy_from_slice(
+                &keccak::hashv(&[&PublicKey::from_secret_key(x).serialize()[1..]]).0[12..],
+            );
+            result
+        })
+        .collect::<Vec<[u8; 20]>>()
+}
+
 pub fn build_guardian_set_account(wrong_setup_option: WrongSetupOption) -> Account {
-    let guardian_set = GuardianSet {
-        index:           {
+    build_guardian_set_account_with_keys(
+        {
             if matches!(wrong_setup_option, WrongSetupOption::GuardianSetWrongIndex) {
                 WRONG_GUARDIAN_SET_INDEX
             } else {
                 DEFAULT_GUARDIAN_SET_INDEX
             }
         },
-        keys:            dummy_guardians()
-            .iter()
-            .map(|x| {
-                let mut result: [u8; 20] = [0u8; 20];
-                result.copy_from_slice(
-                    &keccak::hashv(&[&PublicKey::from_secret_key(x).serialize()[1..]]).0[12..],
-                );
-                result
-            })
-            .collect::<Vec<[u8; 20]>>(),
-        creation_time:   0.into(),
-        expiration_time: {
-            if matches!(wrong_setup_option, WrongSetupOption::GuardianSetExpired) {
-                1
+        {
+            if matches!(wrong_setup_option, WrongSetupOption::GuardianSetTooManyKeys) {
+                vec![dummy_guardian_keys()[0]; MAX_GUARDIANS + 1]
             } else {
-                0
+                dummy_guardian_keys()
             }
-        }
-        .into(),
+        },
+        {
+            match wrong_setup_option {
+                WrongSetupOption::GuardianSetExpired => 1,
+                WrongSetupOption::GuardianSetExpiringSoon => {
+                    (SystemTime::now().duration_since(UNIX_EPOCH).unwrap()
+                        + GUARDIAN_SET_EXPIRING_SOON_DELAY)
+                        .as_secs() as u32
+                }
+                _ => 0,
+            }
+        },
+    )
+}
+
+/**
+ * Build a guardian set account for the dummy guardians. An `expiration_time` of 0 means that the guardian set doesn't expire.
+ */
+pub fn build_guardian_set_account_with_index(index: u32, expiration_time: u32) -> Account {
+    build_guardian_set_account_with_keys(index, dummy_guardian_keys(), expiration_time)
+}
+
+pub fn build_guardian_set_account_with_keys(
+    index: u32,
+    keys: Vec<[u8; 20]>,
+    expiration_time: u32,
+) -> Account {
+    let guardian_set = GuardianSet {
+        index,
+        keys,
+        creation_time: 0.into(),
+        expiration_time: expiration_time.into(),
     };
 
     let guardian_set_data = (
@@ -151,7 +207,9 @@ pub fn build_guardian_set_account(wrong_setup_option: WrongSetupOption) -> Accou
 pub enum WrongSetupOption {
     None,
     GuardianSetExpired,
+    GuardianSetExpiringSoon,
     GuardianSetWrongIndex,
+    GuardianSetTooManyKeys,
     UnverifiedEncodedVaa,
 }
 
@@ -163,6 +221,17 @@ pub enum WrongSetupOption {
 pub async fn setup_pyth_receiver(
     vaas: Vec<Vaa<&RawMessage>>,
     wrong_setup_option: WrongSetupOption,
+) -> ProgramTestFixtures {
+    setup_pyth_receiver_with_accounts(vaas, wrong_setup_option, vec![]).await
+}
+
+/**
+ * Same as `setup_pyth_receiver`, with additional `accounts` prepopulated at the given addresses (e.g. an account holding accumulator update data for post_updates_from_account_data)
+ */
+pub async fn setup_pyth_receiver_with_accounts(
+    vaas: Vec<Vaa<&RawMessage>>,
+    wrong_setup_option: WrongSetupOption,
+    accounts: Vec<(Pubkey, Account)>,
 ) -> ProgramTestFixtures {
     let mut program_test = ProgramTest::default();
     program_test.add_program("pyth_solana_receiver", ID, None);
@@ -181,6 +250,16 @@ pub async fn setup_pyth_receiver(
         get_guardian_set_address(BRIDGE_ID, DEFAULT_GUARDIAN_SET_INDEX),
         build_guardian_set_account(wrong_setup_option),
     );
+    // An expired guardian set has been replaced by the next one, like after a guardian set rotation on the Wormhole contract
+    if matches!(wrong_setup_option, WrongSetupOption::GuardianSetExpired) {
+        program_test.add_account(
+            get_guardian_set_address(BRIDGE_ID, ROTATED_GUARDIAN_SET_INDEX),
+            build_guardian_set_account_with_index(ROTATED_GUARDIAN_SET_INDEX, 0),
+        );
+    }
+    for (address, account) in accounts {
+        program_test.add_account(address, account);
+    }
 
     let mut program_simulator = ProgramSimulator::start_from_program_test(program_test).await;
 
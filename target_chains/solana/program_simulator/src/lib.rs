@@ -79,6 +79,67 @@ impl ProgramSimulator {
         self.banks_client.process_transaction(transaction).await
     }
 
+    /// Simulate a transaction containing `instruction` signed by `signers`, paid for by the genesis keypair, and return the return data of the transaction.
+    /// Note that the runtime trims the trailing zeros of the return data.
+    pub async fn simulate_ix_return_data(
+        &mut self,
+        instruction: Instruction,
+        signers: &Vec<&Keypair>,
+    ) -> Result<Vec<u8>, BanksClientError> {
+        let (return_data, _) = self.simulate_ix(instruction, signers).await?;
+        Ok(return_data)
+    }
+
+    /// Simulate a transaction containing `instruction` signed by `signers`, paid for by the genesis keypair, and return the compute units consumed by the transaction.
+    pub async fn simulate_ix_compute_units(
+        &mut self,
+        instruction: Instruction,
+        signers: &Vec<&Keypair>,
+    ) -> Result<u64, BanksClientError> {
+        let (_, units_consumed) = self.simulate_ix(instruction, signers).await?;
+        Ok(units_consumed)
+    }
+
+    async fn simulate_ix(
+        &mut self,
+        instruction: Instruction,
+        signers: &Vec<&Keypair>,
+    ) -> Result<(Vec<u8>, u64), BanksClientError> {
+        let compute_units_ixs =
+            compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(2000000);
+        let mut transaction = Transaction::new_with_payer(
+            &[instruction, compute_units_ixs],
+            Some(&self.genesis_keypair.pubkey()),
+        );
+
+        let blockhash = self
+            .banks_client
+            .get_new_latest_blockhash(&self.last_blockhash)
+            .await
+            .unwrap();
+        self.last_blockhash = blockhash;
+
+        transaction.partial_sign(&[&self.genesis_keypair], self.last_blockhash);
+        transaction.partial_sign(signers, self.last_blockhash);
+
+        let simulation = self.banks_client.simulate_transaction(transaction).await?;
+        if let Some(Err(err)) = simulation.result {
+            return Err(BanksClientError::TransactionError(err));
+        }
+        Ok(simulation
+            .simulation_details
+            .map(|simulation_details| {
+                (
+                    simulation_details
+                        .return_data
+                        .map(|return_data| return_data.data)
+                        .unwrap_or_default(),
+                    simulation_details.units_consumed,
+                )
+            })
+            .unwrap_or_default())
+    }
+
     /// Send `lamports` worth of SOL to the pubkey `to`.
     pub async fn airdrop(&mut self, to: &Pubkey, lamports: u64) -> Result<(), BanksClientError> {
         let instruction =
@@ -108,6 +169,12 @@ impl ProgramSimulator {
         Ok(T::deserialize(&mut &account.data[8..])?)
     }
 
+    /// The raw data of the account at `pubkey`, including the discriminator of Anchor accounts.
+    pub async fn get_account_data(&mut self, pubkey: Pubkey) -> Result<Vec<u8>, BanksClientError> {
+        let account = self.banks_client.get_account(pubkey).await?.unwrap();
+        Ok(account.data)
+    }
+
     pub async fn get_balance(&mut self, pubkey: Pubkey) -> Result<u64, BanksClientError> {
         let lamports = self.banks_client.get_balance(pubkey).await.unwrap();
         Ok(lamports)
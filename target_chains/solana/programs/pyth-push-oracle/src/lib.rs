@@ -12,8 +12,9 @@ use {
             FeedId,
             Message,
         },
-        wire::from_slice,
+        wire::from_slice_with_offset,
     },
+    std::cmp::Ordering,
 };
 
 pub mod sdk;
@@ -30,81 +31,148 @@ pub enum PushOracleError {
     UnsupportedMessageType,
     #[msg("Could not deserialize the message in the update")]
     DeserializeMessageFailed,
+    #[msg("The update has the same publish time as the price in the price feed account")]
+    EqualPublishTime,
 }
+
+/// What to do with an update that has the same `publish_time` as the price currently stored in the price feed account.
+/// This can happen when a price is corrected after it was published.
+/// Overwriting lets a protocol that accepts corrections get the latest price for a timestamp, but it also means the price feed account can change without its `publish_time` moving forward.
+/// Consumers that treat `publish_time` as a version number should stick to `Ignore` or `Reject`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum EqualPublishTimePolicy {
+    /// Fail the transaction.
+    Reject,
+    /// Replace the stored price with the update.
+    Overwrite,
+    /// Succeed without changing the stored price, like for older updates.
+    #[default]
+    Ignore,
+}
+
 #[program]
 pub mod pyth_push_oracle {
     use super::*;
 
-    pub fn update_price_feed(
-        ctx: Context<UpdatePriceFeed>,
+    /// Update the price feed account with the price in `params` if it is more recent than the stored price.
+    /// Updates with the same `publish_time` as the stored price are ignored, see `update_price_feed_with_policy` to change this behavior.
+    /// The remaining accounts are passed to the Pyth Solana Receiver, they must be the fee recipient accounts if its config has any, followed by the `PriceChangeBaseline` account of the feed if it has a price change limit.
+    pub fn update_price_feed<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdatePriceFeed<'info>>,
         params: PostUpdateParams,
         shard_id: u16,
         feed_id: FeedId,
     ) -> Result<()> {
-        let cpi_program = ctx.accounts.pyth_solana_receiver.to_account_info().clone();
-        let cpi_accounts = PostUpdate {
-            payer:                ctx.accounts.payer.to_account_info().clone(),
-            encoded_vaa:          ctx.accounts.encoded_vaa.to_account_info().clone(),
-            config:               ctx.accounts.config.to_account_info().clone(),
-            treasury:             ctx.accounts.treasury.to_account_info().clone(),
-            price_update_account: ctx.accounts.price_feed_account.to_account_info().clone(),
-            system_program:       ctx.accounts.system_program.to_account_info().clone(),
-            write_authority:      ctx.accounts.price_feed_account.to_account_info().clone(),
-        };
+        process_update_price_feed(
+            ctx,
+            params,
+            shard_id,
+            feed_id,
+            EqualPublishTimePolicy::default(),
+        )
+    }
 
-        let seeds = &[
-            &shard_id.to_le_bytes(),
-            feed_id.as_ref(),
-            &[*ctx.bumps.get("price_feed_account").unwrap()],
-        ];
-        let signer_seeds = &[&seeds[..]];
-        let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    /// Same as `update_price_feed`, but updates with the same `publish_time` as the stored price are handled according to `equal_publish_time_policy`.
+    pub fn update_price_feed_with_policy<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdatePriceFeed<'info>>,
+        params: PostUpdateParams,
+        shard_id: u16,
+        feed_id: FeedId,
+        equal_publish_time_policy: EqualPublishTimePolicy,
+    ) -> Result<()> {
+        process_update_price_feed(ctx, params, shard_id, feed_id, equal_publish_time_policy)
+    }
+}
 
-        // Get the timestamp of the price currently stored in the price feed account.
-        let current_timestamp = {
-            if ctx.accounts.price_feed_account.data_is_empty() {
-                0
-            } else {
-                let price_feed_account_data = ctx.accounts.price_feed_account.try_borrow_data()?;
-                let price_feed_account =
-                    PriceUpdateV2::try_deserialize(&mut &price_feed_account_data[..])?;
-                price_feed_account.price_message.publish_time
-            }
-        };
+fn process_update_price_feed<'info>(
+    ctx: Context<'_, '_, '_, 'info, UpdatePriceFeed<'info>>,
+    params: PostUpdateParams,
+    shard_id: u16,
+    feed_id: FeedId,
+    equal_publish_time_policy: EqualPublishTimePolicy,
+) -> Result<()> {
+    let cpi_program = ctx.accounts.pyth_solana_receiver.to_account_info().clone();
+    let cpi_accounts = PostUpdate {
+        payer:                ctx.accounts.payer.to_account_info().clone(),
+        encoded_vaa:          ctx.accounts.encoded_vaa.to_account_info().clone(),
+        config:               ctx.accounts.config.to_account_info().clone(),
+        treasury:             ctx.accounts.treasury.to_account_info().clone(),
+        price_update_account: ctx.accounts.price_feed_account.to_account_info().clone(),
+        system_program:       ctx.accounts.system_program.to_account_info().clone(),
+        write_authority:      ctx.accounts.price_feed_account.to_account_info().clone(),
+    };
 
-        // Get the timestamp of the price in the arguments (that we are trying to put in the account).
-        // It is a little annoying that we have to redundantly deserialize the message here, but
-        // it is required to make txs pushing stale prices succeed w/o updating the on-chain price.
-        //
-        // Note that we don't do any validity checks on the proof etc. here. If the caller passes an
-        // invalid message with a newer timestamp, the validity checks will be performed by pyth_solana_receiver.
-        let message =
-            from_slice::<byteorder::BE, Message>(params.merkle_price_update.message.as_ref())
-                .map_err(|_| PushOracleError::DeserializeMessageFailed)?;
-        let next_timestamp = match message {
-            Message::PriceFeedMessage(price_feed_message) => price_feed_message.publish_time,
-            Message::TwapMessage(_) => {
-                return err!(PushOracleError::UnsupportedMessageType);
-            }
-        };
+    let seeds = &[
+        &shard_id.to_le_bytes(),
+        feed_id.as_ref(),
+        &[*ctx.bumps.get("price_feed_account").unwrap()],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+
+    // Get the timestamp of the price currently stored in the price feed account.
+    let current_timestamp = {
+        if ctx.accounts.price_feed_account.data_is_empty() {
+            0
+        } else {
+            let price_feed_account_data = ctx.accounts.price_feed_account.try_borrow_data()?;
+            let price_feed_account =
+                PriceUpdateV2::try_deserialize(&mut &price_feed_account_data[..])?;
+            price_feed_account.price_message.publish_time
+        }
+    };
 
-        // Only update the price feed if the message contains a newer price. Pushing a stale price
-        // suceeds without changing the on-chain state.
-        if next_timestamp > current_timestamp {
-            pyth_solana_receiver_sdk::cpi::post_update(cpi_context, params)?;
-            {
-                let price_feed_account_data = ctx.accounts.price_feed_account.try_borrow_data()?;
-                let price_feed_account =
-                    PriceUpdateV2::try_deserialize(&mut &price_feed_account_data[..])?;
+    // Get the timestamp of the price in the arguments (that we are trying to put in the account).
+    // It is a little annoying that we have to redundantly deserialize the message here, but
+    // it is required to make txs pushing stale prices succeed w/o updating the on-chain price.
+    //
+    // Note that we don't do any validity checks on the proof etc. here. If the caller passes an
+    // invalid message with a newer timestamp, the validity checks will be performed by pyth_solana_receiver.
+    let message = from_slice_with_offset::<byteorder::BE, Message>(
+        params.merkle_price_update.message.as_ref(),
+    )
+    .map_err(|(err, offset)| {
+        msg!(
+            "Failed to deserialize the message at byte offset {} of {}: {}",
+            offset,
+            params.merkle_price_update.message.as_ref().len(),
+            err
+        );
+        PushOracleError::DeserializeMessageFailed
+    })?;
+    let next_timestamp = match message {
+        Message::PriceFeedMessage(price_feed_message) => price_feed_message.publish_time,
+        Message::TwapMessage(_) => {
+            return err!(PushOracleError::UnsupportedMessageType);
+        }
+    };
+
+    // Only update the price feed if the message contains a newer price. Pushing a stale price
+    // suceeds without changing the on-chain state.
+    let should_update = match next_timestamp.cmp(&current_timestamp) {
+        Ordering::Greater => true,
+        Ordering::Equal => match equal_publish_time_policy {
+            EqualPublishTimePolicy::Reject => return err!(PushOracleError::EqualPublishTime),
+            EqualPublishTimePolicy::Overwrite => true,
+            EqualPublishTimePolicy::Ignore => false,
+        },
+        Ordering::Less => false,
+    };
+    if should_update {
+        pyth_solana_receiver_sdk::cpi::post_update(cpi_context, params)?;
+        {
+            let price_feed_account_data = ctx.accounts.price_feed_account.try_borrow_data()?;
+            let price_feed_account =
+                PriceUpdateV2::try_deserialize(&mut &price_feed_account_data[..])?;
 
-                require!(
-                    price_feed_account.price_message.feed_id == feed_id,
-                    PushOracleError::PriceFeedMessageMismatch
-                );
-            }
+            require!(
+                price_feed_account.price_message.feed_id == feed_id,
+                PushOracleError::PriceFeedMessageMismatch
+            );
         }
-        Ok(())
     }
+    Ok(())
 }
 
 #[derive(Accounts)]
@@ -2,6 +2,7 @@ use {
     crate::{
         accounts,
         instruction,
+        EqualPublishTimePolicy,
         PostUpdateParams,
         ID,
     },
@@ -10,9 +11,15 @@ use {
         system_program,
         InstructionData,
     },
-    pyth_solana_receiver_sdk::pda::{
-        get_config_address,
-        get_treasury_address,
+    pyth_solana_receiver_sdk::{
+        config::{
+            get_fee_recipient_account_metas,
+            FeeRecipient,
+        },
+        pda::{
+            get_config_address,
+            get_treasury_address,
+        },
     },
     pythnet_sdk::{
         messages::FeedId,
@@ -22,7 +29,16 @@ use {
 };
 
 pub fn get_price_feed_address(shard_id: u16, feed_id: FeedId) -> Pubkey {
-    Pubkey::find_program_address(&[&shard_id.to_le_bytes(), feed_id.as_ref()], &ID).0
+    get_price_feed_address_for_program(&ID, shard_id, feed_id)
+}
+
+/// Same as `get_price_feed_address` for a deployment of the push oracle at `program_id`, e.g. on another SVM chain.
+pub fn get_price_feed_address_for_program(
+    program_id: &Pubkey,
+    shard_id: u16,
+    feed_id: FeedId,
+) -> Pubkey {
+    Pubkey::find_program_address(&[&shard_id.to_le_bytes(), feed_id.as_ref()], program_id).0
 }
 
 impl accounts::UpdatePriceFeed {
@@ -46,6 +62,7 @@ impl accounts::UpdatePriceFeed {
 }
 
 impl instruction::UpdatePriceFeed {
+    /// `fee_recipients` must be the `fee_recipients` of the Pyth Solana Receiver config account.
     pub fn populate(
         payer: Pubkey,
         encoded_vaa: Pubkey,
@@ -53,10 +70,12 @@ impl instruction::UpdatePriceFeed {
         feed_id: FeedId,
         treasury_id: u8,
         merkle_price_update: MerklePriceUpdate,
+        fee_recipients: &[FeeRecipient],
     ) -> Instruction {
-        let update_price_feed_accounts =
+        let mut update_price_feed_accounts =
             accounts::UpdatePriceFeed::populate(payer, encoded_vaa, shard_id, feed_id, treasury_id)
                 .to_account_metas(None);
+        update_price_feed_accounts.extend(get_fee_recipient_account_metas(fee_recipients));
         Instruction {
             program_id: ID,
             accounts:   update_price_feed_accounts,
@@ -72,3 +91,36 @@ impl instruction::UpdatePriceFeed {
         }
     }
 }
+
+impl instruction::UpdatePriceFeedWithPolicy {
+    /// `fee_recipients` must be the `fee_recipients` of the Pyth Solana Receiver config account.
+    pub fn populate(
+        payer: Pubkey,
+        encoded_vaa: Pubkey,
+        shard_id: u16,
+        feed_id: FeedId,
+        treasury_id: u8,
+        merkle_price_update: MerklePriceUpdate,
+        equal_publish_time_policy: EqualPublishTimePolicy,
+        fee_recipients: &[FeeRecipient],
+    ) -> Instruction {
+        let mut update_price_feed_accounts =
+            accounts::UpdatePriceFeed::populate(payer, encoded_vaa, shard_id, feed_id, treasury_id)
+                .to_account_metas(None);
+        update_price_feed_accounts.extend(get_fee_recipient_account_metas(fee_recipients));
+        Instruction {
+            program_id: ID,
+            accounts:   update_price_feed_accounts,
+            data:       instruction::UpdatePriceFeedWithPolicy {
+                params: PostUpdateParams {
+                    merkle_price_update,
+                    treasury_id,
+                },
+                shard_id,
+                feed_id,
+                equal_publish_time_policy,
+            }
+            .data(),
+        }
+    }
+}
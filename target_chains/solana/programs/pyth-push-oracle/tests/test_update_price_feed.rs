@@ -7,20 +7,37 @@ use {
     },
     program_simulator::into_transaction_error,
     pyth_push_oracle::{
-        instruction::UpdatePriceFeed,
+        instruction::{
+            UpdatePriceFeed,
+            UpdatePriceFeedWithPolicy,
+        },
         sdk::get_price_feed_address,
+        EqualPublishTimePolicy,
         PushOracleError,
     },
-    pyth_solana_receiver::sdk::{
-        deserialize_accumulator_update_data,
-        DEFAULT_TREASURY_ID,
+    pyth_solana_receiver::{
+        error::ReceiverError,
+        instruction::{
+            SetFee,
+            SetFeeRecipients,
+        },
+        sdk::{
+            deserialize_accumulator_update_data,
+            DEFAULT_TREASURY_ID,
+        },
     },
-    pyth_solana_receiver_sdk::price_update::{
-        PriceUpdateV2,
-        VerificationLevel,
+    pyth_solana_receiver_sdk::{
+        config::FeeRecipient,
+        price_update::{
+            PriceUpdateV2,
+            VerificationLevel,
+        },
     },
     pythnet_sdk::{
-        messages::Message,
+        messages::{
+            Message,
+            PriceFeedMessage,
+        },
         test_utils::{
             create_accumulator_message,
             create_dummy_feed_id,
@@ -28,6 +45,7 @@ use {
         },
     },
     solana_sdk::{
+        native_token::LAMPORTS_PER_SOL,
         rent::Rent,
         signer::Signer,
     },
@@ -79,6 +97,7 @@ async fn test_update_price_feed() {
                 feed_id,
                 DEFAULT_TREASURY_ID,
                 merkle_price_updates[0].clone(),
+                &[],
             ),
             &vec![&poster],
             None,
@@ -125,6 +144,7 @@ async fn test_update_price_feed() {
                 feed_id,
                 DEFAULT_TREASURY_ID,
                 merkle_price_updates[1].clone(),
+                &[],
             ),
             &vec![&poster],
             None,
@@ -171,6 +191,7 @@ async fn test_update_price_feed() {
                 feed_id,
                 DEFAULT_TREASURY_ID,
                 merkle_price_updates[0].clone(),
+                &[],
             ),
             &vec![&poster],
             None,
@@ -217,6 +238,7 @@ async fn test_update_price_feed() {
                 feed_id,
                 DEFAULT_TREASURY_ID,
                 merkle_price_updates[0].clone(),
+                &[],
             ),
             &vec![&poster],
             None,
@@ -286,6 +308,7 @@ async fn test_update_price_feed() {
                     feed_id,
                     DEFAULT_TREASURY_ID,
                     merkle_price_updates[2].clone(),
+                    &[],
                 ),
                 &vec![&poster],
                 None,
@@ -318,3 +341,265 @@ async fn test_update_price_feed() {
         program_simulator.get_clock().await.unwrap().slot
     );
 }
+
+#[tokio::test]
+async fn test_update_price_feed_equal_publish_time() {
+    let feed_id: [u8; 32] = create_dummy_feed_id(100);
+
+    let feed_1 = create_dummy_price_feed_message_with_feed_id(100, feed_id);
+    // A correction of feed_1, with the same publish time and a different price
+    let feed_1_corrected = match feed_1 {
+        Message::PriceFeedMessage(price_feed_message) => {
+            Message::PriceFeedMessage(PriceFeedMessage {
+                price: price_feed_message.price + 1,
+                ..price_feed_message
+            })
+        }
+        Message::TwapMessage(_) => unreachable!(),
+    };
+
+    let message = create_accumulator_message(
+        &[feed_1, feed_1_corrected],
+        &[feed_1, feed_1_corrected],
+        false,
+        false,
+    );
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses,
+        governance_authority: _,
+    } = setup_pyth_receiver(
+        vec![serde_wormhole::from_slice(&vaa).unwrap()],
+        WrongSetupOption::None,
+    )
+    .await;
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            UpdatePriceFeed::populate(
+                poster.pubkey(),
+                encoded_vaa_addresses[0],
+                DEFAULT_SHARD,
+                feed_id,
+                DEFAULT_TREASURY_ID,
+                merkle_price_updates[0].clone(),
+                &[],
+            ),
+            &vec![&poster],
+            None,
+        )
+        .await
+        .unwrap();
+
+    // The default policy ignores the correction
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            UpdatePriceFeed::populate(
+                poster.pubkey(),
+                encoded_vaa_addresses[0],
+                DEFAULT_SHARD,
+                feed_id,
+                DEFAULT_TREASURY_ID,
+                merkle_price_updates[1].clone(),
+                &[],
+            ),
+            &vec![&poster],
+            None,
+        )
+        .await
+        .unwrap();
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            UpdatePriceFeedWithPolicy::populate(
+                poster.pubkey(),
+                encoded_vaa_addresses[0],
+                DEFAULT_SHARD,
+                feed_id,
+                DEFAULT_TREASURY_ID,
+                merkle_price_updates[1].clone(),
+                EqualPublishTimePolicy::Ignore,
+                &[],
+            ),
+            &vec![&poster],
+            None,
+        )
+        .await
+        .unwrap();
+
+    let price_feed_account = program_simulator
+        .get_anchor_account_data::<PriceUpdateV2>(get_price_feed_address(DEFAULT_SHARD, feed_id))
+        .await
+        .unwrap();
+    assert_eq!(
+        Message::PriceFeedMessage(price_feed_account.price_message),
+        feed_1
+    );
+
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                UpdatePriceFeedWithPolicy::populate(
+                    poster.pubkey(),
+                    encoded_vaa_addresses[0],
+                    DEFAULT_SHARD,
+                    feed_id,
+                    DEFAULT_TREASURY_ID,
+                    merkle_price_updates[1].clone(),
+                    EqualPublishTimePolicy::Reject,
+                    &[],
+                ),
+                &vec![&poster],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(PushOracleError::EqualPublishTime)
+    );
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            UpdatePriceFeedWithPolicy::populate(
+                poster.pubkey(),
+                encoded_vaa_addresses[0],
+                DEFAULT_SHARD,
+                feed_id,
+                DEFAULT_TREASURY_ID,
+                merkle_price_updates[1].clone(),
+                EqualPublishTimePolicy::Overwrite,
+                &[],
+            ),
+            &vec![&poster],
+            None,
+        )
+        .await
+        .unwrap();
+
+    let price_feed_account = program_simulator
+        .get_anchor_account_data::<PriceUpdateV2>(get_price_feed_address(DEFAULT_SHARD, feed_id))
+        .await
+        .unwrap();
+    assert_eq!(
+        Message::PriceFeedMessage(price_feed_account.price_message),
+        feed_1_corrected
+    );
+}
+
+#[tokio::test]
+async fn test_update_price_feed_with_fee_recipients() {
+    let feed_id: [u8; 32] = create_dummy_feed_id(100);
+    let feed_1 = create_dummy_price_feed_message_with_feed_id(100, feed_id);
+    let message = create_accumulator_message(&[feed_1], &[feed_1], false, false);
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses,
+        governance_authority,
+    } = setup_pyth_receiver(
+        vec![serde_wormhole::from_slice(&vaa).unwrap()],
+        WrongSetupOption::None,
+    )
+    .await;
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let recipient_1 = program_simulator.get_funded_keypair().await.unwrap();
+    let recipient_2 = program_simulator.get_funded_keypair().await.unwrap();
+    let fee_recipients = vec![
+        FeeRecipient {
+            recipient:  recipient_1.pubkey(),
+            weight_bps: 7000,
+        },
+        FeeRecipient {
+            recipient:  recipient_2.pubkey(),
+            weight_bps: 3000,
+        },
+    ];
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetFee::populate(governance_authority.pubkey(), 1000),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetFeeRecipients::populate(governance_authority.pubkey(), fee_recipients.clone()),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    // The receiver rejects the update if the push oracle doesn't pass it the fee recipient accounts
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                UpdatePriceFeed::populate(
+                    poster.pubkey(),
+                    encoded_vaa_addresses[0],
+                    DEFAULT_SHARD,
+                    feed_id,
+                    DEFAULT_TREASURY_ID,
+                    merkle_price_updates[0].clone(),
+                    &[],
+                ),
+                &vec![&poster],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::MissingFeeRecipientAccounts)
+    );
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            UpdatePriceFeed::populate(
+                poster.pubkey(),
+                encoded_vaa_addresses[0],
+                DEFAULT_SHARD,
+                feed_id,
+                DEFAULT_TREASURY_ID,
+                merkle_price_updates[0].clone(),
+                &fee_recipients,
+            ),
+            &vec![&poster],
+            None,
+        )
+        .await
+        .unwrap();
+
+    let price_feed_account = program_simulator
+        .get_anchor_account_data::<PriceUpdateV2>(get_price_feed_address(DEFAULT_SHARD, feed_id))
+        .await
+        .unwrap();
+    assert_eq!(
+        Message::PriceFeedMessage(price_feed_account.price_message),
+        feed_1
+    );
+
+    // The fee is split between the recipients and nothing goes to the treasury
+    assert_eq!(
+        program_simulator
+            .get_balance(recipient_1.pubkey())
+            .await
+            .unwrap(),
+        LAMPORTS_PER_SOL + 700
+    );
+    assert_eq!(
+        program_simulator
+            .get_balance(recipient_2.pubkey())
+            .await
+            .unwrap(),
+        LAMPORTS_PER_SOL + 300
+    );
+    assert_treasury_balance(&mut program_simulator, 0, DEFAULT_TREASURY_ID).await;
+}
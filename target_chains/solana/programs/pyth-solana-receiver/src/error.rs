@@ -53,4 +53,65 @@ pub enum ReceiverError {
     NonexistentGovernanceAuthorityTransferRequest,
     #[msg("The minimum number of signatures should be at least 1")]
     ZeroMinimumSignatures,
+    // Accumulator update data account errors (from post_updates_from_account_data)
+    #[msg("An error occurred when deserializing the accumulator update data")]
+    DeserializeAccumulatorUpdateDataFailed,
+    #[msg("The VAA in the accumulator update data doesn't match the encoded VAA account")]
+    MismatchedVaa,
+    #[msg("The number of price update accounts doesn't match the number of updates")]
+    WrongNumberOfPriceUpdateAccounts,
+    #[msg("The accumulator update data doesn't contain any updates")]
+    NoUpdatesProvided,
+    #[msg("The guardian set doesn't match the guardian set pinned in the config")]
+    PinnedGuardianSetMismatch,
+    #[msg("The weights of the fee recipients must sum to 10000 basis points")]
+    InvalidFeeRecipientWeights,
+    #[msg("The fee recipient accounts don't match the fee recipients in the config")]
+    WrongFeeRecipientAccounts,
+    #[msg("The TWAP message is inconsistent, its previous publish time is after its publish time")]
+    InvalidTwapMessage,
+    #[msg("The encoded VAA account is owned by a program that isn't approved in the config")]
+    UnapprovedPostVaaProgram,
+    #[msg("The guardian set account has more keys than the maximum number of guardians")]
+    TooManyGuardians,
+    #[msg("The price history account stores the updates of a different feed")]
+    PriceHistoryFeedIdMismatch,
+    #[msg("The VAA payload is not a Pyth accumulator message")]
+    NotAnAccumulatorMessage,
+    #[msg("The number of updates doesn't match the expected number of updates")]
+    UnexpectedNumberOfUpdates,
+    #[msg("The price update is not included in the root of any of the VAAs")]
+    NoMatchingVaa,
+    #[msg("The VAA index of an update is out of range, or the number of VAA indices doesn't match the number of updates")]
+    InvalidVaaIndex,
+    #[msg(
+        "The price moved more than the limit set in the config for this feed since the last update"
+    )]
+    PriceMovedTooMuch,
+    #[msg("The price update account was updated too recently to be closed")]
+    PriceUpdateTooRecent,
+    #[msg("The publish time of the price update is too far in the past")]
+    PriceTooStale,
+    #[msg("The publish time of the price update is too far in the future")]
+    PublishTimeInFuture,
+    #[msg("The accumulator update data doesn't start with the accumulator update magic and a supported major version")]
+    InvalidAccumulatorUpdateData,
+    #[msg("The proof type of the accumulator update data is not supported")]
+    UnsupportedProofType,
+    #[msg("The exponent or the confidence interval of the price update is outside the bounds set in the config")]
+    PriceSanityCheckFailed,
+    #[msg("Too many updates to return their messages in the return data of the instruction")]
+    TooManyUpdatesToVerify,
+    #[msg("The consistency level of the VAA is lower than the minimum consistency level set in the config")]
+    InsufficientConsistencyLevel,
+    #[msg("The config doesn't fit in the config account, resize it with resize_config")]
+    ConfigAccountTooSmall,
+    #[msg("The config has fee recipients, but their accounts weren't passed to the instruction")]
+    MissingFeeRecipientAccounts,
+    #[msg("The number of fee recipient accounts doesn't match the number of fee recipients in the config")]
+    WrongNumberOfFeeRecipientAccounts,
+    #[msg("The price change baseline account of the feed is missing or isn't the PDA of the feed")]
+    WrongPriceChangeBaselineAccount,
+    #[msg("The window of a price change limit must be at least 1 second")]
+    InvalidPriceChangeLimitWindow,
 }
@@ -1,32 +1,61 @@
 pub use pythnet_sdk::wire::v1::MerklePriceUpdate;
 use {
     crate::error::ReceiverError,
-    anchor_lang::prelude::*,
+    anchor_lang::{
+        prelude::*,
+        Discriminator,
+    },
     pyth_solana_receiver_sdk::{
         config::{
+            hash_guardian_keys,
+            split_fee,
             Config,
             DataSource,
+            ExponentRange,
+            FeeRecipient,
+            LegacyConfig,
+            PriceChangeBaseline,
+            PriceChangeLimit,
+            TOTAL_FEE_WEIGHT_BPS,
         },
         pda::{
             CONFIG_SEED,
+            PRICE_CHANGE_BASELINE_SEED,
             TREASURY_SEED,
         },
         price_update::{
+            PriceHistory,
             PriceUpdateV2,
+            TwapUpdate,
             VerificationLevel,
         },
+        EventMode,
         PostUpdateAtomicParams,
         PostUpdateParams,
+        PostUpdatesFromAccountDataParams,
+        PostUpdatesFromVaasParams,
+        PostedUpdatesSummary,
+        TaggedPostedUpdatesSummary,
+        MAX_VIEW_UPDATES,
     },
     pythnet_sdk::{
         accumulators::merkle::MerkleRoot,
         hashers::keccak256_160::Keccak160,
-        messages::Message,
+        messages::{
+            FeedId,
+            Message,
+            PriceFeedMessage,
+            TwapMessage,
+        },
         wire::{
-            from_slice,
+            from_slice_with_offset,
             v1::{
+                AccumulatorUpdateData,
+                Proof,
                 WormholeMessage,
                 WormholePayload,
+                ACCUMULATOR_UPDATE_WORMHOLE_VERIFICATION_MAGIC,
+                PYTHNET_ACCUMULATOR_UPDATE_MAGIC,
             },
         },
     },
@@ -41,7 +70,10 @@ use {
             legacy::AccountVariant,
             VaaAccount,
         },
-        state::GuardianSet,
+        state::{
+            EncodedVaa,
+            GuardianSet,
+        },
     },
     wormhole_raw_vaas::{
         utils::quorum,
@@ -51,6 +83,7 @@ use {
 };
 
 pub mod error;
+pub mod prelude;
 pub mod sdk;
 
 declare_id!(pyth_solana_receiver_sdk::ID);
@@ -74,13 +107,17 @@ pub mod pyth_solana_receiver {
         target_governance_authority: Pubkey,
     ) -> Result<()> {
         let config = &mut ctx.accounts.config;
+        let old_config = Config::clone(config);
         config.target_governance_authority = Some(target_governance_authority);
+        emit_config_updated(old_config, config)?;
         Ok(())
     }
 
     pub fn cancel_governance_authority_transfer(ctx: Context<Governance>) -> Result<()> {
         let config = &mut ctx.accounts.config;
+        let old_config = Config::clone(config);
         config.target_governance_authority = None;
+        emit_config_updated(old_config, config)?;
         Ok(())
     }
 
@@ -88,10 +125,12 @@ pub mod pyth_solana_receiver {
         ctx: Context<AcceptGovernanceAuthorityTransfer>,
     ) -> Result<()> {
         let config = &mut ctx.accounts.config;
+        let old_config = Config::clone(config);
         config.governance_authority = config.target_governance_authority.ok_or(error!(
             ReceiverError::NonexistentGovernanceAuthorityTransferRequest
         ))?;
         config.target_governance_authority = None;
+        emit_config_updated(old_config, config)?;
         Ok(())
     }
 
@@ -100,26 +139,239 @@ pub mod pyth_solana_receiver {
         valid_data_sources: Vec<DataSource>,
     ) -> Result<()> {
         let config = &mut ctx.accounts.config;
+        let old_config = Config::clone(config);
         config.valid_data_sources = valid_data_sources;
+        emit_config_updated(old_config, config)?;
         Ok(())
     }
 
     pub fn set_fee(ctx: Context<Governance>, single_update_fee_in_lamports: u64) -> Result<()> {
         let config = &mut ctx.accounts.config;
+        let old_config = Config::clone(config);
         config.single_update_fee_in_lamports = single_update_fee_in_lamports;
+        emit_config_updated(old_config, config)?;
         Ok(())
     }
 
     pub fn set_wormhole_address(ctx: Context<Governance>, wormhole: Pubkey) -> Result<()> {
         let config = &mut ctx.accounts.config;
+        let old_config = Config::clone(config);
         config.wormhole = wormhole;
+        emit_config_updated(old_config, config)?;
         Ok(())
     }
 
     pub fn set_minimum_signatures(ctx: Context<Governance>, minimum_signatures: u8) -> Result<()> {
         let config = &mut ctx.accounts.config;
+        let old_config = Config::clone(config);
         require!(minimum_signatures > 0, ReceiverError::ZeroMinimumSignatures);
         config.minimum_signatures = minimum_signatures;
+        emit_config_updated(old_config, config)?;
+        Ok(())
+    }
+
+    pub fn set_pinned_guardian_set_hash(
+        ctx: Context<Governance>,
+        pinned_guardian_set_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_config = Config::clone(config);
+        config.pinned_guardian_set_hash = pinned_guardian_set_hash;
+        emit_config_updated(old_config, config)?;
+        Ok(())
+    }
+
+    /// Consider guardian sets expired `guardian_set_expiration_skew_seconds` before their expiration time when checking them in `post_update_atomic`.
+    /// This tolerates small differences between the Solana clock and the clock of the chain that set the expiration time.
+    /// The tolerance is only applied in the safe direction: near the boundary, a guardian set that is still valid may be rejected but an expired one is never accepted.
+    /// VAAs posted through the Wormhole receiver (used by `post_update`) are not affected, their guardian set is checked by the Wormhole receiver.
+    pub fn set_guardian_set_expiration_skew(
+        ctx: Context<Governance>,
+        guardian_set_expiration_skew_seconds: u32,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_config = Config::clone(config);
+        config.guardian_set_expiration_skew_seconds = guardian_set_expiration_skew_seconds;
+        emit_config_updated(old_config, config)?;
+        Ok(())
+    }
+
+    /// Accept encoded VAA accounts owned by any of `post_vaa_programs` in addition to the `wormhole` program.
+    /// Every program in this list must only mark VAAs as verified after checking their guardian signatures, since `VaaAccount::load` trusts the `ProcessingStatus` written by the owner.
+    /// Each program takes 32 bytes of the config account.
+    pub fn set_post_vaa_programs(
+        ctx: Context<Governance>,
+        post_vaa_programs: Vec<Pubkey>,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_config = Config::clone(config);
+        config.post_vaa_programs = post_vaa_programs;
+        emit_config_updated(old_config, config)?;
+        Ok(())
+    }
+
+    /// Reject the updates of each feed of `price_change_limits` whose price differs from the last price of the feed by more than the limit of the feed, in basis points of the last price.
+    /// This is a circuit breaker against anomalous jumps in a single update, and feeds without a limit aren't checked.
+    /// The last price of a feed is stored in its `PriceChangeBaseline` account, a PDA of the feed that is updated by every posted update of the feed, whatever the price update account it's posted to. Posting instructions need this account for the updates of the limited feeds, after the fee recipient accounts. The first update of a feed only creates the baseline.
+    /// The limit of a feed only applies to updates published less than its `window_seconds` after the last price, so that a price that really moved is accepted once the window has passed instead of blocking the feed forever. The window must be at least 1 second.
+    /// Each limit takes 38 bytes of the config account.
+    pub fn set_price_change_limits(
+        ctx: Context<Governance>,
+        price_change_limits: Vec<PriceChangeLimit>,
+    ) -> Result<()> {
+        require!(
+            price_change_limits
+                .iter()
+                .all(|price_change_limit| price_change_limit.window_seconds > 0),
+            ReceiverError::InvalidPriceChangeLimitWindow
+        );
+        let config = &mut ctx.accounts.config;
+        let old_config = Config::clone(config);
+        config.price_change_limits = price_change_limits;
+        emit_config_updated(old_config, config)?;
+        Ok(())
+    }
+
+    /// Only let write authorities close their price update accounts with `reclaim_rent` or `close_price_update` `min_price_update_lifetime_slots` slots after the last update of the account.
+    /// This prevents closing an account in the middle of a transaction of a consumer that reads it.
+    pub fn set_min_price_update_lifetime(
+        ctx: Context<Governance>,
+        min_price_update_lifetime_slots: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_config = Config::clone(config);
+        config.min_price_update_lifetime_slots = min_price_update_lifetime_slots;
+        emit_config_updated(old_config, config)?;
+        Ok(())
+    }
+
+    /// Reject price updates whose `publish_time` is more than `max_staleness_seconds` before, or more than `max_future_publish_time_seconds` after, the current time of the cluster.
+    /// Either bound is disabled when it's 0. The future bound protects against updates signed with a skewed clock, since a publish time in the future would look fresh to consumers for longer than it should.
+    pub fn set_publish_time_bounds(
+        ctx: Context<Governance>,
+        max_staleness_seconds: u32,
+        max_future_publish_time_seconds: u32,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_config = Config::clone(config);
+        config.max_staleness_seconds = max_staleness_seconds;
+        config.max_future_publish_time_seconds = max_future_publish_time_seconds;
+        emit_config_updated(old_config, config)?;
+        Ok(())
+    }
+
+    /// Reject price updates whose exponent is outside `exponent_range`, or whose confidence interval is more than `max_conf_bps` basis points of the price.
+    /// `None` and 0 disable the corresponding check, which is also the behavior of config accounts created before these fields existed.
+    pub fn set_price_sanity_bounds(
+        ctx: Context<Governance>,
+        exponent_range: Option<ExponentRange>,
+        max_conf_bps: u32,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_config = Config::clone(config);
+        config.exponent_range = exponent_range;
+        config.max_conf_bps = max_conf_bps;
+        emit_config_updated(old_config, config)?;
+        Ok(())
+    }
+
+    /// Resize the config account to `len` bytes, the governance authority pays the rent of the extra space.
+    /// Config accounts created by earlier versions of the program are 370 bytes, which isn't enough for the lists of the config to grow: `set_post_vaa_programs`, `set_price_change_limits`, `set_allowed_feed_ids` and `set_fee_recipients` fail with `ReceiverError::ConfigAccountTooSmall` if the new list doesn't fit in the account.
+    /// A single call can grow the account by at most 10 KiB, and the account can't be shrunk below the size of the config it stores.
+    // `len` is only used by the `realloc` constraint of `ResizeConfig`
+    #[allow(unused_variables)]
+    pub fn resize_config(ctx: Context<ResizeConfig>, len: u32) -> Result<()> {
+        let config = &ctx.accounts.config;
+        emit_config_updated(Config::clone(config), config)
+    }
+
+    /// Rewrite a config account created by the first version of the program in the current layout.
+    /// That version didn't zero the end of the account when the config shrank, so the bytes after its fields can hold stale data that doesn't deserialize as the fields added since then, and every instruction that loads the config fails.
+    /// This only reads the fields of `LegacyConfig`, sets the other fields to their defaults and zeroes the rest of the account. It must be called once after upgrading the program and before setting any of the new fields, since it resets them.
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        let mut config_data = ctx.accounts.config.try_borrow_mut_data()?;
+        let discriminator_len = Config::discriminator().len();
+        require!(
+            config_data.starts_with(&Config::discriminator()),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let legacy_config = LegacyConfig::deserialize(&mut &config_data[discriminator_len..])
+            .map_err(|_| ErrorCode::AccountDidNotDeserialize)?;
+        require_keys_eq!(
+            ctx.accounts.payer.key(),
+            legacy_config.governance_authority,
+            ReceiverError::GovernanceAuthorityMismatch
+        );
+
+        let config = Config::from(legacy_config);
+        let config_bytes = config.try_to_vec()?;
+        let config_data = &mut config_data[discriminator_len..];
+        require_gte!(
+            config_data.len(),
+            config_bytes.len(),
+            ReceiverError::ConfigAccountTooSmall
+        );
+        config_data.fill(0);
+        config_data[..config_bytes.len()].copy_from_slice(&config_bytes);
+
+        emit!(ConfigUpdated {
+            old_config: config.clone(),
+            new_config: config,
+        });
+        Ok(())
+    }
+
+    /// Only post the updates of the feeds of `allowed_feed_ids` in `post_updates_from_account_data`, `post_updates_with_tag` and `post_updates_from_vaas`. The updates of other feeds are skipped without failing the instruction, and their price update accounts are left untouched.
+    /// This keeps a deployment that only cares about a few feeds from paying for the accounts of irrelevant feeds. An empty list allows every feed.
+    /// Each feed id takes 32 bytes of the config account.
+    pub fn set_allowed_feed_ids(
+        ctx: Context<Governance>,
+        allowed_feed_ids: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_config = Config::clone(config);
+        config.allowed_feed_ids = allowed_feed_ids;
+        emit_config_updated(old_config, config)?;
+        Ok(())
+    }
+
+    /// Reject price updates from VAAs whose consistency level is lower than `min_consistency_level`, for consumers that only want updates from VAAs emitted at a given finality.
+    /// The check applies to every instruction that verifies updates. 0 accepts every VAA, which is also the behavior of config accounts created before this field existed.
+    /// Callers can require a higher consistency level for their own updates (e.g. with `post_update_with_min_consistency`), but can't go below this one.
+    pub fn set_min_consistency_level(
+        ctx: Context<Governance>,
+        min_consistency_level: u8,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_config = Config::clone(config);
+        config.min_consistency_level = min_consistency_level;
+        emit_config_updated(old_config, config)?;
+        Ok(())
+    }
+
+    /// Split the fee of every update between `fee_recipients` according to their weights (in basis points), instead of sending it to the treasury.
+    /// The weights must sum to `TOTAL_FEE_WEIGHT_BPS`. An empty list sends the whole fee to the treasury again.
+    /// Posting instructions then need the fee recipient accounts as remaining accounts, in the same order, and each recipient must be able to receive its share (e.g. a rent-exempt system account).
+    /// Each recipient takes 34 bytes of the config account.
+    pub fn set_fee_recipients(
+        ctx: Context<Governance>,
+        fee_recipients: Vec<FeeRecipient>,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_config = Config::clone(config);
+        if !fee_recipients.is_empty() {
+            let total_weight_bps: u64 = fee_recipients
+                .iter()
+                .map(|fee_recipient| u64::from(fee_recipient.weight_bps))
+                .sum();
+            require_eq!(
+                total_weight_bps,
+                u64::from(TOTAL_FEE_WEIGHT_BPS),
+                ReceiverError::InvalidFeeRecipientWeights
+            );
+        }
+        config.fee_recipients = fee_recipients;
+        emit_config_updated(old_config, config)?;
         Ok(())
     }
 
@@ -133,126 +385,298 @@ pub mod pyth_solana_receiver {
     /// # Warning
     ///
     /// Using partially verified price updates is dangerous, as it lowers the threshold of guardians that need to collude to produce a malicious price update.
-    pub fn post_update_atomic(
-        ctx: Context<PostUpdateAtomic>,
+    ///
+    /// If the config has fee recipients, their accounts must be passed as remaining accounts (see `set_fee_recipients`), followed by the `PriceChangeBaseline` account of the feed if it has a price change limit (see `set_price_change_limits`).
+    pub fn post_update_atomic<'info>(
+        ctx: Context<'_, '_, '_, 'info, PostUpdateAtomic<'info>>,
         params: PostUpdateAtomicParams,
     ) -> Result<()> {
+        process_post_update_atomic(ctx, &params, None)
+    }
+
+    /// Same as `post_update_atomic`, but the VAA must also have at least `min_consistency_level` as its consistency level.
+    /// This lets a caller require a higher consistency level than `Config::min_consistency_level` for its own updates, a lower value has no effect (see `set_min_consistency_level`).
+    pub fn post_update_atomic_with_min_consistency<'info>(
+        ctx: Context<'_, '_, '_, 'info, PostUpdateAtomic<'info>>,
+        params: PostUpdateAtomicParams,
+        min_consistency_level: u8,
+    ) -> Result<()> {
+        process_post_update_atomic(ctx, &params, Some(min_consistency_level))
+    }
+
+    /// Post a price update using an encoded_vaa account and a MerklePriceUpdate calldata.
+    /// This should be called after the client has already verified the Vaa via the Wormhole contract.
+    /// Check out target_chains/solana/cli/src/main.rs for an example of how to do this.
+    /// If the config has fee recipients, their accounts must be passed as remaining accounts (see `set_fee_recipients`), followed by the `PriceChangeBaseline` account of the feed if it has a price change limit (see `set_price_change_limits`).
+    pub fn post_update<'info>(
+        ctx: Context<'_, '_, '_, 'info, PostUpdate<'info>>,
+        params: PostUpdateParams,
+    ) -> Result<()> {
+        process_post_update(ctx, &params, None)
+    }
+
+    /// Same as `post_update`, but the VAA must also have at least `min_consistency_level` as its consistency level.
+    /// This lets a caller require a higher consistency level than `Config::min_consistency_level` for its own updates, a lower value has no effect (see `set_min_consistency_level`).
+    pub fn post_update_with_min_consistency<'info>(
+        ctx: Context<'_, '_, '_, 'info, PostUpdate<'info>>,
+        params: PostUpdateParams,
+        min_consistency_level: u8,
+    ) -> Result<()> {
+        process_post_update(ctx, &params, Some(min_consistency_level))
+    }
+
+    /// Post all the price updates of an `AccumulatorUpdateData` that has been written to an account.
+    /// This is useful for batches of updates that are too large to fit in instruction data.
+    /// Like `post_update`, this should be called after the client has already verified the Vaa via the Wormhole contract, and the VAA embedded in the accumulator update data must match the `encoded_vaa` account.
+    /// The price update accounts are passed as remaining accounts, one for each update and in the same order as the updates, followed by the fee recipient accounts if the config has any, followed by the `PriceChangeBaseline` accounts of the feeds with a price change limit (see `set_price_change_limits`).
+    /// If `params.write_price_history` is true, every update is also added to a `PriceHistory` account, and these accounts are passed right after the price update accounts, one for each update and in the same order.
+    /// `params.event_mode` controls whether one event is emitted per update or a single event for the whole batch.
+    /// `params.expected_updates` and `params.require_exact_count` bound (or fix) the number of updates of the batch, so that the compute and the fee of the instruction are predictable.
+    /// The updates of feeds that aren't in `allowed_feed_ids` are skipped (see `set_allowed_feed_ids`).
+    /// The return data of the instruction is a `PostedUpdatesSummary` of the posted updates.
+    pub fn post_updates_from_account_data<'info>(
+        ctx: Context<'_, '_, '_, 'info, PostUpdatesFromAccountData<'info>>,
+        params: PostUpdatesFromAccountDataParams,
+    ) -> Result<PostedUpdatesSummary> {
+        let (feed_ids, num_skipped) = process_post_updates_from_account_data(ctx, &params)?;
+        Ok(PostedUpdatesSummary::new(&feed_ids, num_skipped))
+    }
+
+    /// Same as `post_updates_from_account_data`, with a correlation tag chosen by the caller.
+    /// The tag is emitted in a `TaggedPriceUpdatesPosted` event along with the feed ids of the updates and the number of skipped updates.
+    /// The return data of the instruction is a `TaggedPostedUpdatesSummary` with the tag and a summary of the posted updates.
+    /// This lets a consumer program that triggered the verification (e.g. in response to one of its own requests) match the verified prices to that request.
+    pub fn post_updates_with_tag<'info>(
+        ctx: Context<'_, '_, '_, 'info, PostUpdatesFromAccountData<'info>>,
+        params: PostUpdatesFromAccountDataParams,
+        tag: [u8; 32],
+    ) -> Result<TaggedPostedUpdatesSummary> {
+        let (feed_ids, num_skipped) = process_post_updates_from_account_data(ctx, &params)?;
+        let tagged_summary = TaggedPostedUpdatesSummary::new(tag, &feed_ids, num_skipped);
+        emit!(TaggedPriceUpdatesPosted {
+            tag,
+            feed_ids,
+            num_skipped,
+        });
+        Ok(tagged_summary)
+    }
+
+    /// Post price updates whose Merkle roots are spread over several VAAs, e.g. a set of feeds that spans an accumulator boundary.
+    /// Like `post_update`, this should be called after the client has already verified the VAAs via the Wormhole contract.
+    /// The remaining accounts are the `params.num_vaas` encoded VAA accounts, followed by one price update account per update in the same order as the updates, followed by the fee recipient accounts if the config has any, followed by the `PriceChangeBaseline` accounts of the feeds with a price change limit (see `set_price_change_limits`).
+    /// Every update is checked against the VAA at its index in `params.vaa_indices`, or if `params.vaa_indices` is empty against the VAAs in order until one of them includes it. An update that isn't included in any of the VAAs is rejected.
+    /// Finding the VAA of an update verifies it once more, so passing `params.vaa_indices` uses less compute for large batches.
+    /// The updates of feeds that aren't in `allowed_feed_ids` are skipped (see `set_allowed_feed_ids`).
+    /// The return data of the instruction is a `PostedUpdatesSummary` of the posted updates.
+    pub fn post_updates_from_vaas<'info>(
+        ctx: Context<'_, '_, '_, 'info, PostUpdatesFromVaas<'info>>,
+        params: PostUpdatesFromVaasParams,
+    ) -> Result<PostedUpdatesSummary> {
         let config = &ctx.accounts.config;
-        let guardian_set =
-            deserialize_guardian_set_checked(&ctx.accounts.guardian_set, &config.wormhole)?;
-
-        // This section is borrowed from https://github.com/wormhole-foundation/wormhole/blob/wen/solana-rewrite/solana/programs/core-bridge/src/processor/parse_and_verify_vaa/verify_encoded_vaa_v1.rs#L59
-        let vaa = Vaa::parse(&params.vaa).map_err(|_| ReceiverError::DeserializeVaaFailed)?;
-        // Must be V1.
-        require_eq!(vaa.version(), 1, ReceiverError::InvalidVaaVersion);
-
-        // Make sure the encoded guardian set index agrees with the guardian set account's index.
-        let guardian_set = guardian_set.inner();
-        require_eq!(
-            vaa.guardian_set_index(),
-            guardian_set.index,
-            ReceiverError::GuardianSetMismatch
-        );
+        let payer: &Signer<'_> = &ctx.accounts.payer;
+        let write_authority: &Signer<'_> = &ctx.accounts.write_authority;
+        let treasury: &AccountInfo<'_> = &ctx.accounts.treasury;
+        let updates = &params.merkle_price_updates;
 
-        let guardian_keys = &guardian_set.keys;
-        let quorum = quorum(guardian_keys.len());
-        require_gte!(
-            vaa.signature_count(),
-            config.minimum_signatures,
-            ReceiverError::InsufficientGuardianSignatures
+        require!(!updates.is_empty(), ReceiverError::NoUpdatesProvided);
+        require!(
+            params.vaa_indices.is_empty() || params.vaa_indices.len() == updates.len(),
+            ReceiverError::InvalidVaaIndex
         );
-        let verification_level = if usize::from(vaa.signature_count()) >= quorum {
-            VerificationLevel::Full
-        } else {
-            VerificationLevel::Partial {
-                num_signatures: vaa.signature_count(),
-            }
-        };
+        let num_vaas = usize::from(params.num_vaas);
+        let (update_account_infos, mut remaining_accounts) =
+            split_remaining_accounts(config, ctx.remaining_accounts, num_vaas + updates.len())?;
+        let (encoded_vaa_account_infos, price_update_account_infos) =
+            update_account_infos.split_at(num_vaas);
+
+        let mut encoded_vaas = Vec::with_capacity(num_vaas);
+        for encoded_vaa_account_info in encoded_vaa_account_infos {
+            require!(
+                config.is_approved_post_vaa_program(encoded_vaa_account_info.owner),
+                ReceiverError::UnapprovedPostVaaProgram
+            );
+            encoded_vaas.push(VaaAccount::load(encoded_vaa_account_info)?); // IMPORTANT: This line checks that the encoded_vaa has ProcessingStatus::Verified. This check is critical otherwise the program could be tricked into accepting unverified VAAs.
+            check_consistency_level(
+                config,
+                params.min_consistency_level,
+                encoded_vaa_account_info,
+            )?;
+        }
+        let vaas = encoded_vaas
+            .iter()
+            .map(|encoded_vaa| {
+                Ok((
+                    VaaComponents {
+                        verification_level: VerificationLevel::Full,
+                        emitter_address:    encoded_vaa.try_emitter_address()?,
+                        emitter_chain:      encoded_vaa.try_emitter_chain()?,
+                    },
+                    encoded_vaa.try_payload()?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
 
-        // Generate the same message hash (using keccak) that the Guardians used to generate their
-        // signatures. This message hash will be hashed again to produce the digest for
-        // `secp256k1_recover`.
-        let digest = keccak::hash(keccak::hash(vaa.body().as_ref()).as_ref());
-
-        let mut last_guardian_index = None;
-        for sig in vaa.signatures() {
-            // We do not allow for non-increasing guardian signature indices.
-            let index = usize::from(sig.guardian_index());
-            if let Some(last_index) = last_guardian_index {
-                require!(index > last_index, ReceiverError::InvalidGuardianOrder);
+        let mut feed_ids = Vec::with_capacity(updates.len());
+        let mut num_skipped: u32 = 0;
+        for (index, (price_update_account_info, merkle_price_update)) in price_update_account_infos
+            .iter()
+            .zip(updates.iter())
+            .enumerate()
+        {
+            if is_update_skipped(config, merkle_price_update) {
+                num_skipped += 1;
+                continue;
             }
 
-            // Does this guardian index exist in this guardian set?
-            let guardian_pubkey = guardian_keys
-                .get(index)
-                .ok_or_else(|| error!(ReceiverError::InvalidGuardianIndex))?;
+            let (vaa_components, vaa_payload) = match params.vaa_indices.get(index) {
+                Some(vaa_index) => vaas
+                    .get(usize::from(*vaa_index))
+                    .ok_or(ReceiverError::InvalidVaaIndex)?,
+                None => vaas
+                    .iter()
+                    .find(|(vaa_components, vaa_payload)| {
+                        verify_price_update(
+                            config,
+                            vaa_components,
+                            vaa_payload.as_ref(),
+                            merkle_price_update,
+                        )
+                        .is_ok()
+                    })
+                    .ok_or(ReceiverError::NoMatchingVaa)?,
+            };
 
-            // Now verify that the signature agrees with the expected Guardian's pubkey.
-            verify_guardian_signature(&sig, guardian_pubkey, digest.as_ref())?;
+            let mut price_update_account = load_or_create_price_update_account(
+                payer,
+                write_authority,
+                price_update_account_info,
+            )?;
 
-            last_guardian_index = Some(index);
+            post_price_update_from_vaa(
+                config,
+                payer,
+                write_authority,
+                treasury,
+                &mut price_update_account,
+                &mut remaining_accounts,
+                vaa_components,
+                vaa_payload.as_ref(),
+                merkle_price_update,
+            )?;
+            emit_price_update_verified(
+                &price_update_account.price_message,
+                vaa_components.emitter_chain,
+            );
+            feed_ids.push(price_update_account.price_message.feed_id);
+
+            // Remaining accounts aren't serialized by Anchor at the end of the instruction
+            price_update_account.exit(&crate::ID)?;
         }
-        // End borrowed section
 
-        let payer = &ctx.accounts.payer;
-        let write_authority: &Signer<'_> = &ctx.accounts.write_authority;
-        let treasury = &ctx.accounts.treasury;
-        let price_update_account = &mut ctx.accounts.price_update_account;
+        Ok(PostedUpdatesSummary::new(&feed_ids, num_skipped))
+    }
+
+    /// Post a TWAP update using an encoded_vaa account and a MerklePriceUpdate calldata containing a TWAP message.
+    /// The fields of the TWAP message are stored in a `TwapUpdate` account, and the fee is the same as for a price update.
+    /// Like `post_update`, this should be called after the client has already verified the Vaa via the Wormhole contract.
+    /// If the config has fee recipients, their accounts must be passed as remaining accounts (see `set_fee_recipients`).
+    pub fn post_twap_update<'info>(
+        ctx: Context<'_, '_, '_, 'info, PostTwapUpdate<'info>>,
+        params: PostUpdateParams,
+    ) -> Result<()> {
+        process_post_twap_update(ctx, &params, None)
+    }
+
+    /// Same as `post_twap_update`, but the VAA must also have at least `min_consistency_level` as its consistency level.
+    /// This lets a caller require a higher consistency level than `Config::min_consistency_level` for its own updates, a lower value has no effect (see `set_min_consistency_level`).
+    pub fn post_twap_update_with_min_consistency<'info>(
+        ctx: Context<'_, '_, '_, 'info, PostTwapUpdate<'info>>,
+        params: PostUpdateParams,
+        min_consistency_level: u8,
+    ) -> Result<()> {
+        process_post_twap_update(ctx, &params, Some(min_consistency_level))
+    }
+
+    /// Verify a price update against an encoded_vaa account without posting it.
+    /// This instruction doesn't write any account and doesn't charge any fee, so it doesn't take a payer and can be simulated by any account (e.g. by monitoring tools).
+    /// Like `post_update`, this should be called after the client has already verified the Vaa via the Wormhole contract.
+    /// If `min_consistency_level` is set, the VAA must also have at least this consistency level (see `set_min_consistency_level`).
+    pub fn verify_update(
+        ctx: Context<VerifyUpdate>,
+        merkle_price_update: MerklePriceUpdate,
+        min_consistency_level: Option<u8>,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let encoded_vaa = VaaAccount::load(&ctx.accounts.encoded_vaa)?; // IMPORTANT: This line checks that the encoded_vaa has ProcessingStatus::Verified. This check is critical otherwise the program could be tricked into accepting unverified VAAs.
+        check_consistency_level(config, min_consistency_level, &ctx.accounts.encoded_vaa)?;
 
         let vaa_components = VaaComponents {
-            verification_level,
-            emitter_address: vaa.body().emitter_address(),
-            emitter_chain: vaa.body().emitter_chain(),
+            verification_level: VerificationLevel::Full,
+            emitter_address:    encoded_vaa.try_emitter_address()?,
+            emitter_chain:      encoded_vaa.try_emitter_chain()?,
         };
 
-        post_price_update_from_vaa(
+        verify_price_update(
             config,
-            payer,
-            write_authority,
-            treasury,
-            price_update_account,
             &vaa_components,
-            vaa.payload().as_ref(),
-            &params.merkle_price_update,
+            encoded_vaa.try_payload()?.as_ref(),
+            &merkle_price_update,
         )?;
 
         Ok(())
     }
 
-    /// Post a price update using an encoded_vaa account and a MerklePriceUpdate calldata.
-    /// This should be called after the client has already verified the Vaa via the Wormhole contract.
-    /// Check out target_chains/solana/cli/src/main.rs for an example of how to do this.
-    pub fn post_update(ctx: Context<PostUpdate>, params: PostUpdateParams) -> Result<()> {
+    /// Verify several price updates against an encoded_vaa account without posting them, and return their decoded messages in the same order as the updates.
+    /// Like `verify_update`, this instruction doesn't write any account and doesn't charge any fee. It's meant to be simulated (e.g. with `simulateTransaction`) to preview the content of a set of updates before posting them.
+    /// At most `MAX_VIEW_UPDATES` updates can be verified at once, so that their messages fit in the return data.
+    /// If `min_consistency_level` is set, the VAA must also have at least this consistency level (see `set_min_consistency_level`).
+    pub fn verify_updates_view(
+        ctx: Context<VerifyUpdate>,
+        merkle_price_updates: Vec<MerklePriceUpdate>,
+        min_consistency_level: Option<u8>,
+    ) -> Result<Vec<PriceFeedMessage>> {
+        require!(
+            !merkle_price_updates.is_empty(),
+            ReceiverError::NoUpdatesProvided
+        );
+        require!(
+            merkle_price_updates.len() <= MAX_VIEW_UPDATES,
+            ReceiverError::TooManyUpdatesToVerify
+        );
         let config = &ctx.accounts.config;
-        let payer: &Signer<'_> = &ctx.accounts.payer;
-        let write_authority: &Signer<'_> = &ctx.accounts.write_authority;
         let encoded_vaa = VaaAccount::load(&ctx.accounts.encoded_vaa)?; // IMPORTANT: This line checks that the encoded_vaa has ProcessingStatus::Verified. This check is critical otherwise the program could be tricked into accepting unverified VAAs.
-        let treasury: &AccountInfo<'_> = &ctx.accounts.treasury;
-        let price_update_account: &mut Account<'_, PriceUpdateV2> =
-            &mut ctx.accounts.price_update_account;
+        check_consistency_level(config, min_consistency_level, &ctx.accounts.encoded_vaa)?;
 
         let vaa_components = VaaComponents {
             verification_level: VerificationLevel::Full,
             emitter_address:    encoded_vaa.try_emitter_address()?,
             emitter_chain:      encoded_vaa.try_emitter_chain()?,
         };
+        let vaa_payload = encoded_vaa.try_payload()?;
 
-        post_price_update_from_vaa(
-            config,
-            payer,
-            write_authority,
-            treasury,
-            price_update_account,
-            &vaa_components,
-            encoded_vaa.try_payload()?.as_ref(),
-            &params.merkle_price_update,
-        )?;
+        merkle_price_updates
+            .iter()
+            .map(|merkle_price_update| {
+                verify_price_update(
+                    config,
+                    &vaa_components,
+                    vaa_payload.as_ref(),
+                    merkle_price_update,
+                )
+            })
+            .collect()
+    }
 
-        Ok(())
+    /// Close a price update account and send its lamports to its write authority.
+    /// The account can only be closed `min_price_update_lifetime_slots` slots after its last update (see `set_min_price_update_lifetime`).
+    pub fn reclaim_rent(ctx: Context<ReclaimRent>) -> Result<()> {
+        check_min_price_update_lifetime(&ctx.accounts.config, &ctx.accounts.price_update_account)
     }
 
-    pub fn reclaim_rent(_ctx: Context<ReclaimRent>) -> Result<()> {
-        Ok(())
+    /// Close a price update account and send its lamports to `recipient`. Only the write authority of the account can close it.
+    /// The account can only be closed `min_price_update_lifetime_slots` slots after its last update (see `set_min_price_update_lifetime`), otherwise the instruction fails with `PriceUpdateTooRecent`.
+    pub fn close_price_update(ctx: Context<ClosePriceUpdate>) -> Result<()> {
+        check_min_price_update_lifetime(&ctx.accounts.config, &ctx.accounts.price_update_account)
     }
 }
 
@@ -277,6 +701,27 @@ pub struct Governance<'info> {
     pub config: Account<'info, Config>,
 }
 
+#[derive(Accounts)]
+#[instruction(len: u32)]
+pub struct ResizeConfig<'info> {
+    #[account(mut, constraint =
+        payer.key() == config.governance_authority @
+        ReceiverError::GovernanceAuthorityMismatch
+    )]
+    pub payer:          Signer<'info>,
+    #[account(mut, seeds = [CONFIG_SEED.as_ref()], bump, realloc = len as usize, realloc::payer = payer, realloc::zero = false)]
+    pub config:         Account<'info, Config>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    pub payer:  Signer<'info>,
+    /// CHECK: A legacy config account may not deserialize as a `Config`, `migrate_config` deserializes it as a `LegacyConfig` and checks the governance authority.
+    #[account(mut, seeds = [CONFIG_SEED.as_ref()], bump, owner = crate::ID)]
+    pub config: AccountInfo<'info>,
+}
+
 #[derive(Accounts)]
 pub struct AcceptGovernanceAuthorityTransfer<'info> {
     #[account(constraint =
@@ -293,7 +738,7 @@ pub struct AcceptGovernanceAuthorityTransfer<'info> {
 pub struct PostUpdate<'info> {
     #[account(mut)]
     pub payer:                Signer<'info>,
-    #[account(owner = config.wormhole @ ReceiverError::WrongVaaOwner)]
+    #[account(constraint = config.is_approved_post_vaa_program(encoded_vaa.owner) @ ReceiverError::UnapprovedPostVaaProgram)]
     /// CHECK: We aren't deserializing the VAA here but later with VaaAccount::load, which is the recommended way
     pub encoded_vaa:          AccountInfo<'info>,
     #[account(seeds = [CONFIG_SEED.as_ref()], bump)]
@@ -332,19 +777,155 @@ pub struct PostUpdateAtomic<'info> {
     pub write_authority:      Signer<'info>,
 }
 
+#[derive(Accounts)]
+#[instruction(params: PostUpdatesFromAccountDataParams)]
+pub struct PostUpdatesFromAccountData<'info> {
+    #[account(mut)]
+    pub payer:                   Signer<'info>,
+    #[account(constraint = config.is_approved_post_vaa_program(encoded_vaa.owner) @ ReceiverError::UnapprovedPostVaaProgram)]
+    /// CHECK: We aren't deserializing the VAA here but later with VaaAccount::load, which is the recommended way
+    pub encoded_vaa:             AccountInfo<'info>,
+    /// CHECK: This account can be owned by anyone. Its content is deserialized as an AccumulatorUpdateData and every update is checked against the root of encoded_vaa.
+    pub accumulator_update_data: AccountInfo<'info>,
+    #[account(seeds = [CONFIG_SEED.as_ref()], bump)]
+    pub config:                  Account<'info, Config>,
+    /// CHECK: This is just a PDA controlled by the program. There is currently no way to withdraw funds from it.
+    #[account(mut, seeds = [TREASURY_SEED.as_ref(), &[params.treasury_id]], bump)]
+    pub treasury:                AccountInfo<'info>,
+    pub system_program:          Program<'info, System>,
+    pub write_authority:         Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: PostUpdatesFromVaasParams)]
+pub struct PostUpdatesFromVaas<'info> {
+    #[account(mut)]
+    pub payer:           Signer<'info>,
+    #[account(seeds = [CONFIG_SEED.as_ref()], bump)]
+    pub config:          Account<'info, Config>,
+    /// CHECK: This is just a PDA controlled by the program. There is currently no way to withdraw funds from it.
+    #[account(mut, seeds = [TREASURY_SEED.as_ref(), &[params.treasury_id]], bump)]
+    pub treasury:        AccountInfo<'info>,
+    pub system_program:  Program<'info, System>,
+    pub write_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: PostUpdateParams)]
+pub struct PostTwapUpdate<'info> {
+    #[account(mut)]
+    pub payer:               Signer<'info>,
+    #[account(constraint = config.is_approved_post_vaa_program(encoded_vaa.owner) @ ReceiverError::UnapprovedPostVaaProgram)]
+    /// CHECK: We aren't deserializing the VAA here but later with VaaAccount::load, which is the recommended way
+    pub encoded_vaa:         AccountInfo<'info>,
+    #[account(seeds = [CONFIG_SEED.as_ref()], bump)]
+    pub config:              Account<'info, Config>,
+    /// CHECK: This is just a PDA controlled by the program. There is currently no way to withdraw funds from it.
+    #[account(mut, seeds = [TREASURY_SEED.as_ref(), &[params.treasury_id]], bump)]
+    pub treasury:            AccountInfo<'info>,
+    /// The constraint is such that either the twap_update_account is uninitialized or the write_authority is the write_authority.
+    /// Pubkey::default() is the SystemProgram on Solana and it can't sign so it's impossible that twap_update_account.write_authority == Pubkey::default() once the account is initialized
+    #[account(init_if_needed, constraint = twap_update_account.write_authority == Pubkey::default() || twap_update_account.write_authority == write_authority.key() @ ReceiverError::WrongWriteAuthority, payer = payer, space = TwapUpdate::LEN)]
+    pub twap_update_account: Account<'info, TwapUpdate>,
+    pub system_program:      Program<'info, System>,
+    pub write_authority:     Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyUpdate<'info> {
+    #[account(constraint = config.is_approved_post_vaa_program(encoded_vaa.owner) @ ReceiverError::UnapprovedPostVaaProgram)]
+    /// CHECK: We aren't deserializing the VAA here but later with VaaAccount::load, which is the recommended way
+    pub encoded_vaa: AccountInfo<'info>,
+    #[account(seeds = [CONFIG_SEED.as_ref()], bump)]
+    pub config:      Account<'info, Config>,
+}
+
 #[derive(Accounts)]
 pub struct ReclaimRent<'info> {
     #[account(mut)]
     pub payer:                Signer<'info>,
     #[account(mut, close = payer, constraint = price_update_account.write_authority == payer.key() @ ReceiverError::WrongWriteAuthority)]
     pub price_update_account: Account<'info, PriceUpdateV2>,
+    #[account(seeds = [CONFIG_SEED.as_ref()], bump)]
+    pub config:               Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct ClosePriceUpdate<'info> {
+    pub write_authority:      Signer<'info>,
+    #[account(mut, close = recipient, constraint = price_update_account.write_authority == write_authority.key() @ ReceiverError::WrongWriteAuthority)]
+    pub price_update_account: Account<'info, PriceUpdateV2>,
+    /// CHECK: This account only receives the lamports of the closed account, the write authority chooses it
+    #[account(mut)]
+    pub recipient:            AccountInfo<'info>,
+    #[account(seeds = [CONFIG_SEED.as_ref()], bump)]
+    pub config:               Account<'info, Config>,
 }
 
+/// Emitted for every update posted by `post_updates_from_account_data` in `EventMode::Verbose`.
+#[event]
+pub struct PriceUpdatePosted {
+    pub price_update_account: Pubkey,
+    pub feed_id:              [u8; 32],
+    pub publish_time:         i64,
+}
+
+/// Emitted once per batch by `post_updates_from_account_data` in `EventMode::Summary`.
+/// The feed ids are in the same order as the price update accounts of the instruction.
+#[event]
+pub struct PriceUpdatesPosted {
+    pub feed_ids: Vec<[u8; 32]>,
+}
+
+/// Emitted for the update posted by `post_update`, `post_update_atomic` and for every update posted by `post_updates_from_vaas`, so that off-chain consumers don't need to parse the logs of the program.
+/// `post_updates_from_account_data` emits `PriceUpdatePosted` or `PriceUpdatesPosted` instead, depending on its `EventMode`.
+#[event]
+pub struct PriceUpdateVerified {
+    pub feed_id:       [u8; 32],
+    pub price:         i64,
+    pub conf:          u64,
+    pub publish_time:  i64,
+    /// The chain of the emitter of the VAA, to tell apart the updates of different data sources.
+    pub emitter_chain: u16,
+}
+
+/// Emitted by `post_twap_update` for the TWAP update it posts.
+#[event]
+pub struct TwapUpdateVerified {
+    pub feed_id:          [u8; 32],
+    pub cumulative_price: i128,
+    pub cumulative_conf:  u128,
+    pub publish_time:     i64,
+    pub emitter_chain:    u16,
+}
+
+/// Emitted by every governance instruction with the config before and after the instruction, so that changes to the config can be audited from the transaction logs.
+#[event]
+pub struct ConfigUpdated {
+    pub old_config: Config,
+    pub new_config: Config,
+}
+
+/// Emitted by `post_updates_with_tag`, with the tag passed by the caller.
+/// The feed ids are in the same order as the price update accounts of the instruction.
+#[event]
+pub struct TaggedPriceUpdatesPosted {
+    pub tag:         [u8; 32],
+    pub feed_ids:    Vec<[u8; 32]>,
+    /// The number of updates that were skipped because their feed isn't in `Config::allowed_feed_ids`.
+    pub num_skipped: u32,
+}
+
+/// The receiver doesn't cache guardian sets: the guardian set account of the Wormhole contract is read and its expiration is checked against the clock on every call.
+/// Guardian set rotations on the Wormhole contract are therefore taken into account immediately, without any action on the receiver.
+/// The guardian set is considered expired `expiration_skew_seconds` before its expiration time (see `set_guardian_set_expiration_skew`).
 fn deserialize_guardian_set_checked(
     account_info: &AccountInfo<'_>,
     wormhole: &Pubkey,
+    expiration_skew_seconds: u32,
 ) -> Result<AccountVariant<GuardianSet>> {
     let mut guardian_set_data: &[u8] = &account_info.try_borrow_data()?;
+    check_guardian_set_keys_len(guardian_set_data)?;
     let guardian_set = AccountVariant::<GuardianSet>::try_deserialize(&mut guardian_set_data)?;
 
     let expected_address = Pubkey::find_program_address(
@@ -361,7 +942,12 @@ fn deserialize_guardian_set_checked(
         ReceiverError::InvalidGuardianSetPda
     );
 
-    let timestamp = Clock::get().map(Into::into)?;
+    // Checking the expiration against a clock that is ahead by the skew can only reject more guardian sets, never accept an expired one.
+    let mut clock = Clock::get()?;
+    clock.unix_timestamp = clock
+        .unix_timestamp
+        .saturating_add(i64::from(expiration_skew_seconds));
+    let timestamp = clock.into();
     require!(
         guardian_set.inner().is_active(&timestamp),
         ReceiverError::GuardianSetExpired
@@ -370,29 +956,743 @@ fn deserialize_guardian_set_checked(
     Ok(guardian_set)
 }
 
+/// The maximum number of guardians in a guardian set, this is the size of the Wormhole guardian network.
+pub const MAX_GUARDIANS: usize = 19;
+/// The offset of the length of the keys vector in a guardian set account: after the discriminator and the index.
+const GUARDIAN_SET_KEYS_LEN_OFFSET: usize = 8 + 4;
+
+/// Check the number of keys of a serialized guardian set before deserializing it, so that a corrupt account can't make the program allocate a huge vector.
+/// Accounts too short to contain the length are left to the deserialization to reject.
+fn check_guardian_set_keys_len(guardian_set_data: &[u8]) -> Result<()> {
+    if let Some(keys_len) = guardian_set_data
+        .get(GUARDIAN_SET_KEYS_LEN_OFFSET..GUARDIAN_SET_KEYS_LEN_OFFSET + 4)
+        .and_then(|keys_len_bytes| keys_len_bytes.try_into().ok())
+        .map(u32::from_le_bytes)
+    {
+        require!(
+            keys_len as usize <= MAX_GUARDIANS,
+            ReceiverError::TooManyGuardians
+        );
+    }
+    Ok(())
+}
+
+/// Load a price update account that was passed as a remaining account, creating it if it doesn't exist yet.
+/// This applies the same constraints as the `init_if_needed` price_update_account of `PostUpdate`.
+fn load_or_create_price_update_account<'info>(
+    payer: &Signer<'info>,
+    write_authority: &Signer<'info>,
+    account_info: &AccountInfo<'info>,
+) -> Result<Account<'info, PriceUpdateV2>> {
+    if account_info.owner == &System::id() {
+        create_program_account(payer, account_info, PriceUpdateV2::LEN, &[])?;
+        Account::try_from_unchecked(account_info)
+    } else {
+        let price_update_account = Account::<PriceUpdateV2>::try_from(account_info)?;
+        require!(
+            price_update_account.write_authority == write_authority.key(),
+            ReceiverError::WrongWriteAuthority
+        );
+        Ok(price_update_account)
+    }
+}
+
+/// Create an account of `space` bytes owned by this program at the address of `account_info`, which must sign the transaction or be a PDA of this program derived from `signer_seeds`.
+/// `system_instruction::create_account` fails if the address already holds lamports, so anyone could prevent the creation of the account by sending lamports to its address. Like Anchor's `init`, this only transfers the lamports missing for the account to be rent exempt and then allocates and assigns the account.
+fn create_program_account<'info>(
+    payer: &Signer<'info>,
+    account_info: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let missing_lamports = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account_info.lamports());
+    if missing_lamports > 0 {
+        anchor_lang::solana_program::program::invoke(
+            &system_instruction::transfer(payer.key, account_info.key, missing_lamports),
+            &[payer.to_account_info(), account_info.clone()],
+        )?;
+    }
+    anchor_lang::solana_program::program::invoke_signed(
+        &system_instruction::allocate(account_info.key, space as u64),
+        &[account_info.clone()],
+        signer_seeds,
+    )?;
+    anchor_lang::solana_program::program::invoke_signed(
+        &system_instruction::assign(account_info.key, &crate::ID),
+        &[account_info.clone()],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Verify and post the update of `post_update_atomic`, rejecting VAAs whose consistency level is lower than `Config::min_consistency_level` or `requested_min_consistency_level` (see `min_consistency_level`).
+fn process_post_update_atomic<'info>(
+    ctx: Context<'_, '_, '_, 'info, PostUpdateAtomic<'info>>,
+    params: &PostUpdateAtomicParams,
+    requested_min_consistency_level: Option<u8>,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let guardian_set = deserialize_guardian_set_checked(
+        &ctx.accounts.guardian_set,
+        &config.wormhole,
+        config.guardian_set_expiration_skew_seconds,
+    )?;
+
+    // This section is borrowed from https://github.com/wormhole-foundation/wormhole/blob/wen/solana-rewrite/solana/programs/core-bridge/src/processor/parse_and_verify_vaa/verify_encoded_vaa_v1.rs#L59
+    let vaa = Vaa::parse(&params.vaa).map_err(|_| ReceiverError::DeserializeVaaFailed)?;
+    // Must be V1.
+    require_eq!(vaa.version(), 1, ReceiverError::InvalidVaaVersion);
+    require!(
+        vaa.body().consistency_level()
+            >= min_consistency_level(config, requested_min_consistency_level),
+        ReceiverError::InsufficientConsistencyLevel
+    );
+
+    // Make sure the encoded guardian set index agrees with the guardian set account's index.
+    let guardian_set = guardian_set.inner();
+    require_eq!(
+        vaa.guardian_set_index(),
+        guardian_set.index,
+        ReceiverError::GuardianSetMismatch
+    );
+
+    let guardian_keys = &guardian_set.keys;
+    // If the governance has pinned a guardian set, don't trust the guardian set account of the Wormhole contract alone.
+    if let Some(pinned_guardian_set_hash) = config.pinned_guardian_set_hash {
+        require!(
+            hash_guardian_keys(guardian_keys) == pinned_guardian_set_hash,
+            ReceiverError::PinnedGuardianSetMismatch
+        );
+    }
+    let quorum = quorum(guardian_keys.len());
+    require_gte!(
+        vaa.signature_count(),
+        config.minimum_signatures,
+        ReceiverError::InsufficientGuardianSignatures
+    );
+    let verification_level = if usize::from(vaa.signature_count()) >= quorum {
+        VerificationLevel::Full
+    } else {
+        VerificationLevel::Partial {
+            num_signatures: vaa.signature_count(),
+        }
+    };
+
+    // Generate the same message hash (using keccak) that the Guardians used to generate their
+    // signatures. This message hash will be hashed again to produce the digest for
+    // `secp256k1_recover`.
+    let digest = keccak::hash(keccak::hash(vaa.body().as_ref()).as_ref());
+
+    let mut last_guardian_index = None;
+    for sig in vaa.signatures() {
+        // We do not allow for non-increasing guardian signature indices.
+        let index = usize::from(sig.guardian_index());
+        if let Some(last_index) = last_guardian_index {
+            require!(index > last_index, ReceiverError::InvalidGuardianOrder);
+        }
+
+        // Does this guardian index exist in this guardian set?
+        let guardian_pubkey = guardian_keys
+            .get(index)
+            .ok_or_else(|| error!(ReceiverError::InvalidGuardianIndex))?;
+
+        // Now verify that the signature agrees with the expected Guardian's pubkey.
+        verify_guardian_signature(&sig, guardian_pubkey, digest.as_ref())?;
+
+        last_guardian_index = Some(index);
+    }
+    // End borrowed section
+
+    let payer = &ctx.accounts.payer;
+    let write_authority: &Signer<'_> = &ctx.accounts.write_authority;
+    let treasury = &ctx.accounts.treasury;
+    let price_update_account = &mut ctx.accounts.price_update_account;
+
+    let vaa_components = VaaComponents {
+        verification_level,
+        emitter_address: vaa.body().emitter_address(),
+        emitter_chain: vaa.body().emitter_chain(),
+    };
+
+    post_price_update_from_vaa(
+        config,
+        payer,
+        write_authority,
+        treasury,
+        price_update_account,
+        &mut split_remaining_accounts(config, ctx.remaining_accounts, 0)?.1,
+        &vaa_components,
+        vaa.payload().as_ref(),
+        &params.merkle_price_update,
+    )?;
+    emit_price_update_verified(
+        &price_update_account.price_message,
+        vaa_components.emitter_chain,
+    );
+
+    Ok(())
+}
+
+/// Post the update of `post_update`, rejecting VAAs whose consistency level is lower than `Config::min_consistency_level` or `requested_min_consistency_level` (see `min_consistency_level`).
+fn process_post_update<'info>(
+    ctx: Context<'_, '_, '_, 'info, PostUpdate<'info>>,
+    params: &PostUpdateParams,
+    requested_min_consistency_level: Option<u8>,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let payer: &Signer<'_> = &ctx.accounts.payer;
+    let write_authority: &Signer<'_> = &ctx.accounts.write_authority;
+    let encoded_vaa = VaaAccount::load(&ctx.accounts.encoded_vaa)?; // IMPORTANT: This line checks that the encoded_vaa has ProcessingStatus::Verified. This check is critical otherwise the program could be tricked into accepting unverified VAAs.
+    check_consistency_level(
+        config,
+        requested_min_consistency_level,
+        &ctx.accounts.encoded_vaa,
+    )?;
+    let treasury: &AccountInfo<'_> = &ctx.accounts.treasury;
+    let price_update_account: &mut Account<'_, PriceUpdateV2> =
+        &mut ctx.accounts.price_update_account;
+
+    let vaa_components = VaaComponents {
+        verification_level: VerificationLevel::Full,
+        emitter_address:    encoded_vaa.try_emitter_address()?,
+        emitter_chain:      encoded_vaa.try_emitter_chain()?,
+    };
+
+    post_price_update_from_vaa(
+        config,
+        payer,
+        write_authority,
+        treasury,
+        price_update_account,
+        &mut split_remaining_accounts(config, ctx.remaining_accounts, 0)?.1,
+        &vaa_components,
+        encoded_vaa.try_payload()?.as_ref(),
+        &params.merkle_price_update,
+    )?;
+    emit_price_update_verified(
+        &price_update_account.price_message,
+        vaa_components.emitter_chain,
+    );
+
+    Ok(())
+}
+
+/// Post the update of `post_twap_update`, rejecting VAAs whose consistency level is lower than `Config::min_consistency_level` or `requested_min_consistency_level` (see `min_consistency_level`).
+fn process_post_twap_update<'info>(
+    ctx: Context<'_, '_, '_, 'info, PostTwapUpdate<'info>>,
+    params: &PostUpdateParams,
+    requested_min_consistency_level: Option<u8>,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let payer: &Signer<'_> = &ctx.accounts.payer;
+    let write_authority: &Signer<'_> = &ctx.accounts.write_authority;
+    let encoded_vaa = VaaAccount::load(&ctx.accounts.encoded_vaa)?; // IMPORTANT: This line checks that the encoded_vaa has ProcessingStatus::Verified. This check is critical otherwise the program could be tricked into accepting unverified VAAs.
+    check_consistency_level(
+        config,
+        requested_min_consistency_level,
+        &ctx.accounts.encoded_vaa,
+    )?;
+    let treasury: &AccountInfo<'_> = &ctx.accounts.treasury;
+
+    let vaa_components = VaaComponents {
+        verification_level: VerificationLevel::Full,
+        emitter_address:    encoded_vaa.try_emitter_address()?,
+        emitter_chain:      encoded_vaa.try_emitter_chain()?,
+    };
+
+    pay_fee(config, payer, treasury, ctx.remaining_accounts)?;
+
+    let twap_message = verify_twap_update(
+        config,
+        &vaa_components,
+        encoded_vaa.try_payload()?.as_ref(),
+        &params.merkle_price_update,
+    )?;
+
+    let twap_update_account = &mut ctx.accounts.twap_update_account;
+    twap_update_account.write_authority = write_authority.key();
+    twap_update_account.verification_level = vaa_components.verification_level;
+    twap_update_account.feed_id = twap_message.feed_id;
+    twap_update_account.cumulative_price = twap_message.cumulative_price;
+    twap_update_account.cumulative_conf = twap_message.cumulative_conf;
+    twap_update_account.num_down_slots = twap_message.num_down_slots;
+    twap_update_account.exponent = twap_message.exponent;
+    twap_update_account.publish_time = twap_message.publish_time;
+    twap_update_account.prev_publish_time = twap_message.prev_publish_time;
+    twap_update_account.publish_slot = twap_message.publish_slot;
+    twap_update_account.posted_slot = Clock::get()?.slot;
+
+    emit!(TwapUpdateVerified {
+        feed_id:          twap_message.feed_id,
+        cumulative_price: twap_message.cumulative_price,
+        cumulative_conf:  twap_message.cumulative_conf,
+        publish_time:     twap_message.publish_time,
+        emitter_chain:    vaa_components.emitter_chain,
+    });
+
+    Ok(())
+}
+
+/// Post the updates of `ctx.accounts.accumulator_update_data` (see `post_updates_from_account_data`) and return the feed ids of the posted updates, in the same order as the updates, and the number of skipped updates.
+fn process_post_updates_from_account_data<'info>(
+    ctx: Context<'_, '_, '_, 'info, PostUpdatesFromAccountData<'info>>,
+    params: &PostUpdatesFromAccountDataParams,
+) -> Result<(Vec<[u8; 32]>, u32)> {
+    let config = &ctx.accounts.config;
+    let payer: &Signer<'_> = &ctx.accounts.payer;
+    let write_authority: &Signer<'_> = &ctx.accounts.write_authority;
+    let encoded_vaa = VaaAccount::load(&ctx.accounts.encoded_vaa)?; // IMPORTANT: This line checks that the encoded_vaa has ProcessingStatus::Verified. This check is critical otherwise the program could be tricked into accepting unverified VAAs.
+    check_consistency_level(
+        config,
+        params.min_consistency_level,
+        &ctx.accounts.encoded_vaa,
+    )?;
+    let treasury: &AccountInfo<'_> = &ctx.accounts.treasury;
+
+    let accumulator_update_data_bytes = ctx.accounts.accumulator_update_data.try_borrow_data()?;
+    check_accumulator_update_data_header(&accumulator_update_data_bytes)?;
+    let accumulator_update_data =
+        AccumulatorUpdateData::try_from_slice(&accumulator_update_data_bytes).map_err(|err| {
+            // The versions are also checked after deserializing, so if deserializing succeeds the error is about them.
+            match from_slice_with_offset::<byteorder::BE, AccumulatorUpdateData>(
+                &accumulator_update_data_bytes,
+            ) {
+                Err((deserializer_error, offset)) => msg!(
+                    "Failed to deserialize the accumulator update data at byte offset {} of {}: {}",
+                    offset,
+                    accumulator_update_data_bytes.len(),
+                    deserializer_error
+                ),
+                Ok(_) => msg!("Invalid accumulator update data: {}", err),
+            }
+            ReceiverError::DeserializeAccumulatorUpdateDataFailed
+        })?;
+    let (vaa, updates) = match accumulator_update_data.proof {
+        Proof::WormholeMerkle { vaa, updates } => (vaa, updates),
+    };
+    require!(!updates.is_empty(), ReceiverError::NoUpdatesProvided);
+    if let Some(expected_updates) = params.expected_updates {
+        let expected_updates = usize::from(expected_updates);
+        require!(
+            if params.require_exact_count {
+                updates.len() == expected_updates
+            } else {
+                updates.len() <= expected_updates
+            },
+            ReceiverError::UnexpectedNumberOfUpdates
+        );
+    }
+
+    // Anyone can write the accumulator update data, so the root must come from the VAA that was verified by the Wormhole contract.
+    let vaa_payload = encoded_vaa.try_payload()?;
+    let vaa = Vaa::parse(vaa.as_ref()).map_err(|_| ReceiverError::DeserializeVaaFailed)?;
+    require!(
+        vaa.payload().as_ref() == vaa_payload.as_ref(),
+        ReceiverError::MismatchedVaa
+    );
+
+    let num_price_history_accounts = if params.write_price_history {
+        updates.len()
+    } else {
+        0
+    };
+    let (update_account_infos, mut remaining_accounts) = split_remaining_accounts(
+        config,
+        ctx.remaining_accounts,
+        updates.len() + num_price_history_accounts,
+    )?;
+    let (price_update_account_infos, price_history_account_infos) =
+        update_account_infos.split_at(updates.len());
+
+    let vaa_components = VaaComponents {
+        verification_level: VerificationLevel::Full,
+        emitter_address:    encoded_vaa.try_emitter_address()?,
+        emitter_chain:      encoded_vaa.try_emitter_chain()?,
+    };
+
+    let mut feed_ids = Vec::with_capacity(updates.len());
+    let mut num_skipped: u32 = 0;
+    for (index, (price_update_account_info, merkle_price_update)) in price_update_account_infos
+        .iter()
+        .zip(updates.iter())
+        .enumerate()
+    {
+        if is_update_skipped(config, merkle_price_update) {
+            num_skipped += 1;
+            continue;
+        }
+
+        let mut price_update_account =
+            load_or_create_price_update_account(payer, write_authority, price_update_account_info)?;
+
+        post_price_update_from_vaa(
+            config,
+            payer,
+            write_authority,
+            treasury,
+            &mut price_update_account,
+            &mut remaining_accounts,
+            &vaa_components,
+            vaa_payload.as_ref(),
+            merkle_price_update,
+        )?;
+
+        if params.event_mode == EventMode::Verbose {
+            emit!(PriceUpdatePosted {
+                price_update_account: price_update_account.key(),
+                feed_id:              price_update_account.price_message.feed_id,
+                publish_time:         price_update_account.price_message.publish_time,
+            });
+        }
+        feed_ids.push(price_update_account.price_message.feed_id);
+
+        if let Some(price_history_account_info) = price_history_account_infos.get(index) {
+            let mut price_history_account = load_or_create_price_history_account(
+                payer,
+                write_authority,
+                price_history_account_info,
+            )?;
+            if price_history_account.updates.is_empty() {
+                price_history_account.feed_id = price_update_account.price_message.feed_id;
+            }
+            require!(
+                price_history_account.feed_id == price_update_account.price_message.feed_id,
+                ReceiverError::PriceHistoryFeedIdMismatch
+            );
+            price_history_account.push(price_update_account.price_message);
+            price_history_account.exit(&crate::ID)?;
+        }
+
+        // Remaining accounts aren't serialized by Anchor at the end of the instruction
+        price_update_account.exit(&crate::ID)?;
+    }
+
+    if params.event_mode == EventMode::Summary {
+        emit!(PriceUpdatesPosted {
+            feed_ids: feed_ids.clone(),
+        });
+    }
+
+    Ok((feed_ids, num_skipped))
+}
+
+/// Whether the batch instructions skip `merkle_price_update` because its feed isn't in `config.allowed_feed_ids`.
+/// Skipped updates aren't verified since nothing is written for them. Updates whose message can't be deserialized aren't skipped, so that they fail when they're verified.
+fn is_update_skipped(config: &Config, merkle_price_update: &MerklePriceUpdate) -> bool {
+    if config.allowed_feed_ids.is_empty() {
+        return false;
+    }
+    from_slice_with_offset::<byteorder::BE, Message>(merkle_price_update.message.as_ref())
+        .map_or(false, |message| !config.is_feed_allowed(&message.feed_id()))
+}
+
+/// The major version of the accumulator update data supported by the receiver.
+const ACCUMULATOR_UPDATE_MAJOR_VERSION: u8 = 1;
+/// The serialized variant index of `Proof::WormholeMerkle`, the only proof type supported by the receiver.
+const WORMHOLE_MERKLE_PROOF_TYPE: u8 = 0;
+
+/// Check the magic, the major version and the proof type of serialized accumulator update data before deserializing it, so that data in another format (e.g. a newer Hermes response) fails with a precise error.
+/// The data starts with the magic, the major and minor versions, and the trailing bytes prefixed with their length, followed by the proof type.
+fn check_accumulator_update_data_header(accumulator_update_data_bytes: &[u8]) -> Result<()> {
+    require!(
+        accumulator_update_data_bytes.starts_with(PYTHNET_ACCUMULATOR_UPDATE_MAGIC)
+            && accumulator_update_data_bytes.get(4) == Some(&ACCUMULATOR_UPDATE_MAJOR_VERSION),
+        ReceiverError::InvalidAccumulatorUpdateData
+    );
+    let trailing_len = accumulator_update_data_bytes
+        .get(6)
+        .ok_or(ReceiverError::InvalidAccumulatorUpdateData)?;
+    let proof_type = accumulator_update_data_bytes
+        .get(7 + usize::from(*trailing_len))
+        .ok_or(ReceiverError::InvalidAccumulatorUpdateData)?;
+    require_eq!(
+        *proof_type,
+        WORMHOLE_MERKLE_PROOF_TYPE,
+        ReceiverError::UnsupportedProofType
+    );
+    Ok(())
+}
+
+/// Load a price history account that was passed as a remaining account, creating it if it doesn't exist yet.
+/// Like for price update accounts, only the write authority of the price history account can add updates to it.
+fn load_or_create_price_history_account<'info>(
+    payer: &Signer<'info>,
+    write_authority: &Signer<'info>,
+    account_info: &AccountInfo<'info>,
+) -> Result<Account<'info, PriceHistory>> {
+    if account_info.owner == &System::id() {
+        create_program_account(payer, account_info, PriceHistory::LEN, &[])?;
+        let mut price_history_account = Account::<PriceHistory>::try_from_unchecked(account_info)?;
+        price_history_account.write_authority = write_authority.key();
+        Ok(price_history_account)
+    } else {
+        let price_history_account = Account::<PriceHistory>::try_from(account_info)?;
+        require!(
+            price_history_account.write_authority == write_authority.key(),
+            ReceiverError::WrongWriteAuthority
+        );
+        Ok(price_history_account)
+    }
+}
+
+/// Load the `PriceChangeBaseline` account of `feed_id` that was passed as a remaining account, creating it if it doesn't exist yet.
+/// The account is a PDA of the feed, so there's a single baseline per feed whatever the price update accounts the feed is posted to.
+fn load_or_create_price_change_baseline_account<'info>(
+    payer: &Signer<'info>,
+    account_info: Option<&AccountInfo<'info>>,
+    feed_id: &FeedId,
+) -> Result<Account<'info, PriceChangeBaseline>> {
+    let (address, bump) = Pubkey::find_program_address(
+        &[PRICE_CHANGE_BASELINE_SEED.as_ref(), feed_id.as_ref()],
+        &crate::ID,
+    );
+    let account_info = account_info
+        .filter(|account_info| account_info.key() == address)
+        .ok_or(ReceiverError::WrongPriceChangeBaselineAccount)?;
+    if account_info.owner == &System::id() {
+        create_program_account(
+            payer,
+            account_info,
+            PriceChangeBaseline::LEN,
+            &[&[
+                PRICE_CHANGE_BASELINE_SEED.as_ref(),
+                feed_id.as_ref(),
+                &[bump],
+            ]],
+        )?;
+        Account::try_from_unchecked(account_info)
+    } else {
+        Account::try_from(account_info)
+    }
+}
+
+/// The minimum consistency level of the VAAs of an instruction: the higher of `config.min_consistency_level` and the level requested by the caller, so that callers can only raise it.
+fn min_consistency_level(config: &Config, requested_min_consistency_level: Option<u8>) -> u8 {
+    config
+        .min_consistency_level
+        .max(requested_min_consistency_level.unwrap_or(0))
+}
+
+/// Check the consistency level of the VAA of an encoded VAA account against `config.min_consistency_level` and `requested_min_consistency_level` (see `min_consistency_level`).
+/// `VaaAccount` doesn't expose the consistency level, so the VAA is parsed from the account, which is only done if there is a minimum consistency level.
+fn check_consistency_level(
+    config: &Config,
+    requested_min_consistency_level: Option<u8>,
+    encoded_vaa_account_info: &AccountInfo<'_>,
+) -> Result<()> {
+    let min_consistency_level = min_consistency_level(config, requested_min_consistency_level);
+    if min_consistency_level == 0 {
+        return Ok(());
+    }
+    let encoded_vaa =
+        EncodedVaa::try_deserialize(&mut &encoded_vaa_account_info.try_borrow_data()?[..])?;
+    let vaa = Vaa::parse(&encoded_vaa.buf).map_err(|_| ReceiverError::DeserializeVaaFailed)?;
+    require!(
+        vaa.body().consistency_level() >= min_consistency_level,
+        ReceiverError::InsufficientConsistencyLevel
+    );
+    Ok(())
+}
+
+/// Price update accounts can only be closed `config.min_price_update_lifetime_slots` slots after their last update, so that they aren't closed in the middle of a transaction of a consumer that reads them.
+fn check_min_price_update_lifetime(
+    config: &Config,
+    price_update_account: &PriceUpdateV2,
+) -> Result<()> {
+    require!(
+        Clock::get()?.slot
+            >= price_update_account
+                .posted_slot
+                .saturating_add(config.min_price_update_lifetime_slots),
+        ReceiverError::PriceUpdateTooRecent
+    );
+    Ok(())
+}
+
 struct VaaComponents {
     verification_level: VerificationLevel,
     emitter_address:    [u8; 32],
     emitter_chain:      u16,
 }
 
+/// Verify a price update against the root in `vaa_payload` and write it to `price_update_account`.
+/// The payer is charged exactly `single_update_fee_in_lamports` for every call (plus the rent of the treasury the first time it's used), so posting several updates costs the fee times the number of updates.
+/// The fee is moved with explicit transfers, so the payer can't overpay and any lamports above the fee stay with the payer.
+/// The fee is transferred before the update is verified, but if the verification fails the instruction errors and the whole transaction is reverted, so the payer is never charged for an update that wasn't posted.
 fn post_price_update_from_vaa<'info>(
     config: &Account<'info, Config>,
     payer: &Signer<'info>,
     write_authority: &Signer<'info>,
     treasury: &AccountInfo<'info>,
     price_update_account: &mut Account<'_, PriceUpdateV2>,
+    remaining_accounts: &mut PostUpdateRemainingAccounts<'_, 'info>,
     vaa_components: &VaaComponents,
     vaa_payload: &[u8],
     price_update: &MerklePriceUpdate,
 ) -> Result<()> {
-    let amount_to_pay = if treasury.lamports() == 0 {
+    pay_fee(
+        config,
+        payer,
+        treasury,
+        remaining_accounts.fee_recipient_accounts,
+    )?;
+
+    let price_feed_message =
+        verify_price_update(config, vaa_components, vaa_payload, price_update)?;
+
+    require!(
+        config.is_price_sane(&price_feed_message),
+        ReceiverError::PriceSanityCheckFailed
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    if config.max_staleness_seconds > 0 {
+        require!(
+            now.saturating_sub(price_feed_message.publish_time)
+                <= i64::from(config.max_staleness_seconds),
+            ReceiverError::PriceTooStale
+        );
+    }
+    if config.max_future_publish_time_seconds > 0 {
+        require!(
+            price_feed_message.publish_time.saturating_sub(now)
+                <= i64::from(config.max_future_publish_time_seconds),
+            ReceiverError::PublishTimeInFuture
+        );
+    }
+
+    // The price is compared with the last price of the feed posted to any account (see set_price_change_limits)
+    if let Some(price_change_limit) = config.price_change_limit(&price_feed_message.feed_id) {
+        let mut price_change_baseline_account = load_or_create_price_change_baseline_account(
+            payer,
+            remaining_accounts.price_change_baseline_accounts.next(),
+            &price_feed_message.feed_id,
+        )?;
+        let baseline = &mut price_change_baseline_account.price_message;
+        // A new baseline account doesn't store a price of the feed yet
+        let has_baseline = baseline.feed_id == price_feed_message.feed_id;
+        if has_baseline {
+            require!(
+                !price_change_limit.is_exceeded_by(baseline, &price_feed_message),
+                ReceiverError::PriceMovedTooMuch
+            );
+        }
+        if !has_baseline || price_feed_message.publish_time > baseline.publish_time {
+            *baseline = price_feed_message;
+        }
+        // Remaining accounts aren't serialized by Anchor at the end of the instruction
+        price_change_baseline_account.exit(&crate::ID)?;
+    }
+
+    price_update_account.write_authority = write_authority.key();
+    price_update_account.verification_level = vaa_components.verification_level;
+    price_update_account.price_message = price_feed_message;
+    price_update_account.posted_slot = Clock::get()?.slot;
+    Ok(())
+}
+
+fn emit_price_update_verified(price_feed_message: &PriceFeedMessage, emitter_chain: u16) {
+    emit!(PriceUpdateVerified {
+        feed_id: price_feed_message.feed_id,
+        price: price_feed_message.price,
+        conf: price_feed_message.conf,
+        publish_time: price_feed_message.publish_time,
+        emitter_chain,
+    });
+}
+
+/// Check that the updated config fits in the config account, zero the end of the account and emit `ConfigUpdated`.
+/// Config accounts created by earlier versions of the program are smaller than `Config::LEN`, so growing one of the lists of the config can require `resize_config` first.
+/// Anchor only overwrites the bytes of the serialized config when the instruction exits, so without zeroing the end of the account a config that shrinks would leave stale bytes where fields added by later versions of the program are read.
+fn emit_config_updated(old_config: Config, new_config: &Account<Config>) -> Result<()> {
+    let config_len = Config::discriminator().len() + new_config.try_to_vec()?.len();
+    let config_account_info = new_config.to_account_info();
+    require_gte!(
+        config_account_info.data_len(),
+        config_len,
+        ReceiverError::ConfigAccountTooSmall
+    );
+    config_account_info.try_borrow_mut_data()?[config_len..].fill(0);
+    emit!(ConfigUpdated {
+        old_config,
+        new_config: Config::clone(new_config),
+    });
+    Ok(())
+}
+
+/// The remaining accounts of the posting instructions that come after the accounts of their updates: one account per fee recipient of the config, followed by one `PriceChangeBaseline` account per posted update whose feed has a price change limit, in the same order as the updates.
+struct PostUpdateRemainingAccounts<'a, 'info> {
+    fee_recipient_accounts:         &'a [AccountInfo<'info>],
+    price_change_baseline_accounts: std::slice::Iter<'a, AccountInfo<'info>>,
+}
+
+/// Split `remaining_accounts` into the `num_update_accounts` accounts of the updates and the `PostUpdateRemainingAccounts` that follow them, checking the number of fee recipient accounts.
+fn split_remaining_accounts<'a, 'info>(
+    config: &Config,
+    remaining_accounts: &'a [AccountInfo<'info>],
+    num_update_accounts: usize,
+) -> Result<(
+    &'a [AccountInfo<'info>],
+    PostUpdateRemainingAccounts<'a, 'info>,
+)> {
+    require_gte!(
+        remaining_accounts.len(),
+        num_update_accounts,
+        ReceiverError::WrongNumberOfPriceUpdateAccounts
+    );
+    let (update_accounts, remaining_accounts) = remaining_accounts.split_at(num_update_accounts);
+    let (fee_recipient_accounts, price_change_baseline_accounts) =
+        remaining_accounts.split_at(config.fee_recipients.len().min(remaining_accounts.len()));
+    check_num_fee_recipient_accounts(config, fee_recipient_accounts.len())?;
+    Ok((
+        update_accounts,
+        PostUpdateRemainingAccounts {
+            fee_recipient_accounts,
+            price_change_baseline_accounts: price_change_baseline_accounts.iter(),
+        },
+    ))
+}
+
+/// Check that one account was passed per fee recipient of the config, with a dedicated error when the config has fee recipients but none of their accounts were passed.
+fn check_num_fee_recipient_accounts(
+    config: &Config,
+    num_fee_recipient_accounts: usize,
+) -> Result<()> {
+    require!(
+        config.fee_recipients.is_empty() || num_fee_recipient_accounts > 0,
+        ReceiverError::MissingFeeRecipientAccounts
+    );
+    require_eq!(
+        num_fee_recipient_accounts,
+        config.fee_recipients.len(),
+        ReceiverError::WrongNumberOfFeeRecipientAccounts
+    );
+    Ok(())
+}
+
+/// Charge the fee of one update to the payer.
+/// The fee goes to the treasury, unless the config has fee recipients, in which case it's split between `fee_recipient_accounts`.
+fn pay_fee<'info>(
+    config: &Config,
+    payer: &Signer<'info>,
+    treasury: &AccountInfo<'info>,
+    fee_recipient_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    if !config.fee_recipients.is_empty() {
+        check_num_fee_recipient_accounts(config, fee_recipient_accounts.len())?;
+    }
+    let amount_to_pay = if config.fee_recipients.is_empty() && treasury.lamports() == 0 {
         Rent::get()?
             .minimum_balance(0)
             .max(config.single_update_fee_in_lamports)
     } else {
         config.single_update_fee_in_lamports
     }; // First person to use the treasury account has to pay rent
+    if amount_to_pay == 0 {
+        return Ok(());
+    }
     if payer.lamports()
         < Rent::get()?
             .minimum_balance(payer.data_len())
@@ -401,12 +1701,78 @@ fn post_price_update_from_vaa<'info>(
         return err!(ReceiverError::InsufficientFunds);
     };
 
-    let transfer_instruction = system_instruction::transfer(payer.key, treasury.key, amount_to_pay);
-    anchor_lang::solana_program::program::invoke(
-        &transfer_instruction,
-        &[payer.to_account_info(), treasury.to_account_info()],
-    )?;
+    if config.fee_recipients.is_empty() {
+        let transfer_instruction =
+            system_instruction::transfer(payer.key, treasury.key, amount_to_pay);
+        anchor_lang::solana_program::program::invoke(
+            &transfer_instruction,
+            &[payer.to_account_info(), treasury.to_account_info()],
+        )?;
+        return Ok(());
+    }
 
+    let shares = split_fee(amount_to_pay, &config.fee_recipients);
+    for ((fee_recipient, fee_recipient_account), share) in config
+        .fee_recipients
+        .iter()
+        .zip(fee_recipient_accounts.iter())
+        .zip(shares)
+    {
+        require_keys_eq!(
+            fee_recipient_account.key(),
+            fee_recipient.recipient,
+            ReceiverError::WrongFeeRecipientAccounts
+        );
+        if share > 0 {
+            let transfer_instruction =
+                system_instruction::transfer(payer.key, fee_recipient_account.key, share);
+            anchor_lang::solana_program::program::invoke(
+                &transfer_instruction,
+                &[payer.to_account_info(), fee_recipient_account.clone()],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Check that `vaa_components` come from a valid data source and that `price_update` is included in the root in `vaa_payload`.
+fn verify_price_update(
+    config: &Config,
+    vaa_components: &VaaComponents,
+    vaa_payload: &[u8],
+    price_update: &MerklePriceUpdate,
+) -> Result<PriceFeedMessage> {
+    match verify_message(config, vaa_components, vaa_payload, price_update)? {
+        Message::PriceFeedMessage(price_feed_message) => Ok(price_feed_message),
+        Message::TwapMessage(_) => err!(ReceiverError::UnsupportedMessageType),
+    }
+}
+
+/// Same as `verify_price_update` for a TWAP update, the fields of the TWAP message are also checked for consistency.
+fn verify_twap_update(
+    config: &Config,
+    vaa_components: &VaaComponents,
+    vaa_payload: &[u8],
+    twap_update: &MerklePriceUpdate,
+) -> Result<TwapMessage> {
+    match verify_message(config, vaa_components, vaa_payload, twap_update)? {
+        Message::TwapMessage(twap_message) => {
+            require!(
+                twap_message.prev_publish_time <= twap_message.publish_time,
+                ReceiverError::InvalidTwapMessage
+            );
+            Ok(twap_message)
+        }
+        Message::PriceFeedMessage(_) => err!(ReceiverError::UnsupportedMessageType),
+    }
+}
+
+fn verify_message(
+    config: &Config,
+    vaa_components: &VaaComponents,
+    vaa_payload: &[u8],
+    price_update: &MerklePriceUpdate,
+) -> Result<Message> {
     let valid_data_source = config.valid_data_sources.iter().any(|x| {
         *x == DataSource {
             chain:   vaa_components.emitter_chain,
@@ -417,31 +1783,32 @@ fn post_price_update_from_vaa<'info>(
         return err!(ReceiverError::InvalidDataSource);
     }
 
+    // A VAA from a valid data source may carry another kind of message, check the magic before parsing so that this case isn't reported as a corrupted message.
+    require!(
+        vaa_payload.starts_with(ACCUMULATOR_UPDATE_WORMHOLE_VERIFICATION_MAGIC),
+        ReceiverError::NotAnAccumulatorMessage
+    );
     let wormhole_message = WormholeMessage::try_from_bytes(vaa_payload)
         .map_err(|_| ReceiverError::InvalidWormholeMessage)?;
     let root: MerkleRoot<Keccak160> = MerkleRoot::new(match wormhole_message.payload {
         WormholePayload::Merkle(merkle_root) => merkle_root.root,
     });
 
-    if !root.check(price_update.proof.clone(), price_update.message.as_ref()) {
+    if !root.check_ref(&price_update.proof, price_update.message.as_ref()) {
         return err!(ReceiverError::InvalidPriceUpdate);
     }
 
-    let message = from_slice::<byteorder::BE, Message>(price_update.message.as_ref())
-        .map_err(|_| ReceiverError::DeserializeMessageFailed)?;
-
-    match message {
-        Message::PriceFeedMessage(price_feed_message) => {
-            price_update_account.write_authority = write_authority.key();
-            price_update_account.verification_level = vaa_components.verification_level;
-            price_update_account.price_message = price_feed_message;
-            price_update_account.posted_slot = Clock::get()?.slot;
-        }
-        Message::TwapMessage(_) => {
-            return err!(ReceiverError::UnsupportedMessageType);
-        }
-    }
-    Ok(())
+    from_slice_with_offset::<byteorder::BE, Message>(price_update.message.as_ref()).map_err(
+        |(err, offset)| {
+            msg!(
+                "Failed to deserialize the message at byte offset {} of {}: {}",
+                offset,
+                price_update.message.as_ref().len(),
+                err
+            );
+            error!(ReceiverError::DeserializeMessageFailed)
+        },
+    )
 }
 
 /**
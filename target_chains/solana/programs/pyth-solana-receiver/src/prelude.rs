@@ -0,0 +1,49 @@
+//! The items most clients need to build instructions for the Pyth Solana Receiver and read its accounts.
+//!
+//! ```ignore
+//! use pyth_solana_receiver::prelude::*;
+//! ```
+pub use {
+    crate::{
+        accounts,
+        instruction,
+        sdk::{
+            deserialize_accumulator_update_data,
+            fits_in_transaction,
+            get_fee_recipient_account_metas,
+            get_guardian_set_address,
+            get_price_change_baseline_account_meta,
+            get_random_treasury_id,
+            get_transaction_size,
+            DEFAULT_TREASURY_ID,
+            MAX_TRANSACTION_SIZE,
+            SECONDARY_TREASURY_ID,
+            VAA_SPLIT_INDEX,
+        },
+        ID,
+    },
+    pyth_solana_receiver_sdk::{
+        config::{
+            Config,
+            DataSource,
+        },
+        pda::{
+            get_config_address,
+            get_treasury_address,
+        },
+        price_update::{
+            get_feed_id_from_hex,
+            FeedId,
+            Price,
+            PriceHistory,
+            PriceUpdateV2,
+            TwapUpdate,
+            VerificationLevel,
+        },
+        EventMode,
+        PostUpdateAtomicParams,
+        PostUpdateParams,
+        PostUpdatesFromAccountDataParams,
+    },
+    pythnet_sdk::wire::v1::MerklePriceUpdate,
+};
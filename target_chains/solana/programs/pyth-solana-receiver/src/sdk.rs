@@ -1,6 +1,7 @@
 use {
     crate::{
         accounts,
+        error::ReceiverError,
         instruction,
         ID,
     },
@@ -13,22 +14,41 @@ use {
         config::{
             Config,
             DataSource,
+            ExponentRange,
+            FeeRecipient,
+            PriceChangeLimit,
         },
         pda::{
             get_config_address,
             get_treasury_address,
         },
+        EventMode,
         PostUpdateAtomicParams,
         PostUpdateParams,
+        PostUpdatesFromAccountDataParams,
+        PostUpdatesFromVaasParams,
     },
-    pythnet_sdk::wire::v1::{
-        AccumulatorUpdateData,
-        MerklePriceUpdate,
-        Proof,
+    pythnet_sdk::{
+        messages::Message,
+        wire::{
+            from_slice,
+            v1::{
+                AccumulatorUpdateData,
+                MerklePriceUpdate,
+                Proof,
+            },
+        },
     },
     rand::Rng,
-    solana_program::instruction::Instruction,
+    solana_program::{
+        instruction::{
+            AccountMeta,
+            Instruction,
+        },
+        message::Message,
+    },
     wormhole_core_bridge_solana::state::GuardianSet,
+    wormhole_raw_vaas::Vaa,
 };
 
 /**
@@ -37,6 +57,16 @@ use {
  */
 pub const VAA_SPLIT_INDEX: usize = 755;
 
+/**
+ * The maximum size of a serialized transaction, this is `solana_sdk::packet::PACKET_DATA_SIZE`.
+ */
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
+
+pub use pyth_solana_receiver_sdk::config::{
+    get_fee_recipient_account_metas,
+    get_price_change_baseline_account_meta,
+};
+
 pub const DEFAULT_TREASURY_ID: u8 = 0;
 pub const SECONDARY_TREASURY_ID: u8 = 1;
 
@@ -99,6 +129,74 @@ impl accounts::PostUpdate {
     }
 }
 
+impl accounts::PostTwapUpdate {
+    pub fn populate(
+        payer: Pubkey,
+        write_authority: Pubkey,
+        encoded_vaa: Pubkey,
+        twap_update_account: Pubkey,
+        treasury_id: u8,
+    ) -> Self {
+        let config = get_config_address();
+        let treasury = get_treasury_address(treasury_id);
+        accounts::PostTwapUpdate {
+            payer,
+            encoded_vaa,
+            config,
+            treasury,
+            twap_update_account,
+            system_program: system_program::ID,
+            write_authority,
+        }
+    }
+}
+
+impl accounts::PostUpdatesFromAccountData {
+    pub fn populate(
+        payer: Pubkey,
+        write_authority: Pubkey,
+        encoded_vaa: Pubkey,
+        accumulator_update_data: Pubkey,
+        treasury_id: u8,
+    ) -> Self {
+        let config = get_config_address();
+        let treasury = get_treasury_address(treasury_id);
+        accounts::PostUpdatesFromAccountData {
+            payer,
+            encoded_vaa,
+            accumulator_update_data,
+            config,
+            treasury,
+            system_program: system_program::ID,
+            write_authority,
+        }
+    }
+}
+
+impl accounts::PostUpdatesFromVaas {
+    pub fn populate(payer: Pubkey, write_authority: Pubkey, treasury_id: u8) -> Self {
+        let config = get_config_address();
+        let treasury = get_treasury_address(treasury_id);
+        accounts::PostUpdatesFromVaas {
+            payer,
+            config,
+            treasury,
+            system_program: system_program::ID,
+            write_authority,
+        }
+    }
+}
+
+impl accounts::VerifyUpdate {
+    pub fn populate(encoded_vaa: Pubkey) -> Self {
+        let config = get_config_address();
+        accounts::VerifyUpdate {
+            encoded_vaa,
+            config,
+        }
+    }
+}
+
 impl accounts::Governance {
     pub fn populate(payer: Pubkey) -> Self {
         let config = get_config_address();
@@ -106,6 +204,24 @@ impl accounts::Governance {
     }
 }
 
+impl accounts::ResizeConfig {
+    pub fn populate(payer: Pubkey) -> Self {
+        let config = get_config_address();
+        accounts::ResizeConfig {
+            payer,
+            config,
+            system_program: system_program::ID,
+        }
+    }
+}
+
+impl accounts::MigrateConfig {
+    pub fn populate(payer: Pubkey) -> Self {
+        let config = get_config_address();
+        accounts::MigrateConfig { payer, config }
+    }
+}
+
 impl accounts::AcceptGovernanceAuthorityTransfer {
     pub fn populate(payer: Pubkey) -> Self {
         let config = get_config_address();
@@ -115,10 +231,27 @@ impl accounts::AcceptGovernanceAuthorityTransfer {
 
 impl accounts::ReclaimRent {
     pub fn populate(payer: Pubkey, price_update_account: Pubkey) -> Self {
-        let _config = get_config_address();
+        let config = get_config_address();
         accounts::ReclaimRent {
             payer,
             price_update_account,
+            config,
+        }
+    }
+}
+
+impl accounts::ClosePriceUpdate {
+    pub fn populate(
+        write_authority: Pubkey,
+        price_update_account: Pubkey,
+        recipient: Pubkey,
+    ) -> Self {
+        let config = get_config_address();
+        accounts::ClosePriceUpdate {
+            write_authority,
+            price_update_account,
+            recipient,
+            config,
         }
     }
 }
@@ -164,6 +297,69 @@ impl instruction::PostUpdate {
     }
 }
 
+impl instruction::PostTwapUpdate {
+    pub fn populate(
+        payer: Pubkey,
+        write_authority: Pubkey,
+        encoded_vaa: Pubkey,
+        twap_update_account: Pubkey,
+        merkle_price_update: MerklePriceUpdate,
+        treasury_id: u8,
+    ) -> Instruction {
+        let post_twap_update_accounts = accounts::PostTwapUpdate::populate(
+            payer,
+            write_authority,
+            encoded_vaa,
+            twap_update_account,
+            treasury_id,
+        )
+        .to_account_metas(None);
+        Instruction {
+            program_id: ID,
+            accounts:   post_twap_update_accounts,
+            data:       instruction::PostTwapUpdate {
+                params: PostUpdateParams {
+                    merkle_price_update,
+                    treasury_id,
+                },
+            }
+            .data(),
+        }
+    }
+}
+
+impl instruction::PostTwapUpdateWithMinConsistency {
+    pub fn populate(
+        payer: Pubkey,
+        write_authority: Pubkey,
+        encoded_vaa: Pubkey,
+        twap_update_account: Pubkey,
+        merkle_price_update: MerklePriceUpdate,
+        treasury_id: u8,
+        min_consistency_level: u8,
+    ) -> Instruction {
+        let post_twap_update_accounts = accounts::PostTwapUpdate::populate(
+            payer,
+            write_authority,
+            encoded_vaa,
+            twap_update_account,
+            treasury_id,
+        )
+        .to_account_metas(None);
+        Instruction {
+            program_id: ID,
+            accounts:   post_twap_update_accounts,
+            data:       instruction::PostTwapUpdateWithMinConsistency {
+                params: PostUpdateParams {
+                    merkle_price_update,
+                    treasury_id,
+                },
+                min_consistency_level,
+            }
+            .data(),
+        }
+    }
+}
 
 impl instruction::PostUpdateAtomic {
     pub fn populate(
@@ -200,6 +396,248 @@ impl instruction::PostUpdateAtomic {
     }
 }
 
+impl instruction::PostUpdateWithMinConsistency {
+    pub fn populate(
+        payer: Pubkey,
+        write_authority: Pubkey,
+        encoded_vaa: Pubkey,
+        price_update_account: Pubkey,
+        merkle_price_update: MerklePriceUpdate,
+        treasury_id: u8,
+        min_consistency_level: u8,
+    ) -> Instruction {
+        let post_update_accounts = accounts::PostUpdate::populate(
+            payer,
+            write_authority,
+            encoded_vaa,
+            price_update_account,
+            treasury_id,
+        )
+        .to_account_metas(None);
+        Instruction {
+            program_id: ID,
+            accounts:   post_update_accounts,
+            data:       instruction::PostUpdateWithMinConsistency {
+                params: PostUpdateParams {
+                    merkle_price_update,
+                    treasury_id,
+                },
+                min_consistency_level,
+            }
+            .data(),
+        }
+    }
+}
+
+impl instruction::PostUpdateAtomicWithMinConsistency {
+    pub fn populate(
+        payer: Pubkey,
+        write_authority: Pubkey,
+        price_update_account: Pubkey,
+        wormhole_address: Pubkey,
+        guardian_set_index: u32,
+        vaa: Vec<u8>,
+        merkle_price_update: MerklePriceUpdate,
+        treasury_id: u8,
+        min_consistency_level: u8,
+    ) -> Instruction {
+        let post_update_accounts = accounts::PostUpdateAtomic::populate(
+            payer,
+            write_authority,
+            price_update_account,
+            wormhole_address,
+            guardian_set_index,
+            treasury_id,
+        )
+        .to_account_metas(None);
+        Instruction {
+            program_id: ID,
+            accounts:   post_update_accounts,
+            data:       instruction::PostUpdateAtomicWithMinConsistency {
+                params: PostUpdateAtomicParams {
+                    vaa,
+                    merkle_price_update,
+                    treasury_id,
+                },
+                min_consistency_level,
+            }
+            .data(),
+        }
+    }
+}
+
+impl instruction::PostUpdatesFromAccountData {
+    /// `price_update_accounts` must have one account per update in `accumulator_update_data`, in the same order.
+    /// They need to sign the transaction since they get created if they don't exist yet.
+    /// `price_history_accounts` is either empty, or has one `PriceHistory` account per update in the same order, and these accounts also need to sign the first time they are used.
+    /// See `PostUpdatesFromAccountDataParams` for `expected_updates` and `require_exact_count`.
+    pub fn populate(
+        payer: Pubkey,
+        write_authority: Pubkey,
+        encoded_vaa: Pubkey,
+        accumulator_update_data: Pubkey,
+        price_update_accounts: &[Pubkey],
+        price_history_accounts: &[Pubkey],
+        treasury_id: u8,
+        event_mode: EventMode,
+        expected_updates: Option<u16>,
+        require_exact_count: bool,
+    ) -> Instruction {
+        let mut post_updates_accounts = accounts::PostUpdatesFromAccountData::populate(
+            payer,
+            write_authority,
+            encoded_vaa,
+            accumulator_update_data,
+            treasury_id,
+        )
+        .to_account_metas(None);
+        post_updates_accounts.extend(
+            price_update_accounts
+                .iter()
+                .map(|price_update_account| AccountMeta::new(*price_update_account, true)),
+        );
+        post_updates_accounts.extend(
+            price_history_accounts
+                .iter()
+                .map(|price_history_account| AccountMeta::new(*price_history_account, true)),
+        );
+        Instruction {
+            program_id: ID,
+            accounts:   post_updates_accounts,
+            data:       instruction::PostUpdatesFromAccountData {
+                params: PostUpdatesFromAccountDataParams {
+                    treasury_id,
+                    event_mode,
+                    write_price_history: !price_history_accounts.is_empty(),
+                    expected_updates,
+                    require_exact_count,
+                    min_consistency_level: None,
+                },
+            }
+            .data(),
+        }
+    }
+}
+
+impl instruction::PostUpdatesFromVaas {
+    /// `encoded_vaas` are the verified encoded VAA accounts whose roots include the updates.
+    /// `price_update_accounts` must have one account per update in `merkle_price_updates`, in the same order. They need to sign the transaction since they get created if they don't exist yet.
+    /// `vaa_indices` is either empty, to let the program find the VAA of every update, or has the index in `encoded_vaas` of the VAA of every update.
+    pub fn populate(
+        payer: Pubkey,
+        write_authority: Pubkey,
+        encoded_vaas: &[Pubkey],
+        price_update_accounts: &[Pubkey],
+        merkle_price_updates: Vec<MerklePriceUpdate>,
+        vaa_indices: Vec<u8>,
+        treasury_id: u8,
+    ) -> Instruction {
+        let mut post_updates_accounts =
+            accounts::PostUpdatesFromVaas::populate(payer, write_authority, treasury_id)
+                .to_account_metas(None);
+        post_updates_accounts.extend(
+            encoded_vaas
+                .iter()
+                .map(|encoded_vaa| AccountMeta::new_readonly(*encoded_vaa, false)),
+        );
+        post_updates_accounts.extend(
+            price_update_accounts
+                .iter()
+                .map(|price_update_account| AccountMeta::new(*price_update_account, true)),
+        );
+        Instruction {
+            program_id: ID,
+            accounts:   post_updates_accounts,
+            data:       instruction::PostUpdatesFromVaas {
+                params: PostUpdatesFromVaasParams {
+                    merkle_price_updates,
+                    num_vaas: encoded_vaas.len() as u8,
+                    vaa_indices,
+                    treasury_id,
+                    min_consistency_level: None,
+                },
+            }
+            .data(),
+        }
+    }
+}
+
+impl instruction::PostUpdatesWithTag {
+    /// Same as `instruction::PostUpdatesFromAccountData::populate`, with the correlation `tag` of the updates.
+    pub fn populate(
+        payer: Pubkey,
+        write_authority: Pubkey,
+        encoded_vaa: Pubkey,
+        accumulator_update_data: Pubkey,
+        price_update_accounts: &[Pubkey],
+        price_history_accounts: &[Pubkey],
+        treasury_id: u8,
+        event_mode: EventMode,
+        expected_updates: Option<u16>,
+        require_exact_count: bool,
+        tag: [u8; 32],
+    ) -> Instruction {
+        let post_updates_instruction = instruction::PostUpdatesFromAccountData::populate(
+            payer,
+            write_authority,
+            encoded_vaa,
+            accumulator_update_data,
+            price_update_accounts,
+            price_history_accounts,
+            treasury_id,
+            event_mode,
+            expected_updates,
+            require_exact_count,
+        );
+        Instruction {
+            program_id: ID,
+            accounts:   post_updates_instruction.accounts,
+            data:       instruction::PostUpdatesWithTag {
+                params: PostUpdatesFromAccountDataParams {
+                    treasury_id,
+                    event_mode,
+                    write_price_history: !price_history_accounts.is_empty(),
+                    expected_updates,
+                    require_exact_count,
+                    min_consistency_level: None,
+                },
+                tag,
+            }
+            .data(),
+        }
+    }
+}
+
+impl instruction::VerifyUpdate {
+    pub fn populate(encoded_vaa: Pubkey, merkle_price_update: MerklePriceUpdate) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts:   accounts::VerifyUpdate::populate(encoded_vaa).to_account_metas(None),
+            data:       instruction::VerifyUpdate {
+                merkle_price_update,
+                min_consistency_level: None,
+            }
+            .data(),
+        }
+    }
+}
+
+impl instruction::VerifyUpdatesView {
+    pub fn populate(
+        encoded_vaa: Pubkey,
+        merkle_price_updates: Vec<MerklePriceUpdate>,
+    ) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts:   accounts::VerifyUpdate::populate(encoded_vaa).to_account_metas(None),
+            data:       instruction::VerifyUpdatesView {
+                merkle_price_updates,
+                min_consistency_level: None,
+            }
+            .data(),
+        }
+    }
+}
 
 impl instruction::SetDataSources {
     pub fn populate(payer: Pubkey, data_sources: Vec<DataSource>) -> Instruction {
@@ -253,6 +691,169 @@ impl instruction::SetMinimumSignatures {
     }
 }
 
+impl instruction::SetPinnedGuardianSetHash {
+    pub fn populate(payer: Pubkey, pinned_guardian_set_hash: Option<[u8; 32]>) -> Instruction {
+        let governance_accounts = accounts::Governance::populate(payer).to_account_metas(None);
+        Instruction {
+            program_id: ID,
+            accounts:   governance_accounts,
+            data:       instruction::SetPinnedGuardianSetHash {
+                pinned_guardian_set_hash,
+            }
+            .data(),
+        }
+    }
+}
+
+impl instruction::SetFeeRecipients {
+    pub fn populate(payer: Pubkey, fee_recipients: Vec<FeeRecipient>) -> Instruction {
+        let governance_accounts = accounts::Governance::populate(payer).to_account_metas(None);
+        Instruction {
+            program_id: ID,
+            accounts:   governance_accounts,
+            data:       instruction::SetFeeRecipients { fee_recipients }.data(),
+        }
+    }
+}
+
+impl instruction::SetPostVaaPrograms {
+    pub fn populate(payer: Pubkey, post_vaa_programs: Vec<Pubkey>) -> Instruction {
+        let governance_accounts = accounts::Governance::populate(payer).to_account_metas(None);
+        Instruction {
+            program_id: ID,
+            accounts:   governance_accounts,
+            data:       instruction::SetPostVaaPrograms { post_vaa_programs }.data(),
+        }
+    }
+}
+
+impl instruction::SetGuardianSetExpirationSkew {
+    pub fn populate(payer: Pubkey, guardian_set_expiration_skew_seconds: u32) -> Instruction {
+        let governance_accounts = accounts::Governance::populate(payer).to_account_metas(None);
+        Instruction {
+            program_id: ID,
+            accounts:   governance_accounts,
+            data:       instruction::SetGuardianSetExpirationSkew {
+                guardian_set_expiration_skew_seconds,
+            }
+            .data(),
+        }
+    }
+}
+
+impl instruction::SetPriceChangeLimits {
+    pub fn populate(payer: Pubkey, price_change_limits: Vec<PriceChangeLimit>) -> Instruction {
+        let governance_accounts = accounts::Governance::populate(payer).to_account_metas(None);
+        Instruction {
+            program_id: ID,
+            accounts:   governance_accounts,
+            data:       instruction::SetPriceChangeLimits {
+                price_change_limits,
+            }
+            .data(),
+        }
+    }
+}
+
+impl instruction::SetMinPriceUpdateLifetime {
+    pub fn populate(payer: Pubkey, min_price_update_lifetime_slots: u64) -> Instruction {
+        let governance_accounts = accounts::Governance::populate(payer).to_account_metas(None);
+        Instruction {
+            program_id: ID,
+            accounts:   governance_accounts,
+            data:       instruction::SetMinPriceUpdateLifetime {
+                min_price_update_lifetime_slots,
+            }
+            .data(),
+        }
+    }
+}
+
+impl instruction::SetPublishTimeBounds {
+    pub fn populate(
+        payer: Pubkey,
+        max_staleness_seconds: u32,
+        max_future_publish_time_seconds: u32,
+    ) -> Instruction {
+        let governance_accounts = accounts::Governance::populate(payer).to_account_metas(None);
+        Instruction {
+            program_id: ID,
+            accounts:   governance_accounts,
+            data:       instruction::SetPublishTimeBounds {
+                max_staleness_seconds,
+                max_future_publish_time_seconds,
+            }
+            .data(),
+        }
+    }
+}
+
+impl instruction::SetPriceSanityBounds {
+    pub fn populate(
+        payer: Pubkey,
+        exponent_range: Option<ExponentRange>,
+        max_conf_bps: u32,
+    ) -> Instruction {
+        let governance_accounts = accounts::Governance::populate(payer).to_account_metas(None);
+        Instruction {
+            program_id: ID,
+            accounts:   governance_accounts,
+            data:       instruction::SetPriceSanityBounds {
+                exponent_range,
+                max_conf_bps,
+            }
+            .data(),
+        }
+    }
+}
+
+impl instruction::ResizeConfig {
+    pub fn populate(payer: Pubkey, len: u32) -> Instruction {
+        let governance_accounts = accounts::ResizeConfig::populate(payer).to_account_metas(None);
+        Instruction {
+            program_id: ID,
+            accounts:   governance_accounts,
+            data:       instruction::ResizeConfig { len }.data(),
+        }
+    }
+}
+
+impl instruction::MigrateConfig {
+    pub fn populate(payer: Pubkey) -> Instruction {
+        let governance_accounts = accounts::MigrateConfig::populate(payer).to_account_metas(None);
+        Instruction {
+            program_id: ID,
+            accounts:   governance_accounts,
+            data:       instruction::MigrateConfig {}.data(),
+        }
+    }
+}
+
+impl instruction::SetAllowedFeedIds {
+    pub fn populate(payer: Pubkey, allowed_feed_ids: Vec<[u8; 32]>) -> Instruction {
+        let governance_accounts = accounts::Governance::populate(payer).to_account_metas(None);
+        Instruction {
+            program_id: ID,
+            accounts:   governance_accounts,
+            data:       instruction::SetAllowedFeedIds { allowed_feed_ids }.data(),
+        }
+    }
+}
+
+impl instruction::SetMinConsistencyLevel {
+    pub fn populate(payer: Pubkey, min_consistency_level: u8) -> Instruction {
+        let governance_accounts = accounts::Governance::populate(payer).to_account_metas(None);
+        Instruction {
+            program_id: ID,
+            accounts:   governance_accounts,
+            data:       instruction::SetMinConsistencyLevel {
+                min_consistency_level,
+            }
+            .data(),
+        }
+    }
+}
+
 impl instruction::RequestGovernanceAuthorityTransfer {
     pub fn populate(payer: Pubkey, target_governance_authority: Pubkey) -> Instruction {
         let governance_accounts = accounts::Governance::populate(payer).to_account_metas(None);
@@ -302,6 +903,40 @@ impl instruction::ReclaimRent {
     }
 }
 
+impl instruction::ClosePriceUpdate {
+    pub fn populate(
+        write_authority: Pubkey,
+        price_update_account: Pubkey,
+        recipient: Pubkey,
+    ) -> Instruction {
+        let close_price_update_accounts =
+            accounts::ClosePriceUpdate::populate(write_authority, price_update_account, recipient)
+                .to_account_metas(None);
+        Instruction {
+            program_id: ID,
+            accounts:   close_price_update_accounts,
+            data:       instruction::ClosePriceUpdate {}.data(),
+        }
+    }
+}
+
+/**
+ * Returns the size of a signed transaction containing `instructions` and paid by `payer`.
+ * Compare it with `MAX_TRANSACTION_SIZE` before sending a transaction to avoid a "transaction too large" error,
+ * e.g. to check that a `post_update_atomic` instruction with a given number of VAA signatures fits in one transaction.
+ */
+pub fn get_transaction_size(instructions: &[Instruction], payer: &Pubkey) -> usize {
+    let message = Message::new(instructions, Some(payer));
+    let num_signatures = usize::from(message.header.num_required_signatures);
+    // The signatures are prefixed by their number encoded as a compact-u16
+    let signatures_length_size = if num_signatures < 0x80 { 1 } else { 2 };
+    signatures_length_size + num_signatures * 64 + message.serialize().len()
+}
+
+pub fn fits_in_transaction(instructions: &[Instruction], payer: &Pubkey) -> bool {
+    get_transaction_size(instructions, payer) <= MAX_TRANSACTION_SIZE
+}
+
 pub fn get_guardian_set_address(wormhole_address: Pubkey, guardian_set_index: u32) -> Pubkey {
     Pubkey::find_program_address(
         &[
@@ -313,17 +948,74 @@ pub fn get_guardian_set_address(wormhole_address: Pubkey, guardian_set_index: u3
     .0
 }
 
+/// Split accumulator update data (e.g. from a Hermes response) into its VAA and its updates.
+/// Malformed data returns `ReceiverError::DeserializeAccumulatorUpdateDataFailed` instead of panicking.
 pub fn deserialize_accumulator_update_data(
     accumulator_message: Vec<u8>,
 ) -> Result<(Vec<u8>, Vec<MerklePriceUpdate>)> {
     let accumulator_update_data =
-        AccumulatorUpdateData::try_from_slice(accumulator_message.as_slice()).unwrap();
+        AccumulatorUpdateData::try_from_slice(accumulator_message.as_slice())
+            .map_err(|_| ReceiverError::DeserializeAccumulatorUpdateDataFailed)?;
 
     match accumulator_update_data.proof {
-        Proof::WormholeMerkle { vaa, updates } => return Ok((vaa.as_ref().to_vec(), updates)),
+        Proof::WormholeMerkle { vaa, updates } => Ok((vaa.as_ref().to_vec(), updates)),
     }
 }
 
+/// Build one `post_update_atomic` instruction per update of `accumulator_update_data` (e.g. from a Hermes response), posting the update at the same index to `price_update_accounts`.
+/// The guardian set account is derived from the guardian set index of the VAA, so the caller only needs to know the address of the Wormhole program.
+/// The VAA is used with all of its signatures, which may not fit in a transaction. Callers that want to post partially verified updates should trim the signatures and call `instruction::PostUpdateAtomic::populate` directly.
+/// `config` must be the config account, the accounts of its fee recipients and the `PriceChangeBaseline` account of the feed of the update (if the feed has a price change limit) are appended to every instruction.
+pub fn get_post_update_atomic_instructions(
+    payer: Pubkey,
+    write_authority: Pubkey,
+    wormhole_address: Pubkey,
+    accumulator_update_data: Vec<u8>,
+    price_update_accounts: &[Pubkey],
+    treasury_id: u8,
+    config: &Config,
+) -> Result<Vec<Instruction>> {
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(accumulator_update_data)?;
+    require_eq!(
+        price_update_accounts.len(),
+        merkle_price_updates.len(),
+        ReceiverError::WrongNumberOfPriceUpdateAccounts
+    );
+    let guardian_set_index = Vaa::parse(&vaa)
+        .map_err(|_| ReceiverError::DeserializeVaaFailed)?
+        .guardian_set_index();
+
+    Ok(price_update_accounts
+        .iter()
+        .zip(merkle_price_updates)
+        .map(|(price_update_account, merkle_price_update)| {
+            // Updates that can't be deserialized don't get a baseline account, their instruction fails anyway
+            let feed_id =
+                from_slice::<byteorder::BE, Message>(merkle_price_update.message.as_ref())
+                    .map(|message| message.feed_id());
+            let mut instruction = instruction::PostUpdateAtomic::populate(
+                payer,
+                write_authority,
+                *price_update_account,
+                wormhole_address,
+                guardian_set_index,
+                vaa.clone(),
+                merkle_price_update,
+                treasury_id,
+            );
+            instruction
+                .accounts
+                .extend(get_fee_recipient_account_metas(&config.fee_recipients));
+            if let Ok(feed_id) = feed_id {
+                instruction
+                    .accounts
+                    .extend(get_price_change_baseline_account_meta(config, &feed_id));
+            }
+            instruction
+        })
+        .collect())
+}
+
 pub fn get_random_treasury_id() -> u8 {
     rand::thread_rng().gen()
 }
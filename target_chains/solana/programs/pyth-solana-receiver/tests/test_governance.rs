@@ -1,19 +1,39 @@
 use {
+    anchor_lang::{
+        error::ErrorCode,
+        AnchorSerialize,
+        Discriminator,
+    },
     common_test_utils::{
         setup_pyth_receiver,
         ProgramTestFixtures,
         WrongSetupOption,
     },
-    program_simulator::into_transaction_error,
+    program_simulator::{
+        into_transaction_error,
+        ProgramSimulator,
+    },
     pyth_solana_receiver::{
         error::ReceiverError,
         instruction::{
             AcceptGovernanceAuthorityTransfer,
             CancelGovernanceAuthorityTransfer,
+            MigrateConfig,
             RequestGovernanceAuthorityTransfer,
+            ResizeConfig,
+            SetAllowedFeedIds,
             SetDataSources,
             SetFee,
+            SetFeeRecipients,
+            SetGuardianSetExpirationSkew,
+            SetMinConsistencyLevel,
+            SetMinPriceUpdateLifetime,
             SetMinimumSignatures,
+            SetPinnedGuardianSetHash,
+            SetPostVaaPrograms,
+            SetPriceChangeLimits,
+            SetPriceSanityBounds,
+            SetPublishTimeBounds,
             SetWormholeAddress,
         },
     },
@@ -21,6 +41,11 @@ use {
         config::{
             Config,
             DataSource,
+            ExponentRange,
+            FeeRecipient,
+            LegacyConfig,
+            PriceChangeLimit,
+            TOTAL_FEE_WEIGHT_BPS,
         },
         pda::get_config_address,
     },
@@ -29,9 +54,18 @@ use {
         native_token::LAMPORTS_PER_SOL,
         pubkey::Pubkey,
     },
-    solana_sdk::signer::Signer,
+    solana_program_test::ProgramTest,
+    solana_sdk::{
+        account::Account,
+        rent::Rent,
+        signature::Keypair,
+        signer::Signer,
+    },
 };
 
+/// The size of the config accounts created by earlier versions of the program.
+const LEGACY_CONFIG_LEN: u32 = 370;
+
 
 #[tokio::test]
 async fn test_governance() {
@@ -49,15 +83,40 @@ async fn test_governance() {
         .unwrap();
 
     let new_config = Config {
-        governance_authority:          new_governance_authority.pubkey(),
-        target_governance_authority:   None,
-        wormhole:                      Pubkey::new_unique(),
-        valid_data_sources:            vec![DataSource {
+        governance_authority:                 new_governance_authority.pubkey(),
+        target_governance_authority:          None,
+        wormhole:                             Pubkey::new_unique(),
+        valid_data_sources:                   vec![DataSource {
             chain:   SECONDARY_DATA_SOURCE.chain.into(),
             emitter: Pubkey::from(SECONDARY_DATA_SOURCE.address.0),
         }],
-        single_update_fee_in_lamports: LAMPORTS_PER_SOL,
-        minimum_signatures:            20,
+        single_update_fee_in_lamports:        LAMPORTS_PER_SOL,
+        minimum_signatures:                   20,
+        pinned_guardian_set_hash:             Some([1; 32]),
+        fee_recipients:                       vec![
+            FeeRecipient {
+                recipient:  Pubkey::new_unique(),
+                weight_bps: 6000,
+            },
+            FeeRecipient {
+                recipient:  Pubkey::new_unique(),
+                weight_bps: 4000,
+            },
+        ],
+        post_vaa_programs:                    vec![Pubkey::new_unique()],
+        guardian_set_expiration_skew_seconds: 60,
+        price_change_limits:                  vec![PriceChangeLimit {
+            feed_id:        [1; 32],
+            max_change_bps: 1000,
+            window_seconds: 60,
+        }],
+        min_price_update_lifetime_slots:      10,
+        max_staleness_seconds:                60,
+        max_future_publish_time_seconds:      5,
+        exponent_range:                       Some(ExponentRange { min: -12, max: 0 }),
+        max_conf_bps:                         1000,
+        allowed_feed_ids:                     vec![[1; 32], [2; 32]],
+        min_consistency_level:                1,
     };
 
 
@@ -126,6 +185,168 @@ async fn test_governance() {
         into_transaction_error(ReceiverError::GovernanceAuthorityMismatch)
     );
 
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetPinnedGuardianSetHash::populate(
+                    new_governance_authority.pubkey(),
+                    new_config.pinned_guardian_set_hash,
+                ),
+                &vec![&new_governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::GovernanceAuthorityMismatch)
+    );
+
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetFeeRecipients::populate(
+                    new_governance_authority.pubkey(),
+                    new_config.fee_recipients.clone(),
+                ),
+                &vec![&new_governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::GovernanceAuthorityMismatch)
+    );
+
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetPostVaaPrograms::populate(
+                    new_governance_authority.pubkey(),
+                    new_config.post_vaa_programs.clone(),
+                ),
+                &vec![&new_governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::GovernanceAuthorityMismatch)
+    );
+
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetGuardianSetExpirationSkew::populate(
+                    new_governance_authority.pubkey(),
+                    new_config.guardian_set_expiration_skew_seconds,
+                ),
+                &vec![&new_governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::GovernanceAuthorityMismatch)
+    );
+
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetPriceChangeLimits::populate(
+                    new_governance_authority.pubkey(),
+                    new_config.price_change_limits.clone(),
+                ),
+                &vec![&new_governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::GovernanceAuthorityMismatch)
+    );
+
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetMinPriceUpdateLifetime::populate(
+                    new_governance_authority.pubkey(),
+                    new_config.min_price_update_lifetime_slots,
+                ),
+                &vec![&new_governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::GovernanceAuthorityMismatch)
+    );
+
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetPublishTimeBounds::populate(
+                    new_governance_authority.pubkey(),
+                    new_config.max_staleness_seconds,
+                    new_config.max_future_publish_time_seconds,
+                ),
+                &vec![&new_governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::GovernanceAuthorityMismatch)
+    );
+
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetPriceSanityBounds::populate(
+                    new_governance_authority.pubkey(),
+                    new_config.exponent_range,
+                    new_config.max_conf_bps,
+                ),
+                &vec![&new_governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::GovernanceAuthorityMismatch)
+    );
+
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetAllowedFeedIds::populate(
+                    new_governance_authority.pubkey(),
+                    new_config.allowed_feed_ids.clone(),
+                ),
+                &vec![&new_governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::GovernanceAuthorityMismatch)
+    );
+
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetMinConsistencyLevel::populate(
+                    new_governance_authority.pubkey(),
+                    new_config.min_consistency_level,
+                ),
+                &vec![&new_governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::GovernanceAuthorityMismatch)
+    );
+
     assert_eq!(
         program_simulator
             .process_ix_with_default_compute_limit(
@@ -186,6 +407,45 @@ async fn test_governance() {
         current_config.minimum_signatures,
         initial_config.minimum_signatures
     );
+    assert_eq!(
+        current_config.pinned_guardian_set_hash,
+        initial_config.pinned_guardian_set_hash
+    );
+    assert_eq!(current_config.fee_recipients, initial_config.fee_recipients);
+    assert_eq!(
+        current_config.post_vaa_programs,
+        initial_config.post_vaa_programs
+    );
+    assert_eq!(
+        current_config.guardian_set_expiration_skew_seconds,
+        initial_config.guardian_set_expiration_skew_seconds
+    );
+    assert_eq!(
+        current_config.price_change_limits,
+        initial_config.price_change_limits
+    );
+    assert_eq!(
+        current_config.min_price_update_lifetime_slots,
+        initial_config.min_price_update_lifetime_slots
+    );
+    assert_eq!(
+        current_config.max_staleness_seconds,
+        initial_config.max_staleness_seconds
+    );
+    assert_eq!(
+        current_config.max_future_publish_time_seconds,
+        initial_config.max_future_publish_time_seconds
+    );
+    assert_eq!(current_config.exponent_range, initial_config.exponent_range);
+    assert_eq!(current_config.max_conf_bps, initial_config.max_conf_bps);
+    assert_eq!(
+        current_config.allowed_feed_ids,
+        initial_config.allowed_feed_ids
+    );
+    assert_eq!(
+        current_config.min_consistency_level,
+        initial_config.min_consistency_level
+    );
 
     program_simulator
         .process_ix_with_default_compute_limit(
@@ -221,6 +481,45 @@ async fn test_governance() {
         current_config.minimum_signatures,
         initial_config.minimum_signatures
     );
+    assert_eq!(
+        current_config.pinned_guardian_set_hash,
+        initial_config.pinned_guardian_set_hash
+    );
+    assert_eq!(current_config.fee_recipients, initial_config.fee_recipients);
+    assert_eq!(
+        current_config.post_vaa_programs,
+        initial_config.post_vaa_programs
+    );
+    assert_eq!(
+        current_config.guardian_set_expiration_skew_seconds,
+        initial_config.guardian_set_expiration_skew_seconds
+    );
+    assert_eq!(
+        current_config.price_change_limits,
+        initial_config.price_change_limits
+    );
+    assert_eq!(
+        current_config.min_price_update_lifetime_slots,
+        initial_config.min_price_update_lifetime_slots
+    );
+    assert_eq!(
+        current_config.max_staleness_seconds,
+        initial_config.max_staleness_seconds
+    );
+    assert_eq!(
+        current_config.max_future_publish_time_seconds,
+        initial_config.max_future_publish_time_seconds
+    );
+    assert_eq!(current_config.exponent_range, initial_config.exponent_range);
+    assert_eq!(current_config.max_conf_bps, initial_config.max_conf_bps);
+    assert_eq!(
+        current_config.allowed_feed_ids,
+        initial_config.allowed_feed_ids
+    );
+    assert_eq!(
+        current_config.min_consistency_level,
+        initial_config.min_consistency_level
+    );
 
     program_simulator
         .process_ix_with_default_compute_limit(
@@ -253,6 +552,45 @@ async fn test_governance() {
         current_config.minimum_signatures,
         initial_config.minimum_signatures
     );
+    assert_eq!(
+        current_config.pinned_guardian_set_hash,
+        initial_config.pinned_guardian_set_hash
+    );
+    assert_eq!(current_config.fee_recipients, initial_config.fee_recipients);
+    assert_eq!(
+        current_config.post_vaa_programs,
+        initial_config.post_vaa_programs
+    );
+    assert_eq!(
+        current_config.guardian_set_expiration_skew_seconds,
+        initial_config.guardian_set_expiration_skew_seconds
+    );
+    assert_eq!(
+        current_config.price_change_limits,
+        initial_config.price_change_limits
+    );
+    assert_eq!(
+        current_config.min_price_update_lifetime_slots,
+        initial_config.min_price_update_lifetime_slots
+    );
+    assert_eq!(
+        current_config.max_staleness_seconds,
+        initial_config.max_staleness_seconds
+    );
+    assert_eq!(
+        current_config.max_future_publish_time_seconds,
+        initial_config.max_future_publish_time_seconds
+    );
+    assert_eq!(current_config.exponent_range, initial_config.exponent_range);
+    assert_eq!(current_config.max_conf_bps, initial_config.max_conf_bps);
+    assert_eq!(
+        current_config.allowed_feed_ids,
+        initial_config.allowed_feed_ids
+    );
+    assert_eq!(
+        current_config.min_consistency_level,
+        initial_config.min_consistency_level
+    );
 
     // Minimum signatures can't be 0
     assert_eq!(
@@ -302,27 +640,51 @@ async fn test_governance() {
         current_config.minimum_signatures,
         new_config.minimum_signatures
     );
-
-    // Target is not defined yet
     assert_eq!(
-        program_simulator
-            .process_ix_with_default_compute_limit(
-                AcceptGovernanceAuthorityTransfer::populate(new_governance_authority.pubkey()),
-                &vec![&new_governance_authority],
-                None,
-            )
-            .await
-            .unwrap_err()
-            .unwrap(),
-        into_transaction_error(ReceiverError::NonexistentGovernanceAuthorityTransferRequest)
+        current_config.pinned_guardian_set_hash,
+        initial_config.pinned_guardian_set_hash
+    );
+    assert_eq!(current_config.fee_recipients, initial_config.fee_recipients);
+    assert_eq!(
+        current_config.post_vaa_programs,
+        initial_config.post_vaa_programs
+    );
+    assert_eq!(
+        current_config.guardian_set_expiration_skew_seconds,
+        initial_config.guardian_set_expiration_skew_seconds
+    );
+    assert_eq!(
+        current_config.price_change_limits,
+        initial_config.price_change_limits
+    );
+    assert_eq!(
+        current_config.min_price_update_lifetime_slots,
+        initial_config.min_price_update_lifetime_slots
+    );
+    assert_eq!(
+        current_config.max_staleness_seconds,
+        initial_config.max_staleness_seconds
+    );
+    assert_eq!(
+        current_config.max_future_publish_time_seconds,
+        initial_config.max_future_publish_time_seconds
+    );
+    assert_eq!(current_config.exponent_range, initial_config.exponent_range);
+    assert_eq!(current_config.max_conf_bps, initial_config.max_conf_bps);
+    assert_eq!(
+        current_config.allowed_feed_ids,
+        initial_config.allowed_feed_ids
+    );
+    assert_eq!(
+        current_config.min_consistency_level,
+        initial_config.min_consistency_level
     );
 
-    // Request transfer
     program_simulator
         .process_ix_with_default_compute_limit(
-            RequestGovernanceAuthorityTransfer::populate(
+            SetPinnedGuardianSetHash::populate(
                 governance_authority.pubkey(),
-                new_governance_authority.pubkey(),
+                new_config.pinned_guardian_set_hash,
             ),
             &vec![&governance_authority],
             None,
@@ -330,7 +692,6 @@ async fn test_governance() {
         .await
         .unwrap();
 
-
     current_config = program_simulator
         .get_anchor_account_data::<Config>(get_config_address())
         .await
@@ -339,10 +700,7 @@ async fn test_governance() {
         current_config.governance_authority,
         initial_config.governance_authority
     );
-    assert_eq!(
-        current_config.target_governance_authority,
-        Some(new_governance_authority.pubkey())
-    );
+    assert_eq!(current_config.target_governance_authority, None);
     assert_eq!(current_config.wormhole, new_config.wormhole);
     assert_eq!(
         current_config.valid_data_sources,
@@ -356,28 +714,170 @@ async fn test_governance() {
         current_config.minimum_signatures,
         new_config.minimum_signatures
     );
-
-    let poster = program_simulator.get_funded_keypair().await.unwrap();
-    // Random guy can't accept
-
     assert_eq!(
-        program_simulator
-            .process_ix_with_default_compute_limit(
-                AcceptGovernanceAuthorityTransfer::populate(poster.pubkey()),
-                &vec![&poster],
-                None,
-            )
-            .await
-            .unwrap_err()
-            .unwrap(),
-        into_transaction_error(ReceiverError::TargetGovernanceAuthorityMismatch)
+        current_config.pinned_guardian_set_hash,
+        new_config.pinned_guardian_set_hash
     );
-
-
-    // Undo the request
-    program_simulator
+    assert_eq!(current_config.fee_recipients, initial_config.fee_recipients);
+    assert_eq!(
+        current_config.post_vaa_programs,
+        initial_config.post_vaa_programs
+    );
+    assert_eq!(
+        current_config.guardian_set_expiration_skew_seconds,
+        initial_config.guardian_set_expiration_skew_seconds
+    );
+    assert_eq!(
+        current_config.price_change_limits,
+        initial_config.price_change_limits
+    );
+    assert_eq!(
+        current_config.min_price_update_lifetime_slots,
+        initial_config.min_price_update_lifetime_slots
+    );
+    assert_eq!(
+        current_config.max_staleness_seconds,
+        initial_config.max_staleness_seconds
+    );
+    assert_eq!(
+        current_config.max_future_publish_time_seconds,
+        initial_config.max_future_publish_time_seconds
+    );
+    assert_eq!(current_config.exponent_range, initial_config.exponent_range);
+    assert_eq!(current_config.max_conf_bps, initial_config.max_conf_bps);
+    assert_eq!(
+        current_config.allowed_feed_ids,
+        initial_config.allowed_feed_ids
+    );
+    assert_eq!(
+        current_config.min_consistency_level,
+        initial_config.min_consistency_level
+    );
+
+    // The weights must sum to 10000 basis points
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetFeeRecipients::populate(
+                    governance_authority.pubkey(),
+                    vec![FeeRecipient {
+                        recipient:  Pubkey::new_unique(),
+                        weight_bps: 9999,
+                    }],
+                ),
+                &vec![&governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::InvalidFeeRecipientWeights)
+    );
+
+    program_simulator
         .process_ix_with_default_compute_limit(
-            CancelGovernanceAuthorityTransfer::populate(governance_authority.pubkey()),
+            SetFeeRecipients::populate(
+                governance_authority.pubkey(),
+                new_config.fee_recipients.clone(),
+            ),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetPostVaaPrograms::populate(
+                governance_authority.pubkey(),
+                new_config.post_vaa_programs.clone(),
+            ),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetGuardianSetExpirationSkew::populate(
+                governance_authority.pubkey(),
+                new_config.guardian_set_expiration_skew_seconds,
+            ),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetPriceChangeLimits::populate(
+                governance_authority.pubkey(),
+                new_config.price_change_limits.clone(),
+            ),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetMinPriceUpdateLifetime::populate(
+                governance_authority.pubkey(),
+                new_config.min_price_update_lifetime_slots,
+            ),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetPublishTimeBounds::populate(
+                governance_authority.pubkey(),
+                new_config.max_staleness_seconds,
+                new_config.max_future_publish_time_seconds,
+            ),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetPriceSanityBounds::populate(
+                governance_authority.pubkey(),
+                new_config.exponent_range,
+                new_config.max_conf_bps,
+            ),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetAllowedFeedIds::populate(
+                governance_authority.pubkey(),
+                new_config.allowed_feed_ids.clone(),
+            ),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetMinConsistencyLevel::populate(
+                governance_authority.pubkey(),
+                new_config.min_consistency_level,
+            ),
             &vec![&governance_authority],
             None,
         )
@@ -406,9 +906,58 @@ async fn test_governance() {
         current_config.minimum_signatures,
         new_config.minimum_signatures
     );
+    assert_eq!(
+        current_config.pinned_guardian_set_hash,
+        new_config.pinned_guardian_set_hash
+    );
+    assert_eq!(current_config.fee_recipients, new_config.fee_recipients);
+    assert_eq!(
+        current_config.post_vaa_programs,
+        new_config.post_vaa_programs
+    );
+    assert_eq!(
+        current_config.guardian_set_expiration_skew_seconds,
+        new_config.guardian_set_expiration_skew_seconds
+    );
+    assert_eq!(
+        current_config.price_change_limits,
+        new_config.price_change_limits
+    );
+    assert_eq!(
+        current_config.min_price_update_lifetime_slots,
+        new_config.min_price_update_lifetime_slots
+    );
+    assert_eq!(
+        current_config.max_staleness_seconds,
+        new_config.max_staleness_seconds
+    );
+    assert_eq!(
+        current_config.max_future_publish_time_seconds,
+        new_config.max_future_publish_time_seconds
+    );
+    assert_eq!(current_config.exponent_range, new_config.exponent_range);
+    assert_eq!(current_config.max_conf_bps, new_config.max_conf_bps);
+    assert_eq!(current_config.allowed_feed_ids, new_config.allowed_feed_ids);
+    assert_eq!(
+        current_config.min_consistency_level,
+        new_config.min_consistency_level
+    );
 
+    // Target is not defined yet
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                AcceptGovernanceAuthorityTransfer::populate(new_governance_authority.pubkey()),
+                &vec![&new_governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::NonexistentGovernanceAuthorityTransferRequest)
+    );
 
-    // Redo the request
+    // Request transfer
     program_simulator
         .process_ix_with_default_compute_limit(
             RequestGovernanceAuthorityTransfer::populate(
@@ -421,6 +970,7 @@ async fn test_governance() {
         .await
         .unwrap();
 
+
     current_config = program_simulator
         .get_anchor_account_data::<Config>(get_config_address())
         .await
@@ -446,13 +996,65 @@ async fn test_governance() {
         current_config.minimum_signatures,
         new_config.minimum_signatures
     );
+    assert_eq!(
+        current_config.pinned_guardian_set_hash,
+        new_config.pinned_guardian_set_hash
+    );
+    assert_eq!(current_config.fee_recipients, new_config.fee_recipients);
+    assert_eq!(
+        current_config.post_vaa_programs,
+        new_config.post_vaa_programs
+    );
+    assert_eq!(
+        current_config.guardian_set_expiration_skew_seconds,
+        new_config.guardian_set_expiration_skew_seconds
+    );
+    assert_eq!(
+        current_config.price_change_limits,
+        new_config.price_change_limits
+    );
+    assert_eq!(
+        current_config.min_price_update_lifetime_slots,
+        new_config.min_price_update_lifetime_slots
+    );
+    assert_eq!(
+        current_config.max_staleness_seconds,
+        new_config.max_staleness_seconds
+    );
+    assert_eq!(
+        current_config.max_future_publish_time_seconds,
+        new_config.max_future_publish_time_seconds
+    );
+    assert_eq!(current_config.exponent_range, new_config.exponent_range);
+    assert_eq!(current_config.max_conf_bps, new_config.max_conf_bps);
+    assert_eq!(current_config.allowed_feed_ids, new_config.allowed_feed_ids);
+    assert_eq!(
+        current_config.min_consistency_level,
+        new_config.min_consistency_level
+    );
 
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    // Random guy can't accept
 
-    // New authority can accept
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                AcceptGovernanceAuthorityTransfer::populate(poster.pubkey()),
+                &vec![&poster],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::TargetGovernanceAuthorityMismatch)
+    );
+
+
+    // Undo the request
     program_simulator
         .process_ix_with_default_compute_limit(
-            AcceptGovernanceAuthorityTransfer::populate(new_governance_authority.pubkey()),
-            &vec![&new_governance_authority],
+            CancelGovernanceAuthorityTransfer::populate(governance_authority.pubkey()),
+            &vec![&governance_authority],
             None,
         )
         .await
@@ -462,13 +1064,70 @@ async fn test_governance() {
         .get_anchor_account_data::<Config>(get_config_address())
         .await
         .unwrap();
-    assert_eq!(current_config, new_config);
+    assert_eq!(
+        current_config.governance_authority,
+        initial_config.governance_authority
+    );
+    assert_eq!(current_config.target_governance_authority, None);
+    assert_eq!(current_config.wormhole, new_config.wormhole);
+    assert_eq!(
+        current_config.valid_data_sources,
+        new_config.valid_data_sources
+    );
+    assert_eq!(
+        current_config.single_update_fee_in_lamports,
+        new_config.single_update_fee_in_lamports
+    );
+    assert_eq!(
+        current_config.minimum_signatures,
+        new_config.minimum_signatures
+    );
+    assert_eq!(
+        current_config.pinned_guardian_set_hash,
+        new_config.pinned_guardian_set_hash
+    );
+    assert_eq!(current_config.fee_recipients, new_config.fee_recipients);
+    assert_eq!(
+        current_config.post_vaa_programs,
+        new_config.post_vaa_programs
+    );
+    assert_eq!(
+        current_config.guardian_set_expiration_skew_seconds,
+        new_config.guardian_set_expiration_skew_seconds
+    );
+    assert_eq!(
+        current_config.price_change_limits,
+        new_config.price_change_limits
+    );
+    assert_eq!(
+        current_config.min_price_update_lifetime_slots,
+        new_config.min_price_update_lifetime_slots
+    );
+    assert_eq!(
+        current_config.max_staleness_seconds,
+        new_config.max_staleness_seconds
+    );
+    assert_eq!(
+        current_config.max_future_publish_time_seconds,
+        new_config.max_future_publish_time_seconds
+    );
+    assert_eq!(current_config.exponent_range, new_config.exponent_range);
+    assert_eq!(current_config.max_conf_bps, new_config.max_conf_bps);
+    assert_eq!(current_config.allowed_feed_ids, new_config.allowed_feed_ids);
+    assert_eq!(
+        current_config.min_consistency_level,
+        new_config.min_consistency_level
+    );
 
-    // Now the new authority can do stuff
+
+    // Redo the request
     program_simulator
         .process_ix_with_default_compute_limit(
-            SetFee::populate(new_governance_authority.pubkey(), 9),
-            &vec![&new_governance_authority],
+            RequestGovernanceAuthorityTransfer::populate(
+                governance_authority.pubkey(),
+                new_governance_authority.pubkey(),
+            ),
+            &vec![&governance_authority],
             None,
         )
         .await
@@ -480,17 +1139,548 @@ async fn test_governance() {
         .unwrap();
     assert_eq!(
         current_config.governance_authority,
-        new_config.governance_authority
+        initial_config.governance_authority
+    );
+    assert_eq!(
+        current_config.target_governance_authority,
+        Some(new_governance_authority.pubkey())
     );
-    assert_eq!(current_config.target_governance_authority, None);
     assert_eq!(current_config.wormhole, new_config.wormhole);
     assert_eq!(
         current_config.valid_data_sources,
         new_config.valid_data_sources
     );
-    assert_eq!(current_config.single_update_fee_in_lamports, 9);
+    assert_eq!(
+        current_config.single_update_fee_in_lamports,
+        new_config.single_update_fee_in_lamports
+    );
     assert_eq!(
         current_config.minimum_signatures,
         new_config.minimum_signatures
     );
+    assert_eq!(
+        current_config.pinned_guardian_set_hash,
+        new_config.pinned_guardian_set_hash
+    );
+    assert_eq!(current_config.fee_recipients, new_config.fee_recipients);
+    assert_eq!(
+        current_config.post_vaa_programs,
+        new_config.post_vaa_programs
+    );
+    assert_eq!(
+        current_config.guardian_set_expiration_skew_seconds,
+        new_config.guardian_set_expiration_skew_seconds
+    );
+    assert_eq!(
+        current_config.price_change_limits,
+        new_config.price_change_limits
+    );
+    assert_eq!(
+        current_config.min_price_update_lifetime_slots,
+        new_config.min_price_update_lifetime_slots
+    );
+    assert_eq!(
+        current_config.max_staleness_seconds,
+        new_config.max_staleness_seconds
+    );
+    assert_eq!(
+        current_config.max_future_publish_time_seconds,
+        new_config.max_future_publish_time_seconds
+    );
+    assert_eq!(current_config.exponent_range, new_config.exponent_range);
+    assert_eq!(current_config.max_conf_bps, new_config.max_conf_bps);
+    assert_eq!(current_config.allowed_feed_ids, new_config.allowed_feed_ids);
+    assert_eq!(
+        current_config.min_consistency_level,
+        new_config.min_consistency_level
+    );
+
+
+    // New authority can accept
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            AcceptGovernanceAuthorityTransfer::populate(new_governance_authority.pubkey()),
+            &vec![&new_governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    current_config = program_simulator
+        .get_anchor_account_data::<Config>(get_config_address())
+        .await
+        .unwrap();
+    assert_eq!(current_config, new_config);
+
+    // Now the new authority can do stuff
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetFee::populate(new_governance_authority.pubkey(), 9),
+            &vec![&new_governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    current_config = program_simulator
+        .get_anchor_account_data::<Config>(get_config_address())
+        .await
+        .unwrap();
+    assert_eq!(
+        current_config.governance_authority,
+        new_config.governance_authority
+    );
+    assert_eq!(current_config.target_governance_authority, None);
+    assert_eq!(current_config.wormhole, new_config.wormhole);
+    assert_eq!(
+        current_config.valid_data_sources,
+        new_config.valid_data_sources
+    );
+    assert_eq!(current_config.single_update_fee_in_lamports, 9);
+    assert_eq!(
+        current_config.minimum_signatures,
+        new_config.minimum_signatures
+    );
+    assert_eq!(
+        current_config.pinned_guardian_set_hash,
+        new_config.pinned_guardian_set_hash
+    );
+    assert_eq!(current_config.fee_recipients, new_config.fee_recipients);
+    assert_eq!(
+        current_config.post_vaa_programs,
+        new_config.post_vaa_programs
+    );
+    assert_eq!(
+        current_config.guardian_set_expiration_skew_seconds,
+        new_config.guardian_set_expiration_skew_seconds
+    );
+    assert_eq!(
+        current_config.price_change_limits,
+        new_config.price_change_limits
+    );
+    assert_eq!(
+        current_config.min_price_update_lifetime_slots,
+        new_config.min_price_update_lifetime_slots
+    );
+    assert_eq!(
+        current_config.max_staleness_seconds,
+        new_config.max_staleness_seconds
+    );
+    assert_eq!(
+        current_config.max_future_publish_time_seconds,
+        new_config.max_future_publish_time_seconds
+    );
+    assert_eq!(current_config.exponent_range, new_config.exponent_range);
+    assert_eq!(current_config.max_conf_bps, new_config.max_conf_bps);
+    assert_eq!(current_config.allowed_feed_ids, new_config.allowed_feed_ids);
+    assert_eq!(
+        current_config.min_consistency_level,
+        new_config.min_consistency_level
+    );
+}
+
+#[tokio::test]
+async fn test_resize_config() {
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses: _,
+        governance_authority,
+    } = setup_pyth_receiver(vec![], WrongSetupOption::None).await;
+
+    let initial_config = program_simulator
+        .get_anchor_account_data::<Config>(get_config_address())
+        .await
+        .unwrap();
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+
+    // Only the governance authority can resize the config account
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                ResizeConfig::populate(poster.pubkey(), LEGACY_CONFIG_LEN),
+                &vec![&poster],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::GovernanceAuthorityMismatch)
+    );
+
+    // Shrink the config account to the size of the accounts created by earlier versions of the program
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            ResizeConfig::populate(governance_authority.pubkey(), LEGACY_CONFIG_LEN),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        program_simulator
+            .get_anchor_account_data::<Config>(get_config_address())
+            .await
+            .unwrap(),
+        initial_config
+    );
+
+    // A legacy config account fits 6 fee recipients
+    let fee_recipients = |count: u16| -> Vec<FeeRecipient> {
+        (0..count)
+            .map(|i| FeeRecipient {
+                recipient:  Pubkey::new_unique(),
+                weight_bps: if i == 0 {
+                    TOTAL_FEE_WEIGHT_BPS - (count - 1)
+                } else {
+                    1
+                },
+            })
+            .collect()
+    };
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetFeeRecipients::populate(governance_authority.pubkey(), fee_recipients(7)),
+                &vec![&governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::ConfigAccountTooSmall)
+    );
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetFeeRecipients::populate(governance_authority.pubkey(), fee_recipients(6)),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetFeeRecipients::populate(governance_authority.pubkey(), vec![]),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    // A legacy config account fits 6 post VAA programs
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetPostVaaPrograms::populate(
+                    governance_authority.pubkey(),
+                    vec![Pubkey::new_unique(); 7]
+                ),
+                &vec![&governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::ConfigAccountTooSmall)
+    );
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetPostVaaPrograms::populate(
+                governance_authority.pubkey(),
+                vec![Pubkey::new_unique(); 6],
+            ),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetPostVaaPrograms::populate(governance_authority.pubkey(), vec![]),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    // A legacy config account fits 5 price change limits
+    let price_change_limits = |count: usize| {
+        vec![
+            PriceChangeLimit {
+                feed_id:        [1; 32],
+                max_change_bps: 1000,
+                window_seconds: 60,
+            };
+            count
+        ]
+    };
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetPriceChangeLimits::populate(
+                    governance_authority.pubkey(),
+                    price_change_limits(6)
+                ),
+                &vec![&governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::ConfigAccountTooSmall)
+    );
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetPriceChangeLimits::populate(governance_authority.pubkey(), price_change_limits(5)),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetPriceChangeLimits::populate(governance_authority.pubkey(), vec![]),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    // A legacy config account fits 6 allowed feed ids
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetAllowedFeedIds::populate(governance_authority.pubkey(), vec![[1; 32]; 7]),
+                &vec![&governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::ConfigAccountTooSmall)
+    );
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetAllowedFeedIds::populate(governance_authority.pubkey(), vec![[1; 32]; 6]),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    // The config account can't be shrunk below the size of the config
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                ResizeConfig::populate(governance_authority.pubkey(), 200),
+                &vec![&governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::ConfigAccountTooSmall)
+    );
+
+    // The governance authority pays the rent of the extra space
+    let governance_authority_balance = program_simulator
+        .get_balance(governance_authority.pubkey())
+        .await
+        .unwrap();
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            ResizeConfig::populate(governance_authority.pubkey(), Config::LEN as u32),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        program_simulator
+            .get_balance(get_config_address())
+            .await
+            .unwrap(),
+        Rent::default().minimum_balance(Config::LEN)
+    );
+    assert!(
+        program_simulator
+            .get_balance(governance_authority.pubkey())
+            .await
+            .unwrap()
+            <= governance_authority_balance
+                - (Rent::default().minimum_balance(Config::LEN)
+                    - Rent::default().minimum_balance(LEGACY_CONFIG_LEN as usize))
+    );
+
+    // A config account of Config::LEN bytes fits 13 allowed feed ids
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetAllowedFeedIds::populate(governance_authority.pubkey(), vec![[1; 32]; 13]),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetAllowedFeedIds::populate(governance_authority.pubkey(), vec![[1; 32]; 14]),
+                &vec![&governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::ConfigAccountTooSmall)
+    );
+
+    let current_config = program_simulator
+        .get_anchor_account_data::<Config>(get_config_address())
+        .await
+        .unwrap();
+    assert_eq!(current_config.allowed_feed_ids, vec![[1; 32]; 13]);
+}
+
+#[tokio::test]
+async fn test_migrate_config() {
+    let governance_authority = Keypair::new();
+    let previous_governance_authority = Pubkey::new_unique();
+    // Part of the emitter ends up where `pinned_guardian_set_hash` starts after the shift below, like with most real emitters its bytes aren't 0 or 1
+    let data_source = DataSource {
+        chain:   26,
+        emitter: Pubkey::from([0x9b; 32]),
+    };
+    let legacy_config = LegacyConfig {
+        governance_authority:          governance_authority.pubkey(),
+        target_governance_authority:   None,
+        wormhole:                      Pubkey::new_unique(),
+        valid_data_sources:            vec![data_source],
+        single_update_fee_in_lamports: 1,
+        minimum_signatures:            5,
+    };
+
+    // A config account written by the first version of the program after a governance authority transfer: the transfer request was serialized with `target_governance_authority` set, and accepting it serialized a config 32 bytes shorter over it without zeroing the end of the account
+    let mut legacy_config_data = vec![0; LEGACY_CONFIG_LEN as usize];
+    for config in [
+        LegacyConfig {
+            governance_authority: previous_governance_authority,
+            target_governance_authority: Some(governance_authority.pubkey()),
+            ..legacy_config.clone()
+        },
+        legacy_config.clone(),
+    ] {
+        let config_bytes = (Config::discriminator(), config).try_to_vec().unwrap();
+        legacy_config_data[..config_bytes.len()].copy_from_slice(&config_bytes);
+    }
+    let legacy_config_len = 8 + legacy_config.try_to_vec().unwrap().len();
+    assert!(legacy_config_data[legacy_config_len] > 1);
+
+    let mut program_test = ProgramTest::default();
+    program_test.add_program("pyth_solana_receiver", pyth_solana_receiver::ID, None);
+    program_test.add_account(
+        get_config_address(),
+        Account {
+            lamports:   Rent::default().minimum_balance(legacy_config_data.len()),
+            data:       legacy_config_data,
+            owner:      pyth_solana_receiver::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    let mut program_simulator = ProgramSimulator::start_from_program_test(program_test).await;
+    program_simulator
+        .airdrop(&governance_authority.pubkey(), LAMPORTS_PER_SOL)
+        .await
+        .unwrap();
+
+    // The stale byte is read as the tag of `pinned_guardian_set_hash`, so the config can't be loaded
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetFee::populate(governance_authority.pubkey(), 2),
+                &vec![&governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ErrorCode::AccountDidNotDeserialize)
+    );
+
+    // Only the governance authority can migrate the config
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                MigrateConfig::populate(poster.pubkey()),
+                &vec![&poster],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::GovernanceAuthorityMismatch)
+    );
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            MigrateConfig::populate(governance_authority.pubkey()),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        program_simulator
+            .get_anchor_account_data::<Config>(get_config_address())
+            .await
+            .unwrap(),
+        Config::from(legacy_config.clone())
+    );
+    let config_data = program_simulator
+        .get_account_data(get_config_address())
+        .await
+        .unwrap();
+    assert_eq!(config_data.len(), LEGACY_CONFIG_LEN as usize);
+    assert!(config_data[legacy_config_len..]
+        .iter()
+        .all(|byte| *byte == 0));
+
+    // The governance instructions work again, and shrinking the config doesn't leave stale bytes anymore
+    let new_governance_authority = program_simulator.get_funded_keypair().await.unwrap();
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            RequestGovernanceAuthorityTransfer::populate(
+                governance_authority.pubkey(),
+                new_governance_authority.pubkey(),
+            ),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            AcceptGovernanceAuthorityTransfer::populate(new_governance_authority.pubkey()),
+            &vec![&new_governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+    let config_data = program_simulator
+        .get_account_data(get_config_address())
+        .await
+        .unwrap();
+    assert!(config_data[legacy_config_len..]
+        .iter()
+        .all(|byte| *byte == 0));
+    assert_eq!(
+        program_simulator
+            .get_anchor_account_data::<Config>(get_config_address())
+            .await
+            .unwrap(),
+        Config {
+            governance_authority: new_governance_authority.pubkey(),
+            ..Config::from(legacy_config)
+        }
+    );
 }
@@ -13,14 +13,19 @@ use {
             PostUpdateAtomic,
             SetDataSources,
             SetFee,
+            SetFeeRecipients,
         },
         sdk::{
             deserialize_accumulator_update_data,
+            get_fee_recipient_account_metas,
             DEFAULT_TREASURY_ID,
         },
     },
     pyth_solana_receiver_sdk::{
-        config::DataSource,
+        config::{
+            DataSource,
+            FeeRecipient,
+        },
         price_update::{
             PriceUpdateV2,
             VerificationLevel,
@@ -32,10 +37,12 @@ use {
             create_accumulator_message,
             create_dummy_price_feed_message,
             create_dummy_twap_message,
+            create_vaa_from_payload,
             trim_vaa_signatures,
             DEFAULT_DATA_SOURCE,
             SECONDARY_DATA_SOURCE,
         },
+        wire::v1::ACCUMULATOR_UPDATE_WORMHOLE_VERIFICATION_MAGIC,
     },
     solana_program::{
         native_token::LAMPORTS_PER_SOL,
@@ -71,7 +78,7 @@ async fn test_invalid_wormhole_message() {
     let poster = program_simulator.get_funded_keypair().await.unwrap();
     let price_update_keypair = Keypair::new();
 
-    // corrupted wormhole message
+    // the magic of the wormhole message is corrupted, so it's not an accumulator message
     assert_eq!(
         program_simulator
             .process_ix_with_default_compute_limit(
@@ -91,6 +98,36 @@ async fn test_invalid_wormhole_message() {
             .await
             .unwrap_err()
             .unwrap(),
+        into_transaction_error(ReceiverError::NotAnAccumulatorMessage)
+    );
+
+    // corrupted wormhole message, the magic is right but the payload is truncated
+    let truncated_vaa = serde_wormhole::to_vec(&create_vaa_from_payload(
+        ACCUMULATOR_UPDATE_WORMHOLE_VERIFICATION_MAGIC,
+        DEFAULT_DATA_SOURCE.address,
+        DEFAULT_DATA_SOURCE.chain,
+        0,
+    ))
+    .unwrap();
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                PostUpdateAtomic::populate(
+                    poster.pubkey(),
+                    poster.pubkey(),
+                    price_update_keypair.pubkey(),
+                    BRIDGE_ID,
+                    DEFAULT_GUARDIAN_SET_INDEX,
+                    truncated_vaa,
+                    merkle_price_updates[0].clone(),
+                    DEFAULT_TREASURY_ID
+                ),
+                &vec![&poster, &price_update_keypair],
+                None
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
         into_transaction_error(ReceiverError::InvalidWormholeMessage)
     );
 }
@@ -491,6 +528,11 @@ async fn test_post_price_update_from_vaa() {
         .await
         .unwrap();
 
+    let poster_balance_before = program_simulator
+        .get_balance(poster.pubkey())
+        .await
+        .unwrap();
+
     program_simulator
         .process_ix_with_default_compute_limit(
             PostUpdateAtomic::populate(
@@ -516,6 +558,16 @@ async fn test_post_price_update_from_vaa() {
     )
     .await;
 
+    // The poster is charged exactly the fee, the rest of their balance is left untouched
+    assert_eq!(
+        poster_balance_before
+            - program_simulator
+                .get_balance(poster.pubkey())
+                .await
+                .unwrap(),
+        LAMPORTS_PER_SOL
+    );
+
     price_update_account = program_simulator
         .get_anchor_account_data::<PriceUpdateV2>(price_update_keypair.pubkey())
         .await
@@ -579,3 +631,157 @@ async fn test_post_price_update_from_vaa() {
         into_transaction_error(ReceiverError::WrongWriteAuthority)
     );
 }
+
+#[tokio::test]
+async fn test_fee_recipients() {
+    let feed_1 = create_dummy_price_feed_message(100);
+    let feed_2 = create_dummy_price_feed_message(200);
+    let message = create_accumulator_message(&[feed_1, feed_2], &[feed_1, feed_2], false, false);
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses: _,
+        governance_authority,
+    } = setup_pyth_receiver(
+        vec![serde_wormhole::from_slice(&vaa).unwrap()],
+        WrongSetupOption::None,
+    )
+    .await;
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let price_update_keypair = Keypair::new();
+    let recipient_1 = program_simulator.get_funded_keypair().await.unwrap();
+    let recipient_2 = program_simulator.get_funded_keypair().await.unwrap();
+    let fee_recipients = vec![
+        FeeRecipient {
+            recipient:  recipient_1.pubkey(),
+            weight_bps: 7000,
+        },
+        FeeRecipient {
+            recipient:  recipient_2.pubkey(),
+            weight_bps: 3000,
+        },
+    ];
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetFee::populate(governance_authority.pubkey(), 1001),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetFeeRecipients::populate(governance_authority.pubkey(), fee_recipients.clone()),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    let post_update_atomic_ix = PostUpdateAtomic::populate(
+        poster.pubkey(),
+        poster.pubkey(),
+        price_update_keypair.pubkey(),
+        BRIDGE_ID,
+        DEFAULT_GUARDIAN_SET_INDEX,
+        vaa.clone(),
+        merkle_price_updates[0].clone(),
+        DEFAULT_TREASURY_ID,
+    );
+
+    // The fee recipient accounts are missing
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                post_update_atomic_ix.clone(),
+                &vec![&poster, &price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::MissingFeeRecipientAccounts)
+    );
+
+    // Only one of the fee recipient accounts is passed
+    let mut missing_recipient_ix = post_update_atomic_ix.clone();
+    missing_recipient_ix
+        .accounts
+        .extend(get_fee_recipient_account_metas(&fee_recipients[..1]));
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                missing_recipient_ix,
+                &vec![&poster, &price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::WrongNumberOfFeeRecipientAccounts)
+    );
+
+    // The fee recipient accounts are in the wrong order
+    let mut wrong_order_ix = post_update_atomic_ix.clone();
+    wrong_order_ix
+        .accounts
+        .extend(get_fee_recipient_account_metas(&[
+            fee_recipients[1].clone(),
+            fee_recipients[0].clone(),
+        ]));
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                wrong_order_ix,
+                &vec![&poster, &price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::WrongFeeRecipientAccounts)
+    );
+
+    let poster_balance_before = program_simulator
+        .get_balance(poster.pubkey())
+        .await
+        .unwrap();
+
+    let mut ix = post_update_atomic_ix.clone();
+    ix.accounts
+        .extend(get_fee_recipient_account_metas(&fee_recipients));
+    program_simulator
+        .process_ix_with_default_compute_limit(ix, &vec![&poster, &price_update_keypair], None)
+        .await
+        .unwrap();
+
+    // The fee is split 70/30, the remainder of the rounding goes to the first recipient and nothing goes to the treasury
+    assert_eq!(
+        program_simulator
+            .get_balance(recipient_1.pubkey())
+            .await
+            .unwrap(),
+        LAMPORTS_PER_SOL + 701
+    );
+    assert_eq!(
+        program_simulator
+            .get_balance(recipient_2.pubkey())
+            .await
+            .unwrap(),
+        LAMPORTS_PER_SOL + 300
+    );
+    assert_treasury_balance(&mut program_simulator, 0, DEFAULT_TREASURY_ID).await;
+
+    // The poster pays the fee and the rent of the new price update account
+    assert_eq!(
+        poster_balance_before
+            - program_simulator
+                .get_balance(poster.pubkey())
+                .await
+                .unwrap(),
+        1001 + Rent::default().minimum_balance(PriceUpdateV2::LEN)
+    );
+}
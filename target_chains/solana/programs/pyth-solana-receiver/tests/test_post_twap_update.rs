@@ -0,0 +1,207 @@
+use {
+    common_test_utils::{
+        assert_treasury_balance,
+        setup_pyth_receiver,
+        ProgramTestFixtures,
+        WrongSetupOption,
+    },
+    program_simulator::into_transaction_error,
+    pyth_solana_receiver::{
+        error::ReceiverError,
+        instruction::{
+            PostTwapUpdate,
+            PostTwapUpdateWithMinConsistency,
+        },
+        sdk::{
+            deserialize_accumulator_update_data,
+            DEFAULT_TREASURY_ID,
+        },
+    },
+    pyth_solana_receiver_sdk::price_update::{
+        TwapUpdate,
+        VerificationLevel,
+    },
+    pythnet_sdk::{
+        messages::{
+            Message,
+            TwapMessage,
+        },
+        test_utils::{
+            create_accumulator_message,
+            create_dummy_feed_id,
+            create_dummy_price_feed_message,
+        },
+    },
+    solana_sdk::{
+        rent::Rent,
+        signature::Keypair,
+        signer::Signer,
+    },
+};
+
+
+#[tokio::test]
+async fn test_post_twap_update() {
+    let twap_message = TwapMessage {
+        feed_id:           create_dummy_feed_id(100),
+        cumulative_price:  -(1 << 100),
+        cumulative_conf:   1 << 90,
+        num_down_slots:    3,
+        exponent:          -8,
+        publish_time:      1_700_000_100,
+        prev_publish_time: 1_700_000_000,
+        publish_slot:      250_000_000,
+    };
+    let invalid_twap_message = TwapMessage {
+        prev_publish_time: 1_700_000_200,
+        ..twap_message
+    };
+    let feed_1 = Message::TwapMessage(twap_message);
+    let feed_2 = Message::TwapMessage(invalid_twap_message);
+    let feed_3 = create_dummy_price_feed_message(300);
+    let message = create_accumulator_message(
+        &[feed_1, feed_2, feed_3],
+        &[feed_1, feed_2, feed_3],
+        false,
+        false,
+    );
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses,
+        governance_authority: _,
+    } = setup_pyth_receiver(
+        vec![serde_wormhole::from_slice(&vaa).unwrap()],
+        WrongSetupOption::None,
+    )
+    .await;
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let twap_update_keypair = Keypair::new();
+
+    // price feed messages can't be posted as TWAP updates
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                PostTwapUpdate::populate(
+                    poster.pubkey(),
+                    poster.pubkey(),
+                    encoded_vaa_addresses[0],
+                    twap_update_keypair.pubkey(),
+                    merkle_price_updates[2].clone(),
+                    DEFAULT_TREASURY_ID,
+                ),
+                &vec![&poster, &twap_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::UnsupportedMessageType)
+    );
+
+    // the previous publish time is after the publish time
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                PostTwapUpdate::populate(
+                    poster.pubkey(),
+                    poster.pubkey(),
+                    encoded_vaa_addresses[0],
+                    twap_update_keypair.pubkey(),
+                    merkle_price_updates[1].clone(),
+                    DEFAULT_TREASURY_ID,
+                ),
+                &vec![&poster, &twap_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::InvalidTwapMessage)
+    );
+
+    // the consistency level of the test VAAs is 0, so a caller that requires a higher one can't post them
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                PostTwapUpdateWithMinConsistency::populate(
+                    poster.pubkey(),
+                    poster.pubkey(),
+                    encoded_vaa_addresses[0],
+                    twap_update_keypair.pubkey(),
+                    merkle_price_updates[0].clone(),
+                    DEFAULT_TREASURY_ID,
+                    1,
+                ),
+                &vec![&poster, &twap_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::InsufficientConsistencyLevel)
+    );
+
+    assert_treasury_balance(&mut program_simulator, 0, DEFAULT_TREASURY_ID).await;
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            PostTwapUpdate::populate(
+                poster.pubkey(),
+                poster.pubkey(),
+                encoded_vaa_addresses[0],
+                twap_update_keypair.pubkey(),
+                merkle_price_updates[0].clone(),
+                DEFAULT_TREASURY_ID,
+            ),
+            &vec![&poster, &twap_update_keypair],
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert_treasury_balance(
+        &mut program_simulator,
+        Rent::default().minimum_balance(0),
+        DEFAULT_TREASURY_ID,
+    )
+    .await;
+
+    let twap_update_account = program_simulator
+        .get_anchor_account_data::<TwapUpdate>(twap_update_keypair.pubkey())
+        .await
+        .unwrap();
+
+    assert_eq!(twap_update_account.write_authority, poster.pubkey());
+    assert_eq!(
+        twap_update_account.verification_level,
+        VerificationLevel::Full
+    );
+    assert_eq!(twap_update_account.feed_id, twap_message.feed_id);
+    assert_eq!(
+        twap_update_account.cumulative_price,
+        twap_message.cumulative_price
+    );
+    assert_eq!(
+        twap_update_account.cumulative_conf,
+        twap_message.cumulative_conf
+    );
+    assert_eq!(
+        twap_update_account.num_down_slots,
+        twap_message.num_down_slots
+    );
+    assert_eq!(twap_update_account.exponent, twap_message.exponent);
+    assert_eq!(twap_update_account.publish_time, twap_message.publish_time);
+    assert_eq!(
+        twap_update_account.prev_publish_time,
+        twap_message.prev_publish_time
+    );
+    assert_eq!(twap_update_account.publish_slot, twap_message.publish_slot);
+    assert_eq!(
+        twap_update_account.posted_slot,
+        program_simulator.get_clock().await.unwrap().slot
+    );
+}
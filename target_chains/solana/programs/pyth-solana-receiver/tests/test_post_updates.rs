@@ -1,7 +1,12 @@
 use {
+    anchor_lang::{
+        AnchorDeserialize,
+        AnchorSerialize,
+    },
     common_test_utils::{
         assert_treasury_balance,
         setup_pyth_receiver,
+        setup_pyth_receiver_with_accounts,
         ProgramTestFixtures,
         WrongSetupOption,
     },
@@ -9,8 +14,23 @@ use {
     pyth_solana_receiver::{
         error::ReceiverError,
         instruction::{
+            ClosePriceUpdate,
             PostUpdate,
+            PostUpdateWithMinConsistency,
+            PostUpdatesFromAccountData,
+            PostUpdatesFromVaas,
+            PostUpdatesWithTag,
             ReclaimRent,
+            SetAllowedFeedIds,
+            SetMinConsistencyLevel,
+            SetMinPriceUpdateLifetime,
+            SetPostVaaPrograms,
+            SetPriceChangeLimits,
+            SetPriceSanityBounds,
+            SetPublishTimeBounds,
+            SetWormholeAddress,
+            VerifyUpdate,
+            VerifyUpdatesView,
         },
         sdk::{
             deserialize_accumulator_update_data,
@@ -18,23 +38,53 @@ use {
             DEFAULT_TREASURY_ID,
         },
     },
-    pyth_solana_receiver_sdk::price_update::{
-        PriceUpdateV2,
-        VerificationLevel,
+    pyth_solana_receiver_sdk::{
+        config::{
+            Config,
+            ExponentRange,
+            PriceChangeBaseline,
+            PriceChangeLimit,
+        },
+        pda::{
+            get_config_address,
+            get_price_change_baseline_address,
+        },
+        price_update::{
+            PriceUpdateV2,
+            VerificationLevel,
+        },
+        EventMode,
+        PostedUpdatesSummary,
+        TaggedPostedUpdatesSummary,
+        MAX_VIEW_UPDATES,
     },
     pythnet_sdk::{
-        messages::Message,
+        accumulators::merkle::MerklePath,
+        hashers::keccak256_160::Keccak160,
+        messages::{
+            Message,
+            PriceFeedMessage,
+        },
         test_utils::{
             create_accumulator_message,
             create_dummy_price_feed_message,
         },
+        wire::v1::MerklePriceUpdate,
+    },
+    solana_program::{
+        instruction::AccountMeta,
+        pubkey::Pubkey,
     },
-    solana_program::pubkey::Pubkey,
     solana_sdk::{
+        account::Account,
         rent::Rent,
         signature::Keypair,
         signer::Signer,
     },
+    std::time::{
+        SystemTime,
+        UNIX_EPOCH,
+    },
 };
 
 
@@ -49,7 +99,7 @@ async fn test_post_update() {
     let ProgramTestFixtures {
         mut program_simulator,
         encoded_vaa_addresses,
-        governance_authority: _,
+        governance_authority,
     } = setup_pyth_receiver(
         vec![serde_wormhole::from_slice(&vaa).unwrap()],
         WrongSetupOption::None,
@@ -163,6 +213,36 @@ async fn test_post_update() {
         into_transaction_error(ReceiverError::WrongWriteAuthority)
     );
 
+    // The account can't be closed until the minimum lifetime has passed since its last update
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetMinPriceUpdateLifetime::populate(governance_authority.pubkey(), 10),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                ReclaimRent::populate(poster.pubkey(), price_update_keypair.pubkey()),
+                &vec![&poster],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::PriceUpdateTooRecent)
+    );
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetMinPriceUpdateLifetime::populate(governance_authority.pubkey(), 0),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
     program_simulator
         .process_ix_with_default_compute_limit(
             ReclaimRent::populate(poster.pubkey(), price_update_keypair.pubkey()),
@@ -181,6 +261,259 @@ async fn test_post_update() {
     );
 }
 
+#[tokio::test]
+async fn test_close_price_update() {
+    let feed_1 = create_dummy_price_feed_message(100);
+    let message = create_accumulator_message(&[feed_1], &[feed_1], false, false);
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses,
+        governance_authority,
+    } = setup_pyth_receiver(
+        vec![serde_wormhole::from_slice(&vaa).unwrap()],
+        WrongSetupOption::None,
+    )
+    .await;
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let price_update_keypair = Keypair::new();
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            PostUpdate::populate(
+                poster.pubkey(),
+                poster.pubkey(),
+                encoded_vaa_addresses[0],
+                price_update_keypair.pubkey(),
+                merkle_price_updates[0].clone(),
+                DEFAULT_TREASURY_ID,
+            ),
+            &vec![&poster, &price_update_keypair],
+            None,
+        )
+        .await
+        .unwrap();
+
+    let recipient = Pubkey::new_unique();
+
+    // This poster doesn't have the write authority
+    let poster_2 = program_simulator.get_funded_keypair().await.unwrap();
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                ClosePriceUpdate::populate(
+                    poster_2.pubkey(),
+                    price_update_keypair.pubkey(),
+                    recipient,
+                ),
+                &vec![&poster_2],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::WrongWriteAuthority)
+    );
+
+    // The account can't be closed until the minimum lifetime has passed since its last update
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetMinPriceUpdateLifetime::populate(governance_authority.pubkey(), 10),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                ClosePriceUpdate::populate(
+                    poster.pubkey(),
+                    price_update_keypair.pubkey(),
+                    recipient,
+                ),
+                &vec![&poster],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::PriceUpdateTooRecent)
+    );
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetMinPriceUpdateLifetime::populate(governance_authority.pubkey(), 0),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            ClosePriceUpdate::populate(poster.pubkey(), price_update_keypair.pubkey(), recipient),
+            &vec![&poster],
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        program_simulator
+            .get_balance(price_update_keypair.pubkey())
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        program_simulator.get_balance(recipient).await.unwrap(),
+        Rent::default().minimum_balance(PriceUpdateV2::LEN)
+    );
+}
+
+#[tokio::test]
+async fn test_verify_update() {
+    let feed_1 = create_dummy_price_feed_message(100);
+    let feed_2 = create_dummy_price_feed_message(200);
+    let message = create_accumulator_message(&[feed_1, feed_2], &[feed_1, feed_2], false, false);
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+
+    let other_message = create_accumulator_message(&[feed_2], &[feed_2], false, false);
+    let (_, other_merkle_price_updates) =
+        deserialize_accumulator_update_data(other_message).unwrap();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses,
+        governance_authority: _,
+    } = setup_pyth_receiver(
+        vec![serde_wormhole::from_slice(&vaa).unwrap()],
+        WrongSetupOption::None,
+    )
+    .await;
+
+    // No signer other than the fee payer of the transaction is needed
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            VerifyUpdate::populate(encoded_vaa_addresses[0], merkle_price_updates[0].clone()),
+            &vec![],
+            None,
+        )
+        .await
+        .unwrap();
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            VerifyUpdate::populate(encoded_vaa_addresses[0], merkle_price_updates[1].clone()),
+            &vec![],
+            None,
+        )
+        .await
+        .unwrap();
+
+    // Nothing is charged
+    assert_treasury_balance(&mut program_simulator, 0, DEFAULT_TREASURY_ID).await;
+
+    // An update that isn't part of the VAA's root is rejected
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                VerifyUpdate::populate(
+                    encoded_vaa_addresses[0],
+                    other_merkle_price_updates[0].clone()
+                ),
+                &vec![],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::InvalidPriceUpdate)
+    );
+}
+
+#[tokio::test]
+async fn test_verify_updates_view() {
+    let feed_1 = create_dummy_price_feed_message(100);
+    let feed_2 = create_dummy_price_feed_message(200);
+    let message = create_accumulator_message(&[feed_1, feed_2], &[feed_1, feed_2], false, false);
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+
+    let other_message = create_accumulator_message(&[feed_2], &[feed_2], false, false);
+    let (_, other_merkle_price_updates) =
+        deserialize_accumulator_update_data(other_message).unwrap();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses,
+        governance_authority: _,
+    } = setup_pyth_receiver(
+        vec![serde_wormhole::from_slice(&vaa).unwrap()],
+        WrongSetupOption::None,
+    )
+    .await;
+
+    let expected_price_feed_messages: Vec<PriceFeedMessage> = [feed_1, feed_2]
+        .into_iter()
+        .map(|message| match message {
+            Message::PriceFeedMessage(price_feed_message) => price_feed_message,
+            _ => unreachable!(),
+        })
+        .collect();
+
+    let mut return_data = program_simulator
+        .simulate_ix_return_data(
+            VerifyUpdatesView::populate(encoded_vaa_addresses[0], merkle_price_updates.clone()),
+            &vec![],
+        )
+        .await
+        .unwrap();
+    // The runtime trims the trailing zeros of the return data
+    return_data.resize(expected_price_feed_messages.try_to_vec().unwrap().len(), 0);
+    assert_eq!(
+        Vec::<PriceFeedMessage>::deserialize(&mut &return_data[..]).unwrap(),
+        expected_price_feed_messages
+    );
+
+    // Nothing is charged
+    assert_treasury_balance(&mut program_simulator, 0, DEFAULT_TREASURY_ID).await;
+
+    // An update that isn't part of the VAA's root makes the whole instruction fail
+    assert_eq!(
+        program_simulator
+            .simulate_ix_return_data(
+                VerifyUpdatesView::populate(
+                    encoded_vaa_addresses[0],
+                    vec![
+                        merkle_price_updates[0].clone(),
+                        other_merkle_price_updates[0].clone()
+                    ],
+                ),
+                &vec![]
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::InvalidPriceUpdate)
+    );
+
+    assert_eq!(
+        program_simulator
+            .simulate_ix_return_data(
+                VerifyUpdatesView::populate(
+                    encoded_vaa_addresses[0],
+                    vec![merkle_price_updates[0].clone(); MAX_VIEW_UPDATES + 1],
+                ),
+                &vec![]
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::TooManyUpdatesToVerify)
+    );
+}
+
 #[tokio::test]
 async fn test_post_update_wrong_encoded_vaa_owner() {
     let feed_1 = create_dummy_price_feed_message(100);
@@ -218,12 +551,12 @@ async fn test_post_update_wrong_encoded_vaa_owner() {
             .await
             .unwrap_err()
             .unwrap(),
-        into_transaction_error(ReceiverError::WrongVaaOwner)
+        into_transaction_error(ReceiverError::UnapprovedPostVaaProgram)
     );
 }
 
 #[tokio::test]
-async fn test_post_update_wrong_setup() {
+async fn test_post_update_post_vaa_programs() {
     let feed_1 = create_dummy_price_feed_message(100);
     let feed_2 = create_dummy_price_feed_message(200);
     let message = create_accumulator_message(&[feed_1, feed_2], &[feed_1, feed_2], false, false);
@@ -232,13 +565,28 @@ async fn test_post_update_wrong_setup() {
     let ProgramTestFixtures {
         mut program_simulator,
         encoded_vaa_addresses,
-        governance_authority: _,
+        governance_authority,
     } = setup_pyth_receiver(
         vec![serde_wormhole::from_slice(&vaa).unwrap()],
-        WrongSetupOption::UnverifiedEncodedVaa,
+        WrongSetupOption::None,
     )
     .await;
 
+    let initial_config = program_simulator
+        .get_anchor_account_data::<Config>(get_config_address())
+        .await
+        .unwrap();
+
+    // Migrate to a new wormhole program, the encoded VAA is owned by the old one
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetWormholeAddress::populate(governance_authority.pubkey(), Pubkey::new_unique()),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
     let poster = program_simulator.get_funded_keypair().await.unwrap();
     let price_update_keypair = Keypair::new();
 
@@ -251,7 +599,7 @@ async fn test_post_update_wrong_setup() {
                     encoded_vaa_addresses[0],
                     price_update_keypair.pubkey(),
                     merkle_price_updates[0].clone(),
-                    get_random_treasury_id()
+                    DEFAULT_TREASURY_ID,
                 ),
                 &vec![&poster, &price_update_keypair],
                 None,
@@ -259,6 +607,1349 @@ async fn test_post_update_wrong_setup() {
             .await
             .unwrap_err()
             .unwrap(),
-        into_transaction_error(wormhole_core_bridge_solana::error::CoreBridgeError::UnverifiedVaa)
+        into_transaction_error(ReceiverError::UnapprovedPostVaaProgram)
+    );
+
+    // Keep accepting the VAAs of the old program during the migration
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetPostVaaPrograms::populate(
+                governance_authority.pubkey(),
+                vec![initial_config.wormhole],
+            ),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            PostUpdate::populate(
+                poster.pubkey(),
+                poster.pubkey(),
+                encoded_vaa_addresses[0],
+                price_update_keypair.pubkey(),
+                merkle_price_updates[0].clone(),
+                DEFAULT_TREASURY_ID,
+            ),
+            &vec![&poster, &price_update_keypair],
+            None,
+        )
+        .await
+        .unwrap();
+
+    let price_update_account = program_simulator
+        .get_anchor_account_data::<PriceUpdateV2>(price_update_keypair.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(
+        Message::PriceFeedMessage(price_update_account.price_message),
+        feed_1
+    );
+}
+
+#[tokio::test]
+async fn test_post_update_wrong_setup() {
+    let feed_1 = create_dummy_price_feed_message(100);
+    let feed_2 = create_dummy_price_feed_message(200);
+    let message = create_accumulator_message(&[feed_1, feed_2], &[feed_1, feed_2], false, false);
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses,
+        governance_authority: _,
+    } = setup_pyth_receiver(
+        vec![serde_wormhole::from_slice(&vaa).unwrap()],
+        WrongSetupOption::UnverifiedEncodedVaa,
+    )
+    .await;
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let price_update_keypair = Keypair::new();
+
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                PostUpdate::populate(
+                    poster.pubkey(),
+                    poster.pubkey(),
+                    encoded_vaa_addresses[0],
+                    price_update_keypair.pubkey(),
+                    merkle_price_updates[0].clone(),
+                    get_random_treasury_id()
+                ),
+                &vec![&poster, &price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(wormhole_core_bridge_solana::error::CoreBridgeError::UnverifiedVaa)
+    );
+}
+
+#[tokio::test]
+async fn test_post_update_reordered_proof() {
+    let feed_1 = create_dummy_price_feed_message(100);
+    let feed_2 = create_dummy_price_feed_message(200);
+    let feed_3 = create_dummy_price_feed_message(300);
+    let feed_4 = create_dummy_price_feed_message(400);
+    let message = create_accumulator_message(
+        &[feed_1, feed_2, feed_3, feed_4],
+        &[feed_1, feed_2, feed_3, feed_4],
+        false,
+        false,
+    );
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses,
+        governance_authority: _,
+    } = setup_pyth_receiver(
+        vec![serde_wormhole::from_slice(&vaa).unwrap()],
+        WrongSetupOption::None,
+    )
+    .await;
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let price_update_keypair = Keypair::new();
+
+    let proof_nodes = |merkle_price_update: &MerklePriceUpdate| -> Vec<[u8; 20]> {
+        merkle_price_update
+            .proof
+            .to_bytes()
+            .chunks(20)
+            .map(|node| node.try_into().unwrap())
+            .collect()
+    };
+    let nodes = proof_nodes(&merkle_price_updates[0]);
+    assert_eq!(nodes.len(), 2);
+
+    // The same proof nodes in the opposite order
+    let mut reordered_nodes = nodes.clone();
+    reordered_nodes.reverse();
+
+    // The sibling of feed_1 (feed_2) replaced by feed_3, which is the sibling of feed_4
+    let mut swapped_sibling_nodes = nodes.clone();
+    swapped_sibling_nodes[0] = proof_nodes(&merkle_price_updates[3])[0];
+
+    for tampered_nodes in [reordered_nodes, swapped_sibling_nodes] {
+        let tampered_update = MerklePriceUpdate {
+            message: merkle_price_updates[0].message.clone(),
+            proof:   MerklePath::<Keccak160>::new(tampered_nodes),
+        };
+
+        assert_eq!(
+            program_simulator
+                .process_ix_with_default_compute_limit(
+                    PostUpdate::populate(
+                        poster.pubkey(),
+                        poster.pubkey(),
+                        encoded_vaa_addresses[0],
+                        price_update_keypair.pubkey(),
+                        tampered_update,
+                        DEFAULT_TREASURY_ID,
+                    ),
+                    &vec![&poster, &price_update_keypair],
+                    None,
+                )
+                .await
+                .unwrap_err()
+                .unwrap(),
+            into_transaction_error(ReceiverError::InvalidPriceUpdate)
+        );
+    }
+
+    // The untampered proof is accepted
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            PostUpdate::populate(
+                poster.pubkey(),
+                poster.pubkey(),
+                encoded_vaa_addresses[0],
+                price_update_keypair.pubkey(),
+                merkle_price_updates[0].clone(),
+                DEFAULT_TREASURY_ID,
+            ),
+            &vec![&poster, &price_update_keypair],
+            None,
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_post_update_fee_reverted_on_failed_verification() {
+    let feed_1 = create_dummy_price_feed_message(100);
+    let feed_2 = create_dummy_price_feed_message(200);
+    let message = create_accumulator_message(&[feed_1, feed_2], &[feed_1, feed_2], false, false);
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses,
+        governance_authority: _,
+    } = setup_pyth_receiver(
+        vec![serde_wormhole::from_slice(&vaa).unwrap()],
+        WrongSetupOption::None,
+    )
+    .await;
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let price_update_keypair = Keypair::new();
+    let poster_balance_before = program_simulator
+        .get_balance(poster.pubkey())
+        .await
+        .unwrap();
+
+    // The message of feed_1 with the proof of feed_2
+    let invalid_update = MerklePriceUpdate {
+        message: merkle_price_updates[0].message.clone(),
+        proof:   merkle_price_updates[1].proof.clone(),
+    };
+
+    // The fee is transferred before the update is verified, but the failed verification reverts the whole transaction, including the fee transfer
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                PostUpdate::populate(
+                    poster.pubkey(),
+                    poster.pubkey(),
+                    encoded_vaa_addresses[0],
+                    price_update_keypair.pubkey(),
+                    invalid_update,
+                    DEFAULT_TREASURY_ID,
+                ),
+                &vec![&poster, &price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::InvalidPriceUpdate)
+    );
+
+    assert_treasury_balance(&mut program_simulator, 0, DEFAULT_TREASURY_ID).await;
+    assert_eq!(
+        program_simulator
+            .get_balance(poster.pubkey())
+            .await
+            .unwrap(),
+        poster_balance_before
+    );
+
+    // The valid update is charged as usual
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            PostUpdate::populate(
+                poster.pubkey(),
+                poster.pubkey(),
+                encoded_vaa_addresses[0],
+                price_update_keypair.pubkey(),
+                merkle_price_updates[0].clone(),
+                DEFAULT_TREASURY_ID,
+            ),
+            &vec![&poster, &price_update_keypair],
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert_treasury_balance(
+        &mut program_simulator,
+        Rent::default().minimum_balance(0),
+        DEFAULT_TREASURY_ID,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_post_update_price_change_limit() {
+    let feed_id = [1; 32];
+    let price_message = |price: i64| {
+        Message::PriceFeedMessage(PriceFeedMessage {
+            feed_id,
+            price,
+            conf: 1,
+            exponent: -2,
+            publish_time: price,
+            prev_publish_time: price,
+            ema_price: price,
+            ema_conf: 1,
+        })
+    };
+    // Each price is in a different VAA, and is published `price` seconds after the epoch
+    let prices = [100, 110, 122, 200];
+    let (vaas, merkle_price_updates): (Vec<_>, Vec<_>) = prices
+        .iter()
+        .map(|price| {
+            let message = create_accumulator_message(
+                &[price_message(*price)],
+                &[price_message(*price)],
+                false,
+                false,
+            );
+            let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+            (
+                serde_wormhole::from_slice(&vaa).unwrap(),
+                merkle_price_updates[0].clone(),
+            )
+        })
+        .unzip();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses,
+        governance_authority,
+    } = setup_pyth_receiver(vaas, WrongSetupOption::None).await;
+
+    // The price of the feed can move by at most 10% in a single update published within a minute of its last price
+    let price_change_limits = vec![PriceChangeLimit {
+        feed_id,
+        max_change_bps: 1000,
+        window_seconds: 60,
+    }];
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetPriceChangeLimits::populate(
+                governance_authority.pubkey(),
+                price_change_limits.clone(),
+            ),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let price_update_keypair = Keypair::new();
+    let other_price_update_keypair = Keypair::new();
+    let price_change_baseline_address = get_price_change_baseline_address(&feed_id);
+    // Updates of the limited feed need the baseline account of the feed
+    let post_update = |i: usize, price_update_account: Pubkey| {
+        let mut instruction = PostUpdate::populate(
+            poster.pubkey(),
+            poster.pubkey(),
+            encoded_vaa_addresses[i],
+            price_update_account,
+            merkle_price_updates[i].clone(),
+            DEFAULT_TREASURY_ID,
+        );
+        instruction
+            .accounts
+            .push(AccountMeta::new(price_change_baseline_address, false));
+        instruction
+    };
+
+    // The first update of the feed creates its baseline, +10% is within the limit
+    for i in 0..2 {
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                post_update(i, price_update_keypair.pubkey()),
+                &vec![&poster, &price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    // +10.9% is above the limit
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                post_update(2, price_update_keypair.pubkey()),
+                &vec![&poster, &price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::PriceMovedTooMuch)
+    );
+
+    let price_update_account = program_simulator
+        .get_anchor_account_data::<PriceUpdateV2>(price_update_keypair.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(
+        Message::PriceFeedMessage(price_update_account.price_message),
+        price_message(110)
+    );
+
+    // The baseline is per feed, so posting to another account doesn't skip the limit
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                post_update(2, other_price_update_keypair.pubkey()),
+                &vec![&poster, &other_price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::PriceMovedTooMuch)
+    );
+
+    // Nor does leaving out the baseline account
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                PostUpdate::populate(
+                    poster.pubkey(),
+                    poster.pubkey(),
+                    encoded_vaa_addresses[2],
+                    other_price_update_keypair.pubkey(),
+                    merkle_price_updates[2].clone(),
+                    DEFAULT_TREASURY_ID,
+                ),
+                &vec![&poster, &other_price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::WrongPriceChangeBaselineAccount)
+    );
+
+    // Removing the limit lets the update through, without the baseline account
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetPriceChangeLimits::populate(governance_authority.pubkey(), vec![]),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            PostUpdate::populate(
+                poster.pubkey(),
+                poster.pubkey(),
+                encoded_vaa_addresses[2],
+                price_update_keypair.pubkey(),
+                merkle_price_updates[2].clone(),
+                DEFAULT_TREASURY_ID,
+            ),
+            &vec![&poster, &price_update_keypair],
+            None,
+        )
+        .await
+        .unwrap();
+
+    // An update published after the window is accepted whatever its price, so that a price that really moved can't block the feed
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetPriceChangeLimits::populate(governance_authority.pubkey(), price_change_limits),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            post_update(3, other_price_update_keypair.pubkey()),
+            &vec![&poster, &other_price_update_keypair],
+            None,
+        )
+        .await
+        .unwrap();
+
+    let price_update_account = program_simulator
+        .get_anchor_account_data::<PriceUpdateV2>(other_price_update_keypair.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(
+        Message::PriceFeedMessage(price_update_account.price_message),
+        price_message(200)
+    );
+    let price_change_baseline = program_simulator
+        .get_anchor_account_data::<PriceChangeBaseline>(price_change_baseline_address)
+        .await
+        .unwrap();
+    assert_eq!(
+        Message::PriceFeedMessage(price_change_baseline.price_message),
+        price_message(200)
+    );
+
+    // A limit needs a window
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetPriceChangeLimits::populate(
+                    governance_authority.pubkey(),
+                    vec![PriceChangeLimit {
+                        feed_id,
+                        max_change_bps: 1000,
+                        window_seconds: 0,
+                    }],
+                ),
+                &vec![&governance_authority],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::InvalidPriceChangeLimitWindow)
+    );
+}
+
+#[tokio::test]
+async fn test_post_update_publish_time_bounds() {
+    let price_message = |feed: u8, publish_time: i64| {
+        Message::PriceFeedMessage(PriceFeedMessage {
+            feed_id: [feed; 32],
+            price: 100,
+            conf: 1,
+            exponent: -2,
+            publish_time,
+            prev_publish_time: publish_time,
+            ema_price: 100,
+            ema_conf: 1,
+        })
+    };
+
+    // The program simulator starts with the clock of the host
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    let stale_feed = price_message(1, now - 3600);
+    let future_feed = price_message(2, now + 3600);
+    let message = create_accumulator_message(
+        &[stale_feed, future_feed],
+        &[stale_feed, future_feed],
+        false,
+        false,
+    );
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses,
+        governance_authority,
+    } = setup_pyth_receiver(
+        vec![serde_wormhole::from_slice(&vaa).unwrap()],
+        WrongSetupOption::None,
+    )
+    .await;
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetPublishTimeBounds::populate(governance_authority.pubkey(), 60, 60),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let price_update_keypair = Keypair::new();
+
+    for (merkle_price_update, expected_error) in [
+        (&merkle_price_updates[0], ReceiverError::PriceTooStale),
+        (&merkle_price_updates[1], ReceiverError::PublishTimeInFuture),
+    ] {
+        assert_eq!(
+            program_simulator
+                .process_ix_with_default_compute_limit(
+                    PostUpdate::populate(
+                        poster.pubkey(),
+                        poster.pubkey(),
+                        encoded_vaa_addresses[0],
+                        price_update_keypair.pubkey(),
+                        merkle_price_update.clone(),
+                        DEFAULT_TREASURY_ID,
+                    ),
+                    &vec![&poster, &price_update_keypair],
+                    None,
+                )
+                .await
+                .unwrap_err()
+                .unwrap(),
+            into_transaction_error(expected_error)
+        );
+    }
+
+    // Both bounds are disabled when they're 0
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetPublishTimeBounds::populate(governance_authority.pubkey(), 0, 0),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    for merkle_price_update in &merkle_price_updates {
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                PostUpdate::populate(
+                    poster.pubkey(),
+                    poster.pubkey(),
+                    encoded_vaa_addresses[0],
+                    price_update_keypair.pubkey(),
+                    merkle_price_update.clone(),
+                    DEFAULT_TREASURY_ID,
+                ),
+                &vec![&poster, &price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_post_update_price_sanity_bounds() {
+    // The exponent and the confidence interval of this message are both 100, and its price is 100
+    let feed_1 = create_dummy_price_feed_message(100);
+    let message = create_accumulator_message(&[feed_1], &[feed_1], false, false);
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses,
+        governance_authority,
+    } = setup_pyth_receiver(
+        vec![serde_wormhole::from_slice(&vaa).unwrap()],
+        WrongSetupOption::None,
+    )
+    .await;
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let price_update_keypair = Keypair::new();
+
+    for (exponent_range, max_conf_bps) in
+        [(Some(ExponentRange { min: -12, max: 0 }), 0), (None, 5000)]
+    {
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                SetPriceSanityBounds::populate(
+                    governance_authority.pubkey(),
+                    exponent_range,
+                    max_conf_bps,
+                ),
+                &vec![&governance_authority],
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            program_simulator
+                .process_ix_with_default_compute_limit(
+                    PostUpdate::populate(
+                        poster.pubkey(),
+                        poster.pubkey(),
+                        encoded_vaa_addresses[0],
+                        price_update_keypair.pubkey(),
+                        merkle_price_updates[0].clone(),
+                        DEFAULT_TREASURY_ID,
+                    ),
+                    &vec![&poster, &price_update_keypair],
+                    None,
+                )
+                .await
+                .unwrap_err()
+                .unwrap(),
+            into_transaction_error(ReceiverError::PriceSanityCheckFailed)
+        );
+    }
+
+    // Bounds that include the message
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetPriceSanityBounds::populate(
+                governance_authority.pubkey(),
+                Some(ExponentRange { min: 0, max: 100 }),
+                10_000,
+            ),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            PostUpdate::populate(
+                poster.pubkey(),
+                poster.pubkey(),
+                encoded_vaa_addresses[0],
+                price_update_keypair.pubkey(),
+                merkle_price_updates[0].clone(),
+                DEFAULT_TREASURY_ID,
+            ),
+            &vec![&poster, &price_update_keypair],
+            None,
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_post_update_min_consistency_level() {
+    let feed_1 = create_dummy_price_feed_message(100);
+    let message = create_accumulator_message(&[feed_1], &[feed_1], false, false);
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses,
+        governance_authority,
+    } = setup_pyth_receiver(
+        vec![serde_wormhole::from_slice(&vaa).unwrap()],
+        WrongSetupOption::None,
+    )
+    .await;
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let price_update_keypair = Keypair::new();
+
+    // The consistency level of the test VAAs is 0
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetMinConsistencyLevel::populate(governance_authority.pubkey(), 1),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                PostUpdate::populate(
+                    poster.pubkey(),
+                    poster.pubkey(),
+                    encoded_vaa_addresses[0],
+                    price_update_keypair.pubkey(),
+                    merkle_price_updates[0].clone(),
+                    DEFAULT_TREASURY_ID,
+                ),
+                &vec![&poster, &price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::InsufficientConsistencyLevel)
+    );
+
+    // A caller can't lower the minimum consistency level of the config ...
+    let post_update_with_min_consistency_level = |min_consistency_level| {
+        PostUpdateWithMinConsistency::populate(
+            poster.pubkey(),
+            poster.pubkey(),
+            encoded_vaa_addresses[0],
+            price_update_keypair.pubkey(),
+            merkle_price_updates[0].clone(),
+            DEFAULT_TREASURY_ID,
+            min_consistency_level,
+        )
+    };
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                post_update_with_min_consistency_level(0),
+                &vec![&poster, &price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::InsufficientConsistencyLevel)
+    );
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetMinConsistencyLevel::populate(governance_authority.pubkey(), 0),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    // ... but can raise it for its own updates
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                post_update_with_min_consistency_level(1),
+                &vec![&poster, &price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::InsufficientConsistencyLevel)
+    );
+    let mut verify_update_instruction =
+        VerifyUpdate::populate(encoded_vaa_addresses[0], merkle_price_updates[0].clone());
+    verify_update_instruction.data = VerifyUpdate {
+        merkle_price_update:   merkle_price_updates[0].clone(),
+        min_consistency_level: Some(1),
+    }
+    .data();
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(verify_update_instruction, &vec![], None)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::InsufficientConsistencyLevel)
+    );
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            PostUpdate::populate(
+                poster.pubkey(),
+                poster.pubkey(),
+                encoded_vaa_addresses[0],
+                price_update_keypair.pubkey(),
+                merkle_price_updates[0].clone(),
+                DEFAULT_TREASURY_ID,
+            ),
+            &vec![&poster, &price_update_keypair],
+            None,
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_post_updates_from_vaas() {
+    let feed_1 = create_dummy_price_feed_message(100);
+    let feed_2 = create_dummy_price_feed_message(200);
+    let feed_3 = create_dummy_price_feed_message(300);
+    // The updates of feed_1 and feed_2 are in different VAAs
+    let (vaa_1, merkle_price_updates_1) = deserialize_accumulator_update_data(
+        create_accumulator_message(&[feed_1], &[feed_1], false, false),
+    )
+    .unwrap();
+    let (vaa_2, merkle_price_updates_2) = deserialize_accumulator_update_data(
+        create_accumulator_message(&[feed_2], &[feed_2], false, false),
+    )
+    .unwrap();
+    // This VAA isn't posted
+    let (_, merkle_price_updates_3) = deserialize_accumulator_update_data(
+        create_accumulator_message(&[feed_3], &[feed_3], false, false),
+    )
+    .unwrap();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses,
+        governance_authority: _,
+    } = setup_pyth_receiver(
+        vec![
+            serde_wormhole::from_slice(&vaa_1).unwrap(),
+            serde_wormhole::from_slice(&vaa_2).unwrap(),
+        ],
+        WrongSetupOption::None,
+    )
+    .await;
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let price_update_keypairs = [Keypair::new(), Keypair::new()];
+    let price_update_accounts = [
+        price_update_keypairs[0].pubkey(),
+        price_update_keypairs[1].pubkey(),
+    ];
+    let merkle_price_updates = vec![
+        merkle_price_updates_1[0].clone(),
+        merkle_price_updates_2[0].clone(),
+    ];
+
+    // The update of feed_1 isn't in the second VAA
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                PostUpdatesFromVaas::populate(
+                    poster.pubkey(),
+                    poster.pubkey(),
+                    &encoded_vaa_addresses,
+                    &price_update_accounts,
+                    merkle_price_updates.clone(),
+                    vec![1, 1],
+                    DEFAULT_TREASURY_ID,
+                ),
+                &vec![
+                    &poster,
+                    &price_update_keypairs[0],
+                    &price_update_keypairs[1]
+                ],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::InvalidPriceUpdate)
+    );
+
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                PostUpdatesFromVaas::populate(
+                    poster.pubkey(),
+                    poster.pubkey(),
+                    &encoded_vaa_addresses,
+                    &price_update_accounts,
+                    merkle_price_updates.clone(),
+                    vec![0, 2],
+                    DEFAULT_TREASURY_ID,
+                ),
+                &vec![
+                    &poster,
+                    &price_update_keypairs[0],
+                    &price_update_keypairs[1]
+                ],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::InvalidVaaIndex)
+    );
+
+    // The update of feed_3 isn't in any of the VAAs
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                PostUpdatesFromVaas::populate(
+                    poster.pubkey(),
+                    poster.pubkey(),
+                    &encoded_vaa_addresses,
+                    &price_update_accounts,
+                    vec![
+                        merkle_price_updates_1[0].clone(),
+                        merkle_price_updates_3[0].clone(),
+                    ],
+                    vec![],
+                    DEFAULT_TREASURY_ID,
+                ),
+                &vec![
+                    &poster,
+                    &price_update_keypairs[0],
+                    &price_update_keypairs[1]
+                ],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::NoMatchingVaa)
+    );
+
+    // Both with explicit indices and by trying every VAA
+    for vaa_indices in [vec![0, 1], vec![]] {
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                PostUpdatesFromVaas::populate(
+                    poster.pubkey(),
+                    poster.pubkey(),
+                    &encoded_vaa_addresses,
+                    &price_update_accounts,
+                    merkle_price_updates.clone(),
+                    vaa_indices,
+                    DEFAULT_TREASURY_ID,
+                ),
+                &vec![
+                    &poster,
+                    &price_update_keypairs[0],
+                    &price_update_keypairs[1],
+                ],
+                None,
+            )
+            .await
+            .unwrap();
+
+        for (price_update_account, feed) in price_update_accounts.iter().zip([feed_1, feed_2]) {
+            let price_update_account = program_simulator
+                .get_anchor_account_data::<PriceUpdateV2>(*price_update_account)
+                .await
+                .unwrap();
+            assert_eq!(price_update_account.write_authority, poster.pubkey());
+            assert_eq!(
+                Message::PriceFeedMessage(price_update_account.price_message),
+                feed
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_post_updates_from_vaas_prefunded_accounts() {
+    let feed_1 = create_dummy_price_feed_message(100);
+    let feed_2 = create_dummy_price_feed_message(200);
+    let message = create_accumulator_message(&[feed_1, feed_2], &[feed_1, feed_2], false, false);
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses,
+        governance_authority: _,
+    } = setup_pyth_receiver(
+        vec![serde_wormhole::from_slice(&vaa).unwrap()],
+        WrongSetupOption::None,
+    )
+    .await;
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let price_update_keypairs = [Keypair::new(), Keypair::new()];
+    let price_update_accounts = [
+        price_update_keypairs[0].pubkey(),
+        price_update_keypairs[1].pubkey(),
+    ];
+
+    // Anyone can send lamports to the address of a price update account before it's created, less or more than its rent
+    let price_update_rent = Rent::default().minimum_balance(PriceUpdateV2::LEN);
+    program_simulator
+        .airdrop(
+            &price_update_accounts[0],
+            Rent::default().minimum_balance(0),
+        )
+        .await
+        .unwrap();
+    program_simulator
+        .airdrop(&price_update_accounts[1], 2 * price_update_rent)
+        .await
+        .unwrap();
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            PostUpdatesFromVaas::populate(
+                poster.pubkey(),
+                poster.pubkey(),
+                &encoded_vaa_addresses,
+                &price_update_accounts,
+                merkle_price_updates,
+                vec![],
+                DEFAULT_TREASURY_ID,
+            ),
+            &vec![
+                &poster,
+                &price_update_keypairs[0],
+                &price_update_keypairs[1],
+            ],
+            None,
+        )
+        .await
+        .unwrap();
+
+    for (price_update_account, feed) in price_update_accounts.iter().zip([feed_1, feed_2]) {
+        let price_update = program_simulator
+            .get_anchor_account_data::<PriceUpdateV2>(*price_update_account)
+            .await
+            .unwrap();
+        assert_eq!(price_update.write_authority, poster.pubkey());
+        assert_eq!(Message::PriceFeedMessage(price_update.price_message), feed);
+    }
+    // The payer only paid the rent that was missing
+    assert_eq!(
+        program_simulator
+            .get_balance(price_update_accounts[0])
+            .await
+            .unwrap(),
+        price_update_rent
+    );
+    assert_eq!(
+        program_simulator
+            .get_balance(price_update_accounts[1])
+            .await
+            .unwrap(),
+        2 * price_update_rent
+    );
+}
+
+#[tokio::test]
+async fn test_post_updates_from_vaas_allowed_feed_ids() {
+    let feed_1 = create_dummy_price_feed_message(100);
+    let feed_2 = create_dummy_price_feed_message(200);
+    let feed_3 = create_dummy_price_feed_message(300);
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(
+        create_accumulator_message(&[feed_1, feed_2], &[feed_1, feed_2], false, false),
+    )
+    .unwrap();
+    // This VAA isn't posted
+    let (_, merkle_price_updates_3) = deserialize_accumulator_update_data(
+        create_accumulator_message(&[feed_3], &[feed_3], false, false),
+    )
+    .unwrap();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses,
+        governance_authority,
+    } = setup_pyth_receiver(
+        vec![serde_wormhole::from_slice(&vaa).unwrap()],
+        WrongSetupOption::None,
+    )
+    .await;
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetAllowedFeedIds::populate(governance_authority.pubkey(), vec![feed_2.feed_id()]),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let price_update_keypairs = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let price_update_accounts = [
+        price_update_keypairs[0].pubkey(),
+        price_update_keypairs[1].pubkey(),
+        price_update_keypairs[2].pubkey(),
+    ];
+    // The updates of feed_1 and feed_3 are skipped, so feed_3 not being in the VAA doesn't matter
+    let post_updates_instruction = PostUpdatesFromVaas::populate(
+        poster.pubkey(),
+        poster.pubkey(),
+        &encoded_vaa_addresses,
+        &price_update_accounts,
+        vec![
+            merkle_price_updates[0].clone(),
+            merkle_price_updates[1].clone(),
+            merkle_price_updates_3[0].clone(),
+        ],
+        vec![],
+        DEFAULT_TREASURY_ID,
+    );
+    let signers = vec![
+        &poster,
+        &price_update_keypairs[0],
+        &price_update_keypairs[1],
+        &price_update_keypairs[2],
+    ];
+
+    let expected_summary = PostedUpdatesSummary {
+        count:     1,
+        feed_ids:  vec![feed_2.feed_id()],
+        truncated: false,
+        skipped:   2,
+    };
+    let mut return_data = program_simulator
+        .simulate_ix_return_data(post_updates_instruction.clone(), &signers)
+        .await
+        .unwrap();
+    // The runtime trims the trailing zeros of the return data
+    return_data.resize(expected_summary.try_to_vec().unwrap().len(), 0);
+    assert_eq!(
+        PostedUpdatesSummary::deserialize(&mut &return_data[..]).unwrap(),
+        expected_summary
+    );
+
+    program_simulator
+        .process_ix_with_default_compute_limit(post_updates_instruction, &signers, None)
+        .await
+        .unwrap();
+
+    // Only the allowed update is posted and charged
+    assert_treasury_balance(
+        &mut program_simulator,
+        Rent::default().minimum_balance(0),
+        DEFAULT_TREASURY_ID,
+    )
+    .await;
+    let price_update_account = program_simulator
+        .get_anchor_account_data::<PriceUpdateV2>(price_update_accounts[1])
+        .await
+        .unwrap();
+    assert_eq!(
+        Message::PriceFeedMessage(price_update_account.price_message),
+        feed_2
+    );
+    for price_update_account in [price_update_accounts[0], price_update_accounts[2]] {
+        assert_eq!(
+            program_simulator
+                .get_balance(price_update_account)
+                .await
+                .unwrap(),
+            0
+        );
+    }
+}
+
+/// The default compute limit of a transaction. `post_updates_from_account_data` should fit a batch of `NUM_UPDATES` updates under it.
+const DEFAULT_COMPUTE_UNIT_LIMIT: u64 = 200_000;
+
+#[tokio::test]
+async fn test_post_updates_from_account_data_compute_units() {
+    const NUM_UPDATES: usize = 15;
+
+    let feeds: Vec<Message> = (1..=NUM_UPDATES as i64)
+        .map(create_dummy_price_feed_message)
+        .collect();
+    let message = create_accumulator_message(&feeds, &feeds, false, false);
+    let (vaa, _) = deserialize_accumulator_update_data(message.clone()).unwrap();
+
+    // The accumulator update data is written to an account that can be owned by anyone
+    let accumulator_update_data = Pubkey::new_unique();
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses,
+        governance_authority: _,
+    } = setup_pyth_receiver_with_accounts(
+        vec![serde_wormhole::from_slice(&vaa).unwrap()],
+        WrongSetupOption::None,
+        vec![(
+            accumulator_update_data,
+            Account {
+                lamports:   Rent::default().minimum_balance(message.len()),
+                data:       message,
+                owner:      Pubkey::new_unique(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        )],
+    )
+    .await;
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let price_update_keypairs: Vec<Keypair> = (0..NUM_UPDATES).map(|_| Keypair::new()).collect();
+    let price_update_accounts: Vec<Pubkey> = price_update_keypairs
+        .iter()
+        .map(|price_update_keypair| price_update_keypair.pubkey())
+        .collect();
+    let mut signers = vec![&poster];
+    signers.extend(price_update_keypairs.iter());
+
+    let post_updates_instruction = PostUpdatesFromAccountData::populate(
+        poster.pubkey(),
+        poster.pubkey(),
+        encoded_vaa_addresses[0],
+        accumulator_update_data,
+        &price_update_accounts,
+        &[],
+        DEFAULT_TREASURY_ID,
+        EventMode::Summary,
+        Some(NUM_UPDATES as u16),
+        true,
+    );
+    let units_consumed = program_simulator
+        .simulate_ix_compute_units(post_updates_instruction.clone(), &signers)
+        .await
+        .unwrap();
+    assert!(
+        units_consumed < DEFAULT_COMPUTE_UNIT_LIMIT,
+        "post_updates_from_account_data used {} compute units for {} updates",
+        units_consumed,
+        NUM_UPDATES
+    );
+
+    program_simulator
+        .process_ix_with_default_compute_limit(post_updates_instruction, &signers, None)
+        .await
+        .unwrap();
+    for (price_update_account, feed) in price_update_accounts.iter().zip(feeds) {
+        let price_update = program_simulator
+            .get_anchor_account_data::<PriceUpdateV2>(*price_update_account)
+            .await
+            .unwrap();
+        assert_eq!(Message::PriceFeedMessage(price_update.price_message), feed);
+    }
+}
+
+#[tokio::test]
+async fn test_post_updates_with_tag_allowed_feed_ids() {
+    let feeds: Vec<Message> = (1..=3).map(create_dummy_price_feed_message).collect();
+    let message = create_accumulator_message(&feeds, &feeds, false, false);
+    let (vaa, _) = deserialize_accumulator_update_data(message.clone()).unwrap();
+
+    let accumulator_update_data = Pubkey::new_unique();
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses,
+        governance_authority,
+    } = setup_pyth_receiver_with_accounts(
+        vec![serde_wormhole::from_slice(&vaa).unwrap()],
+        WrongSetupOption::None,
+        vec![(
+            accumulator_update_data,
+            Account {
+                lamports:   Rent::default().minimum_balance(message.len()),
+                data:       message,
+                owner:      Pubkey::new_unique(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        )],
+    )
+    .await;
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetAllowedFeedIds::populate(governance_authority.pubkey(), vec![feeds[1].feed_id()]),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let price_update_keypairs = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let price_update_accounts = [
+        price_update_keypairs[0].pubkey(),
+        price_update_keypairs[1].pubkey(),
+        price_update_keypairs[2].pubkey(),
+    ];
+    let signers = vec![
+        &poster,
+        &price_update_keypairs[0],
+        &price_update_keypairs[1],
+        &price_update_keypairs[2],
+    ];
+    let tag = [7; 32];
+
+    let post_updates_instruction = PostUpdatesWithTag::populate(
+        poster.pubkey(),
+        poster.pubkey(),
+        encoded_vaa_addresses[0],
+        accumulator_update_data,
+        &price_update_accounts,
+        &[],
+        DEFAULT_TREASURY_ID,
+        EventMode::Summary,
+        None,
+        false,
+        tag,
+    );
+
+    // The skipped updates are reported along with the tag
+    let expected_tagged_summary = TaggedPostedUpdatesSummary {
+        tag,
+        summary: PostedUpdatesSummary {
+            count:     1,
+            feed_ids:  vec![feeds[1].feed_id()],
+            truncated: false,
+            skipped:   2,
+        },
+    };
+    let mut return_data = program_simulator
+        .simulate_ix_return_data(post_updates_instruction.clone(), &signers)
+        .await
+        .unwrap();
+    // The runtime trims the trailing zeros of the return data
+    return_data.resize(expected_tagged_summary.try_to_vec().unwrap().len(), 0);
+    assert_eq!(
+        TaggedPostedUpdatesSummary::deserialize(&mut &return_data[..]).unwrap(),
+        expected_tagged_summary
+    );
+
+    program_simulator
+        .process_ix_with_default_compute_limit(post_updates_instruction, &signers, None)
+        .await
+        .unwrap();
+    let price_update = program_simulator
+        .get_anchor_account_data::<PriceUpdateV2>(price_update_accounts[1])
+        .await
+        .unwrap();
+    assert_eq!(
+        Message::PriceFeedMessage(price_update.price_message),
+        feeds[1]
     );
 }
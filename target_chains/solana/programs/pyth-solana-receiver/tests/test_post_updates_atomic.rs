@@ -1,25 +1,46 @@
 use {
     common_test_utils::{
         assert_treasury_balance,
+        default_receiver_config,
+        dummy_guardian_keys,
         setup_pyth_receiver,
         ProgramTestFixtures,
         WrongSetupOption,
         DEFAULT_GUARDIAN_SET_INDEX,
+        GUARDIAN_SET_EXPIRING_SOON_DELAY,
+        ROTATED_GUARDIAN_SET_INDEX,
     },
     program_simulator::into_transaction_error,
     pyth_solana_receiver::{
         error::ReceiverError,
-        instruction::PostUpdateAtomic,
+        instruction::{
+            PostUpdateAtomic,
+            PostUpdateAtomicWithMinConsistency,
+            SetGuardianSetExpirationSkew,
+            SetMinConsistencyLevel,
+            SetPinnedGuardianSetHash,
+        },
         sdk::{
             deserialize_accumulator_update_data,
+            fits_in_transaction,
             get_guardian_set_address,
+            get_post_update_atomic_instructions,
             DEFAULT_TREASURY_ID,
             SECONDARY_TREASURY_ID,
         },
     },
-    pyth_solana_receiver_sdk::price_update::{
-        PriceUpdateV2,
-        VerificationLevel,
+    pyth_solana_receiver_sdk::{
+        config::{
+            hash_guardian_keys,
+            Config,
+            FeeRecipient,
+            PriceChangeLimit,
+        },
+        pda::get_price_change_baseline_address,
+        price_update::{
+            PriceUpdateV2,
+            VerificationLevel,
+        },
     },
     pythnet_sdk::{
         messages::Message,
@@ -31,6 +52,9 @@ use {
     },
     serde_wormhole::RawMessage,
     solana_sdk::{
+        compute_budget::ComputeBudgetInstruction,
+        instruction::AccountMeta,
+        pubkey::Pubkey,
         rent::Rent,
         signature::Keypair,
         signer::Signer,
@@ -205,6 +229,228 @@ async fn test_post_update_atomic() {
     );
 }
 
+#[test]
+fn test_get_post_update_atomic_instructions() {
+    let feed_1 = create_dummy_price_feed_message(100);
+    let feed_2 = create_dummy_price_feed_message(200);
+    let message = create_accumulator_message(&[feed_1, feed_2], &[feed_1, feed_2], false, false);
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message.clone()).unwrap();
+
+    let payer = Keypair::new().pubkey();
+    let price_update_accounts = [Keypair::new().pubkey(), Keypair::new().pubkey()];
+    let config = default_receiver_config(payer);
+
+    assert_eq!(
+        get_post_update_atomic_instructions(
+            payer,
+            payer,
+            BRIDGE_ID,
+            message.clone(),
+            &price_update_accounts,
+            DEFAULT_TREASURY_ID,
+            &config,
+        )
+        .unwrap(),
+        price_update_accounts
+            .iter()
+            .zip(merkle_price_updates.clone())
+            .map(|(price_update_account, merkle_price_update)| {
+                PostUpdateAtomic::populate(
+                    payer,
+                    payer,
+                    *price_update_account,
+                    BRIDGE_ID,
+                    DEFAULT_GUARDIAN_SET_INDEX,
+                    vaa.clone(),
+                    merkle_price_update,
+                    DEFAULT_TREASURY_ID,
+                )
+            })
+            .collect::<Vec<_>>()
+    );
+
+    let fee_recipients = vec![
+        FeeRecipient {
+            recipient:  Pubkey::new_unique(),
+            weight_bps: 5000,
+        },
+        FeeRecipient {
+            recipient:  Pubkey::new_unique(),
+            weight_bps: 5000,
+        },
+    ];
+    // Only the second feed has a price change limit, so only its instruction gets a baseline account
+    let config_with_fee_recipients_and_limit = Config {
+        fee_recipients: fee_recipients.clone(),
+        price_change_limits: vec![PriceChangeLimit {
+            feed_id:        feed_2.feed_id(),
+            max_change_bps: 1000,
+            window_seconds: 60,
+        }],
+        ..config.clone()
+    };
+    assert_eq!(
+        get_post_update_atomic_instructions(
+            payer,
+            payer,
+            BRIDGE_ID,
+            message.clone(),
+            &price_update_accounts,
+            DEFAULT_TREASURY_ID,
+            &config_with_fee_recipients_and_limit,
+        )
+        .unwrap(),
+        price_update_accounts
+            .iter()
+            .zip(merkle_price_updates)
+            .zip([None, Some(feed_2.feed_id())])
+            .map(
+                |((price_update_account, merkle_price_update), limited_feed_id)| {
+                    let mut instruction = PostUpdateAtomic::populate(
+                        payer,
+                        payer,
+                        *price_update_account,
+                        BRIDGE_ID,
+                        DEFAULT_GUARDIAN_SET_INDEX,
+                        vaa.clone(),
+                        merkle_price_update,
+                        DEFAULT_TREASURY_ID,
+                    );
+                    instruction.accounts.extend(
+                        fee_recipients
+                            .iter()
+                            .map(|fee_recipient| AccountMeta::new(fee_recipient.recipient, false)),
+                    );
+                    instruction.accounts.extend(limited_feed_id.map(|feed_id| {
+                        AccountMeta::new(get_price_change_baseline_address(&feed_id), false)
+                    }));
+                    instruction
+                }
+            )
+            .collect::<Vec<_>>()
+    );
+
+    assert_eq!(
+        into_transaction_error(
+            get_post_update_atomic_instructions(
+                payer,
+                payer,
+                BRIDGE_ID,
+                message,
+                &price_update_accounts[..1],
+                DEFAULT_TREASURY_ID,
+                &config,
+            )
+            .unwrap_err()
+        ),
+        into_transaction_error(ReceiverError::WrongNumberOfPriceUpdateAccounts)
+    );
+}
+
+#[tokio::test]
+async fn test_post_update_atomic_min_consistency_level() {
+    let feed_1 = create_dummy_price_feed_message(100);
+    let message = create_accumulator_message(&[feed_1], &[feed_1], false, false);
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+    let vaa = serde_wormhole::to_vec(&trim_vaa_signatures(
+        serde_wormhole::from_slice(&vaa).unwrap(),
+        5,
+    ))
+    .unwrap();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses: _,
+        governance_authority,
+    } = setup_pyth_receiver(vec![], WrongSetupOption::None).await;
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let price_update_keypair = Keypair::new();
+
+    // The consistency level of the test VAAs is 0
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetMinConsistencyLevel::populate(governance_authority.pubkey(), 1),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    let post_update_atomic_with_min_consistency_level =
+        |min_consistency_level: Option<u8>| match min_consistency_level {
+            Some(min_consistency_level) => PostUpdateAtomicWithMinConsistency::populate(
+                poster.pubkey(),
+                poster.pubkey(),
+                price_update_keypair.pubkey(),
+                BRIDGE_ID,
+                DEFAULT_GUARDIAN_SET_INDEX,
+                vaa.clone(),
+                merkle_price_updates[0].clone(),
+                DEFAULT_TREASURY_ID,
+                min_consistency_level,
+            ),
+            None => PostUpdateAtomic::populate(
+                poster.pubkey(),
+                poster.pubkey(),
+                price_update_keypair.pubkey(),
+                BRIDGE_ID,
+                DEFAULT_GUARDIAN_SET_INDEX,
+                vaa.clone(),
+                merkle_price_updates[0].clone(),
+                DEFAULT_TREASURY_ID,
+            ),
+        };
+
+    // A caller can't lower the minimum consistency level of the config
+    for min_consistency_level in [None, Some(0)] {
+        assert_eq!(
+            program_simulator
+                .process_ix_with_default_compute_limit(
+                    post_update_atomic_with_min_consistency_level(min_consistency_level),
+                    &vec![&poster, &price_update_keypair],
+                    None,
+                )
+                .await
+                .unwrap_err()
+                .unwrap(),
+            into_transaction_error(ReceiverError::InsufficientConsistencyLevel)
+        );
+    }
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetMinConsistencyLevel::populate(governance_authority.pubkey(), 0),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    // But can raise it for its own updates
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                post_update_atomic_with_min_consistency_level(Some(1)),
+                &vec![&poster, &price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::InsufficientConsistencyLevel)
+    );
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            post_update_atomic_with_min_consistency_level(Some(0)),
+            &vec![&poster, &price_update_keypair],
+            None,
+        )
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
 async fn test_post_update_atomic_wrong_vaa() {
     let feed_1 = create_dummy_price_feed_message(100);
@@ -518,4 +764,369 @@ async fn test_post_update_atomic_wrong_setup() {
             .unwrap(),
         into_transaction_error(ReceiverError::GuardianSetExpired)
     );
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses: _,
+        governance_authority: _,
+    } = setup_pyth_receiver(vec![], WrongSetupOption::GuardianSetTooManyKeys).await;
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                PostUpdateAtomic::populate(
+                    poster.pubkey(),
+                    poster.pubkey(),
+                    price_update_keypair.pubkey(),
+                    BRIDGE_ID,
+                    DEFAULT_GUARDIAN_SET_INDEX,
+                    vaa.clone(),
+                    merkle_price_updates[0].clone(),
+                    DEFAULT_TREASURY_ID
+                ),
+                &vec![&poster, &price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::TooManyGuardians)
+    );
+}
+
+#[tokio::test]
+async fn test_post_update_atomic_pinned_guardian_set() {
+    let feed_1 = create_dummy_price_feed_message(100);
+    let message = create_accumulator_message(&[feed_1], &[feed_1], false, false);
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+    let vaa = serde_wormhole::to_vec(&trim_vaa_signatures(
+        serde_wormhole::from_slice(&vaa).unwrap(),
+        5,
+    ))
+    .unwrap();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses: _,
+        governance_authority,
+    } = setup_pyth_receiver(vec![], WrongSetupOption::None).await;
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let price_update_keypair = Keypair::new();
+
+    // Pin a guardian set that isn't the one in the guardian set account
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetPinnedGuardianSetHash::populate(
+                governance_authority.pubkey(),
+                Some(hash_guardian_keys(&dummy_guardian_keys()[1..])),
+            ),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                PostUpdateAtomic::populate(
+                    poster.pubkey(),
+                    poster.pubkey(),
+                    price_update_keypair.pubkey(),
+                    BRIDGE_ID,
+                    DEFAULT_GUARDIAN_SET_INDEX,
+                    vaa.clone(),
+                    merkle_price_updates[0].clone(),
+                    DEFAULT_TREASURY_ID,
+                ),
+                &vec![&poster, &price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::PinnedGuardianSetMismatch)
+    );
+
+    // Pin the right guardian set
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetPinnedGuardianSetHash::populate(
+                governance_authority.pubkey(),
+                Some(hash_guardian_keys(&dummy_guardian_keys())),
+            ),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            PostUpdateAtomic::populate(
+                poster.pubkey(),
+                poster.pubkey(),
+                price_update_keypair.pubkey(),
+                BRIDGE_ID,
+                DEFAULT_GUARDIAN_SET_INDEX,
+                vaa.clone(),
+                merkle_price_updates[0].clone(),
+                DEFAULT_TREASURY_ID,
+            ),
+            &vec![&poster, &price_update_keypair],
+            None,
+        )
+        .await
+        .unwrap();
+
+    let price_update_account = program_simulator
+        .get_anchor_account_data::<PriceUpdateV2>(price_update_keypair.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(
+        Message::PriceFeedMessage(price_update_account.price_message),
+        feed_1
+    );
+}
+
+#[tokio::test]
+async fn test_post_update_atomic_guardian_set_expiration_skew() {
+    let feed_1 = create_dummy_price_feed_message(100);
+    let message = create_accumulator_message(&[feed_1], &[feed_1], false, false);
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+    let vaa = serde_wormhole::to_vec(&trim_vaa_signatures(
+        serde_wormhole::from_slice(&vaa).unwrap(),
+        5,
+    ))
+    .unwrap();
+
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses: _,
+        governance_authority,
+    } = setup_pyth_receiver(vec![], WrongSetupOption::GuardianSetExpiringSoon).await;
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let price_update_keypair = Keypair::new();
+
+    // The guardian set hasn't expired yet
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            PostUpdateAtomic::populate(
+                poster.pubkey(),
+                poster.pubkey(),
+                price_update_keypair.pubkey(),
+                BRIDGE_ID,
+                DEFAULT_GUARDIAN_SET_INDEX,
+                vaa.clone(),
+                merkle_price_updates[0].clone(),
+                DEFAULT_TREASURY_ID,
+            ),
+            &vec![&poster, &price_update_keypair],
+            None,
+        )
+        .await
+        .unwrap();
+
+    // With a skew larger than the time left, the guardian set is considered expired
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetGuardianSetExpirationSkew::populate(
+                governance_authority.pubkey(),
+                2 * GUARDIAN_SET_EXPIRING_SOON_DELAY.as_secs() as u32,
+            ),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                PostUpdateAtomic::populate(
+                    poster.pubkey(),
+                    poster.pubkey(),
+                    price_update_keypair.pubkey(),
+                    BRIDGE_ID,
+                    DEFAULT_GUARDIAN_SET_INDEX,
+                    vaa.clone(),
+                    merkle_price_updates[0].clone(),
+                    DEFAULT_TREASURY_ID,
+                ),
+                &vec![&poster, &price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::GuardianSetExpired)
+    );
+
+    // A skew smaller than the time left doesn't affect the guardian set
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            SetGuardianSetExpirationSkew::populate(governance_authority.pubkey(), 60 * 60),
+            &vec![&governance_authority],
+            None,
+        )
+        .await
+        .unwrap();
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            PostUpdateAtomic::populate(
+                poster.pubkey(),
+                poster.pubkey(),
+                price_update_keypair.pubkey(),
+                BRIDGE_ID,
+                DEFAULT_GUARDIAN_SET_INDEX,
+                vaa.clone(),
+                merkle_price_updates[0].clone(),
+                DEFAULT_TREASURY_ID,
+            ),
+            &vec![&poster, &price_update_keypair],
+            None,
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_post_update_atomic_guardian_set_rotation() {
+    let feed_1 = create_dummy_price_feed_message(100);
+    let message = create_accumulator_message(&[feed_1], &[feed_1], false, false);
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+    let vaa = serde_wormhole::to_vec(&trim_vaa_signatures(
+        serde_wormhole::from_slice(&vaa).unwrap(),
+        5,
+    ))
+    .unwrap();
+    // The same VAA signed by the guardian set that replaced the expired one (the guardians sign the body, not the guardian set index)
+    let mut rotated_vaa: Vaa<&RawMessage> = serde_wormhole::from_slice(&vaa).unwrap();
+    rotated_vaa.guardian_set_index = ROTATED_GUARDIAN_SET_INDEX;
+    let rotated_vaa = serde_wormhole::to_vec(&rotated_vaa).unwrap();
+
+    // The guardian set DEFAULT_GUARDIAN_SET_INDEX is expired and ROTATED_GUARDIAN_SET_INDEX is the current one
+    let ProgramTestFixtures {
+        mut program_simulator,
+        encoded_vaa_addresses: _,
+        governance_authority: _,
+    } = setup_pyth_receiver(vec![], WrongSetupOption::GuardianSetExpired).await;
+
+    let poster = program_simulator.get_funded_keypair().await.unwrap();
+    let price_update_keypair = Keypair::new();
+
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                PostUpdateAtomic::populate(
+                    poster.pubkey(),
+                    poster.pubkey(),
+                    price_update_keypair.pubkey(),
+                    BRIDGE_ID,
+                    DEFAULT_GUARDIAN_SET_INDEX,
+                    vaa.clone(),
+                    merkle_price_updates[0].clone(),
+                    DEFAULT_TREASURY_ID,
+                ),
+                &vec![&poster, &price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::GuardianSetExpired)
+    );
+
+    // The guardian set index of the VAA must match the guardian set account
+    assert_eq!(
+        program_simulator
+            .process_ix_with_default_compute_limit(
+                PostUpdateAtomic::populate(
+                    poster.pubkey(),
+                    poster.pubkey(),
+                    price_update_keypair.pubkey(),
+                    BRIDGE_ID,
+                    ROTATED_GUARDIAN_SET_INDEX,
+                    vaa.clone(),
+                    merkle_price_updates[0].clone(),
+                    DEFAULT_TREASURY_ID,
+                ),
+                &vec![&poster, &price_update_keypair],
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        into_transaction_error(ReceiverError::GuardianSetMismatch)
+    );
+
+    program_simulator
+        .process_ix_with_default_compute_limit(
+            PostUpdateAtomic::populate(
+                poster.pubkey(),
+                poster.pubkey(),
+                price_update_keypair.pubkey(),
+                BRIDGE_ID,
+                ROTATED_GUARDIAN_SET_INDEX,
+                rotated_vaa,
+                merkle_price_updates[0].clone(),
+                DEFAULT_TREASURY_ID,
+            ),
+            &vec![&poster, &price_update_keypair],
+            None,
+        )
+        .await
+        .unwrap();
+
+    let price_update_account = program_simulator
+        .get_anchor_account_data::<PriceUpdateV2>(price_update_keypair.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(
+        Message::PriceFeedMessage(price_update_account.price_message),
+        feed_1
+    );
+}
+
+#[test]
+fn test_post_update_atomic_transaction_size() {
+    let feed_1 = create_dummy_price_feed_message(100);
+    let feed_2 = create_dummy_price_feed_message(200);
+    let message = create_accumulator_message(&[feed_1, feed_2], &[feed_1, feed_2], false, false);
+    let (vaa, merkle_price_updates) = deserialize_accumulator_update_data(message).unwrap();
+
+    let payer = Keypair::new();
+    let price_update_keypair = Keypair::new();
+    let instructions_with_signatures = |n_signatures: u8| {
+        vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(400_000),
+            PostUpdateAtomic::populate(
+                payer.pubkey(),
+                payer.pubkey(),
+                price_update_keypair.pubkey(),
+                BRIDGE_ID,
+                DEFAULT_GUARDIAN_SET_INDEX,
+                serde_wormhole::to_vec(&trim_vaa_signatures(
+                    serde_wormhole::from_slice(&vaa).unwrap(),
+                    n_signatures,
+                ))
+                .unwrap(),
+                merkle_price_updates[0].clone(),
+                DEFAULT_TREASURY_ID,
+            ),
+        ]
+    };
+
+    // 5 signatures fit in a transaction, but not all the signatures of the dummy guardians
+    assert!(fits_in_transaction(
+        &instructions_with_signatures(5),
+        &payer.pubkey()
+    ));
+    assert!(!fits_in_transaction(
+        &instructions_with_signatures(dummy_guardian_keys().len() as u8),
+        &payer.pubkey()
+    ));
 }
@@ -1,17 +1,79 @@
 use {
+    crate::{
+        pda::get_price_change_baseline_address,
+        price_update::rescale,
+    },
     anchor_lang::prelude::*,
-    solana_program::pubkey::Pubkey,
+    pythnet_sdk::messages::{
+        FeedId,
+        PriceFeedMessage,
+    },
+    solana_program::{
+        instruction::AccountMeta,
+        keccak,
+        pubkey::Pubkey,
+    },
 };
 
 #[account]
 #[derive(Debug, PartialEq)]
 pub struct Config {
-    pub governance_authority:          Pubkey, // This authority can update the other fields
-    pub target_governance_authority:   Option<Pubkey>, // This field is used for a two-step governance authority transfer
-    pub wormhole:                      Pubkey,         // The address of the wormhole receiver
-    pub valid_data_sources:            Vec<DataSource>, // The list of valid data sources for oracle price updates
-    pub single_update_fee_in_lamports: u64, // The fee in lamports for a single price update
-    pub minimum_signatures:            u8, // The minimum number of signatures required to accept a VAA
+    pub governance_authority:                 Pubkey, // This authority can update the other fields
+    pub target_governance_authority:          Option<Pubkey>, // This field is used for a two-step governance authority transfer
+    pub wormhole:                             Pubkey, // The address of the wormhole receiver
+    pub valid_data_sources:                   Vec<DataSource>, // The list of valid data sources for oracle price updates
+    pub single_update_fee_in_lamports:        u64, // The fee in lamports for a single price update
+    pub minimum_signatures:                   u8, // The minimum number of signatures required to accept a VAA
+    pub pinned_guardian_set_hash:             Option<[u8; 32]>, // If set, post_update_atomic only accepts guardian sets whose keys hash to this value (see hash_guardian_keys)
+    pub fee_recipients:                       Vec<FeeRecipient>, // If not empty, the fee of every update is split between these accounts instead of going to the treasury
+    pub post_vaa_programs:                    Vec<Pubkey>, // Programs other than `wormhole` whose encoded VAA accounts are accepted (see is_approved_post_vaa_program)
+    pub guardian_set_expiration_skew_seconds: u32, // Guardian sets are considered expired this many seconds before their expiration time to tolerate clock differences (see set_guardian_set_expiration_skew)
+    pub price_change_limits:                  Vec<PriceChangeLimit>, // Updates that move the price of these feeds by more than their limit shortly after the last price of the feed are rejected (see set_price_change_limits)
+    pub min_price_update_lifetime_slots:      u64, // Price update accounts can only be closed this many slots after their last update (see reclaim_rent and close_price_update)
+    pub max_staleness_seconds:                u32, // If not 0, price updates published more than this many seconds before the current time are rejected (see set_publish_time_bounds)
+    pub max_future_publish_time_seconds:      u32, // If not 0, price updates published more than this many seconds after the current time are rejected (see set_publish_time_bounds)
+    pub exponent_range:                       Option<ExponentRange>, // If set, price updates whose exponent is outside this range are rejected (see set_price_sanity_bounds)
+    pub max_conf_bps:                         u32, // If not 0, price updates whose confidence interval is more than this many basis points of the price are rejected (see set_price_sanity_bounds)
+    pub allowed_feed_ids:                     Vec<FeedId>, // If not empty, the batch instructions skip the updates of other feeds (see set_allowed_feed_ids)
+    pub min_consistency_level:                u8, // Price updates from VAAs with a lower consistency level are rejected (see set_min_consistency_level)
+}
+
+/// The layout of the config accounts created by the first version of the program, which only had the fields up to `minimum_signatures`.
+/// That version didn't zero the end of the config account when the config shrank (e.g. when a governance authority transfer completed), so the bytes after these fields can be stale data rather than zeros (see `migrate_config`).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct LegacyConfig {
+    pub governance_authority:          Pubkey,
+    pub target_governance_authority:   Option<Pubkey>,
+    pub wormhole:                      Pubkey,
+    pub valid_data_sources:            Vec<DataSource>,
+    pub single_update_fee_in_lamports: u64,
+    pub minimum_signatures:            u8,
+}
+
+impl From<LegacyConfig> for Config {
+    /// The fields that don't exist in the legacy layout get the values that disable them.
+    fn from(legacy_config: LegacyConfig) -> Self {
+        Config {
+            governance_authority:                 legacy_config.governance_authority,
+            target_governance_authority:          legacy_config.target_governance_authority,
+            wormhole:                             legacy_config.wormhole,
+            valid_data_sources:                   legacy_config.valid_data_sources,
+            single_update_fee_in_lamports:        legacy_config.single_update_fee_in_lamports,
+            minimum_signatures:                   legacy_config.minimum_signatures,
+            pinned_guardian_set_hash:             None,
+            fee_recipients:                       vec![],
+            post_vaa_programs:                    vec![],
+            guardian_set_expiration_skew_seconds: 0,
+            price_change_limits:                  vec![],
+            min_price_update_lifetime_slots:      0,
+            max_staleness_seconds:                0,
+            max_future_publish_time_seconds:      0,
+            exponent_range:                       None,
+            max_conf_bps:                         0,
+            allowed_feed_ids:                     vec![],
+            min_consistency_level:                0,
+        }
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
@@ -20,29 +82,201 @@ pub struct DataSource {
     pub emitter: Pubkey,
 }
 
+/// A recipient of a share of the update fee. The weights of all the recipients in `Config::fee_recipients` sum to `TOTAL_FEE_WEIGHT_BPS`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct FeeRecipient {
+    pub recipient:  Pubkey,
+    pub weight_bps: u16,
+}
+
+pub const TOTAL_FEE_WEIGHT_BPS: u16 = 10_000;
+
+/// If the config has fee recipients, the posting instructions need their accounts at the end of the account list.
+/// Append the result of this function to the accounts of the instruction.
+pub fn get_fee_recipient_account_metas(fee_recipients: &[FeeRecipient]) -> Vec<AccountMeta> {
+    fee_recipients
+        .iter()
+        .map(|fee_recipient| AccountMeta::new(fee_recipient.recipient, false))
+        .collect()
+}
+
+/// If `feed_id` has a price change limit in the config, the posting instructions need its `PriceChangeBaseline` account after the fee recipient accounts.
+/// Append the result of this function to the accounts of the instruction, after `get_fee_recipient_account_metas`.
+pub fn get_price_change_baseline_account_meta(
+    config: &Config,
+    feed_id: &FeedId,
+) -> Option<AccountMeta> {
+    config
+        .price_change_limit(feed_id)
+        .map(|_| AccountMeta::new(get_price_change_baseline_address(feed_id), false))
+}
+
+/// The maximum change of the price of `feed_id` in a single update, in basis points of the last price of the feed (see `PriceChangeBaseline`).
+/// The limit only applies to updates published less than `window_seconds` after the last price, so that a lasting move of the price can't leave the feed stuck on the last price: the first update published after the window is accepted whatever its price. `window_seconds` can't be 0.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct PriceChangeLimit {
+    pub feed_id:        FeedId,
+    pub max_change_bps: u16,
+    pub window_seconds: u32,
+}
+
+impl PriceChangeLimit {
+    /// Whether `new` breaks this limit when the last price of the feed is `previous`.
+    pub fn is_exceeded_by(&self, previous: &PriceFeedMessage, new: &PriceFeedMessage) -> bool {
+        new.publish_time
+            < previous
+                .publish_time
+                .saturating_add(i64::from(self.window_seconds))
+            && price_moved_too_much(previous, new, self.max_change_bps)
+    }
+}
+
+/// The last price of a feed with a `PriceChangeLimit`, whatever the price update account it was posted to.
+/// There is one such account per feed, at the PDA of `get_price_change_baseline_address`. It's created by the first update of the feed after the limit is set, and every later update of the feed is compared with it.
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct PriceChangeBaseline {
+    pub price_message: PriceFeedMessage,
+}
+
+impl PriceChangeBaseline {
+    pub const LEN: usize = 8 + 84;
+}
+
+/// The range of exponents accepted for price updates, inclusive.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct ExponentRange {
+    pub min: i32,
+    pub max: i32,
+}
+
 impl Config {
-    pub const LEN: usize = 370; // This is two times the current size of a Config account with 2 data sources, to leave space for more fields
+    pub const LEN: usize = 600; // This leaves space for more fields after a Config account with 2 data sources, 2 fee recipients, a post VAA program, a price change limit and 5 allowed feed ids. Accounts created before are 370 bytes, they can be grown with `resize_config`
+
+    /// Whether an encoded VAA account owned by `program` can be used to post updates.
+    /// `wormhole` is always approved, `post_vaa_programs` lets a second version of the Wormhole receiver coexist with it during a migration.
+    pub fn is_approved_post_vaa_program(&self, program: &Pubkey) -> bool {
+        *program == self.wormhole || self.post_vaa_programs.contains(program)
+    }
+
+    /// The limit on the price change of `feed_id` in a single update, if there is one in `price_change_limits`.
+    pub fn price_change_limit(&self, feed_id: &FeedId) -> Option<&PriceChangeLimit> {
+        self.price_change_limits
+            .iter()
+            .find(|price_change_limit| price_change_limit.feed_id == *feed_id)
+    }
+
+    /// Whether the batch instructions post the updates of `feed_id`, which is the case of every feed if `allowed_feed_ids` is empty.
+    pub fn is_feed_allowed(&self, feed_id: &FeedId) -> bool {
+        self.allowed_feed_ids.is_empty() || self.allowed_feed_ids.contains(feed_id)
+    }
+
+    /// Whether the exponent and the confidence interval of `price_feed_message` are within the bounds of `exponent_range` and `max_conf_bps`.
+    pub fn is_price_sane(&self, price_feed_message: &PriceFeedMessage) -> bool {
+        let exponent_in_range = self.exponent_range.map_or(true, |exponent_range| {
+            (exponent_range.min..=exponent_range.max).contains(&price_feed_message.exponent)
+        });
+        let conf_in_range = self.max_conf_bps == 0
+            || u128::from(price_feed_message.conf) * 10_000
+                <= u128::from(price_feed_message.price.unsigned_abs())
+                    * u128::from(self.max_conf_bps);
+        exponent_in_range && conf_in_range
+    }
+}
+
+/// The hash of a guardian set that can be pinned in `Config::pinned_guardian_set_hash`: the keccak256 hash of the concatenated guardian keys.
+pub fn hash_guardian_keys(keys: &[[u8; 20]]) -> [u8; 32] {
+    keccak::hashv(&keys.iter().map(|key| key.as_ref()).collect::<Vec<&[u8]>>()).to_bytes()
+}
+
+/// Split `fee` between `fee_recipients` according to their weights, returning the share of each recipient in the same order.
+/// The shares are rounded down and the remainder goes to the first recipient, so the shares always sum to `fee`.
+pub fn split_fee(fee: u64, fee_recipients: &[FeeRecipient]) -> Vec<u64> {
+    let mut shares: Vec<u64> = fee_recipients
+        .iter()
+        .map(|fee_recipient| {
+            (u128::from(fee) * u128::from(fee_recipient.weight_bps)
+                / u128::from(TOTAL_FEE_WEIGHT_BPS)) as u64
+        })
+        .collect();
+    let remainder = fee.saturating_sub(shares.iter().sum::<u64>());
+    if let Some(first_share) = shares.first_mut() {
+        *first_share += remainder;
+    }
+    shares
+}
+
+/// Whether the price of `new` differs from the price of `previous` by more than `max_change_bps` basis points of the price of `previous`.
+/// The prices are compared with the smaller of their exponents, so that a change of exponent isn't mistaken for a price move. If they can't be compared (`rescale` overflows because the exponents are too far apart), the price is considered to have moved too much.
+pub fn price_moved_too_much(
+    previous: &PriceFeedMessage,
+    new: &PriceFeedMessage,
+    max_change_bps: u16,
+) -> bool {
+    let exponent = previous.exponent.min(new.exponent);
+    let scale_price = |price_message: &PriceFeedMessage| {
+        rescale(price_message.price, 0, price_message.exponent, exponent).map(|(price, _)| price)
+    };
+    let (Ok(previous_price), Ok(new_price)) = (scale_price(previous), scale_price(new)) else {
+        return true;
+    };
+
+    u128::from(new_price.abs_diff(previous_price)) * 10_000
+        > u128::from(previous_price.unsigned_abs()) * u128::from(max_change_bps)
 }
 
 #[cfg(test)]
 pub mod tests {
     use {
-        super::DataSource,
+        super::{
+            price_moved_too_much,
+            split_fee,
+            DataSource,
+            ExponentRange,
+            FeeRecipient,
+            LegacyConfig,
+            PriceChangeBaseline,
+            PriceChangeLimit,
+        },
         crate::config::Config,
         anchor_lang::{
+            AnchorDeserialize,
             AnchorSerialize,
             Discriminator,
         },
+        pythnet_sdk::messages::PriceFeedMessage,
         solana_program::pubkey::Pubkey,
     };
 
+    /// A config with empty lists and every optional check disabled, tests set the fields they need with struct update syntax.
+    fn test_config() -> Config {
+        Config {
+            governance_authority:                 Pubkey::new_unique(),
+            target_governance_authority:          None,
+            wormhole:                             Pubkey::new_unique(),
+            valid_data_sources:                   vec![],
+            single_update_fee_in_lamports:        0,
+            minimum_signatures:                   0,
+            pinned_guardian_set_hash:             None,
+            fee_recipients:                       vec![],
+            post_vaa_programs:                    vec![],
+            guardian_set_expiration_skew_seconds: 0,
+            price_change_limits:                  vec![],
+            min_price_update_lifetime_slots:      0,
+            max_staleness_seconds:                0,
+            max_future_publish_time_seconds:      0,
+            exponent_range:                       None,
+            max_conf_bps:                         0,
+            allowed_feed_ids:                     vec![],
+            min_consistency_level:                0,
+        }
+    }
+
     #[test]
     fn check_size() {
         let test_config = Config {
-            governance_authority:          Pubkey::new_unique(),
-            target_governance_authority:   Some(Pubkey::new_unique()),
-            wormhole:                      Pubkey::new_unique(),
-            valid_data_sources:            vec![
+            target_governance_authority: Some(Pubkey::new_unique()),
+            valid_data_sources: vec![
                 DataSource {
                     chain:   1,
                     emitter: Pubkey::new_unique(),
@@ -52,16 +286,289 @@ pub mod tests {
                     emitter: Pubkey::new_unique(),
                 },
             ],
-            single_update_fee_in_lamports: 0,
-            minimum_signatures:            0,
+            pinned_guardian_set_hash: Some([0; 32]),
+            fee_recipients: vec![
+                FeeRecipient {
+                    recipient:  Pubkey::new_unique(),
+                    weight_bps: 5000,
+                },
+                FeeRecipient {
+                    recipient:  Pubkey::new_unique(),
+                    weight_bps: 5000,
+                },
+            ],
+            post_vaa_programs: vec![Pubkey::new_unique()],
+            price_change_limits: vec![PriceChangeLimit {
+                feed_id:        [0; 32],
+                max_change_bps: 1000,
+                window_seconds: 60,
+            }],
+            exponent_range: Some(ExponentRange { min: -12, max: 0 }),
+            allowed_feed_ids: vec![[0; 32]; 5],
+            ..test_config()
         };
 
         assert_eq!(
             test_config.try_to_vec().unwrap().len(),
-            32 + 1 + 32 + 32 + 4 + 1 + 33 + 1 + 33 + 8 + 1
+            32 + 1
+                + 32
+                + 32
+                + 4
+                + 1
+                + 33
+                + 1
+                + 33
+                + 8
+                + 1
+                + 1
+                + 32
+                + 4
+                + 34
+                + 34
+                + 4
+                + 32
+                + 4
+                + 4
+                + 38
+                + 8
+                + 4
+                + 4
+                + 9
+                + 4
+                + 4
+                + 5 * 32
+                + 1
         );
         assert!(
             Config::discriminator().len() + test_config.try_to_vec().unwrap().len() <= Config::LEN
         );
+
+        let price_change_baseline = PriceChangeBaseline {
+            price_message: PriceFeedMessage {
+                feed_id:           [0; 32],
+                price:             0,
+                conf:              0,
+                exponent:          0,
+                publish_time:      0,
+                prev_publish_time: 0,
+                ema_price:         0,
+                ema_conf:          0,
+            },
+        };
+        assert_eq!(
+            PriceChangeBaseline::discriminator().len()
+                + price_change_baseline.try_to_vec().unwrap().len(),
+            PriceChangeBaseline::LEN
+        );
+    }
+
+    #[test]
+    fn check_legacy_config() {
+        let legacy_config = LegacyConfig {
+            governance_authority:          Pubkey::new_unique(),
+            target_governance_authority:   None,
+            wormhole:                      Pubkey::new_unique(),
+            valid_data_sources:            vec![DataSource {
+                chain:   26,
+                emitter: Pubkey::new_unique(),
+            }],
+            single_update_fee_in_lamports: 1,
+            minimum_signatures:            5,
+        };
+
+        // The defaults of the new fields are what a legacy config followed by zeros deserializes to
+        let mut legacy_config_data = legacy_config.try_to_vec().unwrap();
+        legacy_config_data.resize(Config::LEN, 0);
+        assert_eq!(
+            Config::deserialize(&mut legacy_config_data.as_slice()).unwrap(),
+            Config::from(legacy_config.clone())
+        );
+
+        // A stale byte where `pinned_guardian_set_hash` starts keeps the config from deserializing
+        legacy_config_data[legacy_config.try_to_vec().unwrap().len()] = 0x9b;
+        assert!(Config::deserialize(&mut legacy_config_data.as_slice()).is_err());
+        assert_eq!(
+            LegacyConfig::deserialize(&mut legacy_config_data.as_slice()).unwrap(),
+            legacy_config
+        );
+    }
+
+    #[test]
+    fn check_is_approved_post_vaa_program() {
+        let wormhole = Pubkey::new_unique();
+        let post_vaa_program = Pubkey::new_unique();
+        let test_config = Config {
+            wormhole,
+            post_vaa_programs: vec![post_vaa_program],
+            ..test_config()
+        };
+
+        assert!(test_config.is_approved_post_vaa_program(&wormhole));
+        assert!(test_config.is_approved_post_vaa_program(&post_vaa_program));
+        assert!(!test_config.is_approved_post_vaa_program(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn check_split_fee() {
+        let fee_recipient = |weight_bps: u16| FeeRecipient {
+            recipient: Pubkey::new_unique(),
+            weight_bps,
+        };
+
+        assert_eq!(split_fee(100, &[]), Vec::<u64>::new());
+        assert_eq!(split_fee(100, &[fee_recipient(10_000)]), vec![100]);
+        assert_eq!(
+            split_fee(100, &[fee_recipient(7_000), fee_recipient(3_000)]),
+            vec![70, 30]
+        );
+        // The remainder of the rounding goes to the first recipient
+        assert_eq!(
+            split_fee(
+                10,
+                &[
+                    fee_recipient(3_334),
+                    fee_recipient(3_333),
+                    fee_recipient(3_333)
+                ]
+            ),
+            vec![4, 3, 3]
+        );
+        assert_eq!(
+            split_fee(1, &[fee_recipient(5_000), fee_recipient(5_000)]),
+            vec![1, 0]
+        );
+        assert_eq!(
+            split_fee(0, &[fee_recipient(5_000), fee_recipient(5_000)]),
+            vec![0, 0]
+        );
+        assert_eq!(
+            split_fee(u64::MAX, &[fee_recipient(1), fee_recipient(9_999)]),
+            vec![1_844_674_407_370_956, 18_444_899_399_302_180_659]
+        );
+    }
+
+    #[test]
+    fn check_price_moved_too_much() {
+        let price_message = |price: i64, exponent: i32| PriceFeedMessage {
+            feed_id: [0; 32],
+            price,
+            conf: 0,
+            exponent,
+            publish_time: 0,
+            prev_publish_time: 0,
+            ema_price: 0,
+            ema_conf: 0,
+        };
+
+        assert!(!price_moved_too_much(
+            &price_message(100, -2),
+            &price_message(110, -2),
+            1000
+        ));
+        assert!(!price_moved_too_much(
+            &price_message(100, -2),
+            &price_message(90, -2),
+            1000
+        ));
+        assert!(price_moved_too_much(
+            &price_message(100, -2),
+            &price_message(111, -2),
+            1000
+        ));
+        assert!(price_moved_too_much(
+            &price_message(100, -2),
+            &price_message(89, -2),
+            1000
+        ));
+        assert!(price_moved_too_much(
+            &price_message(-100, -2),
+            &price_message(-111, -2),
+            1000
+        ));
+        // The same price with another exponent
+        assert!(!price_moved_too_much(
+            &price_message(100, -2),
+            &price_message(1000, -3),
+            0
+        ));
+        assert!(price_moved_too_much(
+            &price_message(100, -2),
+            &price_message(100, -3),
+            1000
+        ));
+        // Any change from a price of 0 is too much
+        assert!(price_moved_too_much(
+            &price_message(0, -2),
+            &price_message(1, -2),
+            u16::MAX
+        ));
+        // Prices that can't be compared
+        assert!(price_moved_too_much(
+            &price_message(100, i32::MAX),
+            &price_message(100, i32::MIN),
+            u16::MAX
+        ));
+        assert!(price_moved_too_much(
+            &price_message(i64::MAX, -2),
+            &price_message(i64::MAX, -3),
+            u16::MAX
+        ));
+    }
+
+    #[test]
+    fn check_price_change_limit() {
+        let price_message = |price: i64, publish_time: i64| PriceFeedMessage {
+            feed_id: [0; 32],
+            price,
+            conf: 0,
+            exponent: -2,
+            publish_time,
+            prev_publish_time: 0,
+            ema_price: 0,
+            ema_conf: 0,
+        };
+        let price_change_limit = PriceChangeLimit {
+            feed_id:        [0; 32],
+            max_change_bps: 1000,
+            window_seconds: 60,
+        };
+
+        assert!(!price_change_limit.is_exceeded_by(&price_message(100, 0), &price_message(110, 59)));
+        assert!(price_change_limit.is_exceeded_by(&price_message(100, 0), &price_message(111, 59)));
+        // Updates published after the window are accepted whatever their price
+        assert!(!price_change_limit.is_exceeded_by(&price_message(100, 0), &price_message(111, 60)));
+        // Older updates are always checked
+        assert!(price_change_limit.is_exceeded_by(&price_message(100, 60), &price_message(111, 0)));
+    }
+
+    #[test]
+    fn check_is_price_sane() {
+        let mut test_config = test_config();
+        let price_message = |price: i64, conf: u64, exponent: i32| PriceFeedMessage {
+            feed_id: [0; 32],
+            price,
+            conf,
+            exponent,
+            publish_time: 0,
+            prev_publish_time: 0,
+            ema_price: 0,
+            ema_conf: 0,
+        };
+
+        // Everything is accepted by default
+        assert!(test_config.is_price_sane(&price_message(0, u64::MAX, i32::MAX)));
+
+        test_config.exponent_range = Some(ExponentRange { min: -12, max: 0 });
+        assert!(test_config.is_price_sane(&price_message(100, 1, -12)));
+        assert!(test_config.is_price_sane(&price_message(100, 1, 0)));
+        assert!(!test_config.is_price_sane(&price_message(100, 1, -13)));
+        assert!(!test_config.is_price_sane(&price_message(100, 1, 1)));
+
+        test_config.max_conf_bps = 1000;
+        assert!(test_config.is_price_sane(&price_message(100, 10, -8)));
+        assert!(test_config.is_price_sane(&price_message(-100, 10, -8)));
+        assert!(!test_config.is_price_sane(&price_message(100, 11, -8)));
+        assert!(!test_config.is_price_sane(&price_message(0, 1, -8)));
+        assert!(!test_config.is_price_sane(&price_message(i64::MIN, u64::MAX, -8)));
     }
 }
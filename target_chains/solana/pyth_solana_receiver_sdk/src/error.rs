@@ -13,6 +13,8 @@ pub enum GetPriceError {
     FeedIdMustBe32Bytes,
     #[msg("Feed id contains non-hex characters")]
     FeedIdNonHexCharacter,
+    #[msg("Rescaling the price to the requested exponent overflows")]
+    RescaleOverflow,
 }
 
 #[macro_export]
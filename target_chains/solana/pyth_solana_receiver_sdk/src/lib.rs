@@ -33,3 +33,174 @@ pub struct PostUpdateParams {
     pub merkle_price_update: MerklePriceUpdate,
     pub treasury_id:         u8,
 }
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PostUpdatesFromAccountDataParams {
+    pub treasury_id:           u8,
+    pub event_mode:            EventMode,
+    /// If true, every update is also added to a `PriceHistory` account passed after the price update accounts.
+    pub write_price_history:   bool,
+    /// If set, the instruction fails if the accumulator update data contains more updates than this, which bounds the compute and the fee of the instruction.
+    pub expected_updates:      Option<u16>,
+    /// If true, the accumulator update data must contain exactly `expected_updates` updates instead of at most `expected_updates`. This has no effect if `expected_updates` isn't set.
+    pub require_exact_count:   bool,
+    /// If set, updates from VAAs whose consistency level is lower than this are rejected. This can only raise `Config::min_consistency_level`, a lower value has no effect.
+    pub min_consistency_level: Option<u8>,
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PostUpdatesFromVaasParams {
+    pub merkle_price_updates:  Vec<MerklePriceUpdate>,
+    /// The number of encoded VAA accounts at the start of the remaining accounts.
+    pub num_vaas:              u8,
+    /// For each update, the index of the encoded VAA account whose root includes it. If empty, every update is checked against the VAAs in order until one includes it.
+    pub vaa_indices:           Vec<u8>,
+    pub treasury_id:           u8,
+    /// See `PostUpdatesFromAccountDataParams::min_consistency_level`.
+    pub min_consistency_level: Option<u8>,
+}
+
+/// The maximum number of feed ids in a `PostedUpdatesSummary`, so that it fits in the 1024 bytes of return data of an instruction.
+pub const MAX_SUMMARY_FEED_IDS: usize = 31;
+
+/// The return data of the instructions that post a batch of price updates.
+/// A program that calls one of these instructions through CPI can read it with `get_return_data` to know which updates were verified.
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct PostedUpdatesSummary {
+    /// The number of updates posted by the instruction.
+    pub count:     u32,
+    /// The feed ids of the updates, in the same order as the updates. Only the first `MAX_SUMMARY_FEED_IDS` are included.
+    pub feed_ids:  Vec<[u8; 32]>,
+    /// Whether `feed_ids` is missing the feed ids of some of the updates.
+    pub truncated: bool,
+    /// The number of updates that weren't posted because their feed isn't in `Config::allowed_feed_ids`.
+    pub skipped:   u32,
+}
+
+impl PostedUpdatesSummary {
+    pub fn new(feed_ids: &[[u8; 32]], num_skipped: u32) -> Self {
+        Self::with_max_feed_ids(feed_ids, num_skipped, MAX_SUMMARY_FEED_IDS)
+    }
+
+    /// Same as `new`, with at most `max_feed_ids` feed ids instead of `MAX_SUMMARY_FEED_IDS`.
+    pub fn with_max_feed_ids(feed_ids: &[[u8; 32]], num_skipped: u32, max_feed_ids: usize) -> Self {
+        PostedUpdatesSummary {
+            count:     feed_ids.len() as u32,
+            feed_ids:  feed_ids.iter().take(max_feed_ids).copied().collect(),
+            truncated: feed_ids.len() > max_feed_ids,
+            skipped:   num_skipped,
+        }
+    }
+}
+
+/// The maximum number of feed ids in the summary of a `TaggedPostedUpdatesSummary`, one less than `MAX_SUMMARY_FEED_IDS` to make room for the tag.
+pub const MAX_TAGGED_SUMMARY_FEED_IDS: usize = MAX_SUMMARY_FEED_IDS - 1;
+
+/// The return data of `post_updates_with_tag`: the tag passed by the caller along with a `PostedUpdatesSummary` of the posted updates.
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct TaggedPostedUpdatesSummary {
+    pub tag:     [u8; 32],
+    /// Only the first `MAX_TAGGED_SUMMARY_FEED_IDS` feed ids are included.
+    pub summary: PostedUpdatesSummary,
+}
+
+impl TaggedPostedUpdatesSummary {
+    pub fn new(tag: [u8; 32], feed_ids: &[[u8; 32]], num_skipped: u32) -> Self {
+        TaggedPostedUpdatesSummary {
+            tag,
+            summary: PostedUpdatesSummary::with_max_feed_ids(
+                feed_ids,
+                num_skipped,
+                MAX_TAGGED_SUMMARY_FEED_IDS,
+            ),
+        }
+    }
+}
+
+/// The maximum number of updates that `verify_updates_view` accepts, so that the decoded `PriceFeedMessage`s fit in the 1024 bytes of return data of an instruction.
+pub const MAX_VIEW_UPDATES: usize = 12;
+
+/// Controls which events are emitted when posting a batch of price updates.
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum EventMode {
+    /// Emit one `PriceUpdatePosted` event per update.
+    Verbose,
+    /// Emit a single `PriceUpdatesPosted` event for the whole batch. Use this for large batches to stay within the transaction log limits.
+    Summary,
+}
+
+#[cfg(test)]
+pub mod tests {
+    use {
+        super::{
+            PostedUpdatesSummary,
+            TaggedPostedUpdatesSummary,
+            MAX_SUMMARY_FEED_IDS,
+            MAX_TAGGED_SUMMARY_FEED_IDS,
+            MAX_VIEW_UPDATES,
+        },
+        anchor_lang::AnchorSerialize,
+        pythnet_sdk::messages::PriceFeedMessage,
+        solana_program::program::MAX_RETURN_DATA,
+    };
+
+    #[test]
+    fn check_posted_updates_summary_size() {
+        let summary = PostedUpdatesSummary::new(&[[1; 32]; MAX_SUMMARY_FEED_IDS], u32::MAX);
+        assert_eq!(summary.count as usize, MAX_SUMMARY_FEED_IDS);
+        assert!(!summary.truncated);
+        assert!(summary.try_to_vec().unwrap().len() <= MAX_RETURN_DATA);
+
+        let summary = PostedUpdatesSummary::new(&[[1; 32]; MAX_SUMMARY_FEED_IDS + 1], u32::MAX);
+        assert_eq!(summary.count as usize, MAX_SUMMARY_FEED_IDS + 1);
+        assert_eq!(summary.feed_ids.len(), MAX_SUMMARY_FEED_IDS);
+        assert!(summary.truncated);
+        assert!(summary.try_to_vec().unwrap().len() <= MAX_RETURN_DATA);
+    }
+
+    #[test]
+    fn check_tagged_posted_updates_summary_size() {
+        let tagged_summary =
+            TaggedPostedUpdatesSummary::new([2; 32], &[[1; 32]; MAX_SUMMARY_FEED_IDS], u32::MAX);
+        assert_eq!(tagged_summary.tag, [2; 32]);
+        assert_eq!(tagged_summary.summary.count as usize, MAX_SUMMARY_FEED_IDS);
+        assert_eq!(
+            tagged_summary.summary.feed_ids.len(),
+            MAX_TAGGED_SUMMARY_FEED_IDS
+        );
+        assert!(tagged_summary.summary.truncated);
+        assert_eq!(tagged_summary.summary.skipped, u32::MAX);
+        assert!(tagged_summary.try_to_vec().unwrap().len() <= MAX_RETURN_DATA);
+
+        let tagged_summary =
+            TaggedPostedUpdatesSummary::new([2; 32], &[[1; 32]; MAX_TAGGED_SUMMARY_FEED_IDS], 0);
+        assert!(!tagged_summary.summary.truncated);
+    }
+    #[test]
+    fn check_view_updates_size() {
+        let price_feed_message = PriceFeedMessage {
+            feed_id:           [1; 32],
+            price:             1,
+            conf:              1,
+            exponent:          1,
+            publish_time:      1,
+            prev_publish_time: 1,
+            ema_price:         1,
+            ema_conf:          1,
+        };
+        assert!(
+            vec![price_feed_message; MAX_VIEW_UPDATES]
+                .try_to_vec()
+                .unwrap()
+                .len()
+                <= MAX_RETURN_DATA
+        );
+        assert!(
+            vec![price_feed_message; MAX_VIEW_UPDATES + 1]
+                .try_to_vec()
+                .unwrap()
+                .len()
+                > MAX_RETURN_DATA
+        );
+    }
+}
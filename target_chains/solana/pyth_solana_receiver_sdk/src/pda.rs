@@ -1,10 +1,12 @@
 use {
     crate::ID,
     anchor_lang::prelude::*,
+    pythnet_sdk::messages::FeedId,
 };
 
 pub const CONFIG_SEED: &str = "config";
 pub const TREASURY_SEED: &str = "treasury";
+pub const PRICE_CHANGE_BASELINE_SEED: &str = "price_change_baseline";
 
 // There is one treasury for each u8 value
 // This is to load balance the write load
@@ -15,3 +17,12 @@ pub fn get_treasury_address(treasury_id: u8) -> Pubkey {
 pub fn get_config_address() -> Pubkey {
     Pubkey::find_program_address(&[CONFIG_SEED.as_ref()], &ID).0
 }
+
+// There is one price change baseline for each feed with a price change limit
+pub fn get_price_change_baseline_address(feed_id: &FeedId) -> Pubkey {
+    Pubkey::find_program_address(
+        &[PRICE_CHANGE_BASELINE_SEED.as_ref(), feed_id.as_ref()],
+        &ID,
+    )
+    .0
+}
@@ -48,6 +48,16 @@ impl VerificationLevel {
     }
 }
 
+/// The timestamp used to measure the age of a price update.
+/// A `PriceUpdateV2` account stores both, so each consumer can pick the one that matches its freshness-accounting model.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TimestampSource {
+    /// The `publish_time` of the price message, i.e. when the price was published by Pyth. Ages are measured in seconds.
+    Message,
+    /// The `posted_slot` of the price update account, i.e. when the price update was verified on Solana. Ages are measured in slots.
+    OnChain,
+}
+
 /// A price update account. This account is used by the Pyth Receiver program to store a verified price update from a Pyth price feed.
 /// It contains:
 /// - `write_authority`: The write authority for this account. This authority can close this account to reclaim rent or update the account to contain a different price update.
@@ -67,6 +77,90 @@ impl PriceUpdateV2 {
     pub const LEN: usize = 8 + 32 + 2 + 32 + 8 + 8 + 4 + 8 + 8 + 8 + 8 + 8;
 }
 
+/// A TWAP update account. This account is used by the Pyth Receiver program to store a verified TWAP message from a Pyth price feed.
+/// It contains the same `write_authority`, `verification_level` and `posted_slot` as [`PriceUpdateV2`], and each field of the TWAP message:
+/// - `feed_id`: The feed id of the price feed.
+/// - `cumulative_price` and `cumulative_conf`: The sums of the price and the confidence interval of the feed at every slot since the feed started.
+/// - `num_down_slots`: The number of slots during which the feed didn't have a valid price since the feed started.
+/// - `exponent`: The exponent of the cumulative price and confidence interval.
+/// - `publish_time`, `prev_publish_time`: The timestamps of this update and of the previous update.
+/// - `publish_slot`: The Pythnet slot of this update.
+///
+/// The TWAP between two TWAP updates is `(cumulative_price_2 - cumulative_price_1) / (publish_slot_2 - publish_slot_1)`.
+#[account]
+#[derive(BorshSchema)]
+pub struct TwapUpdate {
+    pub write_authority:    Pubkey,
+    pub verification_level: VerificationLevel,
+    pub feed_id:            FeedId,
+    pub cumulative_price:   i128,
+    pub cumulative_conf:    u128,
+    pub num_down_slots:     u64,
+    pub exponent:           i32,
+    pub publish_time:       i64,
+    pub prev_publish_time:  i64,
+    pub publish_slot:       u64,
+    pub posted_slot:        u64,
+}
+
+impl TwapUpdate {
+    pub const LEN: usize = 8 + 32 + 2 + 32 + 16 + 16 + 8 + 4 + 8 + 8 + 8 + 8;
+}
+
+/// The number of price updates kept in a [`PriceHistory`] account.
+pub const PRICE_HISTORY_LEN: usize = 32;
+
+/// A price history account. This account is used by the Pyth Receiver program to store the most recent price updates of a single feed, for consumers that need more than the latest price (e.g. to compute a TWAP or the volatility).
+/// It contains:
+/// - `write_authority`: The write authority for this account, like for [`PriceUpdateV2`].
+/// - `feed_id`: The feed of the price updates. It is set by the first update.
+/// - `next_index`: The index of `updates` that the next price update will overwrite once the buffer is full.
+/// - `updates`: A ring buffer of the last `PRICE_HISTORY_LEN` price updates, use [`PriceHistory::get_updates`] to read them in chronological order.
+#[account]
+pub struct PriceHistory {
+    pub write_authority: Pubkey,
+    pub feed_id:         FeedId,
+    pub next_index:      u8,
+    pub updates:         Vec<PriceFeedMessage>,
+}
+
+impl PriceHistory {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 4 + PRICE_HISTORY_LEN * 84;
+
+    /// The most recent price update.
+    pub fn latest(&self) -> Option<&PriceFeedMessage> {
+        if self.updates.len() < PRICE_HISTORY_LEN {
+            self.updates.last()
+        } else {
+            self.updates
+                .get((usize::from(self.next_index) + PRICE_HISTORY_LEN - 1) % PRICE_HISTORY_LEN)
+        }
+    }
+
+    /// Add a price update to the history, overwriting the oldest one once the buffer is full.
+    /// Updates that aren't more recent than the latest update are ignored, in which case this returns false.
+    pub fn push(&mut self, price_message: PriceFeedMessage) -> bool {
+        if self.latest().map_or(false, |latest| {
+            price_message.publish_time <= latest.publish_time
+        }) {
+            return false;
+        }
+        if self.updates.len() < PRICE_HISTORY_LEN {
+            self.updates.push(price_message);
+        } else {
+            self.updates[usize::from(self.next_index)] = price_message;
+            self.next_index = ((usize::from(self.next_index) + 1) % PRICE_HISTORY_LEN) as u8;
+        }
+        true
+    }
+
+    /// The price updates in chronological order, from the oldest to the most recent.
+    pub fn get_updates(&self) -> Vec<PriceFeedMessage> {
+        let (newest, oldest) = self.updates.split_at(usize::from(self.next_index));
+        oldest.iter().chain(newest.iter()).copied().collect()
+    }
+}
+
 /// A Pyth price.
 /// The actual price is `(price ± conf)* 10^exponent`. `publish_time` may be used to check the recency of the price.
 #[derive(PartialEq, Debug, Clone, Copy)]
@@ -77,6 +171,22 @@ pub struct Price {
     pub publish_time: i64,
 }
 
+impl Price {
+    /// Express this price with `target_exponent` as its exponent, see [`rescale`].
+    pub fn scale_to_exponent(
+        &self,
+        target_exponent: i32,
+    ) -> std::result::Result<Price, GetPriceError> {
+        let (price, conf) = rescale(self.price, self.conf, self.exponent, target_exponent)?;
+        Ok(Price {
+            price,
+            conf,
+            exponent: target_exponent,
+            publish_time: self.publish_time,
+        })
+    }
+}
+
 impl PriceUpdateV2 {
     /// Get a `Price` from a `PriceUpdateV2` account for a given `FeedId`.
     ///
@@ -134,19 +244,51 @@ impl PriceUpdateV2 {
         maximum_age: u64,
         feed_id: &FeedId,
         verification_level: VerificationLevel,
+    ) -> std::result::Result<Price, GetPriceError> {
+        self.get_price_no_older_than_with_timestamp_source(
+            clock,
+            maximum_age,
+            feed_id,
+            verification_level,
+            TimestampSource::Message,
+        )
+    }
+
+    /// Get a `Price` from a `PriceUpdateV2` account for a given `FeedId` no older than `maximum_age` with customizable verification level, where the age is measured from `timestamp_source`.
+    ///
+    /// With `TimestampSource::Message`, `maximum_age` is in seconds and is compared against the `publish_time` of the price.
+    /// With `TimestampSource::OnChain`, `maximum_age` is in slots and is compared against the slot at which the price update was posted.
+    /// Note that a price update posted recently may still contain an old price, so `TimestampSource::OnChain` only makes sense for protocols that account for freshness in terms of when prices land on Solana.
+    ///
+    /// # Warning
+    /// Lowering the verification level from `Full` to `Partial` increases the risk of using a malicious price update.
+    /// Please read the documentation for [`VerificationLevel`] for more information.
+    pub fn get_price_no_older_than_with_timestamp_source(
+        &self,
+        clock: &Clock,
+        maximum_age: u64,
+        feed_id: &FeedId,
+        verification_level: VerificationLevel,
+        timestamp_source: TimestampSource,
     ) -> std::result::Result<Price, GetPriceError> {
         check!(
             self.verification_level.gte(verification_level),
             GetPriceError::InsufficientVerificationLevel
         );
         let price = self.get_price_unchecked(feed_id)?;
-        check!(
-            price
-                .publish_time
-                .saturating_add(maximum_age.try_into().unwrap())
-                >= clock.unix_timestamp,
-            GetPriceError::PriceTooOld
-        );
+        match timestamp_source {
+            TimestampSource::Message => check!(
+                price
+                    .publish_time
+                    .saturating_add(maximum_age.try_into().unwrap())
+                    >= clock.unix_timestamp,
+                GetPriceError::PriceTooOld
+            ),
+            TimestampSource::OnChain => check!(
+                self.posted_slot.saturating_add(maximum_age) >= clock.slot,
+                GetPriceError::PriceTooOld
+            ),
+        }
         Ok(price)
     }
 
@@ -212,18 +354,67 @@ pub fn get_feed_id_from_hex(input: &str) -> std::result::Result<FeedId, GetPrice
     Ok(feed_id)
 }
 
+/// Rescale a price and its confidence interval from `from_expo` to `to_expo`, i.e. return `(price * 10^(from_expo - to_expo), conf * 10^(from_expo - to_expo))`.
+/// All the intermediate math is checked and done with 128-bit integers.
+///
+/// When `to_expo > from_expo` precision is lost and the results are truncated towards zero.
+/// When `to_expo < from_expo` the results are multiplied by a power of 10 and this returns `GetPriceError::RescaleOverflow` if they don't fit in an `i64` and a `u64`.
+pub fn rescale(
+    price: i64,
+    conf: u64,
+    from_expo: i32,
+    to_expo: i32,
+) -> std::result::Result<(i64, u64), GetPriceError> {
+    let expo_diff = i64::from(from_expo) - i64::from(to_expo);
+    let pow_10 = |exponent: i64| {
+        u32::try_from(exponent)
+            .ok()
+            .and_then(|exponent| 10i128.checked_pow(exponent))
+    };
+
+    let (price, conf) = if expo_diff >= 0 {
+        let factor = pow_10(expo_diff).ok_or(GetPriceError::RescaleOverflow)?;
+        (
+            i128::from(price)
+                .checked_mul(factor)
+                .ok_or(GetPriceError::RescaleOverflow)?,
+            i128::from(conf)
+                .checked_mul(factor)
+                .ok_or(GetPriceError::RescaleOverflow)?,
+        )
+    } else {
+        match pow_10(-expo_diff) {
+            Some(divisor) => (i128::from(price) / divisor, i128::from(conf) / divisor),
+            None => (0, 0), // The divisor is larger than any i64 or u64
+        }
+    };
+
+    Ok((
+        i64::try_from(price).map_err(|_| GetPriceError::RescaleOverflow)?,
+        u64::try_from(conf).map_err(|_| GetPriceError::RescaleOverflow)?,
+    ))
+}
+
 #[cfg(test)]
 pub mod tests {
     use {
         crate::{
             error::GetPriceError,
             price_update::{
+                rescale,
                 Price,
+                PriceHistory,
                 PriceUpdateV2,
+                TimestampSource,
+                TwapUpdate,
                 VerificationLevel,
+                PRICE_HISTORY_LEN,
             },
         },
-        anchor_lang::Discriminator,
+        anchor_lang::{
+            AnchorSerialize,
+            Discriminator,
+        },
         pythnet_sdk::messages::PriceFeedMessage,
         solana_program::{
             borsh0_10,
@@ -238,6 +429,73 @@ pub mod tests {
             PriceUpdateV2::discriminator().len() + borsh0_10::get_packed_len::<PriceUpdateV2>()
                 == PriceUpdateV2::LEN
         );
+        assert!(
+            TwapUpdate::discriminator().len() + borsh0_10::get_packed_len::<TwapUpdate>()
+                == TwapUpdate::LEN
+        );
+        let full_price_history = PriceHistory {
+            write_authority: Pubkey::new_unique(),
+            feed_id:         [0; 32],
+            next_index:      0,
+            updates:         vec![price_message(0); PRICE_HISTORY_LEN],
+        };
+        assert_eq!(
+            PriceHistory::discriminator().len() + full_price_history.try_to_vec().unwrap().len(),
+            PriceHistory::LEN
+        );
+    }
+
+    fn price_message(publish_time: i64) -> PriceFeedMessage {
+        PriceFeedMessage {
+            feed_id: [0; 32],
+            price: publish_time,
+            conf: 1,
+            exponent: -8,
+            publish_time,
+            prev_publish_time: publish_time - 1,
+            ema_price: publish_time,
+            ema_conf: 1,
+        }
+    }
+
+    #[test]
+    fn price_history() {
+        let mut price_history = PriceHistory {
+            write_authority: Pubkey::new_unique(),
+            feed_id:         [0; 32],
+            next_index:      0,
+            updates:         vec![],
+        };
+        assert_eq!(price_history.latest(), None);
+        assert_eq!(price_history.get_updates(), vec![]);
+
+        assert!(price_history.push(price_message(1)));
+        assert!(price_history.push(price_message(2)));
+        // Older and duplicate updates are ignored
+        assert!(!price_history.push(price_message(2)));
+        assert!(!price_history.push(price_message(1)));
+        assert_eq!(price_history.latest(), Some(&price_message(2)));
+        assert_eq!(
+            price_history.get_updates(),
+            vec![price_message(1), price_message(2)]
+        );
+
+        // Fill the buffer and wrap around
+        for publish_time in 3..=(PRICE_HISTORY_LEN as i64 + 5) {
+            assert!(price_history.push(price_message(publish_time)));
+        }
+        assert_eq!(price_history.updates.len(), PRICE_HISTORY_LEN);
+        assert_eq!(price_history.next_index, 5);
+        assert_eq!(
+            price_history.latest(),
+            Some(&price_message(PRICE_HISTORY_LEN as i64 + 5))
+        );
+        assert_eq!(
+            price_history.get_updates(),
+            (6..=(PRICE_HISTORY_LEN as i64 + 5))
+                .map(price_message)
+                .collect::<Vec<_>>()
+        );
     }
 
     #[test]
@@ -463,4 +721,144 @@ pub mod tests {
             Err(GetPriceError::MismatchedFeedId)
         );
     }
+
+    #[test]
+    fn get_price_with_timestamp_source() {
+        let feed_id = [0; 32];
+        let mock_clock = Clock {
+            slot: 110,
+            unix_timestamp: 1000,
+            ..Default::default()
+        };
+
+        let price_update = PriceUpdateV2 {
+            write_authority:    Pubkey::new_unique(),
+            verification_level: VerificationLevel::Full,
+            price_message:      PriceFeedMessage {
+                feed_id,
+                ema_conf: 0,
+                ema_price: 0,
+                price: 1,
+                conf: 2,
+                exponent: 3,
+                prev_publish_time: 899,
+                publish_time: 900,
+            },
+            posted_slot:        100,
+        };
+
+        let expected_price = Price {
+            price:        1,
+            conf:         2,
+            exponent:     3,
+            publish_time: 900,
+        };
+
+        // The price was published 100 seconds ago but posted 10 slots ago
+        assert_eq!(
+            price_update.get_price_no_older_than_with_timestamp_source(
+                &mock_clock,
+                50,
+                &feed_id,
+                VerificationLevel::Full,
+                TimestampSource::Message
+            ),
+            Err(GetPriceError::PriceTooOld)
+        );
+        assert_eq!(
+            price_update.get_price_no_older_than_with_timestamp_source(
+                &mock_clock,
+                50,
+                &feed_id,
+                VerificationLevel::Full,
+                TimestampSource::OnChain
+            ),
+            Ok(expected_price)
+        );
+        assert_eq!(
+            price_update.get_price_no_older_than_with_timestamp_source(
+                &mock_clock,
+                5,
+                &feed_id,
+                VerificationLevel::Full,
+                TimestampSource::OnChain
+            ),
+            Err(GetPriceError::PriceTooOld)
+        );
+    }
+
+    #[test]
+    fn rescale_price() {
+        assert_eq!(rescale(12345, 67, -5, -5), Ok((12345, 67)));
+        assert_eq!(rescale(12345, 67, -5, -8), Ok((12345000, 67000)));
+        assert_eq!(rescale(12345, 67, -5, -3), Ok((123, 0)));
+        assert_eq!(rescale(-12345, 67, -5, -3), Ok((-123, 0)));
+        assert_eq!(rescale(12345, 67, 2, -2), Ok((123450000, 670000)));
+
+        // i64 and u64 boundaries
+        assert_eq!(
+            rescale(i64::MAX, u64::MAX, -8, -8),
+            Ok((i64::MAX, u64::MAX))
+        );
+        assert_eq!(rescale(i64::MIN, 0, -8, -8), Ok((i64::MIN, 0)));
+        assert_eq!(
+            rescale(i64::MAX, u64::MAX, -8, -9),
+            Err(GetPriceError::RescaleOverflow)
+        );
+        assert_eq!(
+            rescale(i64::MIN, 0, -8, -9),
+            Err(GetPriceError::RescaleOverflow)
+        );
+        assert_eq!(
+            rescale(0, u64::MAX, -8, -9),
+            Err(GetPriceError::RescaleOverflow)
+        );
+        assert_eq!(
+            rescale(i64::MAX, u64::MAX, -8, -7),
+            Ok((i64::MAX / 10, u64::MAX / 10))
+        );
+        assert_eq!(rescale(i64::MIN, 0, -8, -7), Ok((i64::MIN / 10, 0)));
+        assert_eq!(
+            rescale(i64::MAX / 10, 0, -8, -9),
+            Ok((i64::MAX / 10 * 10, 0))
+        );
+        assert_eq!(
+            rescale(i64::MAX / 10 + 1, 0, -8, -9),
+            Err(GetPriceError::RescaleOverflow)
+        );
+
+        // Large exponent differences
+        assert_eq!(rescale(i64::MAX, u64::MAX, -8, 11), Ok((0, 1)));
+        assert_eq!(rescale(i64::MIN, u64::MAX, i32::MIN, i32::MAX), Ok((0, 0)));
+        assert_eq!(rescale(1, 0, 0, -18), Ok((10i64.pow(18), 0)));
+        assert_eq!(rescale(1, 0, 0, -19), Err(GetPriceError::RescaleOverflow));
+        assert_eq!(
+            rescale(1, 1, i32::MAX, i32::MIN),
+            Err(GetPriceError::RescaleOverflow)
+        );
+
+        let price = Price {
+            price:        12345,
+            conf:         67,
+            exponent:     -5,
+            publish_time: 100,
+        };
+        assert_eq!(
+            price.scale_to_exponent(-8),
+            Ok(Price {
+                price:        12345000,
+                conf:         67000,
+                exponent:     -8,
+                publish_time: 100,
+            })
+        );
+        assert_eq!(
+            Price {
+                price: i64::MAX,
+                ..price
+            }
+            .scale_to_exponent(-6),
+            Err(GetPriceError::RescaleOverflow)
+        );
+    }
 }
@@ -1,5 +1,6 @@
 import { AnchorProvider, IdlAccounts, Program } from "@coral-xyz/anchor";
 import {
+  AccountMeta,
   AddressLookupTableAccount,
   Connection,
   Signer,
@@ -20,6 +21,7 @@ import {
   DEFAULT_WORMHOLE_PROGRAM_ID,
   getConfigPda,
   getGuardianSetPda,
+  getPriceChangeBaselinePda,
   getRandomTreasuryId,
   getTreasuryPda,
 } from "./address";
@@ -404,6 +406,7 @@ export class PythSolanaReceiver {
     const closeInstructions: InstructionWithEphemeralSigners[] = [];
 
     const treasuryId = getRandomTreasuryId();
+    const getRemainingAccounts = await this.fetchPostUpdateRemainingAccounts();
 
     for (const priceUpdateData of priceUpdateDataArray) {
       const accumulatorUpdateData = parseAccumulatorUpdateData(
@@ -430,6 +433,11 @@ export class PythSolanaReceiver {
                 this.wormhole.programId
               ),
             })
+            .remainingAccounts(
+              getRemainingAccounts(
+                parsePriceFeedMessage(update.message).feedId
+              )
+            )
             .instruction(),
           signers: [priceUpdateKeypair],
           computeUnits: POST_UPDATE_ATOMIC_COMPUTE_BUDGET,
@@ -542,6 +550,7 @@ export class PythSolanaReceiver {
     const closeInstructions: InstructionWithEphemeralSigners[] = [];
 
     const treasuryId = getRandomTreasuryId();
+    const getRemainingAccounts = await this.fetchPostUpdateRemainingAccounts();
 
     for (const priceUpdateData of priceUpdateDataArray) {
       const accumulatorUpdateData = parseAccumulatorUpdateData(
@@ -570,6 +579,11 @@ export class PythSolanaReceiver {
               treasury: getTreasuryPda(treasuryId, this.receiver.programId),
               config: getConfigPda(this.receiver.programId),
             })
+            .remainingAccounts(
+              getRemainingAccounts(
+                parsePriceFeedMessage(update.message).feedId
+              )
+            )
             .instruction(),
           signers: [priceUpdateKeypair],
           computeUnits: POST_UPDATE_COMPUTE_BUDGET,
@@ -615,6 +629,7 @@ export class PythSolanaReceiver {
     const closeInstructions: InstructionWithEphemeralSigners[] = [];
 
     const treasuryId = getRandomTreasuryId();
+    const getRemainingAccounts = await this.fetchPostUpdateRemainingAccounts();
 
     for (const priceUpdateData of priceUpdateDataArray) {
       const accumulatorUpdateData = parseAccumulatorUpdateData(
@@ -651,6 +666,7 @@ export class PythSolanaReceiver {
               treasury: getTreasuryPda(treasuryId, this.receiver.programId),
               config: getConfigPda(this.receiver.programId),
             })
+            .remainingAccounts(getRemainingAccounts(feedId))
             .instruction(),
           signers: [],
           computeUnits: UPDATE_PRICE_FEED_COMPUTE_BUDGET,
@@ -706,8 +722,12 @@ export class PythSolanaReceiver {
     priceUpdateAccount: PublicKey
   ): Promise<InstructionWithEphemeralSigners> {
     const instruction = await this.receiver.methods
-      .reclaimRent()
-      .accounts({ priceUpdateAccount })
+      .closePriceUpdate()
+      .accounts({
+        priceUpdateAccount,
+        recipient: this.wallet.publicKey,
+        config: getConfigPda(this.receiver.programId),
+      })
       .instruction();
     return { instruction, signers: [] };
   }
@@ -729,6 +749,43 @@ export class PythSolanaReceiver {
     );
   }
 
+  /**
+   * Fetch the Pyth Solana Receiver config to build the remaining accounts of the post instructions: the fee recipients of the config,
+   * followed by the price change baseline account of the feed if the config has a price change limit for it.
+   * @returns A function that returns the remaining accounts of an instruction posting an update of `feedId`, in the order the program expects them
+   */
+  async fetchPostUpdateRemainingAccounts(): Promise<
+    (feedId: Buffer) => AccountMeta[]
+  > {
+    const config = await this.receiver.account.config.fetch(
+      getConfigPda(this.receiver.programId)
+    );
+    const feeRecipientAccounts = config.feeRecipients.map(({ recipient }) => ({
+      pubkey: recipient,
+      isSigner: false,
+      isWritable: true,
+    }));
+    const limitedFeedIds = new Set(
+      config.priceChangeLimits.map(({ feedId }) =>
+        Buffer.from(feedId).toString("hex")
+      )
+    );
+    return (feedId: Buffer) =>
+      limitedFeedIds.has(feedId.toString("hex"))
+        ? [
+            ...feeRecipientAccounts,
+            {
+              pubkey: getPriceChangeBaselinePda(
+                feedId,
+                this.receiver.programId
+              ),
+              isSigner: false,
+              isWritable: true,
+            },
+          ]
+        : feeRecipientAccounts;
+  }
+
   /**
    * Fetch the contents of a price update account
    * @param priceUpdateAccount The address of the price update account
@@ -55,6 +55,20 @@ export const getTreasuryPda = (
   )[0];
 };
 
+/**
+ * Returns the address of the price change baseline account of a feed from the Pyth Solana Receiver program.
+ * Post instructions need this account for the updates of the feeds with a price change limit in the config.
+ */
+export const getPriceChangeBaselinePda = (
+  feedId: Buffer,
+  receiverProgramId: PublicKey
+) => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("price_change_baseline"), feedId],
+    receiverProgramId
+  )[0];
+};
+
 /**
  * Returns the address of the config account from the Pyth Solana Receiver program.
  */
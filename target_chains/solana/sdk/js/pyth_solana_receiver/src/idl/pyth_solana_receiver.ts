@@ -51,6 +51,22 @@ export type PythSolanaReceiver = {
         }
       ];
     },
+    {
+      name: "cancelGovernanceAuthorityTransfer";
+      accounts: [
+        {
+          name: "payer";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "config";
+          isMut: true;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
     {
       name: "acceptGovernanceAuthorityTransfer";
       accounts: [
@@ -156,72 +172,210 @@ export type PythSolanaReceiver = {
       ];
     },
     {
-      name: "postUpdateAtomic";
+      name: "setPinnedGuardianSetHash";
+      accounts: [
+        {
+          name: "payer";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "config";
+          isMut: true;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "pinnedGuardianSetHash";
+          type: {
+            option: {
+              array: ["u8", 32];
+            };
+          };
+        }
+      ];
+    },
+    {
+      name: "setGuardianSetExpirationSkew";
       docs: [
-        "Post a price update using a VAA and a MerklePriceUpdate.",
-        "This function allows you to post a price update in a single transaction.",
-        "Compared to post_update, it is less secure since you won't be able to verify all guardian signatures if you use this function because of transaction size limitations.",
-        "Typically, you can fit 5 guardian signatures in a transaction that uses this."
+        "Consider guardian sets expired `guardian_set_expiration_skew_seconds` before their expiration time when checking them in `post_update_atomic`.",
+        "This tolerates small differences between the Solana clock and the clock of the chain that set the expiration time.",
+        "The tolerance is only applied in the safe direction: near the boundary, a guardian set that is still valid may be rejected but an expired one is never accepted.",
+        "VAAs posted through the Wormhole receiver (used by `post_update`) are not affected, their guardian set is checked by the Wormhole receiver."
       ];
       accounts: [
         {
           name: "payer";
-          isMut: true;
+          isMut: false;
           isSigner: true;
         },
         {
-          name: "guardianSet";
-          isMut: false;
+          name: "config";
+          isMut: true;
           isSigner: false;
-          docs: [
-            "Instead we do the same steps in deserialize_guardian_set_checked."
-          ];
+        }
+      ];
+      args: [
+        {
+          name: "guardianSetExpirationSkewSeconds";
+          type: "u32";
+        }
+      ];
+    },
+    {
+      name: "setPostVaaPrograms";
+      docs: [
+        "Accept encoded VAA accounts owned by any of `post_vaa_programs` in addition to the `wormhole` program.",
+        "Every program in this list must only mark VAAs as verified after checking their guardian signatures, since `VaaAccount::load` trusts the `ProcessingStatus` written by the owner.",
+        "Each program takes 32 bytes of the config account."
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: false;
+          isSigner: true;
         },
         {
           name: "config";
-          isMut: false;
+          isMut: true;
           isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "postVaaPrograms";
+          type: {
+            vec: "publicKey";
+          };
+        }
+      ];
+    },
+    {
+      name: "setPriceChangeLimits";
+      docs: [
+        "Reject the updates of each feed of `price_change_limits` whose price differs from the last price of the feed by more than the limit of the feed, in basis points of the last price.",
+        "This is a circuit breaker against anomalous jumps in a single update, and feeds without a limit aren't checked.",
+        "The last price of a feed is stored in its `PriceChangeBaseline` account, a PDA of the feed that is updated by every posted update of the feed, whatever the price update account it's posted to. Posting instructions need this account for the updates of the limited feeds, after the fee recipient accounts. The first update of a feed only creates the baseline.",
+        "The limit of a feed only applies to updates published less than its `window_seconds` after the last price, so that a price that really moved is accepted once the window has passed instead of blocking the feed forever. The window must be at least 1 second.",
+        "Each limit takes 38 bytes of the config account."
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: false;
+          isSigner: true;
         },
         {
-          name: "treasury";
+          name: "config";
           isMut: true;
           isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "priceChangeLimits";
+          type: {
+            vec: {
+              defined: "PriceChangeLimit";
+            };
+          };
+        }
+      ];
+    },
+    {
+      name: "setMinPriceUpdateLifetime";
+      docs: [
+        "Only let write authorities close their price update accounts with `reclaim_rent` or `close_price_update` `min_price_update_lifetime_slots` slots after the last update of the account.",
+        "This prevents closing an account in the middle of a transaction of a consumer that reads it."
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: false;
+          isSigner: true;
         },
         {
-          name: "priceUpdateAccount";
+          name: "config";
           isMut: true;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "minPriceUpdateLifetimeSlots";
+          type: "u64";
+        }
+      ];
+    },
+    {
+      name: "setPublishTimeBounds";
+      docs: [
+        "Reject price updates whose `publish_time` is more than `max_staleness_seconds` before, or more than `max_future_publish_time_seconds` after, the current time of the cluster.",
+        "Either bound is disabled when it's 0. The future bound protects against updates signed with a skewed clock, since a publish time in the future would look fresh to consumers for longer than it should."
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: false;
           isSigner: true;
-          docs: [
-            "The contraint is such that either the price_update_account is uninitialized or the payer is the write_authority.",
-            "Pubkey::default() is the SystemProgram on Solana and it can't sign so it's impossible that price_update_account.write_authority == Pubkey::default() once the account is initialized"
-          ];
         },
         {
-          name: "systemProgram";
-          isMut: false;
+          name: "config";
+          isMut: true;
           isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "maxStalenessSeconds";
+          type: "u32";
         },
         {
-          name: "writeAuthority";
+          name: "maxFuturePublishTimeSeconds";
+          type: "u32";
+        }
+      ];
+    },
+    {
+      name: "setPriceSanityBounds";
+      docs: [
+        "Reject price updates whose exponent is outside `exponent_range`, or whose confidence interval is more than `max_conf_bps` basis points of the price.",
+        "`None` and 0 disable the corresponding check, which is also the behavior of config accounts created before these fields existed."
+      ];
+      accounts: [
+        {
+          name: "payer";
           isMut: false;
           isSigner: true;
+        },
+        {
+          name: "config";
+          isMut: true;
+          isSigner: false;
         }
       ];
       args: [
         {
-          name: "params";
+          name: "exponentRange";
           type: {
-            defined: "PostUpdateAtomicParams";
+            option: {
+              defined: "ExponentRange";
+            };
           };
+        },
+        {
+          name: "maxConfBps";
+          type: "u32";
         }
       ];
     },
     {
-      name: "postUpdate";
+      name: "resizeConfig";
       docs: [
-        "Post a price update using an encoded_vaa account and a MerklePriceUpdate calldata.",
-        "This should be called after the client has already verified the Vaa via the Wormhole contract.",
-        "Check out target_chains/solana/cli/src/main.rs for an example of how to do this."
+        "Resize the config account to `len` bytes, the governance authority pays the rent of the extra space.",
+        "Config accounts created by earlier versions of the program are 370 bytes, which isn't enough for the lists of the config to grow: `set_post_vaa_programs`, `set_price_change_limits`, `set_allowed_feed_ids` and `set_fee_recipients` fail with `ReceiverError::ConfigAccountTooSmall` if the new list doesn't fit in the account.",
+        "A single call can grow the account by at most 10 KiB, and the account can't be shrunk below the size of the config it stores."
       ];
       accounts: [
         {
@@ -230,51 +384,147 @@ export type PythSolanaReceiver = {
           isSigner: true;
         },
         {
-          name: "encodedVaa";
-          isMut: false;
+          name: "config";
+          isMut: true;
           isSigner: false;
         },
         {
-          name: "config";
+          name: "systemProgram";
           isMut: false;
           isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "len";
+          type: "u32";
+        }
+      ];
+    },
+    {
+      name: "migrateConfig";
+      docs: [
+        "Rewrite a config account created by the first version of the program in the current layout.",
+        "That version didn't zero the end of the account when the config shrank, so the bytes after its fields can hold stale data that doesn't deserialize as the fields added since then, and every instruction that loads the config fails.",
+        "This only reads the fields of `LegacyConfig`, sets the other fields to their defaults and zeroes the rest of the account. It must be called once after upgrading the program and before setting any of the new fields, since it resets them."
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: false;
+          isSigner: true;
         },
         {
-          name: "treasury";
+          name: "config";
           isMut: true;
           isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "setAllowedFeedIds";
+      docs: [
+        "Only post the updates of the feeds of `allowed_feed_ids` in `post_updates_from_account_data`, `post_updates_with_tag` and `post_updates_from_vaas`. The updates of other feeds are skipped without failing the instruction, and their price update accounts are left untouched.",
+        "This keeps a deployment that only cares about a few feeds from paying for the accounts of irrelevant feeds. An empty list allows every feed.",
+        "Each feed id takes 32 bytes of the config account."
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: false;
+          isSigner: true;
         },
         {
-          name: "priceUpdateAccount";
+          name: "config";
           isMut: true;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "allowedFeedIds";
+          type: {
+            vec: {
+              array: ["u8", 32];
+            };
+          };
+        }
+      ];
+    },
+    {
+      name: "setMinConsistencyLevel";
+      docs: [
+        "Reject price updates from VAAs whose consistency level is lower than `min_consistency_level`, for consumers that only want updates from VAAs emitted at a given finality.",
+        "The check applies to every instruction that verifies updates. 0 accepts every VAA, which is also the behavior of config accounts created before this field existed.",
+        "Callers can require a higher consistency level for their own updates (e.g. with `post_update_with_min_consistency`), but can't go below this one."
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: false;
           isSigner: true;
-          docs: [
-            "The contraint is such that either the price_update_account is uninitialized or the payer is the write_authority.",
-            "Pubkey::default() is the SystemProgram on Solana and it can't sign so it's impossible that price_update_account.write_authority == Pubkey::default() once the account is initialized"
-          ];
         },
         {
-          name: "systemProgram";
-          isMut: false;
+          name: "config";
+          isMut: true;
           isSigner: false;
-        },
+        }
+      ];
+      args: [
         {
-          name: "writeAuthority";
+          name: "minConsistencyLevel";
+          type: "u8";
+        }
+      ];
+    },
+    {
+      name: "setFeeRecipients";
+      docs: [
+        "Split the fee of every update between `fee_recipients` according to their weights (in basis points), instead of sending it to the treasury.",
+        "The weights must sum to `TOTAL_FEE_WEIGHT_BPS`. An empty list sends the whole fee to the treasury again.",
+        "Posting instructions then need the fee recipient accounts as remaining accounts, in the same order, and each recipient must be able to receive its share (e.g. a rent-exempt system account).",
+        "Each recipient takes 34 bytes of the config account."
+      ];
+      accounts: [
+        {
+          name: "payer";
           isMut: false;
           isSigner: true;
+        },
+        {
+          name: "config";
+          isMut: true;
+          isSigner: false;
         }
       ];
       args: [
         {
-          name: "params";
+          name: "feeRecipients";
           type: {
-            defined: "PostUpdateParams";
+            vec: {
+              defined: "FeeRecipient";
+            };
           };
         }
       ];
     },
     {
-      name: "reclaimRent";
+      name: "postUpdateAtomic";
+      docs: [
+        "Post a price update using a VAA and a MerklePriceUpdate.",
+        "This function allows you to post a price update in a single transaction.",
+        "Compared to `post_update`, it only checks whatever signatures are present in the provided VAA and doesn't fail if the number of signatures is lower than the Wormhole quorum of two thirds of the guardians.",
+        "The number of signatures that were in the VAA is stored in the `VerificationLevel` of the `PriceUpdateV2` account.",
+        "",
+        "We recommend using `post_update_atomic` with 5 signatures. This is close to the maximum signatures you can verify in one transaction without exceeding the transaction size limit.",
+        "",
+        "# Warning",
+        "",
+        "Using partially verified price updates is dangerous, as it lowers the threshold of guardians that need to collude to produce a malicious price update.",
+        "",
+        "If the config has fee recipients, their accounts must be passed as remaining accounts (see `set_fee_recipients`), followed by the `PriceChangeBaseline` account of the feed if it has a price change limit (see `set_price_change_limits`)."
+      ];
       accounts: [
         {
           name: "payer";
@@ -282,353 +532,2530 @@ export type PythSolanaReceiver = {
           isSigner: true;
         },
         {
-          name: "priceUpdateAccount";
-          isMut: true;
+          name: "guardianSet";
+          isMut: false;
           isSigner: false;
-        }
-      ];
+          docs: [
+            "Instead we do the same steps in deserialize_guardian_set_checked."
+          ];
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "priceUpdateAccount";
+          isMut: true;
+          isSigner: true;
+          docs: [
+            "The constraint is such that either the price_update_account is uninitialized or the write_authority is the write_authority.",
+            "Pubkey::default() is the SystemProgram on Solana and it can't sign so it's impossible that price_update_account.write_authority == Pubkey::default() once the account is initialized"
+          ];
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "writeAuthority";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: "PostUpdateAtomicParams";
+          };
+        }
+      ];
+    },
+    {
+      name: "postUpdateAtomicWithMinConsistency";
+      docs: [
+        "Same as `post_update_atomic`, but the VAA must also have at least `min_consistency_level` as its consistency level.",
+        "This lets a caller require a higher consistency level than `Config::min_consistency_level` for its own updates, a lower value has no effect (see `set_min_consistency_level`)."
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "guardianSet";
+          isMut: false;
+          isSigner: false;
+          docs: [
+            "Instead we do the same steps in deserialize_guardian_set_checked."
+          ];
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "priceUpdateAccount";
+          isMut: true;
+          isSigner: true;
+          docs: [
+            "The constraint is such that either the price_update_account is uninitialized or the write_authority is the write_authority.",
+            "Pubkey::default() is the SystemProgram on Solana and it can't sign so it's impossible that price_update_account.write_authority == Pubkey::default() once the account is initialized"
+          ];
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "writeAuthority";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: "PostUpdateAtomicParams";
+          };
+        },
+        {
+          name: "minConsistencyLevel";
+          type: "u8";
+        }
+      ];
+    },
+    {
+      name: "postUpdate";
+      docs: [
+        "Post a price update using an encoded_vaa account and a MerklePriceUpdate calldata.",
+        "This should be called after the client has already verified the Vaa via the Wormhole contract.",
+        "Check out target_chains/solana/cli/src/main.rs for an example of how to do this.",
+        "If the config has fee recipients, their accounts must be passed as remaining accounts (see `set_fee_recipients`), followed by the `PriceChangeBaseline` account of the feed if it has a price change limit (see `set_price_change_limits`)."
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "encodedVaa";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "priceUpdateAccount";
+          isMut: true;
+          isSigner: true;
+          docs: [
+            "The constraint is such that either the price_update_account is uninitialized or the write_authority is the write_authority.",
+            "Pubkey::default() is the SystemProgram on Solana and it can't sign so it's impossible that price_update_account.write_authority == Pubkey::default() once the account is initialized"
+          ];
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "writeAuthority";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: "PostUpdateParams";
+          };
+        }
+      ];
+    },
+    {
+      name: "postUpdateWithMinConsistency";
+      docs: [
+        "Same as `post_update`, but the VAA must also have at least `min_consistency_level` as its consistency level.",
+        "This lets a caller require a higher consistency level than `Config::min_consistency_level` for its own updates, a lower value has no effect (see `set_min_consistency_level`)."
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "encodedVaa";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "priceUpdateAccount";
+          isMut: true;
+          isSigner: true;
+          docs: [
+            "The constraint is such that either the price_update_account is uninitialized or the write_authority is the write_authority.",
+            "Pubkey::default() is the SystemProgram on Solana and it can't sign so it's impossible that price_update_account.write_authority == Pubkey::default() once the account is initialized"
+          ];
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "writeAuthority";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: "PostUpdateParams";
+          };
+        },
+        {
+          name: "minConsistencyLevel";
+          type: "u8";
+        }
+      ];
+    },
+    {
+      name: "postUpdatesFromAccountData";
+      docs: [
+        "Post all the price updates of an `AccumulatorUpdateData` that has been written to an account.",
+        "This is useful for batches of updates that are too large to fit in instruction data.",
+        "Like `post_update`, this should be called after the client has already verified the Vaa via the Wormhole contract, and the VAA embedded in the accumulator update data must match the `encoded_vaa` account.",
+        "The price update accounts are passed as remaining accounts, one for each update and in the same order as the updates, followed by the fee recipient accounts if the config has any, followed by the `PriceChangeBaseline` accounts of the feeds with a price change limit (see `set_price_change_limits`).",
+        "If `params.write_price_history` is true, every update is also added to a `PriceHistory` account, and these accounts are passed right after the price update accounts, one for each update and in the same order.",
+        "`params.event_mode` controls whether one event is emitted per update or a single event for the whole batch.",
+        "`params.expected_updates` and `params.require_exact_count` bound (or fix) the number of updates of the batch, so that the compute and the fee of the instruction are predictable.",
+        "The updates of feeds that aren't in `allowed_feed_ids` are skipped (see `set_allowed_feed_ids`).",
+        "The return data of the instruction is a `PostedUpdatesSummary` of the posted updates."
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "encodedVaa";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "accumulatorUpdateData";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "writeAuthority";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: "PostUpdatesFromAccountDataParams";
+          };
+        }
+      ];
+      returns: {
+        defined: "PostedUpdatesSummary";
+      };
+    },
+    {
+      name: "postUpdatesWithTag";
+      docs: [
+        "Same as `post_updates_from_account_data`, with a correlation tag chosen by the caller.",
+        "The tag is emitted in a `TaggedPriceUpdatesPosted` event along with the feed ids of the updates and the number of skipped updates.",
+        "The return data of the instruction is a `TaggedPostedUpdatesSummary` with the tag and a summary of the posted updates.",
+        "This lets a consumer program that triggered the verification (e.g. in response to one of its own requests) match the verified prices to that request."
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "encodedVaa";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "accumulatorUpdateData";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "writeAuthority";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: "PostUpdatesFromAccountDataParams";
+          };
+        },
+        {
+          name: "tag";
+          type: {
+            array: ["u8", 32];
+          };
+        }
+      ];
+      returns: {
+        defined: "TaggedPostedUpdatesSummary";
+      };
+    },
+    {
+      name: "postUpdatesFromVaas";
+      docs: [
+        "Post price updates whose Merkle roots are spread over several VAAs, e.g. a set of feeds that spans an accumulator boundary.",
+        "Like `post_update`, this should be called after the client has already verified the VAAs via the Wormhole contract.",
+        "The remaining accounts are the `params.num_vaas` encoded VAA accounts, followed by one price update account per update in the same order as the updates, followed by the fee recipient accounts if the config has any, followed by the `PriceChangeBaseline` accounts of the feeds with a price change limit (see `set_price_change_limits`).",
+        "Every update is checked against the VAA at its index in `params.vaa_indices`, or if `params.vaa_indices` is empty against the VAAs in order until one of them includes it. An update that isn't included in any of the VAAs is rejected.",
+        "Finding the VAA of an update verifies it once more, so passing `params.vaa_indices` uses less compute for large batches.",
+        "The updates of feeds that aren't in `allowed_feed_ids` are skipped (see `set_allowed_feed_ids`).",
+        "The return data of the instruction is a `PostedUpdatesSummary` of the posted updates."
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "writeAuthority";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: "PostUpdatesFromVaasParams";
+          };
+        }
+      ];
+      returns: {
+        defined: "PostedUpdatesSummary";
+      };
+    },
+    {
+      name: "postTwapUpdate";
+      docs: [
+        "Post a TWAP update using an encoded_vaa account and a MerklePriceUpdate calldata containing a TWAP message.",
+        "The fields of the TWAP message are stored in a `TwapUpdate` account, and the fee is the same as for a price update.",
+        "Like `post_update`, this should be called after the client has already verified the Vaa via the Wormhole contract.",
+        "If the config has fee recipients, their accounts must be passed as remaining accounts (see `set_fee_recipients`)."
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "encodedVaa";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "twapUpdateAccount";
+          isMut: true;
+          isSigner: true;
+          docs: [
+            "The constraint is such that either the twap_update_account is uninitialized or the write_authority is the write_authority.",
+            "Pubkey::default() is the SystemProgram on Solana and it can't sign so it's impossible that twap_update_account.write_authority == Pubkey::default() once the account is initialized"
+          ];
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "writeAuthority";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: "PostUpdateParams";
+          };
+        }
+      ];
+    },
+    {
+      name: "postTwapUpdateWithMinConsistency";
+      docs: [
+        "Same as `post_twap_update`, but the VAA must also have at least `min_consistency_level` as its consistency level.",
+        "This lets a caller require a higher consistency level than `Config::min_consistency_level` for its own updates, a lower value has no effect (see `set_min_consistency_level`)."
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "encodedVaa";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "treasury";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "twapUpdateAccount";
+          isMut: true;
+          isSigner: true;
+          docs: [
+            "The constraint is such that either the twap_update_account is uninitialized or the write_authority is the write_authority.",
+            "Pubkey::default() is the SystemProgram on Solana and it can't sign so it's impossible that twap_update_account.write_authority == Pubkey::default() once the account is initialized"
+          ];
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "writeAuthority";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: "PostUpdateParams";
+          };
+        },
+        {
+          name: "minConsistencyLevel";
+          type: "u8";
+        }
+      ];
+    },
+    {
+      name: "verifyUpdate";
+      docs: [
+        "Verify a price update against an encoded_vaa account without posting it.",
+        "This instruction doesn't write any account and doesn't charge any fee, so it doesn't take a payer and can be simulated by any account (e.g. by monitoring tools).",
+        "Like `post_update`, this should be called after the client has already verified the Vaa via the Wormhole contract.",
+        "If `min_consistency_level` is set, the VAA must also have at least this consistency level (see `set_min_consistency_level`)."
+      ];
+      accounts: [
+        {
+          name: "encodedVaa";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "merklePriceUpdate";
+          type: {
+            defined: "MerklePriceUpdate";
+          };
+        },
+        {
+          name: "minConsistencyLevel";
+          type: {
+            option: "u8";
+          };
+        }
+      ];
+    },
+    {
+      name: "verifyUpdatesView";
+      docs: [
+        "Verify several price updates against an encoded_vaa account without posting them, and return their decoded messages in the same order as the updates.",
+        "Like `verify_update`, this instruction doesn't write any account and doesn't charge any fee. It's meant to be simulated (e.g. with `simulateTransaction`) to preview the content of a set of updates before posting them.",
+        "At most `MAX_VIEW_UPDATES` updates can be verified at once, so that their messages fit in the return data.",
+        "If `min_consistency_level` is set, the VAA must also have at least this consistency level (see `set_min_consistency_level`)."
+      ];
+      accounts: [
+        {
+          name: "encodedVaa";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "merklePriceUpdates";
+          type: {
+            vec: {
+              defined: "MerklePriceUpdate";
+            };
+          };
+        },
+        {
+          name: "minConsistencyLevel";
+          type: {
+            option: "u8";
+          };
+        }
+      ];
+      returns: {
+        vec: {
+          defined: "PriceFeedMessage";
+        };
+      };
+    },
+    {
+      name: "reclaimRent";
+      docs: [
+        "Close a price update account and send its lamports to its write authority.",
+        "The account can only be closed `min_price_update_lifetime_slots` slots after its last update (see `set_min_price_update_lifetime`)."
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "priceUpdateAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "closePriceUpdate";
+      docs: [
+        "Close a price update account and send its lamports to `recipient`. Only the write authority of the account can close it.",
+        "The account can only be closed `min_price_update_lifetime_slots` slots after its last update (see `set_min_price_update_lifetime`), otherwise the instruction fails with `PriceUpdateTooRecent`."
+      ];
+      accounts: [
+        {
+          name: "writeAuthority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "priceUpdateAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "recipient";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
       args: [];
     }
   ];
   accounts: [
     {
-      name: "Config";
+      name: "Config";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "governanceAuthority";
+            type: "publicKey";
+          },
+          {
+            name: "targetGovernanceAuthority";
+            type: {
+              option: "publicKey";
+            };
+          },
+          {
+            name: "wormhole";
+            type: "publicKey";
+          },
+          {
+            name: "validDataSources";
+            type: {
+              vec: {
+                defined: "DataSource";
+              };
+            };
+          },
+          {
+            name: "singleUpdateFeeInLamports";
+            type: "u64";
+          },
+          {
+            name: "minimumSignatures";
+            type: "u8";
+          },
+          {
+            name: "pinnedGuardianSetHash";
+            type: {
+              option: {
+                array: ["u8", 32];
+              };
+            };
+          },
+          {
+            name: "feeRecipients";
+            type: {
+              vec: {
+                defined: "FeeRecipient";
+              };
+            };
+          },
+          {
+            name: "postVaaPrograms";
+            type: {
+              vec: "publicKey";
+            };
+          },
+          {
+            name: "guardianSetExpirationSkewSeconds";
+            type: "u32";
+          },
+          {
+            name: "priceChangeLimits";
+            type: {
+              vec: {
+                defined: "PriceChangeLimit";
+              };
+            };
+          },
+          {
+            name: "minPriceUpdateLifetimeSlots";
+            type: "u64";
+          },
+          {
+            name: "maxStalenessSeconds";
+            type: "u32";
+          },
+          {
+            name: "maxFuturePublishTimeSeconds";
+            type: "u32";
+          },
+          {
+            name: "exponentRange";
+            type: {
+              option: {
+                defined: "ExponentRange";
+              };
+            };
+          },
+          {
+            name: "maxConfBps";
+            type: "u32";
+          },
+          {
+            name: "allowedFeedIds";
+            type: {
+              vec: {
+                array: ["u8", 32];
+              };
+            };
+          },
+          {
+            name: "minConsistencyLevel";
+            type: "u8";
+          }
+        ];
+      };
+    },
+    {
+      name: "priceUpdateV2";
+      docs: [
+        "A price update account. This account is used by the Pyth Receiver program to store a verified price update from a Pyth price feed.",
+        "It contains:",
+        "- `write_authority`: The write authority for this account. This authority can close this account to reclaim rent or update the account to contain a different price update.",
+        "- `verification_level`: The [`VerificationLevel`] of this price update. This represents how many Wormhole guardian signatures have been verified for this price update.",
+        "- `price_message`: The actual price update.",
+        "- `posted_slot`: The slot at which this price update was posted."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "writeAuthority";
+            type: "publicKey";
+          },
+          {
+            name: "verificationLevel";
+            type: {
+              defined: "VerificationLevel";
+            };
+          },
+          {
+            name: "priceMessage";
+            type: {
+              defined: "PriceFeedMessage";
+            };
+          },
+          {
+            name: "postedSlot";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "twapUpdate";
+      docs: [
+        "A TWAP update account. This account is used by the Pyth Receiver program to store a verified TWAP message from a Pyth price feed.",
+        "It contains the same `write_authority`, `verification_level` and `posted_slot` as [`PriceUpdateV2`], and each field of the TWAP message:",
+        "- `feed_id`: The feed id of the price feed.",
+        "- `cumulative_price` and `cumulative_conf`: The sums of the price and the confidence interval of the feed at every slot since the feed started.",
+        "- `num_down_slots`: The number of slots during which the feed didn't have a valid price since the feed started.",
+        "- `exponent`: The exponent of the cumulative price and confidence interval.",
+        "- `publish_time`, `prev_publish_time`: The timestamps of this update and of the previous update.",
+        "- `publish_slot`: The Pythnet slot of this update.",
+        "",
+        "The TWAP between two TWAP updates is `(cumulative_price_2 - cumulative_price_1) / (publish_slot_2 - publish_slot_1)`."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "writeAuthority";
+            type: "publicKey";
+          },
+          {
+            name: "verificationLevel";
+            type: {
+              defined: "VerificationLevel";
+            };
+          },
+          {
+            name: "feedId";
+            type: {
+              array: ["u8", 32];
+            };
+          },
+          {
+            name: "cumulativePrice";
+            type: "i128";
+          },
+          {
+            name: "cumulativeConf";
+            type: "u128";
+          },
+          {
+            name: "numDownSlots";
+            type: "u64";
+          },
+          {
+            name: "exponent";
+            type: "i32";
+          },
+          {
+            name: "publishTime";
+            type: "i64";
+          },
+          {
+            name: "prevPublishTime";
+            type: "i64";
+          },
+          {
+            name: "publishSlot";
+            type: "u64";
+          },
+          {
+            name: "postedSlot";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "priceHistory";
+      docs: [
+        "A price history account. This account is used by the Pyth Receiver program to store the most recent price updates of a single feed, for consumers that need more than the latest price (e.g. to compute a TWAP or the volatility).",
+        "It contains:",
+        "- `write_authority`: The write authority for this account, like for [`PriceUpdateV2`].",
+        "- `feed_id`: The feed of the price updates. It is set by the first update.",
+        "- `next_index`: The index of `updates` that the next price update will overwrite once the buffer is full.",
+        "- `updates`: A ring buffer of the last `PRICE_HISTORY_LEN` price updates, use [`PriceHistory::get_updates`] to read them in chronological order."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "writeAuthority";
+            type: "publicKey";
+          },
+          {
+            name: "feedId";
+            type: {
+              array: ["u8", 32];
+            };
+          },
+          {
+            name: "nextIndex";
+            type: "u8";
+          },
+          {
+            name: "updates";
+            type: {
+              vec: {
+                defined: "PriceFeedMessage";
+              };
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "priceChangeBaseline";
+      docs: [
+        "The last price of a feed with a `PriceChangeLimit`, whatever the price update account it was posted to.",
+        "There is one such account per feed, at the PDA of `get_price_change_baseline_address`. It's created by the first update of the feed after the limit is set, and every later update of the feed is compared with it."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "priceMessage";
+            type: {
+              defined: "PriceFeedMessage";
+            };
+          }
+        ];
+      };
+    }
+  ];
+  types: [
+    {
+      name: "MerklePriceUpdate";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "message";
+            type: "bytes";
+          },
+          {
+            name: "proof";
+            type: {
+              vec: {
+                array: ["u8", 20];
+              };
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "PriceFeedMessage";
       type: {
         kind: "struct";
         fields: [
           {
-            name: "governanceAuthority";
-            type: "publicKey";
+            name: "feedId";
+            type: {
+              array: ["u8", 32];
+            };
           },
           {
-            name: "targetGovernanceAuthority";
+            name: "price";
+            type: "i64";
+          },
+          {
+            name: "conf";
+            type: "u64";
+          },
+          {
+            name: "exponent";
+            type: "i32";
+          },
+          {
+            name: "publishTime";
+            type: "i64";
+          },
+          {
+            name: "prevPublishTime";
+            type: "i64";
+          },
+          {
+            name: "emaPrice";
+            type: "i64";
+          },
+          {
+            name: "emaConf";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "DataSource";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "chain";
+            type: "u16";
+          },
+          {
+            name: "emitter";
+            type: "publicKey";
+          }
+        ];
+      };
+    },
+    {
+      name: "PriceChangeLimit";
+      docs: [
+        "The maximum change of the price of `feed_id` in a single update, in basis points of the last price of the feed (see `PriceChangeBaseline`).",
+        "The limit only applies to updates published less than `window_seconds` after the last price, so that a lasting move of the price can't leave the feed stuck on the last price: the first update published after the window is accepted whatever its price. `window_seconds` can't be 0."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "feedId";
             type: {
-              option: "publicKey";
+              array: ["u8", 32];
             };
           },
           {
-            name: "wormhole";
+            name: "maxChangeBps";
+            type: "u16";
+          },
+          {
+            name: "windowSeconds";
+            type: "u32";
+          }
+        ];
+      };
+    },
+    {
+      name: "ExponentRange";
+      docs: [
+        "The range of exponents accepted for price updates, inclusive."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "min";
+            type: "i32";
+          },
+          {
+            name: "max";
+            type: "i32";
+          }
+        ];
+      };
+    },
+    {
+      name: "FeeRecipient";
+      docs: [
+        "A recipient of a share of the update fee. The weights of all the recipients in `Config::fee_recipients` sum to `TOTAL_FEE_WEIGHT_BPS`."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "recipient";
             type: "publicKey";
           },
           {
-            name: "validDataSources";
+            name: "weightBps";
+            type: "u16";
+          }
+        ];
+      };
+    },
+    {
+      name: "PostUpdateAtomicParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "vaa";
+            type: "bytes";
+          },
+          {
+            name: "merklePriceUpdate";
+            type: {
+              defined: "MerklePriceUpdate";
+            };
+          },
+          {
+            name: "treasuryId";
+            type: "u8";
+          }
+        ];
+      };
+    },
+    {
+      name: "PostUpdateParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "merklePriceUpdate";
+            type: {
+              defined: "MerklePriceUpdate";
+            };
+          },
+          {
+            name: "treasuryId";
+            type: "u8";
+          }
+        ];
+      };
+    },
+    {
+      name: "PostUpdatesFromAccountDataParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "treasuryId";
+            type: "u8";
+          },
+          {
+            name: "eventMode";
+            type: {
+              defined: "EventMode";
+            };
+          },
+          {
+            name: "writePriceHistory";
+            docs: [
+              "If true, every update is also added to a `PriceHistory` account passed after the price update accounts."
+            ];
+            type: "bool";
+          },
+          {
+            name: "expectedUpdates";
+            docs: [
+              "If set, the instruction fails if the accumulator update data contains more updates than this, which bounds the compute and the fee of the instruction."
+            ];
+            type: {
+              option: "u16";
+            };
+          },
+          {
+            name: "requireExactCount";
+            docs: [
+              "If true, the accumulator update data must contain exactly `expected_updates` updates instead of at most `expected_updates`. This has no effect if `expected_updates` isn't set."
+            ];
+            type: "bool";
+          },
+          {
+            name: "minConsistencyLevel";
+            docs: [
+              "If set, updates from VAAs whose consistency level is lower than this are rejected. This can only raise `Config::min_consistency_level`, a lower value has no effect."
+            ];
+            type: {
+              option: "u8";
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "PostedUpdatesSummary";
+      docs: [
+        "The return data of the instructions that post a batch of price updates.",
+        "A program that calls one of these instructions through CPI can read it with `get_return_data` to know which updates were verified."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "count";
+            docs: [
+              "The number of updates posted by the instruction."
+            ];
+            type: "u32";
+          },
+          {
+            name: "feedIds";
+            docs: [
+              "The feed ids of the updates, in the same order as the updates. Only the first `MAX_SUMMARY_FEED_IDS` are included."
+            ];
             type: {
               vec: {
-                defined: "DataSource";
+                array: ["u8", 32];
+              };
+            };
+          },
+          {
+            name: "truncated";
+            docs: [
+              "Whether `feed_ids` is missing the feed ids of some of the updates."
+            ];
+            type: "bool";
+          },
+          {
+            name: "skipped";
+            docs: [
+              "The number of updates that weren't posted because their feed isn't in `Config::allowed_feed_ids`."
+            ];
+            type: "u32";
+          }
+        ];
+      };
+    },
+    {
+      name: "TaggedPostedUpdatesSummary";
+      docs: [
+        "The return data of `post_updates_with_tag`: the tag passed by the caller along with a `PostedUpdatesSummary` of the posted updates."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "tag";
+            type: {
+              array: ["u8", 32];
+            };
+          },
+          {
+            name: "summary";
+            docs: [
+              "Only the first `MAX_TAGGED_SUMMARY_FEED_IDS` feed ids are included."
+            ];
+            type: {
+              defined: "PostedUpdatesSummary";
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "PostUpdatesFromVaasParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "merklePriceUpdates";
+            type: {
+              vec: {
+                defined: "MerklePriceUpdate";
               };
             };
           },
           {
-            name: "singleUpdateFeeInLamports";
-            type: "u64";
+            name: "numVaas";
+            docs: [
+              "The number of encoded VAA accounts at the start of the remaining accounts."
+            ];
+            type: "u8";
+          },
+          {
+            name: "vaaIndices";
+            docs: [
+              "For each update, the index of the encoded VAA account whose root includes it. If empty, every update is checked against the VAAs in order until one includes it."
+            ];
+            type: "bytes";
+          },
+          {
+            name: "treasuryId";
+            type: "u8";
+          },
+          {
+            name: "minConsistencyLevel";
+            docs: [
+              "See `PostUpdatesFromAccountDataParams::min_consistency_level`."
+            ];
+            type: {
+              option: "u8";
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "VerificationLevel";
+      docs: [
+        "* This enum represents how many guardian signatures were checked for a Pythnet price update\n * If full, guardian quorum has been attained\n * If partial, at least config.minimum signatures have been verified, but in the case config.minimum_signatures changes in the future we also include the number of signatures that were checked"
+      ];
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Partial";
+            fields: [
+              {
+                name: "numSignatures";
+                type: "u8";
+              }
+            ];
+          },
+          {
+            name: "Full";
+          }
+        ];
+      };
+    },
+    {
+      name: "EventMode";
+      docs: [
+        "Controls which events are emitted when posting a batch of price updates."
+      ];
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Verbose";
+            docs: [
+              "Emit one `PriceUpdatePosted` event per update."
+            ];
           },
           {
-            name: "minimumSignatures";
-            type: "u8";
+            name: "Summary";
+            docs: [
+              "Emit a single `PriceUpdatesPosted` event for the whole batch. Use this for large batches to stay within the transaction log limits."
+            ];
           }
         ];
       };
+    }
+  ];
+  events: [
+    {
+      name: "PriceUpdatePosted";
+      fields: [
+        {
+          name: "priceUpdateAccount";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "feedId";
+          type: {
+            array: ["u8", 32];
+          };
+          index: false;
+        },
+        {
+          name: "publishTime";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "PriceUpdatesPosted";
+      fields: [
+        {
+          name: "feedIds";
+          type: {
+            vec: {
+              array: ["u8", 32];
+            };
+          };
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "PriceUpdateVerified";
+      fields: [
+        {
+          name: "feedId";
+          type: {
+            array: ["u8", 32];
+          };
+          index: false;
+        },
+        {
+          name: "price";
+          type: "i64";
+          index: false;
+        },
+        {
+          name: "conf";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "publishTime";
+          type: "i64";
+          index: false;
+        },
+        {
+          name: "emitterChain";
+          type: "u16";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "TwapUpdateVerified";
+      fields: [
+        {
+          name: "feedId";
+          type: {
+            array: ["u8", 32];
+          };
+          index: false;
+        },
+        {
+          name: "cumulativePrice";
+          type: "i128";
+          index: false;
+        },
+        {
+          name: "cumulativeConf";
+          type: "u128";
+          index: false;
+        },
+        {
+          name: "publishTime";
+          type: "i64";
+          index: false;
+        },
+        {
+          name: "emitterChain";
+          type: "u16";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "ConfigUpdated";
+      fields: [
+        {
+          name: "oldConfig";
+          type: {
+            defined: "Config";
+          };
+          index: false;
+        },
+        {
+          name: "newConfig";
+          type: {
+            defined: "Config";
+          };
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "TaggedPriceUpdatesPosted";
+      fields: [
+        {
+          name: "tag";
+          type: {
+            array: ["u8", 32];
+          };
+          index: false;
+        },
+        {
+          name: "feedIds";
+          type: {
+            vec: {
+              array: ["u8", 32];
+            };
+          };
+          index: false;
+        },
+        {
+          name: "numSkipped";
+          type: "u32";
+          index: false;
+        }
+      ];
+    }
+  ];
+  errors: [
+    {
+      code: 6000;
+      name: "InvalidWormholeMessage";
+      msg: "Received an invalid wormhole message";
+    },
+    {
+      code: 6001;
+      name: "DeserializeMessageFailed";
+      msg: "An error occurred when deserializing the message";
+    },
+    {
+      code: 6002;
+      name: "InvalidPriceUpdate";
+      msg: "Received an invalid price update";
+    },
+    {
+      code: 6003;
+      name: "UnsupportedMessageType";
+      msg: "This type of message is not supported currently";
+    },
+    {
+      code: 6004;
+      name: "InvalidDataSource";
+      msg: "The tuple emitter chain, emitter doesn't match one of the valid data sources.";
+    },
+    {
+      code: 6005;
+      name: "InsufficientFunds";
+      msg: "Funds are insufficient to pay the receiving fee";
+    },
+    {
+      code: 6006;
+      name: "WrongWriteAuthority";
+      msg: "This signer can't write to price update account";
+    },
+    {
+      code: 6007;
+      name: "WrongVaaOwner";
+      msg: "The posted VAA account has the wrong owner.";
+    },
+    {
+      code: 6008;
+      name: "DeserializeVaaFailed";
+      msg: "An error occurred when deserializing the VAA.";
+    },
+    {
+      code: 6009;
+      name: "InsufficientGuardianSignatures";
+      msg: "The number of guardian signatures is below the minimum";
+    },
+    {
+      code: 6010;
+      name: "InvalidVaaVersion";
+      msg: "Invalid VAA version";
+    },
+    {
+      code: 6011;
+      name: "GuardianSetMismatch";
+      msg: "Guardian set version in the VAA doesn't match the guardian set passed";
+    },
+    {
+      code: 6012;
+      name: "InvalidGuardianOrder";
+      msg: "Guardian signature indices must be increasing";
+    },
+    {
+      code: 6013;
+      name: "InvalidGuardianIndex";
+      msg: "Guardian index exceeds the number of guardians in the set";
+    },
+    {
+      code: 6014;
+      name: "InvalidSignature";
+      msg: "A VAA signature is invalid";
+    },
+    {
+      code: 6015;
+      name: "InvalidGuardianKeyRecovery";
+      msg: "The recovered guardian public key doesn't match the guardian set";
+    },
+    {
+      code: 6016;
+      name: "WrongGuardianSetOwner";
+      msg: "The guardian set account is owned by the wrong program";
+    },
+    {
+      code: 6017;
+      name: "InvalidGuardianSetPda";
+      msg: "The Guardian Set account doesn't match the PDA derivation";
+    },
+    {
+      code: 6018;
+      name: "GuardianSetExpired";
+      msg: "The Guardian Set is expired";
+    },
+    {
+      code: 6019;
+      name: "GovernanceAuthorityMismatch";
+      msg: "The signer is not authorized to perform this governance action";
+    },
+    {
+      code: 6020;
+      name: "TargetGovernanceAuthorityMismatch";
+      msg: "The signer is not authorized to accept the governance authority";
+    },
+    {
+      code: 6021;
+      name: "NonexistentGovernanceAuthorityTransferRequest";
+      msg: "The governance authority needs to request a transfer first";
+    },
+    {
+      code: 6022;
+      name: "ZeroMinimumSignatures";
+      msg: "The minimum number of signatures should be at least 1";
+    },
+    {
+      code: 6023;
+      name: "DeserializeAccumulatorUpdateDataFailed";
+      msg: "An error occurred when deserializing the accumulator update data";
+    },
+    {
+      code: 6024;
+      name: "MismatchedVaa";
+      msg: "The VAA in the accumulator update data doesn't match the encoded VAA account";
+    },
+    {
+      code: 6025;
+      name: "WrongNumberOfPriceUpdateAccounts";
+      msg: "The number of price update accounts doesn't match the number of updates";
+    },
+    {
+      code: 6026;
+      name: "NoUpdatesProvided";
+      msg: "The accumulator update data doesn't contain any updates";
+    },
+    {
+      code: 6027;
+      name: "PinnedGuardianSetMismatch";
+      msg: "The guardian set doesn't match the guardian set pinned in the config";
+    },
+    {
+      code: 6028;
+      name: "InvalidFeeRecipientWeights";
+      msg: "The weights of the fee recipients must sum to 10000 basis points";
+    },
+    {
+      code: 6029;
+      name: "WrongFeeRecipientAccounts";
+      msg: "The fee recipient accounts don't match the fee recipients in the config";
+    },
+    {
+      code: 6030;
+      name: "InvalidTwapMessage";
+      msg: "The TWAP message is inconsistent, its previous publish time is after its publish time";
+    },
+    {
+      code: 6031;
+      name: "UnapprovedPostVaaProgram";
+      msg: "The encoded VAA account is owned by a program that isn't approved in the config";
+    },
+    {
+      code: 6032;
+      name: "TooManyGuardians";
+      msg: "The guardian set account has more keys than the maximum number of guardians";
+    },
+    {
+      code: 6033;
+      name: "PriceHistoryFeedIdMismatch";
+      msg: "The price history account stores the updates of a different feed";
+    },
+    {
+      code: 6034;
+      name: "NotAnAccumulatorMessage";
+      msg: "The VAA payload is not a Pyth accumulator message";
+    },
+    {
+      code: 6035;
+      name: "UnexpectedNumberOfUpdates";
+      msg: "The number of updates doesn't match the expected number of updates";
+    },
+    {
+      code: 6036;
+      name: "NoMatchingVaa";
+      msg: "The price update is not included in the root of any of the VAAs";
+    },
+    {
+      code: 6037;
+      name: "InvalidVaaIndex";
+      msg: "The VAA index of an update is out of range, or the number of VAA indices doesn't match the number of updates";
+    },
+    {
+      code: 6038;
+      name: "PriceMovedTooMuch";
+      msg: "The price moved more than the limit set in the config for this feed since the last update";
+    },
+    {
+      code: 6039;
+      name: "PriceUpdateTooRecent";
+      msg: "The price update account was updated too recently to be closed";
+    },
+    {
+      code: 6040;
+      name: "PriceTooStale";
+      msg: "The publish time of the price update is too far in the past";
     },
     {
-      name: "priceUpdateV2";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "writeAuthority";
-            type: "publicKey";
-          },
-          {
-            name: "verificationLevel";
-            type: {
-              defined: "VerificationLevel";
-            };
-          },
-          {
-            name: "priceMessage";
-            type: {
-              defined: "PriceFeedMessage";
-            };
-          },
-          {
-            name: "postedSlot";
-            type: "u64";
-          }
-        ];
-      };
-    }
-  ];
-  types: [
+      code: 6041;
+      name: "PublishTimeInFuture";
+      msg: "The publish time of the price update is too far in the future";
+    },
     {
-      name: "PriceFeedMessage";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "feedId";
-            type: {
-              array: ["u8", 32];
-            };
-          },
-          {
-            name: "price";
-            type: "i64";
-          },
-          {
-            name: "conf";
-            type: "u64";
-          },
-          {
-            name: "exponent";
-            type: "i32";
-          },
-          {
-            name: "publishTime";
-            type: "i64";
-          },
-          {
-            name: "prevPublishTime";
-            type: "i64";
-          },
-          {
-            name: "emaPrice";
-            type: "i64";
-          },
-          {
-            name: "emaConf";
-            type: "u64";
-          }
-        ];
-      };
+      code: 6042;
+      name: "InvalidAccumulatorUpdateData";
+      msg: "The accumulator update data doesn't start with the accumulator update magic and a supported major version";
     },
     {
-      name: "MerklePriceUpdate";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "message";
-            type: "bytes";
-          },
-          {
-            name: "proof";
-            type: {
-              vec: {
-                array: ["u8", 20];
-              };
-            };
-          }
-        ];
-      };
+      code: 6043;
+      name: "UnsupportedProofType";
+      msg: "The proof type of the accumulator update data is not supported";
     },
     {
-      name: "DataSource";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "chain";
-            type: "u16";
-          },
-          {
-            name: "emitter";
-            type: "publicKey";
-          }
-        ];
-      };
+      code: 6044;
+      name: "PriceSanityCheckFailed";
+      msg: "The exponent or the confidence interval of the price update is outside the bounds set in the config";
     },
     {
-      name: "PostUpdateAtomicParams";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "vaa";
-            type: "bytes";
-          },
-          {
-            name: "merklePriceUpdate";
-            type: {
-              defined: "MerklePriceUpdate";
-            };
-          },
-          {
-            name: "treasuryId";
-            type: "u8";
-          }
-        ];
-      };
+      code: 6045;
+      name: "TooManyUpdatesToVerify";
+      msg: "Too many updates to return their messages in the return data of the instruction";
     },
     {
-      name: "PostUpdateParams";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "merklePriceUpdate";
-            type: {
-              defined: "MerklePriceUpdate";
-            };
-          },
-          {
-            name: "treasuryId";
-            type: "u8";
-          }
-        ];
-      };
+      code: 6046;
+      name: "InsufficientConsistencyLevel";
+      msg: "The consistency level of the VAA is lower than the minimum consistency level set in the config";
     },
     {
-      name: "VerificationLevel";
-      docs: [
-        "* This enum represents how many guardian signatures were checked for a Pythnet price update\n * If full, guardian quorum has been attained\n * If partial, at least config.minimum signatures have been verified, but in the case config.minimum_signatures changes in the future we also include the number of signatures that were checked"
-      ];
-      type: {
-        kind: "enum";
-        variants: [
-          {
-            name: "Partial";
-            fields: [
-              {
-                name: "numSignatures";
-                type: "u8";
-              }
-            ];
-          },
-          {
-            name: "Full";
-          }
-        ];
-      };
+      code: 6047;
+      name: "ConfigAccountTooSmall";
+      msg: "The config doesn't fit in the config account, resize it with resize_config";
+    },
+    {
+      code: 6048;
+      name: "MissingFeeRecipientAccounts";
+      msg: "The config has fee recipients, but their accounts weren't passed to the instruction";
+    },
+    {
+      code: 6049;
+      name: "WrongNumberOfFeeRecipientAccounts";
+      msg: "The number of fee recipient accounts doesn't match the number of fee recipients in the config";
+    },
+    {
+      code: 6050;
+      name: "WrongPriceChangeBaselineAccount";
+      msg: "The price change baseline account of the feed is missing or isn't the PDA of the feed";
+    },
+    {
+      code: 6051;
+      name: "InvalidPriceChangeLimitWindow";
+      msg: "The window of a price change limit must be at least 1 second";
     }
   ];
-  errors: [
+};
+
+export const IDL: PythSolanaReceiver = {
+  version: "0.1.0",
+  name: "pyth_solana_receiver",
+  instructions: [
     {
-      code: 6000;
-      name: "InvalidWormholeMessage";
-      msg: "Received an invalid wormhole message";
+      name: "initialize",
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "initialConfig",
+          type: {
+            defined: "Config",
+          },
+        },
+      ],
     },
     {
-      code: 6001;
-      name: "DeserializeMessageFailed";
-      msg: "An error occurred when deserializing the message";
+      name: "requestGovernanceAuthorityTransfer",
+      accounts: [
+        {
+          name: "payer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "targetGovernanceAuthority",
+          type: "publicKey",
+        },
+      ],
+    },
+    {
+      name: "cancelGovernanceAuthorityTransfer",
+      accounts: [
+        {
+          name: "payer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "acceptGovernanceAuthorityTransfer",
+      accounts: [
+        {
+          name: "payer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
     {
-      code: 6002;
-      name: "InvalidPriceUpdate";
-      msg: "Received an invalid price update";
+      name: "setDataSources",
+      accounts: [
+        {
+          name: "payer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "validDataSources",
+          type: {
+            vec: {
+              defined: "DataSource",
+            },
+          },
+        },
+      ],
     },
     {
-      code: 6003;
-      name: "UnsupportedMessageType";
-      msg: "This type of message is not supported currently";
+      name: "setFee",
+      accounts: [
+        {
+          name: "payer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "singleUpdateFeeInLamports",
+          type: "u64",
+        },
+      ],
     },
     {
-      code: 6004;
-      name: "InvalidDataSource";
-      msg: "The tuple emitter chain, emitter doesn't match one of the valid data sources.";
+      name: "setWormholeAddress",
+      accounts: [
+        {
+          name: "payer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "wormhole",
+          type: "publicKey",
+        },
+      ],
     },
     {
-      code: 6005;
-      name: "InsufficientFunds";
-      msg: "Funds are insufficient to pay the receiving fee";
+      name: "setMinimumSignatures",
+      accounts: [
+        {
+          name: "payer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "minimumSignatures",
+          type: "u8",
+        },
+      ],
     },
     {
-      code: 6006;
-      name: "WrongWriteAuthority";
-      msg: "This signer can't write to price update account";
+      name: "setPinnedGuardianSetHash",
+      accounts: [
+        {
+          name: "payer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "pinnedGuardianSetHash",
+          type: {
+            option: {
+              array: ["u8", 32],
+            },
+          },
+        },
+      ],
     },
     {
-      code: 6007;
-      name: "WrongVaaOwner";
-      msg: "The posted VAA account has the wrong owner.";
+      name: "setGuardianSetExpirationSkew",
+      docs: [
+        "Consider guardian sets expired `guardian_set_expiration_skew_seconds` before their expiration time when checking them in `post_update_atomic`.",
+        "This tolerates small differences between the Solana clock and the clock of the chain that set the expiration time.",
+        "The tolerance is only applied in the safe direction: near the boundary, a guardian set that is still valid may be rejected but an expired one is never accepted.",
+        "VAAs posted through the Wormhole receiver (used by `post_update`) are not affected, their guardian set is checked by the Wormhole receiver.",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "guardianSetExpirationSkewSeconds",
+          type: "u32",
+        },
+      ],
     },
     {
-      code: 6008;
-      name: "DeserializeVaaFailed";
-      msg: "An error occurred when deserializing the VAA.";
+      name: "setPostVaaPrograms",
+      docs: [
+        "Accept encoded VAA accounts owned by any of `post_vaa_programs` in addition to the `wormhole` program.",
+        "Every program in this list must only mark VAAs as verified after checking their guardian signatures, since `VaaAccount::load` trusts the `ProcessingStatus` written by the owner.",
+        "Each program takes 32 bytes of the config account.",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "postVaaPrograms",
+          type: {
+            vec: "publicKey",
+          },
+        },
+      ],
     },
     {
-      code: 6009;
-      name: "InsufficientGuardianSignatures";
-      msg: "The number of guardian signatures is below the minimum";
+      name: "setPriceChangeLimits",
+      docs: [
+        "Reject the updates of each feed of `price_change_limits` whose price differs from the last price of the feed by more than the limit of the feed, in basis points of the last price.",
+        "This is a circuit breaker against anomalous jumps in a single update, and feeds without a limit aren't checked.",
+        "The last price of a feed is stored in its `PriceChangeBaseline` account, a PDA of the feed that is updated by every posted update of the feed, whatever the price update account it's posted to. Posting instructions need this account for the updates of the limited feeds, after the fee recipient accounts. The first update of a feed only creates the baseline.",
+        "The limit of a feed only applies to updates published less than its `window_seconds` after the last price, so that a price that really moved is accepted once the window has passed instead of blocking the feed forever. The window must be at least 1 second.",
+        "Each limit takes 38 bytes of the config account.",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "priceChangeLimits",
+          type: {
+            vec: {
+              defined: "PriceChangeLimit",
+            },
+          },
+        },
+      ],
     },
     {
-      code: 6010;
-      name: "InvalidVaaVersion";
-      msg: "Invalid VAA version";
+      name: "setMinPriceUpdateLifetime",
+      docs: [
+        "Only let write authorities close their price update accounts with `reclaim_rent` or `close_price_update` `min_price_update_lifetime_slots` slots after the last update of the account.",
+        "This prevents closing an account in the middle of a transaction of a consumer that reads it.",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "minPriceUpdateLifetimeSlots",
+          type: "u64",
+        },
+      ],
     },
     {
-      code: 6011;
-      name: "GuardianSetMismatch";
-      msg: "Guardian set version in the VAA doesn't match the guardian set passed";
+      name: "setPublishTimeBounds",
+      docs: [
+        "Reject price updates whose `publish_time` is more than `max_staleness_seconds` before, or more than `max_future_publish_time_seconds` after, the current time of the cluster.",
+        "Either bound is disabled when it's 0. The future bound protects against updates signed with a skewed clock, since a publish time in the future would look fresh to consumers for longer than it should.",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "maxStalenessSeconds",
+          type: "u32",
+        },
+        {
+          name: "maxFuturePublishTimeSeconds",
+          type: "u32",
+        },
+      ],
     },
     {
-      code: 6012;
-      name: "InvalidGuardianOrder";
-      msg: "Guardian signature indices must be increasing";
+      name: "setPriceSanityBounds",
+      docs: [
+        "Reject price updates whose exponent is outside `exponent_range`, or whose confidence interval is more than `max_conf_bps` basis points of the price.",
+        "`None` and 0 disable the corresponding check, which is also the behavior of config accounts created before these fields existed.",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "exponentRange",
+          type: {
+            option: {
+              defined: "ExponentRange",
+            },
+          },
+        },
+        {
+          name: "maxConfBps",
+          type: "u32",
+        },
+      ],
     },
     {
-      code: 6013;
-      name: "InvalidGuardianIndex";
-      msg: "Guardian index exceeds the number of guardians in the set";
+      name: "resizeConfig",
+      docs: [
+        "Resize the config account to `len` bytes, the governance authority pays the rent of the extra space.",
+        "Config accounts created by earlier versions of the program are 370 bytes, which isn't enough for the lists of the config to grow: `set_post_vaa_programs`, `set_price_change_limits`, `set_allowed_feed_ids` and `set_fee_recipients` fail with `ReceiverError::ConfigAccountTooSmall` if the new list doesn't fit in the account.",
+        "A single call can grow the account by at most 10 KiB, and the account can't be shrunk below the size of the config it stores.",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "len",
+          type: "u32",
+        },
+      ],
     },
     {
-      code: 6014;
-      name: "InvalidSignature";
-      msg: "A VAA signature is invalid";
+      name: "migrateConfig",
+      docs: [
+        "Rewrite a config account created by the first version of the program in the current layout.",
+        "That version didn't zero the end of the account when the config shrank, so the bytes after its fields can hold stale data that doesn't deserialize as the fields added since then, and every instruction that loads the config fails.",
+        "This only reads the fields of `LegacyConfig`, sets the other fields to their defaults and zeroes the rest of the account. It must be called once after upgrading the program and before setting any of the new fields, since it resets them.",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
     {
-      code: 6015;
-      name: "InvalidGuardianKeyRecovery";
-      msg: "The recovered guardian public key doesn't match the guardian set";
+      name: "setAllowedFeedIds",
+      docs: [
+        "Only post the updates of the feeds of `allowed_feed_ids` in `post_updates_from_account_data`, `post_updates_with_tag` and `post_updates_from_vaas`. The updates of other feeds are skipped without failing the instruction, and their price update accounts are left untouched.",
+        "This keeps a deployment that only cares about a few feeds from paying for the accounts of irrelevant feeds. An empty list allows every feed.",
+        "Each feed id takes 32 bytes of the config account.",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "allowedFeedIds",
+          type: {
+            vec: {
+              array: ["u8", 32],
+            },
+          },
+        },
+      ],
     },
     {
-      code: 6016;
-      name: "WrongGuardianSetOwner";
-      msg: "The guardian set account is owned by the wrong program";
+      name: "setMinConsistencyLevel",
+      docs: [
+        "Reject price updates from VAAs whose consistency level is lower than `min_consistency_level`, for consumers that only want updates from VAAs emitted at a given finality.",
+        "The check applies to every instruction that verifies updates. 0 accepts every VAA, which is also the behavior of config accounts created before this field existed.",
+        "Callers can require a higher consistency level for their own updates (e.g. with `post_update_with_min_consistency`), but can't go below this one.",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "minConsistencyLevel",
+          type: "u8",
+        },
+      ],
     },
     {
-      code: 6017;
-      name: "InvalidGuardianSetPda";
-      msg: "The Guardian Set account doesn't match the PDA derivation";
+      name: "setFeeRecipients",
+      docs: [
+        "Split the fee of every update between `fee_recipients` according to their weights (in basis points), instead of sending it to the treasury.",
+        "The weights must sum to `TOTAL_FEE_WEIGHT_BPS`. An empty list sends the whole fee to the treasury again.",
+        "Posting instructions then need the fee recipient accounts as remaining accounts, in the same order, and each recipient must be able to receive its share (e.g. a rent-exempt system account).",
+        "Each recipient takes 34 bytes of the config account.",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "feeRecipients",
+          type: {
+            vec: {
+              defined: "FeeRecipient",
+            },
+          },
+        },
+      ],
     },
     {
-      code: 6018;
-      name: "GuardianSetExpired";
-      msg: "The Guardian Set is expired";
+      name: "postUpdateAtomic",
+      docs: [
+        "Post a price update using a VAA and a MerklePriceUpdate.",
+        "This function allows you to post a price update in a single transaction.",
+        "Compared to `post_update`, it only checks whatever signatures are present in the provided VAA and doesn't fail if the number of signatures is lower than the Wormhole quorum of two thirds of the guardians.",
+        "The number of signatures that were in the VAA is stored in the `VerificationLevel` of the `PriceUpdateV2` account.",
+        "",
+        "We recommend using `post_update_atomic` with 5 signatures. This is close to the maximum signatures you can verify in one transaction without exceeding the transaction size limit.",
+        "",
+        "# Warning",
+        "",
+        "Using partially verified price updates is dangerous, as it lowers the threshold of guardians that need to collude to produce a malicious price update.",
+        "",
+        "If the config has fee recipients, their accounts must be passed as remaining accounts (see `set_fee_recipients`), followed by the `PriceChangeBaseline` account of the feed if it has a price change limit (see `set_price_change_limits`).",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "guardianSet",
+          isMut: false,
+          isSigner: false,
+          docs: [
+            "Instead we do the same steps in deserialize_guardian_set_checked.",
+          ],
+        },
+        {
+          name: "config",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "priceUpdateAccount",
+          isMut: true,
+          isSigner: true,
+          docs: [
+            "The constraint is such that either the price_update_account is uninitialized or the write_authority is the write_authority.",
+            "Pubkey::default() is the SystemProgram on Solana and it can't sign so it's impossible that price_update_account.write_authority == Pubkey::default() once the account is initialized",
+          ],
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "writeAuthority",
+          isMut: false,
+          isSigner: true,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "PostUpdateAtomicParams",
+          },
+        },
+      ],
     },
     {
-      code: 6019;
-      name: "GovernanceAuthorityMismatch";
-      msg: "The signer is not authorized to perform this governance action";
+      name: "postUpdateAtomicWithMinConsistency",
+      docs: [
+        "Same as `post_update_atomic`, but the VAA must also have at least `min_consistency_level` as its consistency level.",
+        "This lets a caller require a higher consistency level than `Config::min_consistency_level` for its own updates, a lower value has no effect (see `set_min_consistency_level`).",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "guardianSet",
+          isMut: false,
+          isSigner: false,
+          docs: [
+            "Instead we do the same steps in deserialize_guardian_set_checked.",
+          ],
+        },
+        {
+          name: "config",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "priceUpdateAccount",
+          isMut: true,
+          isSigner: true,
+          docs: [
+            "The constraint is such that either the price_update_account is uninitialized or the write_authority is the write_authority.",
+            "Pubkey::default() is the SystemProgram on Solana and it can't sign so it's impossible that price_update_account.write_authority == Pubkey::default() once the account is initialized",
+          ],
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "writeAuthority",
+          isMut: false,
+          isSigner: true,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "PostUpdateAtomicParams",
+          },
+        },
+        {
+          name: "minConsistencyLevel",
+          type: "u8",
+        },
+      ],
+    },
+    {
+      name: "postUpdate",
+      docs: [
+        "Post a price update using an encoded_vaa account and a MerklePriceUpdate calldata.",
+        "This should be called after the client has already verified the Vaa via the Wormhole contract.",
+        "Check out target_chains/solana/cli/src/main.rs for an example of how to do this.",
+        "If the config has fee recipients, their accounts must be passed as remaining accounts (see `set_fee_recipients`), followed by the `PriceChangeBaseline` account of the feed if it has a price change limit (see `set_price_change_limits`).",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "encodedVaa",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "config",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "priceUpdateAccount",
+          isMut: true,
+          isSigner: true,
+          docs: [
+            "The constraint is such that either the price_update_account is uninitialized or the write_authority is the write_authority.",
+            "Pubkey::default() is the SystemProgram on Solana and it can't sign so it's impossible that price_update_account.write_authority == Pubkey::default() once the account is initialized",
+          ],
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "writeAuthority",
+          isMut: false,
+          isSigner: true,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "PostUpdateParams",
+          },
+        },
+      ],
     },
     {
-      code: 6020;
-      name: "TargetGovernanceAuthorityMismatch";
-      msg: "The signer is not authorized to accept the governance authority";
+      name: "postUpdateWithMinConsistency",
+      docs: [
+        "Same as `post_update`, but the VAA must also have at least `min_consistency_level` as its consistency level.",
+        "This lets a caller require a higher consistency level than `Config::min_consistency_level` for its own updates, a lower value has no effect (see `set_min_consistency_level`).",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "encodedVaa",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "config",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "priceUpdateAccount",
+          isMut: true,
+          isSigner: true,
+          docs: [
+            "The constraint is such that either the price_update_account is uninitialized or the write_authority is the write_authority.",
+            "Pubkey::default() is the SystemProgram on Solana and it can't sign so it's impossible that price_update_account.write_authority == Pubkey::default() once the account is initialized",
+          ],
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "writeAuthority",
+          isMut: false,
+          isSigner: true,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "PostUpdateParams",
+          },
+        },
+        {
+          name: "minConsistencyLevel",
+          type: "u8",
+        },
+      ],
     },
     {
-      code: 6021;
-      name: "NonexistentGovernanceAuthorityTransferRequest";
-      msg: "The governance authority needs to request a transfer first";
-    }
-  ];
-};
-
-export const IDL: PythSolanaReceiver = {
-  version: "0.1.0",
-  name: "pyth_solana_receiver",
-  instructions: [
-    {
-      name: "initialize",
+      name: "postUpdatesFromAccountData",
+      docs: [
+        "Post all the price updates of an `AccumulatorUpdateData` that has been written to an account.",
+        "This is useful for batches of updates that are too large to fit in instruction data.",
+        "Like `post_update`, this should be called after the client has already verified the Vaa via the Wormhole contract, and the VAA embedded in the accumulator update data must match the `encoded_vaa` account.",
+        "The price update accounts are passed as remaining accounts, one for each update and in the same order as the updates, followed by the fee recipient accounts if the config has any, followed by the `PriceChangeBaseline` accounts of the feeds with a price change limit (see `set_price_change_limits`).",
+        "If `params.write_price_history` is true, every update is also added to a `PriceHistory` account, and these accounts are passed right after the price update accounts, one for each update and in the same order.",
+        "`params.event_mode` controls whether one event is emitted per update or a single event for the whole batch.",
+        "`params.expected_updates` and `params.require_exact_count` bound (or fix) the number of updates of the batch, so that the compute and the fee of the instruction are predictable.",
+        "The updates of feeds that aren't in `allowed_feed_ids` are skipped (see `set_allowed_feed_ids`).",
+        "The return data of the instruction is a `PostedUpdatesSummary` of the posted updates.",
+      ],
       accounts: [
         {
           name: "payer",
           isMut: true,
           isSigner: true,
         },
+        {
+          name: "encodedVaa",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "accumulatorUpdateData",
+          isMut: false,
+          isSigner: false,
+        },
         {
           name: "config",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
           isMut: true,
           isSigner: false,
         },
@@ -637,148 +3064,337 @@ export const IDL: PythSolanaReceiver = {
           isMut: false,
           isSigner: false,
         },
+        {
+          name: "writeAuthority",
+          isMut: false,
+          isSigner: true,
+        },
       ],
       args: [
         {
-          name: "initialConfig",
+          name: "params",
           type: {
-            defined: "Config",
+            defined: "PostUpdatesFromAccountDataParams",
           },
         },
       ],
+      returns: {
+        defined: "PostedUpdatesSummary",
+      },
     },
     {
-      name: "requestGovernanceAuthorityTransfer",
+      name: "postUpdatesWithTag",
+      docs: [
+        "Same as `post_updates_from_account_data`, with a correlation tag chosen by the caller.",
+        "The tag is emitted in a `TaggedPriceUpdatesPosted` event along with the feed ids of the updates and the number of skipped updates.",
+        "The return data of the instruction is a `TaggedPostedUpdatesSummary` with the tag and a summary of the posted updates.",
+        "This lets a consumer program that triggered the verification (e.g. in response to one of its own requests) match the verified prices to that request.",
+      ],
       accounts: [
         {
           name: "payer",
-          isMut: false,
+          isMut: true,
           isSigner: true,
         },
+        {
+          name: "encodedVaa",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "accumulatorUpdateData",
+          isMut: false,
+          isSigner: false,
+        },
         {
           name: "config",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
           isMut: true,
           isSigner: false,
         },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "writeAuthority",
+          isMut: false,
+          isSigner: true,
+        },
       ],
       args: [
         {
-          name: "targetGovernanceAuthority",
-          type: "publicKey",
+          name: "params",
+          type: {
+            defined: "PostUpdatesFromAccountDataParams",
+          },
+        },
+        {
+          name: "tag",
+          type: {
+            array: ["u8", 32],
+          },
         },
       ],
+      returns: {
+        defined: "TaggedPostedUpdatesSummary",
+      },
     },
     {
-      name: "acceptGovernanceAuthorityTransfer",
+      name: "postUpdatesFromVaas",
+      docs: [
+        "Post price updates whose Merkle roots are spread over several VAAs, e.g. a set of feeds that spans an accumulator boundary.",
+        "Like `post_update`, this should be called after the client has already verified the VAAs via the Wormhole contract.",
+        "The remaining accounts are the `params.num_vaas` encoded VAA accounts, followed by one price update account per update in the same order as the updates, followed by the fee recipient accounts if the config has any, followed by the `PriceChangeBaseline` accounts of the feeds with a price change limit (see `set_price_change_limits`).",
+        "Every update is checked against the VAA at its index in `params.vaa_indices`, or if `params.vaa_indices` is empty against the VAAs in order until one of them includes it. An update that isn't included in any of the VAAs is rejected.",
+        "Finding the VAA of an update verifies it once more, so passing `params.vaa_indices` uses less compute for large batches.",
+        "The updates of feeds that aren't in `allowed_feed_ids` are skipped (see `set_allowed_feed_ids`).",
+        "The return data of the instruction is a `PostedUpdatesSummary` of the posted updates.",
+      ],
       accounts: [
         {
           name: "payer",
-          isMut: false,
+          isMut: true,
           isSigner: true,
         },
         {
           name: "config",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
           isMut: true,
           isSigner: false,
         },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "writeAuthority",
+          isMut: false,
+          isSigner: true,
+        },
       ],
-      args: [],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "PostUpdatesFromVaasParams",
+          },
+        },
+      ],
+      returns: {
+        defined: "PostedUpdatesSummary",
+      },
     },
     {
-      name: "setDataSources",
+      name: "postTwapUpdate",
+      docs: [
+        "Post a TWAP update using an encoded_vaa account and a MerklePriceUpdate calldata containing a TWAP message.",
+        "The fields of the TWAP message are stored in a `TwapUpdate` account, and the fee is the same as for a price update.",
+        "Like `post_update`, this should be called after the client has already verified the Vaa via the Wormhole contract.",
+        "If the config has fee recipients, their accounts must be passed as remaining accounts (see `set_fee_recipients`).",
+      ],
       accounts: [
         {
           name: "payer",
-          isMut: false,
+          isMut: true,
           isSigner: true,
         },
+        {
+          name: "encodedVaa",
+          isMut: false,
+          isSigner: false,
+        },
         {
           name: "config",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "twapUpdateAccount",
           isMut: true,
+          isSigner: true,
+          docs: [
+            "The constraint is such that either the twap_update_account is uninitialized or the write_authority is the write_authority.",
+            "Pubkey::default() is the SystemProgram on Solana and it can't sign so it's impossible that twap_update_account.write_authority == Pubkey::default() once the account is initialized",
+          ],
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
           isSigner: false,
         },
+        {
+          name: "writeAuthority",
+          isMut: false,
+          isSigner: true,
+        },
       ],
       args: [
         {
-          name: "validDataSources",
+          name: "params",
           type: {
-            vec: {
-              defined: "DataSource",
-            },
+            defined: "PostUpdateParams",
           },
         },
       ],
     },
     {
-      name: "setFee",
+      name: "postTwapUpdateWithMinConsistency",
+      docs: [
+        "Same as `post_twap_update`, but the VAA must also have at least `min_consistency_level` as its consistency level.",
+        "This lets a caller require a higher consistency level than `Config::min_consistency_level` for its own updates, a lower value has no effect (see `set_min_consistency_level`).",
+      ],
       accounts: [
         {
           name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "encodedVaa",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "config",
           isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "twapUpdateAccount",
+          isMut: true,
           isSigner: true,
+          docs: [
+            "The constraint is such that either the twap_update_account is uninitialized or the write_authority is the write_authority.",
+            "Pubkey::default() is the SystemProgram on Solana and it can't sign so it's impossible that twap_update_account.write_authority == Pubkey::default() once the account is initialized",
+          ],
         },
         {
-          name: "config",
-          isMut: true,
+          name: "systemProgram",
+          isMut: false,
           isSigner: false,
         },
+        {
+          name: "writeAuthority",
+          isMut: false,
+          isSigner: true,
+        },
       ],
       args: [
         {
-          name: "singleUpdateFeeInLamports",
-          type: "u64",
+          name: "params",
+          type: {
+            defined: "PostUpdateParams",
+          },
+        },
+        {
+          name: "minConsistencyLevel",
+          type: "u8",
         },
       ],
     },
     {
-      name: "setWormholeAddress",
+      name: "verifyUpdate",
+      docs: [
+        "Verify a price update against an encoded_vaa account without posting it.",
+        "This instruction doesn't write any account and doesn't charge any fee, so it doesn't take a payer and can be simulated by any account (e.g. by monitoring tools).",
+        "Like `post_update`, this should be called after the client has already verified the Vaa via the Wormhole contract.",
+        "If `min_consistency_level` is set, the VAA must also have at least this consistency level (see `set_min_consistency_level`).",
+      ],
       accounts: [
         {
-          name: "payer",
+          name: "encodedVaa",
           isMut: false,
-          isSigner: true,
+          isSigner: false,
         },
         {
           name: "config",
-          isMut: true,
+          isMut: false,
           isSigner: false,
         },
       ],
       args: [
         {
-          name: "wormhole",
-          type: "publicKey",
+          name: "merklePriceUpdate",
+          type: {
+            defined: "MerklePriceUpdate",
+          },
+        },
+        {
+          name: "minConsistencyLevel",
+          type: {
+            option: "u8",
+          },
         },
       ],
     },
     {
-      name: "setMinimumSignatures",
+      name: "verifyUpdatesView",
+      docs: [
+        "Verify several price updates against an encoded_vaa account without posting them, and return their decoded messages in the same order as the updates.",
+        "Like `verify_update`, this instruction doesn't write any account and doesn't charge any fee. It's meant to be simulated (e.g. with `simulateTransaction`) to preview the content of a set of updates before posting them.",
+        "At most `MAX_VIEW_UPDATES` updates can be verified at once, so that their messages fit in the return data.",
+        "If `min_consistency_level` is set, the VAA must also have at least this consistency level (see `set_min_consistency_level`).",
+      ],
       accounts: [
         {
-          name: "payer",
+          name: "encodedVaa",
           isMut: false,
-          isSigner: true,
+          isSigner: false,
         },
         {
           name: "config",
-          isMut: true,
+          isMut: false,
           isSigner: false,
         },
       ],
       args: [
         {
-          name: "minimumSignatures",
-          type: "u8",
+          name: "merklePriceUpdates",
+          type: {
+            vec: {
+              defined: "MerklePriceUpdate",
+            },
+          },
+        },
+        {
+          name: "minConsistencyLevel",
+          type: {
+            option: "u8",
+          },
         },
       ],
+      returns: {
+        vec: {
+          defined: "PriceFeedMessage",
+        },
+      },
     },
     {
-      name: "postUpdateAtomic",
+      name: "reclaimRent",
       docs: [
-        "Post a price update using a VAA and a MerklePriceUpdate.",
-        "This function allows you to post a price update in a single transaction.",
-        "Compared to post_update, it is less secure since you won't be able to verify all guardian signatures if you use this function because of transaction size limitations.",
-        "Typically, you can fit 5 guardian signatures in a transaction that uses this.",
+        "Close a price update account and send its lamports to its write authority.",
+        "The account can only be closed `min_price_update_lifetime_slots` slots after its last update (see `set_min_price_update_lifetime`).",
       ],
       accounts: [
         {
@@ -787,310 +3403,662 @@ export const IDL: PythSolanaReceiver = {
           isSigner: true,
         },
         {
-          name: "guardianSet",
-          isMut: false,
+          name: "priceUpdateAccount",
+          isMut: true,
           isSigner: false,
-          docs: [
-            "Instead we do the same steps in deserialize_guardian_set_checked.",
-          ],
         },
         {
           name: "config",
           isMut: false,
           isSigner: false,
         },
+      ],
+      args: [],
+    },
+    {
+      name: "closePriceUpdate",
+      docs: [
+        "Close a price update account and send its lamports to `recipient`. Only the write authority of the account can close it.",
+        "The account can only be closed `min_price_update_lifetime_slots` slots after its last update (see `set_min_price_update_lifetime`), otherwise the instruction fails with `PriceUpdateTooRecent`.",
+      ],
+      accounts: [
         {
-          name: "treasury",
-          isMut: true,
-          isSigner: false,
+          name: "writeAuthority",
+          isMut: false,
+          isSigner: true,
         },
         {
           name: "priceUpdateAccount",
           isMut: true,
-          isSigner: true,
-          docs: [
-            "The contraint is such that either the price_update_account is uninitialized or the payer is the write_authority.",
-            "Pubkey::default() is the SystemProgram on Solana and it can't sign so it's impossible that price_update_account.write_authority == Pubkey::default() once the account is initialized",
-          ],
+          isSigner: false,
         },
         {
-          name: "systemProgram",
-          isMut: false,
+          name: "recipient",
+          isMut: true,
           isSigner: false,
         },
         {
-          name: "writeAuthority",
+          name: "config",
           isMut: false,
-          isSigner: true,
+          isSigner: false,
         },
       ],
-      args: [
-        {
-          name: "params",
-          type: {
-            defined: "PostUpdateAtomicParams",
+      args: [],
+    },
+  ],
+  accounts: [
+    {
+      name: "Config",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "governanceAuthority",
+            type: "publicKey",
+          },
+          {
+            name: "targetGovernanceAuthority",
+            type: {
+              option: "publicKey",
+            },
+          },
+          {
+            name: "wormhole",
+            type: "publicKey",
+          },
+          {
+            name: "validDataSources",
+            type: {
+              vec: {
+                defined: "DataSource",
+              },
+            },
+          },
+          {
+            name: "singleUpdateFeeInLamports",
+            type: "u64",
+          },
+          {
+            name: "minimumSignatures",
+            type: "u8",
+          },
+          {
+            name: "pinnedGuardianSetHash",
+            type: {
+              option: {
+                array: ["u8", 32],
+              },
+            },
+          },
+          {
+            name: "feeRecipients",
+            type: {
+              vec: {
+                defined: "FeeRecipient",
+              },
+            },
+          },
+          {
+            name: "postVaaPrograms",
+            type: {
+              vec: "publicKey",
+            },
+          },
+          {
+            name: "guardianSetExpirationSkewSeconds",
+            type: "u32",
+          },
+          {
+            name: "priceChangeLimits",
+            type: {
+              vec: {
+                defined: "PriceChangeLimit",
+              },
+            },
+          },
+          {
+            name: "minPriceUpdateLifetimeSlots",
+            type: "u64",
+          },
+          {
+            name: "maxStalenessSeconds",
+            type: "u32",
+          },
+          {
+            name: "maxFuturePublishTimeSeconds",
+            type: "u32",
+          },
+          {
+            name: "exponentRange",
+            type: {
+              option: {
+                defined: "ExponentRange",
+              },
+            },
+          },
+          {
+            name: "maxConfBps",
+            type: "u32",
+          },
+          {
+            name: "allowedFeedIds",
+            type: {
+              vec: {
+                array: ["u8", 32],
+              },
+            },
+          },
+          {
+            name: "minConsistencyLevel",
+            type: "u8",
+          },
+        ],
+      },
+    },
+    {
+      name: "priceUpdateV2",
+      docs: [
+        "A price update account. This account is used by the Pyth Receiver program to store a verified price update from a Pyth price feed.",
+        "It contains:",
+        "- `write_authority`: The write authority for this account. This authority can close this account to reclaim rent or update the account to contain a different price update.",
+        "- `verification_level`: The [`VerificationLevel`] of this price update. This represents how many Wormhole guardian signatures have been verified for this price update.",
+        "- `price_message`: The actual price update.",
+        "- `posted_slot`: The slot at which this price update was posted.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "writeAuthority",
+            type: "publicKey",
+          },
+          {
+            name: "verificationLevel",
+            type: {
+              defined: "VerificationLevel",
+            },
+          },
+          {
+            name: "priceMessage",
+            type: {
+              defined: "PriceFeedMessage",
+            },
+          },
+          {
+            name: "postedSlot",
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "twapUpdate",
+      docs: [
+        "A TWAP update account. This account is used by the Pyth Receiver program to store a verified TWAP message from a Pyth price feed.",
+        "It contains the same `write_authority`, `verification_level` and `posted_slot` as [`PriceUpdateV2`], and each field of the TWAP message:",
+        "- `feed_id`: The feed id of the price feed.",
+        "- `cumulative_price` and `cumulative_conf`: The sums of the price and the confidence interval of the feed at every slot since the feed started.",
+        "- `num_down_slots`: The number of slots during which the feed didn't have a valid price since the feed started.",
+        "- `exponent`: The exponent of the cumulative price and confidence interval.",
+        "- `publish_time`, `prev_publish_time`: The timestamps of this update and of the previous update.",
+        "- `publish_slot`: The Pythnet slot of this update.",
+        "",
+        "The TWAP between two TWAP updates is `(cumulative_price_2 - cumulative_price_1) / (publish_slot_2 - publish_slot_1)`.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "writeAuthority",
+            type: "publicKey",
+          },
+          {
+            name: "verificationLevel",
+            type: {
+              defined: "VerificationLevel",
+            },
+          },
+          {
+            name: "feedId",
+            type: {
+              array: ["u8", 32],
+            },
+          },
+          {
+            name: "cumulativePrice",
+            type: "i128",
+          },
+          {
+            name: "cumulativeConf",
+            type: "u128",
+          },
+          {
+            name: "numDownSlots",
+            type: "u64",
+          },
+          {
+            name: "exponent",
+            type: "i32",
+          },
+          {
+            name: "publishTime",
+            type: "i64",
+          },
+          {
+            name: "prevPublishTime",
+            type: "i64",
           },
-        },
-      ],
+          {
+            name: "publishSlot",
+            type: "u64",
+          },
+          {
+            name: "postedSlot",
+            type: "u64",
+          },
+        ],
+      },
     },
     {
-      name: "postUpdate",
+      name: "priceHistory",
       docs: [
-        "Post a price update using an encoded_vaa account and a MerklePriceUpdate calldata.",
-        "This should be called after the client has already verified the Vaa via the Wormhole contract.",
-        "Check out target_chains/solana/cli/src/main.rs for an example of how to do this.",
-      ],
-      accounts: [
-        {
-          name: "payer",
-          isMut: true,
-          isSigner: true,
-        },
-        {
-          name: "encodedVaa",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "config",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "treasury",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "priceUpdateAccount",
-          isMut: true,
-          isSigner: true,
-          docs: [
-            "The contraint is such that either the price_update_account is uninitialized or the payer is the write_authority.",
-            "Pubkey::default() is the SystemProgram on Solana and it can't sign so it's impossible that price_update_account.write_authority == Pubkey::default() once the account is initialized",
-          ],
-        },
-        {
-          name: "systemProgram",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "writeAuthority",
-          isMut: false,
-          isSigner: true,
-        },
+        "A price history account. This account is used by the Pyth Receiver program to store the most recent price updates of a single feed, for consumers that need more than the latest price (e.g. to compute a TWAP or the volatility).",
+        "It contains:",
+        "- `write_authority`: The write authority for this account, like for [`PriceUpdateV2`].",
+        "- `feed_id`: The feed of the price updates. It is set by the first update.",
+        "- `next_index`: The index of `updates` that the next price update will overwrite once the buffer is full.",
+        "- `updates`: A ring buffer of the last `PRICE_HISTORY_LEN` price updates, use [`PriceHistory::get_updates`] to read them in chronological order.",
       ],
-      args: [
-        {
-          name: "params",
-          type: {
-            defined: "PostUpdateParams",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "writeAuthority",
+            type: "publicKey",
           },
-        },
-      ],
+          {
+            name: "feedId",
+            type: {
+              array: ["u8", 32],
+            },
+          },
+          {
+            name: "nextIndex",
+            type: "u8",
+          },
+          {
+            name: "updates",
+            type: {
+              vec: {
+                defined: "PriceFeedMessage",
+              },
+            },
+          },
+        ],
+      },
     },
     {
-      name: "reclaimRent",
-      accounts: [
-        {
-          name: "payer",
-          isMut: true,
-          isSigner: true,
-        },
-        {
-          name: "priceUpdateAccount",
-          isMut: true,
-          isSigner: false,
-        },
+      name: "priceChangeBaseline",
+      docs: [
+        "The last price of a feed with a `PriceChangeLimit`, whatever the price update account it was posted to.",
+        "There is one such account per feed, at the PDA of `get_price_change_baseline_address`. It's created by the first update of the feed after the limit is set, and every later update of the feed is compared with it.",
       ],
-      args: [],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "priceMessage",
+            type: {
+              defined: "PriceFeedMessage",
+            },
+          },
+        ],
+      },
     },
   ],
-  accounts: [
+  types: [
     {
-      name: "Config",
+      name: "MerklePriceUpdate",
       type: {
         kind: "struct",
         fields: [
           {
-            name: "governanceAuthority",
-            type: "publicKey",
+            name: "message",
+            type: "bytes",
           },
           {
-            name: "targetGovernanceAuthority",
+            name: "proof",
             type: {
-              option: "publicKey",
+              vec: {
+                array: ["u8", 20],
+              },
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "PriceFeedMessage",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "feedId",
+            type: {
+              array: ["u8", 32],
             },
           },
           {
-            name: "wormhole",
+            name: "price",
+            type: "i64",
+          },
+          {
+            name: "conf",
+            type: "u64",
+          },
+          {
+            name: "exponent",
+            type: "i32",
+          },
+          {
+            name: "publishTime",
+            type: "i64",
+          },
+          {
+            name: "prevPublishTime",
+            type: "i64",
+          },
+          {
+            name: "emaPrice",
+            type: "i64",
+          },
+          {
+            name: "emaConf",
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "DataSource",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "chain",
+            type: "u16",
+          },
+          {
+            name: "emitter",
             type: "publicKey",
           },
+        ],
+      },
+    },
+    {
+      name: "PriceChangeLimit",
+      docs: [
+        "The maximum change of the price of `feed_id` in a single update, in basis points of the last price of the feed (see `PriceChangeBaseline`).",
+        "The limit only applies to updates published less than `window_seconds` after the last price, so that a lasting move of the price can't leave the feed stuck on the last price: the first update published after the window is accepted whatever its price. `window_seconds` can't be 0.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
           {
-            name: "validDataSources",
+            name: "feedId",
             type: {
-              vec: {
-                defined: "DataSource",
-              },
+              array: ["u8", 32],
             },
           },
           {
-            name: "singleUpdateFeeInLamports",
-            type: "u64",
+            name: "maxChangeBps",
+            type: "u16",
           },
           {
-            name: "minimumSignatures",
-            type: "u8",
+            name: "windowSeconds",
+            type: "u32",
           },
         ],
       },
     },
     {
-      name: "priceUpdateV2",
+      name: "ExponentRange",
+      docs: [
+        "The range of exponents accepted for price updates, inclusive.",
+      ],
       type: {
         kind: "struct",
         fields: [
           {
-            name: "writeAuthority",
+            name: "min",
+            type: "i32",
+          },
+          {
+            name: "max",
+            type: "i32",
+          },
+        ],
+      },
+    },
+    {
+      name: "FeeRecipient",
+      docs: [
+        "A recipient of a share of the update fee. The weights of all the recipients in `Config::fee_recipients` sum to `TOTAL_FEE_WEIGHT_BPS`.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "recipient",
             type: "publicKey",
           },
           {
-            name: "verificationLevel",
+            name: "weightBps",
+            type: "u16",
+          },
+        ],
+      },
+    },
+    {
+      name: "PostUpdateAtomicParams",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "vaa",
+            type: "bytes",
+          },
+          {
+            name: "merklePriceUpdate",
             type: {
-              defined: "VerificationLevel",
+              defined: "MerklePriceUpdate",
             },
           },
           {
-            name: "priceMessage",
+            name: "treasuryId",
+            type: "u8",
+          },
+        ],
+      },
+    },
+    {
+      name: "PostUpdateParams",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "merklePriceUpdate",
             type: {
-              defined: "PriceFeedMessage",
+              defined: "MerklePriceUpdate",
             },
           },
           {
-            name: "postedSlot",
-            type: "u64",
+            name: "treasuryId",
+            type: "u8",
           },
         ],
       },
     },
-  ],
-  types: [
     {
-      name: "PriceFeedMessage",
+      name: "PostUpdatesFromAccountDataParams",
       type: {
         kind: "struct",
         fields: [
           {
-            name: "feedId",
-            type: {
-              array: ["u8", 32],
-            },
-          },
-          {
-            name: "price",
-            type: "i64",
-          },
-          {
-            name: "conf",
-            type: "u64",
+            name: "treasuryId",
+            type: "u8",
           },
           {
-            name: "exponent",
-            type: "i32",
+            name: "eventMode",
+            type: {
+              defined: "EventMode",
+            },
           },
           {
-            name: "publishTime",
-            type: "i64",
+            name: "writePriceHistory",
+            docs: [
+              "If true, every update is also added to a `PriceHistory` account passed after the price update accounts.",
+            ],
+            type: "bool",
           },
           {
-            name: "prevPublishTime",
-            type: "i64",
+            name: "expectedUpdates",
+            docs: [
+              "If set, the instruction fails if the accumulator update data contains more updates than this, which bounds the compute and the fee of the instruction.",
+            ],
+            type: {
+              option: "u16",
+            },
           },
           {
-            name: "emaPrice",
-            type: "i64",
+            name: "requireExactCount",
+            docs: [
+              "If true, the accumulator update data must contain exactly `expected_updates` updates instead of at most `expected_updates`. This has no effect if `expected_updates` isn't set.",
+            ],
+            type: "bool",
           },
           {
-            name: "emaConf",
-            type: "u64",
+            name: "minConsistencyLevel",
+            docs: [
+              "If set, updates from VAAs whose consistency level is lower than this are rejected. This can only raise `Config::min_consistency_level`, a lower value has no effect.",
+            ],
+            type: {
+              option: "u8",
+            },
           },
         ],
       },
     },
     {
-      name: "MerklePriceUpdate",
+      name: "PostedUpdatesSummary",
+      docs: [
+        "The return data of the instructions that post a batch of price updates.",
+        "A program that calls one of these instructions through CPI can read it with `get_return_data` to know which updates were verified.",
+      ],
       type: {
         kind: "struct",
         fields: [
           {
-            name: "message",
-            type: "bytes",
+            name: "count",
+            docs: [
+              "The number of updates posted by the instruction.",
+            ],
+            type: "u32",
           },
           {
-            name: "proof",
+            name: "feedIds",
+            docs: [
+              "The feed ids of the updates, in the same order as the updates. Only the first `MAX_SUMMARY_FEED_IDS` are included.",
+            ],
             type: {
               vec: {
-                array: ["u8", 20],
+                array: ["u8", 32],
               },
             },
           },
-        ],
-      },
-    },
-    {
-      name: "DataSource",
-      type: {
-        kind: "struct",
-        fields: [
           {
-            name: "chain",
-            type: "u16",
+            name: "truncated",
+            docs: [
+              "Whether `feed_ids` is missing the feed ids of some of the updates.",
+            ],
+            type: "bool",
           },
           {
-            name: "emitter",
-            type: "publicKey",
+            name: "skipped",
+            docs: [
+              "The number of updates that weren't posted because their feed isn't in `Config::allowed_feed_ids`.",
+            ],
+            type: "u32",
           },
         ],
       },
     },
     {
-      name: "PostUpdateAtomicParams",
+      name: "TaggedPostedUpdatesSummary",
+      docs: [
+        "The return data of `post_updates_with_tag`: the tag passed by the caller along with a `PostedUpdatesSummary` of the posted updates.",
+      ],
       type: {
         kind: "struct",
         fields: [
           {
-            name: "vaa",
-            type: "bytes",
-          },
-          {
-            name: "merklePriceUpdate",
+            name: "tag",
             type: {
-              defined: "MerklePriceUpdate",
+              array: ["u8", 32],
             },
           },
           {
-            name: "treasuryId",
-            type: "u8",
+            name: "summary",
+            docs: [
+              "Only the first `MAX_TAGGED_SUMMARY_FEED_IDS` feed ids are included.",
+            ],
+            type: {
+              defined: "PostedUpdatesSummary",
+            },
           },
         ],
       },
     },
     {
-      name: "PostUpdateParams",
+      name: "PostUpdatesFromVaasParams",
       type: {
         kind: "struct",
         fields: [
           {
-            name: "merklePriceUpdate",
+            name: "merklePriceUpdates",
             type: {
-              defined: "MerklePriceUpdate",
+              vec: {
+                defined: "MerklePriceUpdate",
+              },
             },
           },
+          {
+            name: "numVaas",
+            docs: [
+              "The number of encoded VAA accounts at the start of the remaining accounts.",
+            ],
+            type: "u8",
+          },
+          {
+            name: "vaaIndices",
+            docs: [
+              "For each update, the index of the encoded VAA account whose root includes it. If empty, every update is checked against the VAAs in order until one includes it.",
+            ],
+            type: "bytes",
+          },
           {
             name: "treasuryId",
             type: "u8",
           },
+          {
+            name: "minConsistencyLevel",
+            docs: [
+              "See `PostUpdatesFromAccountDataParams::min_consistency_level`.",
+            ],
+            type: {
+              option: "u8",
+            },
+          },
         ],
       },
     },
@@ -1117,6 +4085,176 @@ export const IDL: PythSolanaReceiver = {
         ],
       },
     },
+    {
+      name: "EventMode",
+      docs: [
+        "Controls which events are emitted when posting a batch of price updates.",
+      ],
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Verbose",
+            docs: [
+              "Emit one `PriceUpdatePosted` event per update.",
+            ],
+          },
+          {
+            name: "Summary",
+            docs: [
+              "Emit a single `PriceUpdatesPosted` event for the whole batch. Use this for large batches to stay within the transaction log limits.",
+            ],
+          },
+        ],
+      },
+    },
+  ],
+  events: [
+    {
+      name: "PriceUpdatePosted",
+      fields: [
+        {
+          name: "priceUpdateAccount",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "feedId",
+          type: {
+            array: ["u8", 32],
+          },
+          index: false,
+        },
+        {
+          name: "publishTime",
+          type: "i64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "PriceUpdatesPosted",
+      fields: [
+        {
+          name: "feedIds",
+          type: {
+            vec: {
+              array: ["u8", 32],
+            },
+          },
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "PriceUpdateVerified",
+      fields: [
+        {
+          name: "feedId",
+          type: {
+            array: ["u8", 32],
+          },
+          index: false,
+        },
+        {
+          name: "price",
+          type: "i64",
+          index: false,
+        },
+        {
+          name: "conf",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "publishTime",
+          type: "i64",
+          index: false,
+        },
+        {
+          name: "emitterChain",
+          type: "u16",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "TwapUpdateVerified",
+      fields: [
+        {
+          name: "feedId",
+          type: {
+            array: ["u8", 32],
+          },
+          index: false,
+        },
+        {
+          name: "cumulativePrice",
+          type: "i128",
+          index: false,
+        },
+        {
+          name: "cumulativeConf",
+          type: "u128",
+          index: false,
+        },
+        {
+          name: "publishTime",
+          type: "i64",
+          index: false,
+        },
+        {
+          name: "emitterChain",
+          type: "u16",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "ConfigUpdated",
+      fields: [
+        {
+          name: "oldConfig",
+          type: {
+            defined: "Config",
+          },
+          index: false,
+        },
+        {
+          name: "newConfig",
+          type: {
+            defined: "Config",
+          },
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "TaggedPriceUpdatesPosted",
+      fields: [
+        {
+          name: "tag",
+          type: {
+            array: ["u8", 32],
+          },
+          index: false,
+        },
+        {
+          name: "feedIds",
+          type: {
+            vec: {
+              array: ["u8", 32],
+            },
+          },
+          index: false,
+        },
+        {
+          name: "numSkipped",
+          type: "u32",
+          index: false,
+        },
+      ],
+    },
   ],
   errors: [
     {
@@ -1229,5 +4367,155 @@ export const IDL: PythSolanaReceiver = {
       name: "NonexistentGovernanceAuthorityTransferRequest",
       msg: "The governance authority needs to request a transfer first",
     },
+    {
+      code: 6022,
+      name: "ZeroMinimumSignatures",
+      msg: "The minimum number of signatures should be at least 1",
+    },
+    {
+      code: 6023,
+      name: "DeserializeAccumulatorUpdateDataFailed",
+      msg: "An error occurred when deserializing the accumulator update data",
+    },
+    {
+      code: 6024,
+      name: "MismatchedVaa",
+      msg: "The VAA in the accumulator update data doesn't match the encoded VAA account",
+    },
+    {
+      code: 6025,
+      name: "WrongNumberOfPriceUpdateAccounts",
+      msg: "The number of price update accounts doesn't match the number of updates",
+    },
+    {
+      code: 6026,
+      name: "NoUpdatesProvided",
+      msg: "The accumulator update data doesn't contain any updates",
+    },
+    {
+      code: 6027,
+      name: "PinnedGuardianSetMismatch",
+      msg: "The guardian set doesn't match the guardian set pinned in the config",
+    },
+    {
+      code: 6028,
+      name: "InvalidFeeRecipientWeights",
+      msg: "The weights of the fee recipients must sum to 10000 basis points",
+    },
+    {
+      code: 6029,
+      name: "WrongFeeRecipientAccounts",
+      msg: "The fee recipient accounts don't match the fee recipients in the config",
+    },
+    {
+      code: 6030,
+      name: "InvalidTwapMessage",
+      msg: "The TWAP message is inconsistent, its previous publish time is after its publish time",
+    },
+    {
+      code: 6031,
+      name: "UnapprovedPostVaaProgram",
+      msg: "The encoded VAA account is owned by a program that isn't approved in the config",
+    },
+    {
+      code: 6032,
+      name: "TooManyGuardians",
+      msg: "The guardian set account has more keys than the maximum number of guardians",
+    },
+    {
+      code: 6033,
+      name: "PriceHistoryFeedIdMismatch",
+      msg: "The price history account stores the updates of a different feed",
+    },
+    {
+      code: 6034,
+      name: "NotAnAccumulatorMessage",
+      msg: "The VAA payload is not a Pyth accumulator message",
+    },
+    {
+      code: 6035,
+      name: "UnexpectedNumberOfUpdates",
+      msg: "The number of updates doesn't match the expected number of updates",
+    },
+    {
+      code: 6036,
+      name: "NoMatchingVaa",
+      msg: "The price update is not included in the root of any of the VAAs",
+    },
+    {
+      code: 6037,
+      name: "InvalidVaaIndex",
+      msg: "The VAA index of an update is out of range, or the number of VAA indices doesn't match the number of updates",
+    },
+    {
+      code: 6038,
+      name: "PriceMovedTooMuch",
+      msg: "The price moved more than the limit set in the config for this feed since the last update",
+    },
+    {
+      code: 6039,
+      name: "PriceUpdateTooRecent",
+      msg: "The price update account was updated too recently to be closed",
+    },
+    {
+      code: 6040,
+      name: "PriceTooStale",
+      msg: "The publish time of the price update is too far in the past",
+    },
+    {
+      code: 6041,
+      name: "PublishTimeInFuture",
+      msg: "The publish time of the price update is too far in the future",
+    },
+    {
+      code: 6042,
+      name: "InvalidAccumulatorUpdateData",
+      msg: "The accumulator update data doesn't start with the accumulator update magic and a supported major version",
+    },
+    {
+      code: 6043,
+      name: "UnsupportedProofType",
+      msg: "The proof type of the accumulator update data is not supported",
+    },
+    {
+      code: 6044,
+      name: "PriceSanityCheckFailed",
+      msg: "The exponent or the confidence interval of the price update is outside the bounds set in the config",
+    },
+    {
+      code: 6045,
+      name: "TooManyUpdatesToVerify",
+      msg: "Too many updates to return their messages in the return data of the instruction",
+    },
+    {
+      code: 6046,
+      name: "InsufficientConsistencyLevel",
+      msg: "The consistency level of the VAA is lower than the minimum consistency level set in the config",
+    },
+    {
+      code: 6047,
+      name: "ConfigAccountTooSmall",
+      msg: "The config doesn't fit in the config account, resize it with resize_config",
+    },
+    {
+      code: 6048,
+      name: "MissingFeeRecipientAccounts",
+      msg: "The config has fee recipients, but their accounts weren't passed to the instruction",
+    },
+    {
+      code: 6049,
+      name: "WrongNumberOfFeeRecipientAccounts",
+      msg: "The number of fee recipient accounts doesn't match the number of fee recipients in the config",
+    },
+    {
+      code: 6050,
+      name: "WrongPriceChangeBaselineAccount",
+      msg: "The price change baseline account of the feed is missing or isn't the PDA of the feed",
+    },
+    {
+      code: 6051,
+      name: "InvalidPriceChangeLimitWindow",
+      msg: "The window of a price change limit must be at least 1 second",
+    },
   ],
 };